@@ -0,0 +1,339 @@
+use std::{
+  fmt,
+  hash::{Hash, Hasher},
+  marker::PhantomData,
+  ops::{Index, IndexMut},
+};
+
+#[derive(PartialEq, Debug)]
+pub enum HandleError {
+  Stale,
+}
+
+// A slot index plus the generation it was handed out in. Removing a value
+// bumps its slot's generation, so older handles to it stop resolving even
+// once the slot is reused.
+pub struct Handle<T> {
+  index: usize,
+  generation: u64,
+  marker: PhantomData<fn() -> T>,
+}
+
+struct ArenaSlot<T> {
+  generation: u64,
+  // The last walk that lent the value out, see `ArenaWalkMut`.
+  lent: u64,
+  value: Option<T>,
+}
+
+// Invariants:
+//    - `free` lists exactly the empty slots, each once
+//    - `len` counts the occupied slots
+//    - `walks` is at least the `lent` of every slot
+//
+// Handles carry no arena identity, so a handle used on an arena it didn't
+// come from may resolve to an unrelated value, though never unsafely.
+pub struct Arena<T> {
+  slots: Vec<ArenaSlot<T>>,
+  free: Vec<usize>,
+  len: usize,
+  walks: u64,
+}
+
+pub struct ArenaIter<'a, T: 'a> {
+  slots: std::iter::Enumerate<std::slice::Iter<'a, ArenaSlot<T>>>,
+}
+
+impl<'a, T> Iterator for ArenaIter<'a, T> {
+  type Item = (Handle<T>, &'a T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.slots.by_ref().find_map(|(index, slot)| {
+      let value = slot.value.as_ref()?;
+      Some((private::handle(index, slot.generation), value))
+    })
+  }
+}
+
+pub struct ArenaIterMut<'a, T: 'a> {
+  slots: std::iter::Enumerate<std::slice::IterMut<'a, ArenaSlot<T>>>,
+}
+
+impl<'a, T> Iterator for ArenaIterMut<'a, T> {
+  type Item = (Handle<T>, &'a mut T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.slots.by_ref().find_map(|(index, slot)| {
+      let value = slot.value.as_mut()?;
+      Some((private::handle(index, slot.generation), value))
+    })
+  }
+}
+
+// Lends out the values behind the handles it's given, each at most once, for
+// walks that follow links stored in the values. Every slot remembers the
+// last walk that lent it, so a repeated handle is turned down without
+// keeping a visited set.
+pub struct ArenaWalkMut<'a, T: 'a> {
+  slots: *mut ArenaSlot<T>,
+  len: usize,
+  walk: u64,
+  marker: PhantomData<&'a mut Arena<T>>,
+}
+
+// Stands in for the `&'a mut Arena<T>` it was made from.
+unsafe impl<T: Send> Send for ArenaWalkMut<'_, T> {}
+unsafe impl<T: Sync> Sync for ArenaWalkMut<'_, T> {}
+
+impl<'a, T> ArenaWalkMut<'a, T> {
+  // `None` for a stale handle and for one this walk already lent out.
+  pub fn get_mut(&mut self, handle: Handle<T>) -> Option<&'a mut T> {
+    if handle.index >= self.len {
+      return None;
+    }
+    // The pointer comes from the arena borrowed for 'a and is never used to
+    // reach a value lent before, only the bookkeeping next to it, so the
+    // references handed out never overlap.
+    unsafe {
+      let slot = self.slots.add(handle.index);
+      if (*slot).generation != handle.generation || (*slot).lent == self.walk {
+        return None;
+      }
+      (*slot).lent = self.walk;
+      (*slot).value.as_mut()
+    }
+  }
+}
+
+mod private {
+  use super::*;
+
+  pub fn handle<T>(index: usize, generation: u64) -> Handle<T> {
+    Handle {
+      index,
+      generation,
+      marker: PhantomData,
+    }
+  }
+
+  pub fn slot<T>(arena: &Arena<T>, handle: Handle<T>) -> Option<&ArenaSlot<T>> {
+    arena
+      .slots
+      .get(handle.index)
+      .filter(|slot| slot.generation == handle.generation)
+  }
+
+  pub fn slot_mut<T>(arena: &mut Arena<T>, handle: Handle<T>) -> Option<&mut ArenaSlot<T>> {
+    arena
+      .slots
+      .get_mut(handle.index)
+      .filter(|slot| slot.generation == handle.generation)
+  }
+}
+
+impl<T> Handle<T> {
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  pub fn generation(&self) -> u64 {
+    self.generation
+  }
+}
+
+// Handles are plain data whatever `T` is, so these aren't derived.
+impl<T> Clone for Handle<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.index == other.index && self.generation == other.generation
+  }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.index.hash(state);
+    self.generation.hash(state);
+  }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Handle({}v{})", self.index, self.generation)
+  }
+}
+
+impl<T> Arena<T> {
+  pub const fn new() -> Self {
+    Self {
+      slots: Vec::new(),
+      free: Vec::new(),
+      len: 0,
+      walks: 0,
+    }
+  }
+
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      slots: Vec::with_capacity(capacity),
+      free: vec![],
+      len: 0,
+      walks: 0,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn len(&self) -> i32 {
+    self.len as i32
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.slots.capacity()
+  }
+
+  // Reuses the most recently freed slot before growing.
+  pub fn insert(&mut self, value: T) -> Handle<T> {
+    self.len += 1;
+    match self.free.pop() {
+      Some(index) => {
+        let slot = &mut self.slots[index];
+        slot.value = Some(value);
+        private::handle(index, slot.generation)
+      }
+      None => {
+        self.slots.push(ArenaSlot {
+          generation: 0,
+          lent: 0,
+          value: Some(value),
+        });
+        private::handle(self.slots.len() - 1, 0)
+      }
+    }
+  }
+
+  pub fn contains(&self, handle: Handle<T>) -> bool {
+    self.get(handle).is_some()
+  }
+
+  pub fn get(&self, handle: Handle<T>) -> Option<&T> {
+    private::slot(self, handle)?.value.as_ref()
+  }
+
+  pub fn get_mut(&mut self, handle: Handle<T>) -> Option<&mut T> {
+    private::slot_mut(self, handle)?.value.as_mut()
+  }
+
+  pub fn remove(&mut self, handle: Handle<T>) -> Result<T, HandleError> {
+    let slot = private::slot_mut(self, handle).ok_or(HandleError::Stale)?;
+    let value = slot.value.take().ok_or(HandleError::Stale)?;
+    slot.generation += 1;
+    self.free.push(handle.index);
+    self.len -= 1;
+    Ok(value)
+  }
+
+  // Drops every value and invalidates every handle, keeping the slots for
+  // reuse.
+  pub fn clear(&mut self) {
+    self.free.clear();
+    for (index, slot) in self.slots.iter_mut().enumerate().rev() {
+      if slot.value.take().is_some() {
+        slot.generation += 1;
+      }
+      self.free.push(index);
+    }
+    self.len = 0;
+  }
+
+  // Visits values in slot order, which is unrelated to insertion order once
+  // slots get reused.
+  pub fn iter(&self) -> ArenaIter<'_, T> {
+    ArenaIter {
+      slots: self.slots.iter().enumerate(),
+    }
+  }
+
+  pub fn iter_mut(&mut self) -> ArenaIterMut<'_, T> {
+    ArenaIterMut {
+      slots: self.slots.iter_mut().enumerate(),
+    }
+  }
+
+  pub fn walk_mut(&mut self) -> ArenaWalkMut<'_, T> {
+    self.walks += 1;
+    ArenaWalkMut {
+      slots: self.slots.as_mut_ptr(),
+      len: self.slots.len(),
+      walk: self.walks,
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<T> Default for Arena<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// Keeps slots, generations and free order, so handles into the original
+// resolve the same way in the clone.
+impl<T> Clone for Arena<T>
+where
+  T: Clone,
+{
+  fn clone(&self) -> Self {
+    Self {
+      slots: self
+        .slots
+        .iter()
+        .map(|slot| ArenaSlot {
+          generation: slot.generation,
+          lent: 0,
+          value: slot.value.clone(),
+        })
+        .collect(),
+      free: self.free.clone(),
+      len: self.len,
+      walks: 0,
+    }
+  }
+}
+
+impl<T> fmt::Debug for Arena<T>
+where
+  T: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<T> Index<Handle<T>> for Arena<T> {
+  type Output = T;
+
+  fn index(&self, handle: Handle<T>) -> &T {
+    self.get(handle).expect("stale handle")
+  }
+}
+
+impl<T> IndexMut<Handle<T>> for Arena<T> {
+  fn index_mut(&mut self, handle: Handle<T>) -> &mut T {
+    self.get_mut(handle).expect("stale handle")
+  }
+}
+
+#[cfg(test)]
+#[path = "./arena_test.rs"]
+mod test;
@@ -0,0 +1,208 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type ArenaT = Arena<char>;
+
+  pub fn arena_empty() -> ArenaT {
+    Arena::new()
+  }
+
+  pub fn arena_filled() -> (ArenaT, Vec<Handle<char>>) {
+    let mut arena = Arena::new();
+    let handles = ['a', 'b', 'c', 'd']
+      .into_iter()
+      .map(|value| arena.insert(value))
+      .collect();
+    (arena, handles)
+  }
+
+  pub fn values(arena: &ArenaT) -> Vec<char> {
+    arena.iter().map(|(_, value)| *value).collect()
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = setup::arena_empty();
+    assert!(op.is_empty());
+    assert_eq!(op.len(), 0)
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut arena = setup::arena_empty();
+    let handle = arena.insert('a');
+    assert_eq!((handle.index(), handle.generation()), (0, 0));
+    assert_eq!(arena.get(handle), Some(&'a'));
+    assert_eq!(arena.len(), 1)
+  }
+
+  #[test]
+  fn to_filled_reusing() {
+    let (mut arena, handles) = setup::arena_filled();
+    arena.remove(handles[1]).unwrap();
+    let op = arena.insert('z');
+    assert_eq!((op.index(), op.generation()), (1, 1));
+    assert_ne!(op, handles[1]);
+    assert_eq!(arena.get(handles[1]), None);
+    assert_eq!(arena[op], 'z');
+    assert_eq!(setup::values(&arena), vec!['a', 'z', 'c', 'd'])
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (_, handles) = setup::arena_filled();
+    let mut arena = setup::arena_empty();
+    assert_eq!(arena.get(handles[0]), None);
+    assert_eq!(arena.get_mut(handles[0]), None);
+    assert!(!arena.contains(handles[0]))
+  }
+
+  #[test]
+  fn to_filled_after_moves() {
+    let (arena, handles) = setup::arena_filled();
+    let mut moved = vec![arena];
+    for value in 0..100 {
+      moved[0].insert(char::from(value));
+    }
+    let mut arena = moved.pop().unwrap();
+    *arena.get_mut(handles[2]).unwrap() = 'C';
+    arena[handles[3]] = 'D';
+    let op: Vec<char> = handles.iter().map(|handle| arena[*handle]).collect();
+    assert_eq!(op, vec!['a', 'b', 'C', 'D'])
+  }
+
+  #[test]
+  #[should_panic(expected = "stale handle")]
+  fn to_filled_indexing_stale() {
+    let (mut arena, handles) = setup::arena_filled();
+    arena.remove(handles[0]).unwrap();
+    let _ = arena[handles[0]];
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (_, handles) = setup::arena_filled();
+    let mut arena = setup::arena_empty();
+    assert_eq!(arena.remove(handles[0]), Err(HandleError::Stale))
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut arena, handles) = setup::arena_filled();
+    assert_eq!(arena.remove(handles[2]), Ok('c'));
+    assert_eq!(arena.remove(handles[2]), Err(HandleError::Stale));
+    assert_eq!(arena.len(), 3);
+    assert_eq!(setup::values(&arena), vec!['a', 'b', 'd'])
+  }
+}
+
+#[cfg(test)]
+mod clear {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let (mut arena, handles) = setup::arena_filled();
+    arena.remove(handles[1]).unwrap();
+    arena.clear();
+    assert!(arena.is_empty());
+    assert!(handles.iter().all(|handle| !arena.contains(*handle)));
+    let op: Vec<usize> = (0..5).map(|_| arena.insert('x').index()).collect();
+    assert_eq!(op, vec![0, 1, 2, 3, 4]);
+    assert_eq!(arena.free, vec![])
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::arena_empty().iter().count(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut arena, handles) = setup::arena_filled();
+    arena.remove(handles[0]).unwrap();
+    arena
+      .iter_mut()
+      .for_each(|(_, value)| *value = value.to_ascii_uppercase());
+    let op: Vec<Handle<char>> = arena.iter().map(|(handle, _)| handle).collect();
+    assert_eq!(op, handles[1..].to_vec());
+    assert_eq!(
+      format!("{arena:?}"),
+      "{Handle(1v0): 'B', Handle(2v0): 'C', Handle(3v0): 'D'}"
+    )
+  }
+}
+
+#[cfg(test)]
+mod walk_mut {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let handle = setup::arena_filled().1[0];
+    let mut arena = setup::arena_empty();
+    assert_eq!(arena.walk_mut().get_mut(handle), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut arena, handles) = setup::arena_filled();
+    arena.remove(handles[0]).unwrap();
+    let mut walk = arena.walk_mut();
+    assert_eq!(walk.get_mut(handles[0]), None);
+    let d = walk.get_mut(handles[3]).unwrap();
+    let b = walk.get_mut(handles[1]).unwrap();
+    assert_eq!(walk.get_mut(handles[3]), None);
+    (*d, *b) = (*b, *d);
+    assert_eq!(setup::values(&arena), vec!['d', 'c', 'b'])
+  }
+
+  #[test]
+  fn to_filled_walked_again() {
+    let (mut arena, handles) = setup::arena_filled();
+    *arena.walk_mut().get_mut(handles[2]).unwrap() = 'x';
+    let op = arena.walk_mut().get_mut(handles[2]).copied();
+    assert_eq!(op, Some('x'))
+  }
+}
+
+#[cfg(test)]
+mod clone {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let (arena, handles) = setup::arena_filled();
+    let mut op = arena.clone();
+    op.remove(handles[0]).unwrap();
+    assert_eq!(op.get(handles[1]), Some(&'b'));
+    assert_eq!(arena.get(handles[0]), Some(&'a'))
+  }
+}
@@ -0,0 +1,3 @@
+#[path = "./arena.rs"]
+pub mod arena;
+pub use arena::*;
@@ -0,0 +1,362 @@
+use std::{
+  collections::hash_map::RandomState,
+  fmt,
+  hash::{BuildHasher, Hash},
+  mem,
+};
+
+use super::{CacheError, OnEvict, Weigher};
+use crate::ephemeral::{
+  dictionaries::RobinHoodMap,
+  lists::{LinkedList, ListArena, ListHandle, RemoveError},
+};
+
+struct FrequencyBucket<K> {
+  frequency: u64,
+  keys: LinkedList<K>,
+}
+
+#[derive(Clone)]
+struct LfuEntry<K, V> {
+  value: V,
+  weight: usize,
+  bucket: ListHandle<FrequencyBucket<K>>,
+  item: ListHandle<K>,
+}
+
+// Keeps one bucket per frequency in use, so a hit moves its key one bucket
+// along instead of re-sorting anything.
+//
+// Invariants:
+//    - `buckets` is sorted by strictly increasing frequency and holds no
+//      empty bucket
+//    - every key sits in exactly one bucket, which `index` points at together
+//      with the key's node, ordered from most to least recently used
+//    - `bucket_nodes` and `key_nodes` hold nothing but the nodes of `buckets`
+//      and of the buckets' keys
+//    - `weight` is the sum of the entry weights and never exceeds `capacity`
+//      between calls
+pub struct LfuCache<K, V, S = RandomState> {
+  buckets: LinkedList<FrequencyBucket<K>>,
+  bucket_nodes: ListArena<FrequencyBucket<K>>,
+  // Shared by the keys of every bucket.
+  key_nodes: ListArena<K>,
+  index: RobinHoodMap<K, LfuEntry<K, V>, S>,
+  capacity: usize,
+  weight: usize,
+  // `None` weighs every entry as one.
+  weigher: Option<Weigher<K, V>>,
+  on_evict: Option<OnEvict<K, V>>,
+}
+
+mod private {
+  use super::*;
+
+  pub fn weigh<K, V, S>(cache: &LfuCache<K, V, S>, key: &K, value: &V) -> usize {
+    cache
+      .weigher
+      .as_ref()
+      .map_or(1, |weigher| weigher(key, value))
+  }
+
+  // The least recently used key of `bucket`.
+  pub fn oldest_key<K, V, S>(
+    cache: &LfuCache<K, V, S>,
+    bucket: ListHandle<FrequencyBucket<K>>,
+  ) -> Option<&K> {
+    let bucket = cache.buckets.resolve(&cache.bucket_nodes, bucket)?;
+    bucket.keys.back(&cache.key_nodes)
+  }
+
+  // Moves `key` into the bucket one frequency up, creating it if needed.
+  pub fn touch<K, V, S>(cache: &mut LfuCache<K, V, S>, key: &K)
+  where
+    K: Hash + Eq,
+    S: BuildHasher,
+  {
+    let entry = cache.index.get_mut(key).unwrap();
+    let source = entry.bucket;
+    let buckets = &mut cache.buckets;
+    let bucket_nodes = &mut cache.bucket_nodes;
+    let frequency = buckets.resolve(bucket_nodes, source).unwrap().frequency + 1;
+    let target = match buckets.next(bucket_nodes, source) {
+      Some(next) if buckets.resolve(bucket_nodes, next).unwrap().frequency == frequency => next,
+      _ => {
+        let bucket = FrequencyBucket {
+          frequency,
+          keys: LinkedList::new(),
+        };
+        buckets
+          .insert_after_handle(bucket_nodes, source, bucket)
+          .unwrap()
+      }
+    };
+    let keys = &mut buckets.resolve_mut(bucket_nodes, source).unwrap().keys;
+    let key = keys.remove(&mut cache.key_nodes, entry.item).unwrap();
+    if keys.is_empty() {
+      buckets.remove(bucket_nodes, source).unwrap();
+    }
+    let keys = &mut buckets.resolve_mut(bucket_nodes, target).unwrap().keys;
+    entry.item = keys.insert_at_beginning(&mut cache.key_nodes, key);
+    entry.bucket = target;
+  }
+
+  pub fn pop_lfu<K, V, S>(cache: &mut LfuCache<K, V, S>) -> Result<(K, V), RemoveError>
+  where
+    K: Hash + Eq,
+    S: BuildHasher,
+  {
+    let bucket = cache.buckets.front_handle().ok_or(RemoveError::EmptyList)?;
+    let keys = &mut cache
+      .buckets
+      .resolve_mut(&mut cache.bucket_nodes, bucket)
+      .unwrap()
+      .keys;
+    let key = keys.remove_at_end(&mut cache.key_nodes).unwrap();
+    if keys.is_empty() {
+      cache
+        .buckets
+        .remove(&mut cache.bucket_nodes, bucket)
+        .unwrap();
+    }
+    let entry = cache.index.remove(&key).unwrap();
+    cache.weight -= entry.weight;
+    Ok((key, entry.value))
+  }
+
+  // Evicts the least frequently used entries, oldest first among equals,
+  // until the cache fits. `kept` is spared: it fits on its own, so the loop
+  // always finds another entry first.
+  pub fn evict<K, V, S>(cache: &mut LfuCache<K, V, S>, kept: Option<&K>)
+  where
+    K: Hash + Eq + Clone,
+    S: BuildHasher,
+  {
+    while cache.weight > cache.capacity {
+      let mut bucket = cache.buckets.front_handle().unwrap();
+      let mut key = oldest_key(cache, bucket).unwrap();
+      // The kept key was just used, so it's the oldest only when alone.
+      if Some(key) == kept {
+        bucket = cache.buckets.next(&cache.bucket_nodes, bucket).unwrap();
+        key = oldest_key(cache, bucket).unwrap();
+      }
+      let key = key.clone();
+      let value = cache.pop(&key).unwrap();
+      if let Some(on_evict) = cache.on_evict.as_mut() {
+        on_evict(key, value);
+      }
+    }
+  }
+}
+
+impl<K, V> LfuCache<K, V, RandomState>
+where
+  K: Hash + Eq + Clone,
+{
+  // Counts every entry as weighing one.
+  pub fn new(capacity: usize) -> Self {
+    Self::with_hasher(capacity, RandomState::new())
+  }
+}
+
+impl<K, V, S> LfuCache<K, V, S>
+where
+  K: Hash + Eq + Clone,
+  S: BuildHasher,
+{
+  pub fn with_hasher(capacity: usize, hasher: S) -> Self {
+    Self {
+      buckets: LinkedList::new(),
+      bucket_nodes: ListArena::new(),
+      key_nodes: ListArena::new(),
+      index: RobinHoodMap::with_hasher(hasher),
+      capacity,
+      weight: 0,
+      weigher: None,
+      on_evict: None,
+    }
+  }
+
+  // Same rules as `LruCache::set_weigher`.
+  pub fn set_weigher<F>(&mut self, weigher: F)
+  where
+    F: Fn(&K, &V) -> usize + 'static,
+  {
+    for (key, entry) in self.index.iter_mut() {
+      let weight = weigher(key, &entry.value);
+      self.weight = self.weight - entry.weight + weight;
+      entry.weight = weight;
+    }
+    self.weigher = Some(Box::new(weigher));
+    private::evict(self, None);
+  }
+
+  // Same rules as `LruCache::set_on_evict`.
+  pub fn set_on_evict<F>(&mut self, on_evict: F)
+  where
+    F: FnMut(K, V) + 'static,
+  {
+    self.on_evict = Some(Box::new(on_evict));
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.index.is_empty()
+  }
+
+  pub fn len(&self) -> i32 {
+    self.index.len()
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  pub fn weight(&self) -> usize {
+    self.weight
+  }
+
+  pub fn contains(&self, key: &K) -> bool {
+    self.index.contains_key(key)
+  }
+
+  // How many times `key` was put or got since it entered the cache.
+  pub fn frequency(&self, key: &K) -> Option<u64> {
+    let entry = self.index.get(key)?;
+    self
+      .buckets
+      .resolve(&self.bucket_nodes, entry.bucket)
+      .map(|bucket| bucket.frequency)
+  }
+
+  // Looks `key` up without counting it as used.
+  pub fn peek(&self, key: &K) -> Option<&V> {
+    self.index.get(key).map(|entry| &entry.value)
+  }
+
+  pub fn peek_lfu(&self) -> Option<(&K, &V)> {
+    let key = private::oldest_key(self, self.buckets.front_handle()?)?;
+    Some((key, self.peek(key)?))
+  }
+
+  pub fn get(&mut self, key: &K) -> Option<&V> {
+    self.get_mut(key).map(|value| &*value)
+  }
+
+  pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    if !self.index.contains_key(key) {
+      return None;
+    }
+    private::touch(self, key);
+    self.index.get_mut(key).map(|entry| &mut entry.value)
+  }
+
+  // A new key starts at frequency one; replacing a value counts as a use.
+  pub fn put(&mut self, key: K, value: V) -> Result<Option<V>, CacheError<K, V>> {
+    let weight = private::weigh(self, &key, &value);
+    if weight > self.capacity {
+      return Err(CacheError::TooHeavy(key, value));
+    }
+    let old = match self.index.get_mut(&key) {
+      Some(entry) => {
+        self.weight = self.weight - entry.weight + weight;
+        entry.weight = weight;
+        let old = mem::replace(&mut entry.value, value);
+        private::touch(self, &key);
+        Some(old)
+      }
+      None => {
+        let buckets = &mut self.buckets;
+        let bucket_nodes = &mut self.bucket_nodes;
+        let bucket = match buckets.front_handle() {
+          Some(front) if buckets.resolve(bucket_nodes, front).unwrap().frequency == 1 => front,
+          _ => {
+            let bucket = FrequencyBucket {
+              frequency: 1,
+              keys: LinkedList::new(),
+            };
+            buckets.insert_at_beginning(bucket_nodes, bucket)
+          }
+        };
+        let keys = &mut buckets.resolve_mut(bucket_nodes, bucket).unwrap().keys;
+        let item = keys.insert_at_beginning(&mut self.key_nodes, key.clone());
+        let entry = LfuEntry {
+          value,
+          weight,
+          bucket,
+          item,
+        };
+        self.index.insert(key.clone(), entry);
+        self.weight += weight;
+        None
+      }
+    };
+    private::evict(self, Some(&key));
+    Ok(old)
+  }
+
+  pub fn pop(&mut self, key: &K) -> Result<V, RemoveError> {
+    let entry = self.index.remove(key)?;
+    let keys = &mut self
+      .buckets
+      .resolve_mut(&mut self.bucket_nodes, entry.bucket)
+      .unwrap()
+      .keys;
+    keys.remove(&mut self.key_nodes, entry.item).unwrap();
+    if keys.is_empty() {
+      self
+        .buckets
+        .remove(&mut self.bucket_nodes, entry.bucket)
+        .unwrap();
+    }
+    self.weight -= entry.weight;
+    Ok(entry.value)
+  }
+
+  pub fn pop_lfu(&mut self) -> Result<(K, V), RemoveError> {
+    private::pop_lfu(self)
+  }
+
+  // Evicts least frequently used entries until the cache fits `capacity`.
+  pub fn resize(&mut self, capacity: usize) {
+    self.capacity = capacity;
+    private::evict(self, None);
+  }
+
+  pub fn clear(&mut self) {
+    self.bucket_nodes.clear();
+    self.key_nodes.clear();
+    self.buckets = LinkedList::new();
+    self.index.clear();
+    self.weight = 0;
+  }
+
+  // Visits entries from the least to the most frequently used, in eviction
+  // order.
+  pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+    self
+      .buckets
+      .iter(&self.bucket_nodes)
+      .flat_map(move |bucket| {
+        bucket
+          .keys
+          .iter(&self.key_nodes)
+          .rev()
+          .map(move |key| (key, &self.index.get(key).unwrap().value))
+      })
+  }
+}
+
+impl<K, V, S> fmt::Debug for LfuCache<K, V, S>
+where
+  K: Hash + Eq + Clone + fmt::Debug,
+  V: fmt::Debug,
+  S: BuildHasher,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+#[cfg(test)]
+#[path = "./lfu-cache_test.rs"]
+mod test;
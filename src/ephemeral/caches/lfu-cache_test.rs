@@ -0,0 +1,218 @@
+use super::*;
+
+mod setup {
+  use super::*;
+  use std::{cell::RefCell, rc::Rc};
+
+  pub type LfuCacheT = LfuCache<i32, char>;
+
+  // Entries handed to the eviction callback, oldest first.
+  pub type Evicted = Rc<RefCell<Vec<(i32, char)>>>;
+
+  pub fn lfu_cache_empty() -> (LfuCacheT, Evicted) {
+    let mut cache = LfuCache::new(3);
+    let evicted = Evicted::default();
+    let log = evicted.clone();
+    cache.set_on_evict(move |key, value| log.borrow_mut().push((key, value)));
+    (cache, evicted)
+  }
+
+  // Frequencies 0: 3, 1: 1, 2: 2.
+  pub fn lfu_cache_filled() -> (LfuCacheT, Evicted) {
+    let (mut cache, evicted) = lfu_cache_empty();
+    for (key, value) in [(0, 'a'), (1, 'b'), (2, 'c')] {
+      cache.put(key, value).unwrap();
+    }
+    cache.get(&0);
+    cache.get(&0);
+    cache.get(&2);
+    (cache, evicted)
+  }
+
+  pub fn keys(cache: &LfuCacheT) -> Vec<i32> {
+    cache.iter().map(|(key, _)| *key).collect()
+  }
+
+  // Checks the buckets against the invariants on `LfuCache`.
+  pub fn check(cache: &LfuCacheT) {
+    let buckets = || cache.buckets.iter(&cache.bucket_nodes);
+    let frequencies: Vec<u64> = buckets().map(|bucket| bucket.frequency).collect();
+    assert!(frequencies.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(buckets().all(|bucket| !bucket.keys.is_empty()));
+    let count: i32 = buckets().map(|bucket| bucket.keys.len()).sum();
+    assert_eq!(count, cache.len());
+    assert_eq!(cache.key_nodes.len(), cache.len());
+    assert_eq!(cache.bucket_nodes.len(), cache.buckets.len());
+    let weight: usize = cache.index.iter().map(|(_, entry)| entry.weight).sum();
+    assert_eq!(weight, cache.weight());
+    assert!(weight <= cache.capacity())
+  }
+}
+
+#[cfg(test)]
+mod put {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut cache, _) = setup::lfu_cache_empty();
+    assert_eq!(cache.put(0, 'a'), Ok(None));
+    assert_eq!(cache.frequency(&0), Some(1));
+    setup::check(&cache)
+  }
+
+  #[test]
+  fn to_filled_evicting() {
+    let (mut cache, evicted) = setup::lfu_cache_filled();
+    assert_eq!(cache.put(3, 'd'), Ok(None));
+    assert_eq!(evicted.take(), vec![(1, 'b')]);
+    assert_eq!(cache.put(4, 'e'), Ok(None));
+    assert_eq!(evicted.take(), vec![(3, 'd')]);
+    setup::check(&cache);
+    assert_eq!(setup::keys(&cache), vec![4, 2, 0])
+  }
+
+  #[test]
+  fn to_filled_sparing_new_key() {
+    let (mut cache, evicted) = setup::lfu_cache_filled();
+    cache.get(&1);
+    cache.get(&1);
+    assert_eq!(cache.put(3, 'd'), Ok(None));
+    assert_eq!(evicted.take(), vec![(2, 'c')]);
+    setup::check(&cache);
+    assert_eq!(setup::keys(&cache), vec![3, 0, 1])
+  }
+
+  #[test]
+  fn to_filled_with_present_key() {
+    let (mut cache, _) = setup::lfu_cache_filled();
+    assert_eq!(cache.put(1, 'z'), Ok(Some('b')));
+    assert_eq!(cache.frequency(&1), Some(2));
+    setup::check(&cache);
+    assert_eq!(setup::keys(&cache), vec![2, 1, 0])
+  }
+
+  #[test]
+  fn to_filled_too_heavy() {
+    let (mut cache, evicted) = setup::lfu_cache_filled();
+    cache.set_weigher(|key, _| *key as usize + 1);
+    assert_eq!(evicted.take(), vec![(1, 'b'), (2, 'c')]);
+    assert_eq!(cache.put(3, 'd'), Err(CacheError::TooHeavy(3, 'd')));
+    assert_eq!(cache.weight(), 1);
+    setup::check(&cache)
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut cache, _) = setup::lfu_cache_empty();
+    assert_eq!(cache.get(&0), None);
+    assert_eq!(cache.peek_lfu(), None);
+    assert_eq!(cache.frequency(&0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut cache, _) = setup::lfu_cache_filled();
+    assert_eq!(cache.peek_lfu(), Some((&1, &'b')));
+    assert_eq!(cache.get(&1), Some(&'b'));
+    *cache.get_mut(&1).unwrap() = 'B';
+    assert_eq!(cache.frequency(&1), Some(3));
+    assert_eq!(cache.peek(&2), Some(&'c'));
+    assert_eq!(cache.frequency(&2), Some(2));
+    setup::check(&cache);
+    assert_eq!(setup::keys(&cache), vec![2, 0, 1])
+  }
+}
+
+#[cfg(test)]
+mod pop {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut cache, _) = setup::lfu_cache_empty();
+    assert_eq!(cache.pop(&0), Err(RemoveError::ItemNotFound));
+    assert_eq!(cache.pop_lfu(), Err(RemoveError::EmptyList))
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut cache, evicted) = setup::lfu_cache_filled();
+    assert_eq!(cache.pop(&2), Ok('c'));
+    setup::check(&cache);
+    assert_eq!(cache.pop_lfu(), Ok((1, 'b')));
+    assert_eq!(cache.pop_lfu(), Ok((0, 'a')));
+    assert!(cache.is_empty());
+    assert_eq!(cache.buckets.len(), 0);
+    assert_eq!(evicted.take(), vec![])
+  }
+}
+
+#[cfg(test)]
+mod resize {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let (mut cache, evicted) = setup::lfu_cache_filled();
+    cache.resize(1);
+    assert_eq!(evicted.take(), vec![(1, 'b'), (2, 'c')]);
+    assert_eq!(setup::keys(&cache), vec![0]);
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(format!("{cache:?}"), "{}")
+  }
+}
+
+#[cfg(test)]
+mod random {
+  use super::*;
+
+  // Replays random operations against a vector scanned for the victim.
+  #[test]
+  fn to_model() {
+    let mut cache = LfuCache::new(8);
+    // (key, value, frequency, last use)
+    let mut model: Vec<(u64, u64, u64, usize)> = vec![];
+    crate::test_support::replay(|step, rng| {
+      let key = rng.next_u64() % 16;
+      let found = model.iter().position(|entry| entry.0 == key);
+      if rng.next_u64().is_multiple_of(2) {
+        if let Some(index) = found {
+          model[index].2 += 1;
+          model[index].3 = step;
+        }
+        let expected = found.map(|index| model[index].1);
+        assert_eq!(cache.get(&key).copied(), expected);
+      } else {
+        let value = rng.next_u64();
+        let old = match found {
+          Some(index) => {
+            let old = model[index].1;
+            model[index] = (key, value, model[index].2 + 1, step);
+            Some(old)
+          }
+          None => {
+            if model.len() == 8 {
+              let victim = (0..8).min_by_key(|index| (model[*index].2, model[*index].3));
+              model.remove(victim.unwrap());
+            }
+            model.push((key, value, 1, step));
+            None
+          }
+        };
+        assert_eq!(cache.put(key, value), Ok(old));
+      }
+      assert!(model
+        .iter()
+        .all(|entry| cache.peek(&entry.0) == Some(&entry.1)
+          && cache.frequency(&entry.0) == Some(entry.2)));
+      assert_eq!(cache.len(), model.len() as i32)
+    })
+  }
+}
@@ -0,0 +1,261 @@
+use std::{
+  collections::hash_map::RandomState,
+  fmt,
+  hash::{BuildHasher, Hash},
+  mem,
+};
+
+use crate::ephemeral::{
+  dictionaries::RobinHoodMap,
+  lists::{LinkedList, ListArena, ListHandle, RemoveError},
+};
+
+#[derive(PartialEq, Debug)]
+pub enum CacheError<K, V> {
+  // The entry alone weighs more than the cache's capacity.
+  TooHeavy(K, V),
+}
+
+// Boxed so they can capture state, like a write-back target or a counter.
+pub type Weigher<K, V> = Box<dyn Fn(&K, &V) -> usize>;
+pub type OnEvict<K, V> = Box<dyn FnMut(K, V)>;
+
+#[derive(Clone)]
+struct LruEntry<K, V> {
+  key: K,
+  value: V,
+  weight: usize,
+}
+
+// Invariants:
+//    - `index` maps every key in `order` to its node, and nothing else
+//    - `weight` is the sum of the entry weights and never exceeds `capacity`
+//      between calls
+//    - `order` runs from the most to the least recently used entry, and
+//      `nodes` holds nothing but its nodes
+pub struct LruCache<K, V, S = RandomState> {
+  nodes: ListArena<LruEntry<K, V>>,
+  order: LinkedList<LruEntry<K, V>>,
+  index: RobinHoodMap<K, ListHandle<LruEntry<K, V>>, S>,
+  capacity: usize,
+  weight: usize,
+  // `None` weighs every entry as one.
+  weigher: Option<Weigher<K, V>>,
+  on_evict: Option<OnEvict<K, V>>,
+}
+
+mod private {
+  use super::*;
+
+  pub fn weigh<K, V, S>(cache: &LruCache<K, V, S>, key: &K, value: &V) -> usize {
+    cache
+      .weigher
+      .as_ref()
+      .map_or(1, |weigher| weigher(key, value))
+  }
+
+  pub fn pop_lru<K, V, S>(cache: &mut LruCache<K, V, S>) -> Result<(K, V), RemoveError>
+  where
+    K: Hash + Eq,
+    S: BuildHasher,
+  {
+    let entry = cache.order.remove_at_end(&mut cache.nodes)?;
+    cache.index.remove(&entry.key).unwrap();
+    cache.weight -= entry.weight;
+    Ok((entry.key, entry.value))
+  }
+
+  // Evicts from the least recently used end until the cache fits.
+  pub fn evict<K, V, S>(cache: &mut LruCache<K, V, S>)
+  where
+    K: Hash + Eq,
+    S: BuildHasher,
+  {
+    while cache.weight > cache.capacity {
+      let (key, value) = pop_lru(cache).unwrap();
+      if let Some(on_evict) = cache.on_evict.as_mut() {
+        on_evict(key, value);
+      }
+    }
+  }
+}
+
+impl<K, V> LruCache<K, V, RandomState>
+where
+  K: Hash + Eq + Clone,
+{
+  // Counts every entry as weighing one.
+  pub fn new(capacity: usize) -> Self {
+    Self::with_hasher(capacity, RandomState::new())
+  }
+}
+
+impl<K, V, S> LruCache<K, V, S>
+where
+  K: Hash + Eq + Clone,
+  S: BuildHasher,
+{
+  pub fn with_hasher(capacity: usize, hasher: S) -> Self {
+    Self {
+      nodes: ListArena::new(),
+      order: LinkedList::new(),
+      index: RobinHoodMap::with_hasher(hasher),
+      capacity,
+      weight: 0,
+      weigher: None,
+      on_evict: None,
+    }
+  }
+
+  // Re-weighs the current entries, evicting any that no longer fit. Weights
+  // are taken on `put`, so values changed through `get_mut` keep theirs.
+  pub fn set_weigher<F>(&mut self, weigher: F)
+  where
+    F: Fn(&K, &V) -> usize + 'static,
+  {
+    for entry in self.order.iter_mut(&mut self.nodes) {
+      let weight = weigher(&entry.key, &entry.value);
+      self.weight = self.weight - entry.weight + weight;
+      entry.weight = weight;
+    }
+    self.weigher = Some(Box::new(weigher));
+    private::evict(self);
+  }
+
+  // Called with every entry pushed out by `put`, `resize` or `set_weigher`,
+  // but not with those taken out by `pop`, `pop_lru` or `clear`.
+  pub fn set_on_evict<F>(&mut self, on_evict: F)
+  where
+    F: FnMut(K, V) + 'static,
+  {
+    self.on_evict = Some(Box::new(on_evict));
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.order.is_empty()
+  }
+
+  pub fn len(&self) -> i32 {
+    self.order.len()
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  pub fn weight(&self) -> usize {
+    self.weight
+  }
+
+  pub fn contains(&self, key: &K) -> bool {
+    self.index.contains_key(key)
+  }
+
+  // Looks `key` up without counting it as used.
+  pub fn peek(&self, key: &K) -> Option<&V> {
+    let handle = *self.index.get(key)?;
+    self
+      .order
+      .resolve(&self.nodes, handle)
+      .map(|entry| &entry.value)
+  }
+
+  pub fn peek_lru(&self) -> Option<(&K, &V)> {
+    self
+      .order
+      .back(&self.nodes)
+      .map(|entry| (&entry.key, &entry.value))
+  }
+
+  pub fn get(&mut self, key: &K) -> Option<&V> {
+    self.get_mut(key).map(|value| &*value)
+  }
+
+  pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    let handle = *self.index.get(key)?;
+    self.order.move_to_front(&mut self.nodes, handle).unwrap();
+    self
+      .order
+      .resolve_mut(&mut self.nodes, handle)
+      .map(|entry| &mut entry.value)
+  }
+
+  // Marks `key` as most recently used, returning the value it replaced.
+  pub fn put(&mut self, key: K, value: V) -> Result<Option<V>, CacheError<K, V>> {
+    let weight = private::weigh(self, &key, &value);
+    if weight > self.capacity {
+      return Err(CacheError::TooHeavy(key, value));
+    }
+    let old = match self.index.get(&key) {
+      Some(handle) => {
+        let handle = *handle;
+        let entry = self.order.resolve_mut(&mut self.nodes, handle).unwrap();
+        self.weight = self.weight - entry.weight + weight;
+        entry.weight = weight;
+        let old = mem::replace(&mut entry.value, value);
+        self.order.move_to_front(&mut self.nodes, handle).unwrap();
+        Some(old)
+      }
+      None => {
+        let entry = LruEntry {
+          key: key.clone(),
+          value,
+          weight,
+        };
+        let handle = self.order.insert_at_beginning(&mut self.nodes, entry);
+        self.index.insert(key, handle);
+        self.weight += weight;
+        None
+      }
+    };
+    private::evict(self);
+    Ok(old)
+  }
+
+  pub fn pop(&mut self, key: &K) -> Result<V, RemoveError> {
+    let handle = self.index.remove(key)?;
+    let entry = self.order.remove(&mut self.nodes, handle).unwrap();
+    self.weight -= entry.weight;
+    Ok(entry.value)
+  }
+
+  pub fn pop_lru(&mut self) -> Result<(K, V), RemoveError> {
+    private::pop_lru(self)
+  }
+
+  // Evicts least recently used entries until the cache fits `capacity`.
+  pub fn resize(&mut self, capacity: usize) {
+    self.capacity = capacity;
+    private::evict(self);
+  }
+
+  pub fn clear(&mut self) {
+    self.nodes.clear();
+    self.order = LinkedList::new();
+    self.index.clear();
+    self.weight = 0;
+  }
+
+  // Visits entries from the most to the least recently used.
+  pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+    self
+      .order
+      .iter(&self.nodes)
+      .map(|entry| (&entry.key, &entry.value))
+  }
+}
+
+impl<K, V, S> fmt::Debug for LruCache<K, V, S>
+where
+  K: Hash + Eq + Clone + fmt::Debug,
+  V: fmt::Debug,
+  S: BuildHasher,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+#[cfg(test)]
+#[path = "./lru-cache_test.rs"]
+mod test;
@@ -0,0 +1,226 @@
+use super::*;
+
+mod setup {
+  use super::*;
+  use std::{cell::RefCell, rc::Rc};
+
+  pub type LruCacheT = LruCache<i32, char>;
+
+  // Entries handed to the eviction callback, oldest first.
+  pub type Evicted = Rc<RefCell<Vec<(i32, char)>>>;
+
+  pub fn lru_cache_empty() -> (LruCacheT, Evicted) {
+    let mut cache = LruCache::new(3);
+    let evicted = Evicted::default();
+    let log = evicted.clone();
+    cache.set_on_evict(move |key, value| log.borrow_mut().push((key, value)));
+    (cache, evicted)
+  }
+
+  // Least recently used first: 0, 1, 2.
+  pub fn lru_cache_filled() -> (LruCacheT, Evicted) {
+    let (mut cache, evicted) = lru_cache_empty();
+    for (key, value) in [(0, 'a'), (1, 'b'), (2, 'c')] {
+      cache.put(key, value).unwrap();
+    }
+    (cache, evicted)
+  }
+
+  pub fn keys(cache: &LruCacheT) -> Vec<i32> {
+    cache.iter().map(|(key, _)| *key).collect()
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = LruCache::<i32, i32>::new(4);
+    assert!(op.is_empty());
+    assert_eq!((op.capacity(), op.weight()), (4, 0))
+  }
+}
+
+#[cfg(test)]
+mod put {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut cache, _) = setup::lru_cache_empty();
+    assert_eq!(cache.put(0, 'a'), Ok(None));
+    assert_eq!(cache.peek(&0), Some(&'a'));
+    assert_eq!(cache.len(), 1)
+  }
+
+  #[test]
+  fn to_filled_evicting() {
+    let (mut cache, evicted) = setup::lru_cache_filled();
+    assert_eq!(cache.put(3, 'd'), Ok(None));
+    assert_eq!(evicted.take(), vec![(0, 'a')]);
+    assert_eq!(setup::keys(&cache), vec![3, 2, 1]);
+    assert!(!cache.contains(&0))
+  }
+
+  #[test]
+  fn to_filled_with_present_key() {
+    let (mut cache, evicted) = setup::lru_cache_filled();
+    assert_eq!(cache.put(0, 'z'), Ok(Some('a')));
+    cache.put(3, 'd').unwrap();
+    assert_eq!(evicted.take(), vec![(1, 'b')]);
+    assert_eq!(setup::keys(&cache), vec![3, 0, 2])
+  }
+
+  #[test]
+  fn to_filled_too_heavy() {
+    let (mut cache, evicted) = setup::lru_cache_filled();
+    cache.set_weigher(|_, value| if *value == 'x' { 4 } else { 1 });
+    assert_eq!(cache.put(1, 'x'), Err(CacheError::TooHeavy(1, 'x')));
+    assert_eq!(cache.peek(&1), Some(&'b'));
+    assert_eq!(evicted.take(), vec![])
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut cache, _) = setup::lru_cache_empty();
+    assert_eq!(cache.get(&0), None);
+    assert_eq!(cache.peek_lru(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut cache, evicted) = setup::lru_cache_filled();
+    assert_eq!(cache.get(&0), Some(&'a'));
+    *cache.get_mut(&1).unwrap() = 'B';
+    assert_eq!(cache.peek_lru(), Some((&2, &'c')));
+    cache.put(3, 'd').unwrap();
+    assert_eq!(evicted.take(), vec![(2, 'c')]);
+    assert_eq!(setup::keys(&cache), vec![3, 1, 0])
+  }
+
+  #[test]
+  fn to_filled_peeking() {
+    let (mut cache, evicted) = setup::lru_cache_filled();
+    assert_eq!(cache.peek(&0), Some(&'a'));
+    cache.put(3, 'd').unwrap();
+    assert_eq!(evicted.take(), vec![(0, 'a')])
+  }
+}
+
+#[cfg(test)]
+mod pop {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut cache, _) = setup::lru_cache_empty();
+    assert_eq!(cache.pop(&0), Err(RemoveError::ItemNotFound));
+    assert_eq!(cache.pop_lru(), Err(RemoveError::EmptyList))
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut cache, evicted) = setup::lru_cache_filled();
+    assert_eq!(cache.pop(&1), Ok('b'));
+    assert_eq!(cache.pop_lru(), Ok((0, 'a')));
+    assert_eq!((cache.len(), cache.weight()), (1, 1));
+    assert_eq!(evicted.take(), vec![])
+  }
+}
+
+#[cfg(test)]
+mod resize {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let (mut cache, evicted) = setup::lru_cache_filled();
+    cache.resize(1);
+    assert_eq!(evicted.take(), vec![(0, 'a'), (1, 'b')]);
+    assert_eq!(setup::keys(&cache), vec![2]);
+    cache.resize(5);
+    cache.put(3, 'd').unwrap();
+    assert_eq!(cache.len(), 2)
+  }
+}
+
+#[cfg(test)]
+mod set_weigher {
+  use super::*;
+
+  #[test]
+  fn to_filled_by_size() {
+    let mut cache: LruCache<&str, String> = LruCache::new(10);
+    cache.set_weigher(|key, value| key.len() + value.len());
+    cache.put("a", String::from("1234")).unwrap();
+    cache.put("bb", String::from("12")).unwrap();
+    assert_eq!(cache.weight(), 9);
+    cache.put("c", String::from("1")).unwrap();
+    assert_eq!(cache.weight(), 6);
+    assert!(!cache.contains(&"a"));
+    assert_eq!(
+      cache.put("bb", String::from("1234567")),
+      Ok(Some(String::from("12")))
+    );
+    assert_eq!(cache.weight(), 9);
+    assert!(!cache.contains(&"c"));
+    cache.set_weigher(|_, _| 6);
+    assert_eq!(
+      cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+      vec!["bb"]
+    )
+  }
+}
+
+#[cfg(test)]
+mod clear {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let (mut cache, evicted) = setup::lru_cache_filled();
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.weight(), 0);
+    assert_eq!(evicted.take(), vec![]);
+    assert_eq!(format!("{cache:?}"), "{}")
+  }
+}
+
+#[cfg(test)]
+mod random {
+  use super::*;
+
+  // Replays random operations against a vector kept in recency order.
+  #[test]
+  fn to_model() {
+    let mut cache = LruCache::new(16);
+    let mut model: Vec<(u64, u64)> = vec![];
+    crate::test_support::replay(|_, rng| {
+      let key = rng.next_u64() % 32;
+      let found = model.iter().position(|(k, _)| *k == key);
+      if rng.next_u64().is_multiple_of(2) {
+        let expected = found.map(|index| model.remove(index));
+        if let Some(entry) = expected {
+          model.insert(0, entry);
+        }
+        assert_eq!(cache.get(&key), expected.map(|(_, value)| value).as_ref());
+      } else {
+        let value = rng.next_u64();
+        let old = found.map(|index| model.remove(index).1);
+        model.insert(0, (key, value));
+        model.truncate(16);
+        assert_eq!(cache.put(key, value), Ok(old));
+      }
+      let op: Vec<(u64, u64)> = cache.iter().map(|(key, value)| (*key, *value)).collect();
+      assert_eq!(op, model)
+    })
+  }
+}
@@ -0,0 +1,7 @@
+#[path = "./lru-cache.rs"]
+pub mod lru_cache;
+pub use lru_cache::*;
+
+#[path = "./lfu-cache.rs"]
+pub mod lfu_cache;
+pub use lfu_cache::*;
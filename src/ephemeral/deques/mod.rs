@@ -0,0 +1,3 @@
+#[path = "./ring-deque.rs"]
+pub mod ring_deque;
+pub use ring_deque::*;
@@ -0,0 +1,533 @@
+use std::{
+  fmt,
+  mem::MaybeUninit,
+  ops::{Bound, Index, IndexMut, RangeBounds},
+  slice,
+};
+
+use crate::{ephemeral::lists::RemoveError, IndexError};
+
+#[derive(PartialEq, Debug)]
+pub enum PushError<T> {
+  // Hands the rejected item back.
+  Full(T),
+}
+
+// Invariants:
+//    - the items live in the `len` slots starting at `head` and wrapping
+//      around the end of `buffer`; every other slot is uninitialized
+//    - `buffer.len()` is the capacity, which only `push_front` and `push_back`
+//      grow, so a deque filled through `try_push_*` or `push_*_overwrite`
+//      stays within the capacity it was made with
+pub struct RingDeque<T> {
+  buffer: Vec<MaybeUninit<T>>,
+  head: usize,
+  len: usize,
+}
+
+pub struct RingIter<'a, T: 'a> {
+  front: slice::Iter<'a, T>,
+  back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for RingIter<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.front.next().or_else(|| self.back.next())
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.front.len() + self.back.len();
+    (len, Some(len))
+  }
+}
+
+impl<T> DoubleEndedIterator for RingIter<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.back.next_back().or_else(|| self.front.next_back())
+  }
+}
+
+pub struct RingIterMut<'a, T: 'a> {
+  front: slice::IterMut<'a, T>,
+  back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for RingIterMut<'a, T> {
+  type Item = &'a mut T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.front.next().or_else(|| self.back.next())
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.front.len() + self.back.len();
+    (len, Some(len))
+  }
+}
+
+impl<T> DoubleEndedIterator for RingIterMut<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.back.next_back().or_else(|| self.front.next_back())
+  }
+}
+
+pub struct RingIntoIter<T> {
+  deque: RingDeque<T>,
+}
+
+impl<T> Iterator for RingIntoIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.deque.pop_front().ok()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.deque.len, Some(self.deque.len))
+  }
+}
+
+impl<T> DoubleEndedIterator for RingIntoIter<T> {
+  fn next_back(&mut self) -> Option<T> {
+    self.deque.pop_back().ok()
+  }
+}
+
+// The drained items are taken out up front, so the deque is already closed
+// up even if the iterator isn't consumed.
+pub struct RingDrain<T> {
+  items: std::vec::IntoIter<T>,
+}
+
+impl<T> Iterator for RingDrain<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.items.next()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.items.size_hint()
+  }
+}
+
+impl<T> DoubleEndedIterator for RingDrain<T> {
+  fn next_back(&mut self) -> Option<T> {
+    self.items.next_back()
+  }
+}
+
+mod private {
+  use super::*;
+
+  pub fn uninit_buffer<T>(capacity: usize) -> Vec<MaybeUninit<T>> {
+    (0..capacity).map(|_| MaybeUninit::uninit()).collect()
+  }
+
+  pub fn slot<T>(deque: &RingDeque<T>, index: usize) -> usize {
+    (deque.head + index) % deque.buffer.len()
+  }
+
+  // Callers make sure `index` is within `len`.
+  pub fn read<T>(deque: &mut RingDeque<T>, index: usize) -> T {
+    let slot = slot(deque, index);
+    unsafe { deque.buffer[slot].assume_init_read() }
+  }
+
+  pub fn write<T>(deque: &mut RingDeque<T>, index: usize, item: T) {
+    let slot = slot(deque, index);
+    deque.buffer[slot].write(item);
+  }
+
+  pub fn grow<T>(deque: &mut RingDeque<T>) {
+    let capacity = (deque.buffer.len() * 2).max(4);
+    let mut buffer = uninit_buffer(capacity);
+    for (index, slot) in buffer.iter_mut().enumerate().take(deque.len) {
+      slot.write(read(deque, index));
+    }
+    deque.buffer = buffer;
+    deque.head = 0;
+  }
+
+  // The raw operations below assume there is room, or an item to take.
+  pub fn push_back_raw<T>(deque: &mut RingDeque<T>, item: T) {
+    write(deque, deque.len, item);
+    deque.len += 1;
+  }
+
+  pub fn push_front_raw<T>(deque: &mut RingDeque<T>, item: T) {
+    deque.head = (deque.head + deque.buffer.len() - 1) % deque.buffer.len();
+    write(deque, 0, item);
+    deque.len += 1;
+  }
+
+  pub fn pop_front_raw<T>(deque: &mut RingDeque<T>) -> T {
+    let item = read(deque, 0);
+    deque.head = (deque.head + 1) % deque.buffer.len();
+    deque.len -= 1;
+    item
+  }
+
+  pub fn pop_back_raw<T>(deque: &mut RingDeque<T>) -> T {
+    deque.len -= 1;
+    read(deque, deque.len)
+  }
+
+  pub fn bounds<R>(range: R, len: usize) -> Result<(usize, usize), IndexError>
+  where
+    R: RangeBounds<usize>,
+  {
+    let start = match range.start_bound() {
+      Bound::Included(start) => *start,
+      Bound::Excluded(start) => start + 1,
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(end) => end + 1,
+      Bound::Excluded(end) => *end,
+      Bound::Unbounded => len,
+    };
+    if start > end || end > len {
+      return Err(IndexError::OutOfBounds);
+    }
+    Ok((start, end))
+  }
+}
+
+impl<T> RingDeque<T> {
+  pub const fn new() -> Self {
+    Self {
+      buffer: Vec::new(),
+      head: 0,
+      len: 0,
+    }
+  }
+
+  // `push_front` and `push_back` grow past `capacity` when needed.
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      buffer: private::uninit_buffer(capacity),
+      head: 0,
+      len: 0,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn is_full(&self) -> bool {
+    self.len == self.buffer.len()
+  }
+
+  pub fn len(&self) -> i32 {
+    self.len as i32
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.buffer.len()
+  }
+
+  pub fn get(&self, index: usize) -> Option<&T> {
+    if index >= self.len {
+      return None;
+    }
+    let slot = private::slot(self, index);
+    Some(unsafe { self.buffer[slot].assume_init_ref() })
+  }
+
+  pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+    if index >= self.len {
+      return None;
+    }
+    let slot = private::slot(self, index);
+    Some(unsafe { self.buffer[slot].assume_init_mut() })
+  }
+
+  pub fn front(&self) -> Option<&T> {
+    self.get(0)
+  }
+
+  pub fn back(&self) -> Option<&T> {
+    self.get(self.len.wrapping_sub(1))
+  }
+
+  // Grows the buffer when it's full.
+  pub fn push_front(&mut self, item: T) {
+    if self.is_full() {
+      private::grow(self);
+    }
+    private::push_front_raw(self, item);
+  }
+
+  pub fn push_back(&mut self, item: T) {
+    if self.is_full() {
+      private::grow(self);
+    }
+    private::push_back_raw(self, item);
+  }
+
+  // Hands `item` back instead of growing when the deque is full.
+  pub fn try_push_front(&mut self, item: T) -> Result<(), PushError<T>> {
+    if self.is_full() {
+      return Err(PushError::Full(item));
+    }
+    private::push_front_raw(self, item);
+    Ok(())
+  }
+
+  pub fn try_push_back(&mut self, item: T) -> Result<(), PushError<T>> {
+    if self.is_full() {
+      return Err(PushError::Full(item));
+    }
+    private::push_back_raw(self, item);
+    Ok(())
+  }
+
+  // Evicts the back instead of growing when the deque is full, and returns
+  // it. With no capacity at all, `item` itself comes straight back.
+  pub fn push_front_overwrite(&mut self, item: T) -> Option<T> {
+    if self.buffer.is_empty() {
+      return Some(item);
+    }
+    let evicted = self.is_full().then(|| private::pop_back_raw(self));
+    private::push_front_raw(self, item);
+    evicted
+  }
+
+  // Evicts the front, which is the oldest item when only pushing back.
+  pub fn push_back_overwrite(&mut self, item: T) -> Option<T> {
+    if self.buffer.is_empty() {
+      return Some(item);
+    }
+    let evicted = self.is_full().then(|| private::pop_front_raw(self));
+    private::push_back_raw(self, item);
+    evicted
+  }
+
+  pub fn pop_front(&mut self) -> Result<T, RemoveError> {
+    if self.is_empty() {
+      return Err(RemoveError::EmptyList);
+    }
+    Ok(private::pop_front_raw(self))
+  }
+
+  pub fn pop_back(&mut self) -> Result<T, RemoveError> {
+    if self.is_empty() {
+      return Err(RemoveError::EmptyList);
+    }
+    Ok(private::pop_back_raw(self))
+  }
+
+  // Moves the first `n` items to the back, shifting whichever side is shorter.
+  pub fn rotate_left(&mut self, n: usize) -> Result<(), IndexError> {
+    if n > self.len {
+      return Err(IndexError::OutOfBounds);
+    }
+    if n <= self.len - n {
+      for _ in 0..n {
+        let item = private::pop_front_raw(self);
+        private::push_back_raw(self, item);
+      }
+    } else {
+      for _ in n..self.len {
+        let item = private::pop_back_raw(self);
+        private::push_front_raw(self, item);
+      }
+    }
+    Ok(())
+  }
+
+  pub fn rotate_right(&mut self, n: usize) -> Result<(), IndexError> {
+    if n > self.len {
+      return Err(IndexError::OutOfBounds);
+    }
+    self.rotate_left(self.len - n)
+  }
+
+  pub fn as_slices(&self) -> (&[T], &[T]) {
+    let end = (self.head + self.len).min(self.buffer.len());
+    let wrapped = (self.head + self.len).saturating_sub(self.buffer.len());
+    let (front, back) = (&self.buffer[self.head..end], &self.buffer[..wrapped]);
+    unsafe {
+      (
+        &*(front as *const [MaybeUninit<T>] as *const [T]),
+        &*(back as *const [MaybeUninit<T>] as *const [T]),
+      )
+    }
+  }
+
+  pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+    let end = (self.head + self.len).min(self.buffer.len());
+    let wrapped = (self.head + self.len).saturating_sub(self.buffer.len());
+    let (back, front) = self.buffer.split_at_mut(self.head);
+    let (front, back) = (&mut front[..end - self.head], &mut back[..wrapped]);
+    unsafe {
+      (
+        &mut *(front as *mut [MaybeUninit<T>] as *mut [T]),
+        &mut *(back as *mut [MaybeUninit<T>] as *mut [T]),
+      )
+    }
+  }
+
+  // Moves the items so they don't wrap around, and returns them in order.
+  pub fn make_contiguous(&mut self) -> &mut [T] {
+    if self.head + self.len > self.buffer.len() {
+      self.buffer.rotate_left(self.head);
+      self.head = 0;
+    }
+    self.as_mut_slices().0
+  }
+
+  // Removes the items in `range`, closing the gap from the shorter side.
+  pub fn drain<R>(&mut self, range: R) -> Result<RingDrain<T>, IndexError>
+  where
+    R: RangeBounds<usize>,
+  {
+    let (start, end) = private::bounds(range, self.len)?;
+    let count = end - start;
+    let items: Vec<T> = (start..end)
+      .map(|index| private::read(self, index))
+      .collect();
+    if start < self.len - end {
+      for index in (0..start).rev() {
+        let item = private::read(self, index);
+        private::write(self, index + count, item);
+      }
+      if count > 0 {
+        self.head = private::slot(self, count);
+      }
+    } else {
+      for index in end..self.len {
+        let item = private::read(self, index);
+        private::write(self, index - count, item);
+      }
+    }
+    self.len -= count;
+    Ok(RingDrain {
+      items: items.into_iter(),
+    })
+  }
+
+  pub fn clear(&mut self) {
+    while self.pop_back().is_ok() {}
+    self.head = 0;
+  }
+
+  pub fn iter(&self) -> RingIter<'_, T> {
+    let (front, back) = self.as_slices();
+    RingIter {
+      front: front.iter(),
+      back: back.iter(),
+    }
+  }
+
+  pub fn iter_mut(&mut self) -> RingIterMut<'_, T> {
+    let (front, back) = self.as_mut_slices();
+    RingIterMut {
+      front: front.iter_mut(),
+      back: back.iter_mut(),
+    }
+  }
+}
+
+impl<T> Default for RingDeque<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> From<Vec<T>> for RingDeque<T> {
+  fn from(items: Vec<T>) -> Self {
+    let len = items.len();
+    Self {
+      buffer: items.into_iter().map(MaybeUninit::new).collect(),
+      head: 0,
+      len,
+    }
+  }
+}
+
+impl<T> Clone for RingDeque<T>
+where
+  T: Clone,
+{
+  fn clone(&self) -> Self {
+    let mut deque = Self::with_capacity(self.buffer.len());
+    self
+      .iter()
+      .for_each(|item| private::push_back_raw(&mut deque, item.clone()));
+    deque
+  }
+}
+
+impl<T> Drop for RingDeque<T> {
+  fn drop(&mut self) {
+    self.clear()
+  }
+}
+
+impl<T> Index<usize> for RingDeque<T> {
+  type Output = T;
+
+  fn index(&self, index: usize) -> &T {
+    self.get(index).expect("index out of bounds")
+  }
+}
+
+impl<T> IndexMut<usize> for RingDeque<T> {
+  fn index_mut(&mut self, index: usize) -> &mut T {
+    self.get_mut(index).expect("index out of bounds")
+  }
+}
+
+impl<T> PartialEq for RingDeque<T>
+where
+  T: PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.len == other.len && self.iter().eq(other.iter())
+  }
+}
+
+impl<T> fmt::Debug for RingDeque<T>
+where
+  T: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T> IntoIterator for RingDeque<T> {
+  type Item = T;
+  type IntoIter = RingIntoIter<T>;
+
+  fn into_iter(self) -> RingIntoIter<T> {
+    RingIntoIter { deque: self }
+  }
+}
+
+impl<'a, T> IntoIterator for &'a RingDeque<T> {
+  type Item = &'a T;
+  type IntoIter = RingIter<'a, T>;
+
+  fn into_iter(self) -> RingIter<'a, T> {
+    self.iter()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a mut RingDeque<T> {
+  type Item = &'a mut T;
+  type IntoIter = RingIterMut<'a, T>;
+
+  fn into_iter(self) -> RingIterMut<'a, T> {
+    self.iter_mut()
+  }
+}
+
+#[cfg(test)]
+#[path = "./ring-deque_test.rs"]
+mod test;
@@ -0,0 +1,341 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type RingDequeT = RingDeque<i32>;
+
+  pub fn ring_deque_empty() -> RingDequeT {
+    RingDeque::new()
+  }
+
+  // [0, 1, 2, 3] stored wrapped around the end of a buffer of four.
+  pub fn ring_deque_filled() -> RingDequeT {
+    let mut deque = RingDeque::with_capacity(4);
+    deque.push_back(2);
+    deque.push_back(3);
+    deque.push_front(1);
+    deque.push_front(0);
+    deque
+  }
+
+  pub fn items<T: Clone>(deque: &RingDeque<T>) -> Vec<T> {
+    deque.iter().cloned().collect()
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = RingDeque::<i32>::new();
+    assert!(op.is_empty());
+    assert_eq!(op.capacity(), 0)
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::ring_deque_empty().len(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let deque = setup::ring_deque_filled();
+    assert_eq!(deque.len(), 4);
+    assert!(deque.is_full())
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let deque = setup::ring_deque_empty();
+    assert_eq!(deque.get(0), None);
+    assert_eq!(deque.front(), None);
+    assert_eq!(deque.back(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut deque = setup::ring_deque_filled();
+    assert_eq!(deque.head, 2);
+    assert_eq!(deque.get(3), Some(&3));
+    assert_eq!(deque.get(4), None);
+    assert_eq!((deque.front(), deque.back()), (Some(&0), Some(&3)));
+    *deque.get_mut(1).unwrap() = 10;
+    deque[2] = 20;
+    assert_eq!(deque[1], 10);
+    assert_eq!(setup::items(&deque), vec![0, 10, 20, 3])
+  }
+}
+
+#[cfg(test)]
+mod push_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut deque = setup::ring_deque_empty();
+    deque.push_back(0);
+    assert_eq!(setup::items(&deque), vec![0])
+  }
+
+  #[test]
+  fn to_filled_growing() {
+    let mut deque = setup::ring_deque_filled();
+    deque.push_back(4);
+    assert_eq!(deque.capacity(), 8);
+    assert_eq!(setup::items(&deque), vec![0, 1, 2, 3, 4])
+  }
+}
+
+#[cfg(test)]
+mod push_front {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut deque = setup::ring_deque_empty();
+    deque.push_front(0);
+    deque.push_front(-1);
+    assert_eq!(setup::items(&deque), vec![-1, 0])
+  }
+
+  #[test]
+  fn to_filled_growing() {
+    let mut deque = setup::ring_deque_filled();
+    deque.push_front(-1);
+    assert_eq!(setup::items(&deque), vec![-1, 0, 1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod try_push_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut deque = setup::ring_deque_empty();
+    assert_eq!(deque.try_push_back(0), Err(PushError::Full(0)));
+    assert_eq!(deque.try_push_front(0), Err(PushError::Full(0)));
+    assert_eq!(deque.capacity(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut deque = RingDeque::with_capacity(2);
+    assert_eq!(deque.try_push_back(0), Ok(()));
+    assert_eq!(deque.try_push_front(-1), Ok(()));
+    assert_eq!(deque.try_push_back(2), Err(PushError::Full(2)));
+    assert_eq!(deque.try_push_front(2), Err(PushError::Full(2)));
+    assert_eq!(deque.capacity(), 2);
+    assert_eq!(setup::items(&deque), vec![-1, 0])
+  }
+}
+
+#[cfg(test)]
+mod push_back_overwrite {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut deque = setup::ring_deque_empty();
+    assert_eq!(deque.push_back_overwrite(1), Some(1));
+    assert_eq!(deque.push_front_overwrite(1), Some(1));
+    assert!(deque.is_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut deque = RingDeque::with_capacity(3);
+    let evicted: Vec<Option<i32>> = (0..6).map(|item| deque.push_back_overwrite(item)).collect();
+    assert_eq!(evicted, vec![None, None, None, Some(0), Some(1), Some(2)]);
+    assert_eq!(deque.capacity(), 3);
+    assert_eq!(setup::items(&deque), vec![3, 4, 5]);
+    assert_eq!(deque.push_front_overwrite(2), Some(5));
+    assert_eq!(setup::items(&deque), vec![2, 3, 4])
+  }
+}
+
+#[cfg(test)]
+mod pop_front {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut deque = setup::ring_deque_empty();
+    assert_eq!(deque.pop_front(), Err(RemoveError::EmptyList))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut deque = setup::ring_deque_filled();
+    assert_eq!(deque.pop_front(), Ok(0));
+    assert_eq!(deque.pop_front(), Ok(1));
+    assert_eq!(deque.pop_front(), Ok(2));
+    assert_eq!(setup::items(&deque), vec![3])
+  }
+}
+
+#[cfg(test)]
+mod pop_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut deque = setup::ring_deque_empty();
+    assert_eq!(deque.pop_back(), Err(RemoveError::EmptyList))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut deque = setup::ring_deque_filled();
+    assert_eq!(deque.pop_back(), Ok(3));
+    assert_eq!(deque.pop_back(), Ok(2));
+    assert_eq!(deque.pop_back(), Ok(1));
+    assert_eq!(setup::items(&deque), vec![0])
+  }
+}
+
+#[cfg(test)]
+mod rotate {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut deque = setup::ring_deque_empty();
+    assert_eq!(deque.rotate_left(0), Ok(()));
+    assert_eq!(deque.rotate_right(1), Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    for n in 0..=4 {
+      let mut deque = setup::ring_deque_filled();
+      deque.push_back(4);
+      assert_eq!(deque.rotate_left(n), Ok(()));
+      let expected: Vec<i32> = (0..5).map(|item| (item + n as i32) % 5).collect();
+      assert_eq!(setup::items(&deque), expected);
+      assert_eq!(deque.rotate_right(n), Ok(()));
+      assert_eq!(setup::items(&deque), vec![0, 1, 2, 3, 4])
+    }
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let mut deque = setup::ring_deque_filled();
+    assert_eq!(deque.rotate_left(5), Err(IndexError::OutOfBounds));
+    assert_eq!(setup::items(&deque), vec![0, 1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod make_contiguous {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut deque = setup::ring_deque_empty();
+    assert!(deque.make_contiguous().is_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut deque = setup::ring_deque_filled();
+    assert_eq!(deque.as_slices(), (&[0, 1][..], &[2, 3][..]));
+    let op = deque.make_contiguous();
+    op.reverse();
+    assert_eq!(deque.as_slices(), (&[3, 2, 1, 0][..], &[][..]));
+    assert_eq!(deque.head, 0)
+  }
+}
+
+#[cfg(test)]
+mod drain {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut deque = setup::ring_deque_empty();
+    assert_eq!(deque.drain(..).unwrap().count(), 0);
+    assert_eq!(deque.drain(..1).map(|_| ()), Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    for start in 0..=4 {
+      for end in start..=4 {
+        let mut deque = setup::ring_deque_filled();
+        let op: Vec<i32> = deque.drain(start..end).unwrap().collect();
+        assert_eq!(op, (start as i32..end as i32).collect::<Vec<_>>());
+        let expected: Vec<i32> = (0..4)
+          .filter(|item| *item < start as i32 || *item >= end as i32)
+          .collect();
+        assert_eq!(setup::items(&deque), expected)
+      }
+    }
+  }
+
+  #[test]
+  fn to_owned_values() {
+    let mut deque = RingDeque::from(vec![String::from("a"), String::from("b")]);
+    deque.push_front(String::from("z"));
+    let mut op = deque.drain(1..).unwrap();
+    assert_eq!(op.next_back(), Some(String::from("b")));
+    drop(op);
+    assert_eq!(setup::items(&deque), vec![String::from("z")])
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let deque = setup::ring_deque_empty();
+    assert_eq!(deque.iter().next(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut deque = setup::ring_deque_filled();
+    assert_eq!(
+      deque.iter().rev().copied().collect::<Vec<_>>(),
+      vec![3, 2, 1, 0]
+    );
+    deque.iter_mut().for_each(|item| *item *= 10);
+    let mut op = deque.clone().into_iter();
+    assert_eq!(op.next_back(), Some(30));
+    assert_eq!(op.collect::<Vec<_>>(), vec![0, 10, 20]);
+    assert_eq!(format!("{deque:?}"), "[0, 10, 20, 30]")
+  }
+}
+
+#[cfg(test)]
+mod clone {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let deque = RingDeque::with_capacity(2);
+    let mut op = deque.clone();
+    op.push_back(vec![1]);
+    op.push_back(vec![2]);
+    let mut other = op.clone();
+    assert_eq!(other, op);
+    assert_eq!(other.push_back_overwrite(vec![3]), Some(vec![1]));
+    assert_ne!(other, op);
+    assert!(deque.is_empty())
+  }
+}
@@ -0,0 +1,15 @@
+#[path = "./robin-hood-map.rs"]
+pub mod robin_hood_map;
+pub use robin_hood_map::*;
+
+#[path = "./robin-hood-set.rs"]
+pub mod robin_hood_set;
+pub use robin_hood_set::*;
+
+#[path = "./skip-list-map.rs"]
+pub mod skip_list_map;
+pub use skip_list_map::*;
+
+#[path = "./skip-list-set.rs"]
+pub mod skip_list_set;
+pub use skip_list_set::*;
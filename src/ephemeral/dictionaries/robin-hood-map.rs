@@ -0,0 +1,611 @@
+use std::{
+  collections::hash_map::RandomState,
+  fmt,
+  hash::{BuildHasher, Hash},
+  mem,
+};
+
+use crate::ephemeral::lists::RemoveError;
+
+const MIN_SLOTS: usize = 8;
+const DEFAULT_LOAD_FACTOR: f64 = 0.875;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IterOrder {
+  // Removal moves the last entry into the hole, keeping entries dense.
+  Unspecified,
+  // Removal leaves a hole, compacted away once holes outnumber entries.
+  Insertion,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum LoadFactorError {
+  OutOfRange,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Slot {
+  hash: u64,
+  entry: usize,
+}
+
+#[derive(Clone)]
+struct Bucket<K, V> {
+  hash: u64,
+  key: K,
+  value: V,
+}
+
+// The table only holds hashes and indices into `entries`, so probing stays
+// within a few cache lines whatever the size of `K` and `V`.
+//
+// Invariants:
+//    - `slots.len()` is zero or a power of two, and at least one slot is empty
+//    - a slot sits at most one step further from its home than the slot
+//      before it (robin hood), so lookups stop at the first richer slot
+//    - every entry in `entries` is referenced by exactly one slot, holes by
+//      none, and `holes` counts them
+//    - holes only occur under `IterOrder::Insertion`
+#[derive(Clone)]
+pub struct RobinHoodMap<K, V, S = RandomState> {
+  slots: Vec<Option<Slot>>,
+  entries: Vec<Option<Bucket<K, V>>>,
+  holes: usize,
+  load_factor: f64,
+  order: IterOrder,
+  hasher: S,
+}
+
+pub struct RobinHoodIter<'a, K, V> {
+  entries: std::slice::Iter<'a, Option<Bucket<K, V>>>,
+}
+
+impl<'a, K, V> Iterator for RobinHoodIter<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .entries
+      .by_ref()
+      .flatten()
+      .next()
+      .map(|bucket| (&bucket.key, &bucket.value))
+  }
+}
+
+pub struct RobinHoodIterMut<'a, K, V> {
+  entries: std::slice::IterMut<'a, Option<Bucket<K, V>>>,
+}
+
+impl<'a, K, V> Iterator for RobinHoodIterMut<'a, K, V> {
+  type Item = (&'a K, &'a mut V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .entries
+      .by_ref()
+      .flatten()
+      .next()
+      .map(|bucket| (&bucket.key, &mut bucket.value))
+  }
+}
+
+pub struct RobinHoodIntoIter<K, V> {
+  entries: std::vec::IntoIter<Option<Bucket<K, V>>>,
+}
+
+impl<K, V> Iterator for RobinHoodIntoIter<K, V> {
+  type Item = (K, V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self
+      .entries
+      .by_ref()
+      .flatten()
+      .next()
+      .map(|bucket| (bucket.key, bucket.value))
+  }
+}
+
+pub enum RobinHoodEntry<'a, K, V, S> {
+  Occupied(RobinHoodOccupied<'a, K, V, S>),
+  Vacant(RobinHoodVacant<'a, K, V, S>),
+}
+
+pub struct RobinHoodOccupied<'a, K, V, S> {
+  map: &'a mut RobinHoodMap<K, V, S>,
+  position: usize,
+}
+
+pub struct RobinHoodVacant<'a, K, V, S> {
+  map: &'a mut RobinHoodMap<K, V, S>,
+  hash: u64,
+  key: K,
+}
+
+mod private {
+  use super::*;
+
+  // How many steps `position` is past the home slot of `hash`.
+  pub fn distance(slots: usize, position: usize, hash: u64) -> usize {
+    position.wrapping_sub(hash as usize) & (slots - 1)
+  }
+
+  pub fn max_len(slots: usize, load_factor: f64) -> usize {
+    ((slots as f64 * load_factor) as usize).min(slots.saturating_sub(1))
+  }
+
+  // Smallest table able to hold `len` entries under `load_factor`.
+  pub fn slots_for(len: usize, load_factor: f64) -> usize {
+    if len == 0 {
+      return 0;
+    }
+    let mut slots = MIN_SLOTS;
+    while max_len(slots, load_factor) < len {
+      slots *= 2;
+    }
+    slots
+  }
+
+  pub fn find<K, V>(
+    slots: &[Option<Slot>],
+    entries: &[Option<Bucket<K, V>>],
+    hash: u64,
+    key: &K,
+  ) -> Option<usize>
+  where
+    K: Eq,
+  {
+    if slots.is_empty() {
+      return None;
+    }
+    let mask = slots.len() - 1;
+    let mut position = hash as usize & mask;
+    let mut probed = 0;
+    while let Some(slot) = slots[position] {
+      if distance(slots.len(), position, slot.hash) < probed {
+        return None;
+      }
+      if slot.hash == hash && matches!(&entries[slot.entry], Some(bucket) if bucket.key == *key) {
+        return Some(position);
+      }
+      position = (position + 1) & mask;
+      probed += 1;
+    }
+    None
+  }
+
+  // Position of the slot referencing `entry`, which must be in the table.
+  pub fn find_entry(slots: &[Option<Slot>], hash: u64, entry: usize) -> usize {
+    let mask = slots.len() - 1;
+    let mut position = hash as usize & mask;
+    while slots[position].map(|slot| slot.entry) != Some(entry) {
+      position = (position + 1) & mask;
+    }
+    position
+  }
+
+  // Takes over from the first resident closer to its home than `slot` is to
+  // its own, then carries on probing with the displaced resident.
+  pub fn place(slots: &mut [Option<Slot>], mut slot: Slot) -> usize {
+    let mask = slots.len() - 1;
+    let mut position = slot.hash as usize & mask;
+    let mut probed = 0;
+    let mut placed = None;
+    loop {
+      match slots[position] {
+        None => {
+          slots[position] = Some(slot);
+          return placed.unwrap_or(position);
+        }
+        Some(resident) => {
+          let resident_distance = distance(slots.len(), position, resident.hash);
+          if resident_distance < probed {
+            slots[position] = Some(slot);
+            placed = placed.or(Some(position));
+            slot = resident;
+            probed = resident_distance;
+          }
+        }
+      }
+      position = (position + 1) & mask;
+      probed += 1;
+    }
+  }
+
+  // Backward-shift deletion: the following run moves one step closer to
+  // home, so the table never needs tombstones.
+  pub fn unplace(slots: &mut [Option<Slot>], mut position: usize) {
+    let mask = slots.len() - 1;
+    loop {
+      let next = (position + 1) & mask;
+      match slots[next] {
+        Some(slot) if distance(slots.len(), next, slot.hash) > 0 => {
+          slots[position] = Some(slot);
+          position = next;
+        }
+        _ => {
+          slots[position] = None;
+          return;
+        }
+      }
+    }
+  }
+
+  // Compacts holes away and places every entry into a table of `slots`.
+  pub fn rebuild<K, V, S>(map: &mut RobinHoodMap<K, V, S>, slots: usize) {
+    if map.holes > 0 {
+      map.entries.retain(Option::is_some);
+      map.holes = 0;
+    }
+    map.slots = vec![None; slots];
+    for (entry, bucket) in map.entries.iter().enumerate() {
+      let hash = bucket.as_ref().unwrap().hash;
+      place(&mut map.slots, Slot { hash, entry });
+    }
+  }
+
+  pub fn reserve<K, V, S>(map: &mut RobinHoodMap<K, V, S>, additional: usize) {
+    let len = map.entries.len() - map.holes + additional;
+    if len > max_len(map.slots.len(), map.load_factor) {
+      rebuild(map, slots_for(len, map.load_factor));
+    }
+  }
+
+  // Returns the position of the new slot.
+  pub fn push<K, V, S>(map: &mut RobinHoodMap<K, V, S>, hash: u64, key: K, value: V) -> usize {
+    reserve(map, 1);
+    let entry = map.entries.len();
+    map.entries.push(Some(Bucket { hash, key, value }));
+    place(&mut map.slots, Slot { hash, entry })
+  }
+
+  pub fn remove_at<K, V, S>(map: &mut RobinHoodMap<K, V, S>, position: usize) -> Bucket<K, V> {
+    let entry = map.slots[position].unwrap().entry;
+    unplace(&mut map.slots, position);
+    match map.order {
+      IterOrder::Unspecified => {
+        let bucket = map.entries.swap_remove(entry).unwrap();
+        if let Some(moved) = map.entries.get(entry) {
+          let hash = moved.as_ref().unwrap().hash;
+          let position = find_entry(&map.slots, hash, map.entries.len());
+          map.slots[position] = Some(Slot { hash, entry });
+        }
+        bucket
+      }
+      IterOrder::Insertion => {
+        let bucket = map.entries[entry].take().unwrap();
+        map.holes += 1;
+        while let Some(None) = map.entries.last() {
+          map.entries.pop();
+          map.holes -= 1;
+        }
+        if map.holes * 2 > map.entries.len() {
+          rebuild(map, map.slots.len());
+        }
+        bucket
+      }
+    }
+  }
+
+  pub fn bucket<K, V, S>(map: &RobinHoodMap<K, V, S>, position: usize) -> &Bucket<K, V> {
+    map.entries[map.slots[position].unwrap().entry]
+      .as_ref()
+      .unwrap()
+  }
+
+  pub fn bucket_mut<K, V, S>(
+    map: &mut RobinHoodMap<K, V, S>,
+    position: usize,
+  ) -> &mut Bucket<K, V> {
+    map.entries[map.slots[position].unwrap().entry]
+      .as_mut()
+      .unwrap()
+  }
+}
+
+impl<'a, K, V, S> RobinHoodEntry<'a, K, V, S> {
+  pub fn key(&self) -> &K {
+    match self {
+      RobinHoodEntry::Occupied(entry) => entry.key(),
+      RobinHoodEntry::Vacant(entry) => entry.key(),
+    }
+  }
+
+  pub fn or_insert(self, default: V) -> &'a mut V {
+    match self {
+      RobinHoodEntry::Occupied(entry) => entry.into_mut(),
+      RobinHoodEntry::Vacant(entry) => entry.insert(default),
+    }
+  }
+
+  pub fn or_insert_with(self, default: fn() -> V) -> &'a mut V {
+    match self {
+      RobinHoodEntry::Occupied(entry) => entry.into_mut(),
+      RobinHoodEntry::Vacant(entry) => entry.insert(default()),
+    }
+  }
+
+  pub fn or_default(self) -> &'a mut V
+  where
+    V: Default,
+  {
+    self.or_insert_with(V::default)
+  }
+
+  pub fn and_modify(mut self, f: fn(&mut V)) -> Self {
+    if let RobinHoodEntry::Occupied(entry) = &mut self {
+      f(entry.get_mut());
+    }
+    self
+  }
+}
+
+impl<'a, K, V, S> RobinHoodOccupied<'a, K, V, S> {
+  pub fn key(&self) -> &K {
+    &private::bucket(self.map, self.position).key
+  }
+
+  pub fn get(&self) -> &V {
+    &private::bucket(self.map, self.position).value
+  }
+
+  pub fn get_mut(&mut self) -> &mut V {
+    &mut private::bucket_mut(self.map, self.position).value
+  }
+
+  pub fn into_mut(self) -> &'a mut V {
+    &mut private::bucket_mut(self.map, self.position).value
+  }
+
+  // Returns the old value.
+  pub fn insert(&mut self, value: V) -> V {
+    mem::replace(self.get_mut(), value)
+  }
+
+  pub fn remove(self) -> V {
+    private::remove_at(self.map, self.position).value
+  }
+}
+
+impl<'a, K, V, S> RobinHoodVacant<'a, K, V, S> {
+  pub fn key(&self) -> &K {
+    &self.key
+  }
+
+  pub fn insert(self, value: V) -> &'a mut V {
+    let position = private::push(self.map, self.hash, self.key, value);
+    &mut private::bucket_mut(self.map, position).value
+  }
+}
+
+impl<K, V> RobinHoodMap<K, V, RandomState>
+where
+  K: Hash + Eq,
+{
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+}
+
+impl<K, V> Default for RobinHoodMap<K, V, RandomState>
+where
+  K: Hash + Eq,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K, V, S> RobinHoodMap<K, V, S>
+where
+  K: Hash + Eq,
+  S: BuildHasher,
+{
+  pub fn with_hasher(hasher: S) -> Self {
+    Self {
+      slots: vec![],
+      entries: vec![],
+      holes: 0,
+      load_factor: DEFAULT_LOAD_FACTOR,
+      order: IterOrder::Unspecified,
+      hasher,
+    }
+  }
+
+  // `load_factor` must lie strictly between 0 and 1, since probing relies on
+  // finding an empty slot.
+  pub fn with_config(
+    hasher: S,
+    load_factor: f64,
+    order: IterOrder,
+  ) -> Result<Self, LoadFactorError> {
+    if !(load_factor > 0.0 && load_factor < 1.0) {
+      return Err(LoadFactorError::OutOfRange);
+    }
+    Ok(Self {
+      load_factor,
+      order,
+      ..Self::with_hasher(hasher)
+    })
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  pub fn len(&self) -> i32 {
+    (self.entries.len() - self.holes) as i32
+  }
+
+  // How many entries fit before the table grows.
+  pub fn capacity(&self) -> usize {
+    private::max_len(self.slots.len(), self.load_factor)
+  }
+
+  pub fn load_factor(&self) -> f64 {
+    self.load_factor
+  }
+
+  pub fn order(&self) -> IterOrder {
+    self.order
+  }
+
+  pub fn get(&self, key: &K) -> Option<&V> {
+    let hash = self.hasher.hash_one(key);
+    private::find(&self.slots, &self.entries, hash, key)
+      .map(|position| &private::bucket(self, position).value)
+  }
+
+  pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    let hash = self.hasher.hash_one(key);
+    private::find(&self.slots, &self.entries, hash, key)
+      .map(|position| &mut private::bucket_mut(self, position).value)
+  }
+
+  pub fn contains_key(&self, key: &K) -> bool {
+    self.get(key).is_some()
+  }
+
+  pub fn entry(&mut self, key: K) -> RobinHoodEntry<'_, K, V, S> {
+    let hash = self.hasher.hash_one(&key);
+    match private::find(&self.slots, &self.entries, hash, &key) {
+      Some(position) => RobinHoodEntry::Occupied(RobinHoodOccupied {
+        map: self,
+        position,
+      }),
+      None => RobinHoodEntry::Vacant(RobinHoodVacant {
+        map: self,
+        hash,
+        key,
+      }),
+    }
+  }
+
+  // Returns the old value when `key` was already present, keeping the
+  // original key and its place in insertion order.
+  pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+    match self.entry(key) {
+      RobinHoodEntry::Occupied(mut entry) => Some(entry.insert(value)),
+      RobinHoodEntry::Vacant(entry) => {
+        entry.insert(value);
+        None
+      }
+    }
+  }
+
+  pub fn remove_entry(&mut self, key: &K) -> Result<(K, V), RemoveError> {
+    let hash = self.hasher.hash_one(key);
+    let position =
+      private::find(&self.slots, &self.entries, hash, key).ok_or(RemoveError::ItemNotFound)?;
+    let bucket = private::remove_at(self, position);
+    Ok((bucket.key, bucket.value))
+  }
+
+  pub fn remove(&mut self, key: &K) -> Result<V, RemoveError> {
+    self.remove_entry(key).map(|(_, value)| value)
+  }
+
+  pub fn retain(&mut self, f: fn(&K, &mut V) -> bool) {
+    self.entries.retain_mut(|bucket| match bucket {
+      Some(bucket) => f(&bucket.key, &mut bucket.value),
+      None => false,
+    });
+    self.holes = 0;
+    private::rebuild(self, self.slots.len());
+  }
+
+  // Empties the map up front, keeping its table for reuse.
+  pub fn drain(&mut self) -> RobinHoodIntoIter<K, V> {
+    self.slots.fill(None);
+    self.holes = 0;
+    RobinHoodIntoIter {
+      entries: mem::take(&mut self.entries).into_iter(),
+    }
+  }
+
+  pub fn clear(&mut self) {
+    self.drain();
+  }
+
+  pub fn reserve(&mut self, additional: usize) {
+    private::reserve(self, additional);
+    self.entries.reserve(additional);
+  }
+
+  // Shrinks the table to the smallest one holding the current entries under
+  // the load factor, dropping any holes.
+  pub fn shrink_to_fit(&mut self) {
+    let slots = private::slots_for(self.len() as usize, self.load_factor);
+    private::rebuild(self, slots);
+    self.entries.shrink_to_fit();
+  }
+
+  // Under `IterOrder::Insertion` entries come in the order their keys were
+  // first inserted; otherwise the order is unspecified.
+  pub fn iter(&self) -> RobinHoodIter<'_, K, V> {
+    RobinHoodIter {
+      entries: self.entries.iter(),
+    }
+  }
+
+  pub fn iter_mut(&mut self) -> RobinHoodIterMut<'_, K, V> {
+    RobinHoodIterMut {
+      entries: self.entries.iter_mut(),
+    }
+  }
+}
+
+impl<K, V, S> IntoIterator for RobinHoodMap<K, V, S> {
+  type Item = (K, V);
+  type IntoIter = RobinHoodIntoIter<K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    RobinHoodIntoIter {
+      entries: self.entries.into_iter(),
+    }
+  }
+}
+
+impl<K, V, S> PartialEq for RobinHoodMap<K, V, S>
+where
+  K: Hash + Eq,
+  V: PartialEq,
+  S: BuildHasher,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.len() == other.len()
+      && self
+        .iter()
+        .all(|(key, value)| other.get(key) == Some(value))
+  }
+}
+
+impl<K, V, S> fmt::Debug for RobinHoodMap<K, V, S>
+where
+  K: Hash + Eq + fmt::Debug,
+  V: fmt::Debug,
+  S: BuildHasher,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<K, V> From<Vec<(K, V)>> for RobinHoodMap<K, V, RandomState>
+where
+  K: Hash + Eq,
+{
+  fn from(vec: Vec<(K, V)>) -> Self {
+    let mut map = Self::new();
+    map.reserve(vec.len());
+    for (key, value) in vec {
+      map.insert(key, value);
+    }
+    map
+  }
+}
+
+#[cfg(test)]
+#[path = "./robin-hood-map_test.rs"]
+mod test;
@@ -0,0 +1,545 @@
+use super::*;
+
+mod setup {
+  use super::*;
+  use std::hash::{BuildHasherDefault, Hasher};
+
+  // Hashes non-negative integers to themselves, so tests can pick slots.
+  #[derive(Default)]
+  pub struct IdentityHasher(u64);
+
+  impl Hasher for IdentityHasher {
+    fn finish(&self) -> u64 {
+      self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+      for (index, byte) in bytes.iter().enumerate() {
+        self.0 |= (*byte as u64) << (8 * index);
+      }
+    }
+  }
+
+  // Only keeps the two lowest bits, so most keys share a home slot.
+  #[derive(Default)]
+  pub struct CollidingHasher(IdentityHasher);
+
+  impl Hasher for CollidingHasher {
+    fn finish(&self) -> u64 {
+      self.0.finish() & 0b11
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+      self.0.write(bytes)
+    }
+  }
+
+  pub type Identity = BuildHasherDefault<IdentityHasher>;
+  pub type Colliding = BuildHasherDefault<CollidingHasher>;
+  pub type RobinHoodMapT = RobinHoodMap<i32, char, Identity>;
+
+  pub fn robin_hood_map_empty() -> RobinHoodMapT {
+    RobinHoodMap::with_hasher(Identity::default())
+  }
+
+  pub fn robin_hood_map_filled() -> RobinHoodMapT {
+    let mut map = robin_hood_map_empty();
+    for (key, value) in ['a', 'b', 'c', 'd'].into_iter().enumerate() {
+      map.insert(key as i32, value);
+    }
+    map
+  }
+
+  pub fn ordered<S: BuildHasher + Default>() -> RobinHoodMap<i32, char, S> {
+    RobinHoodMap::with_config(S::default(), 0.5, IterOrder::Insertion).unwrap()
+  }
+
+  pub fn entries<K, V, S>(map: &RobinHoodMap<K, V, S>) -> Vec<(K, V)>
+  where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher,
+  {
+    map
+      .iter()
+      .map(|(key, value)| (key.clone(), value.clone()))
+      .collect()
+  }
+
+  // Checks the table layout against the invariants on `RobinHoodMap`.
+  pub fn check<K, V, S>(map: &RobinHoodMap<K, V, S>)
+  where
+    K: Hash + Eq,
+    S: BuildHasher,
+  {
+    let slots = map.slots.len();
+    assert!(slots == 0 || slots.is_power_of_two());
+    assert!(map.len() as usize <= map.capacity());
+    let mut referenced = vec![false; map.entries.len()];
+    for (position, slot) in map.slots.iter().enumerate() {
+      let Some(slot) = slot else { continue };
+      let bucket = map.entries[slot.entry].as_ref().unwrap();
+      assert_eq!(bucket.hash, slot.hash);
+      assert_eq!(map.hasher.hash_one(&bucket.key), slot.hash);
+      assert!(!referenced[slot.entry]);
+      referenced[slot.entry] = true;
+      let distance = private::distance(slots, position, slot.hash);
+      if distance > 0 {
+        let before = map.slots[(position + slots - 1) % slots].unwrap();
+        let before = private::distance(slots, (position + slots - 1) % slots, before.hash);
+        assert!(before + 1 >= distance);
+      }
+    }
+    for (entry, bucket) in map.entries.iter().enumerate() {
+      assert_eq!(referenced[entry], bucket.is_some());
+    }
+    let holes = map.entries.iter().filter(|bucket| bucket.is_none()).count();
+    assert_eq!(map.holes, holes);
+    assert!(map.order == IterOrder::Insertion || holes == 0)
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = RobinHoodMap::<i32, i32>::new();
+    assert!(op.is_empty());
+    assert_eq!(op.capacity(), 0);
+    assert_eq!(op.order(), IterOrder::Unspecified)
+  }
+}
+
+#[cfg(test)]
+mod with_config {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    for load_factor in [0.0, 1.0, -0.5, 2.0, f64::NAN] {
+      let op = RobinHoodMap::<i32, i32>::with_config(
+        RandomState::new(),
+        load_factor,
+        IterOrder::Unspecified,
+      );
+      assert_eq!(op.map(|_| ()), Err(LoadFactorError::OutOfRange))
+    }
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map: RobinHoodMap<i32, char, setup::Identity> = setup::ordered();
+    assert_eq!(map.load_factor(), 0.5);
+    for key in 0..5 {
+      map.insert(key, 'x');
+    }
+    assert_eq!(map.slots.len(), 16);
+    assert_eq!(map.capacity(), 8);
+    setup::check(&map)
+  }
+
+  #[test]
+  fn to_filled_near_full() {
+    let mut map =
+      RobinHoodMap::with_config(setup::Colliding::default(), 0.999, IterOrder::Unspecified)
+        .unwrap();
+    for key in 0..7 {
+      map.insert(key, key);
+    }
+    assert_eq!(map.slots.len(), 8);
+    assert_eq!(map.get(&100), None);
+    map.insert(7, 7);
+    assert_eq!(map.slots.len(), 16);
+    setup::check(&map)
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::robin_hood_map_empty();
+    assert_eq!(map.get(&0), None);
+    assert_eq!(map.get_mut(&0), None);
+    assert!(!map.contains_key(&0))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::robin_hood_map_filled();
+    assert_eq!(map.get(&2), Some(&'c'));
+    assert_eq!(map.get(&4), None);
+    *map.get_mut(&3).unwrap() = 'z';
+    assert_eq!(map.get(&3), Some(&'z'));
+    assert!(map.contains_key(&0))
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::robin_hood_map_empty();
+    assert_eq!(map.insert(7, 'h'), None);
+    assert_eq!(map.get(&7), Some(&'h'));
+    assert_eq!(map.slots.len(), 8);
+    setup::check(&map)
+  }
+
+  #[test]
+  fn to_filled_with_present_key() {
+    let mut map = setup::robin_hood_map_filled();
+    assert_eq!(map.insert(2, 'z'), Some('c'));
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.get(&2), Some(&'z'))
+  }
+
+  #[test]
+  fn to_filled_displacing() {
+    // 8 and 16 share home slot 0 with 0, pushing 1 and 2 further along.
+    let mut map = setup::robin_hood_map_empty();
+    for key in [0, 1, 2, 8, 16] {
+      map.insert(key, 'x');
+      setup::check(&map);
+    }
+    let op: Vec<i32> = map
+      .slots
+      .iter()
+      .map(|slot| slot.map_or(-1, |slot| slot.hash as i32))
+      .collect();
+    assert_eq!(op, vec![0, 8, 16, 1, 2, -1, -1, -1])
+  }
+
+  #[test]
+  fn to_filled_colliding() {
+    let mut map = RobinHoodMap::with_hasher(setup::Colliding::default());
+    for key in 0..100 {
+      map.insert(key, key * 2);
+    }
+    setup::check(&map);
+    assert!((0..100).all(|key| map.get(&key) == Some(&(key * 2))));
+    assert_eq!(map.get(&100), None)
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::robin_hood_map_empty();
+    assert_eq!(map.remove(&0), Err(RemoveError::ItemNotFound))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::robin_hood_map_filled();
+    assert_eq!(map.remove(&1), Ok('b'));
+    assert_eq!(map.remove(&1), Err(RemoveError::ItemNotFound));
+    assert_eq!(map.remove_entry(&0), Ok((0, 'a')));
+    assert_eq!(map.len(), 2);
+    setup::check(&map)
+  }
+
+  #[test]
+  fn to_filled_shifting_back() {
+    let mut map = setup::robin_hood_map_empty();
+    for key in [0, 1, 2, 8, 16] {
+      map.insert(key, 'x');
+    }
+    map.remove(&0).unwrap();
+    setup::check(&map);
+    let op: Vec<i32> = map
+      .slots
+      .iter()
+      .map(|slot| slot.map_or(-1, |slot| slot.hash as i32))
+      .collect();
+    assert_eq!(op, vec![8, 16, 1, 2, -1, -1, -1, -1])
+  }
+
+  #[test]
+  fn to_filled_ordered() {
+    let mut map: RobinHoodMap<i32, char, setup::Identity> = setup::ordered();
+    for (key, value) in [(5, 'a'), (3, 'b'), (9, 'c'), (1, 'd')] {
+      map.insert(key, value);
+    }
+    assert_eq!(map.remove(&3), Ok('b'));
+    setup::check(&map);
+    assert_eq!(map.holes, 1);
+    map.insert(3, 'e');
+    assert_eq!(
+      setup::entries(&map),
+      vec![(5, 'a'), (9, 'c'), (1, 'd'), (3, 'e')]
+    );
+    assert_eq!(map.remove(&3), Ok('e'));
+    assert_eq!((map.entries.len(), map.holes), (4, 1))
+  }
+
+  #[test]
+  fn to_filled_compacting() {
+    let mut map: RobinHoodMap<i32, char, setup::Identity> = setup::ordered();
+    for key in 0..6 {
+      map.insert(key, 'x');
+    }
+    for key in 0..4 {
+      map.remove(&key).unwrap();
+      setup::check(&map);
+    }
+    assert_eq!(map.holes, 0);
+    assert_eq!(setup::entries(&map), vec![(4, 'x'), (5, 'x')])
+  }
+}
+
+#[cfg(test)]
+mod entry {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::robin_hood_map_empty();
+    assert_eq!(map.entry(3).key(), &3);
+    *map.entry(3).or_insert('a') = 'b';
+    assert_eq!(
+      map
+        .entry(4)
+        .and_modify(|value| *value = 'z')
+        .or_insert_with(|| 'c'),
+      &'c'
+    );
+    assert_eq!(setup::entries(&map), vec![(3, 'b'), (4, 'c')]);
+    setup::check(&map)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::robin_hood_map_filled();
+    assert_eq!(
+      map.entry(1).and_modify(|value| *value = 'z').or_insert('y'),
+      &'z'
+    );
+    match map.entry(2) {
+      RobinHoodEntry::Occupied(mut entry) => {
+        assert_eq!((entry.key(), entry.get()), (&2, &'c'));
+        assert_eq!(entry.insert('w'), 'c');
+        assert_eq!(entry.remove(), 'w')
+      }
+      RobinHoodEntry::Vacant(_) => panic!("expected an occupied entry"),
+    }
+    assert_eq!(map.len(), 3);
+    setup::check(&map)
+  }
+
+  #[test]
+  fn to_filled_counting() {
+    let mut map = RobinHoodMap::new();
+    for word in "the cat and the hat and the bat".split(' ') {
+      *map.entry(word).or_default() += 1;
+    }
+    assert_eq!(map.get(&"the"), Some(&3));
+    assert_eq!(map.get(&"and"), Some(&2));
+    assert_eq!(map.len(), 5)
+  }
+}
+
+#[cfg(test)]
+mod retain {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::robin_hood_map_empty();
+    map.retain(|_, _| false);
+    assert!(map.is_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map: RobinHoodMap<i32, char, setup::Identity> = setup::ordered();
+    for (key, value) in [(5, 'a'), (3, 'b'), (9, 'c'), (1, 'd'), (6, 'e')] {
+      map.insert(key, value);
+    }
+    map.remove(&9).unwrap();
+    map.retain(|key, value| {
+      *value = value.to_ascii_uppercase();
+      key % 2 == 1
+    });
+    setup::check(&map);
+    assert_eq!(setup::entries(&map), vec![(5, 'A'), (3, 'B'), (1, 'D')])
+  }
+}
+
+#[cfg(test)]
+mod drain {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::robin_hood_map_empty();
+    assert_eq!(map.drain().count(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::robin_hood_map_filled();
+    let slots = map.slots.len();
+    let mut op: Vec<(i32, char)> = map.drain().collect();
+    op.sort();
+    assert_eq!(op, vec![(0, 'a'), (1, 'b'), (2, 'c'), (3, 'd')]);
+    assert!(map.is_empty());
+    assert_eq!(map.slots.len(), slots);
+    assert_eq!(map.get(&0), None);
+    setup::check(&map)
+  }
+
+  #[test]
+  fn to_filled_dropped_early() {
+    let mut map = RobinHoodMap::new();
+    map.insert(1, String::from("a"));
+    map.insert(2, String::from("b"));
+    drop(map.drain());
+    assert!(map.is_empty());
+    map.insert(3, String::from("c"));
+    assert_eq!(map.len(), 1)
+  }
+}
+
+#[cfg(test)]
+mod shrink_to_fit {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::robin_hood_map_filled();
+    map.clear();
+    map.shrink_to_fit();
+    assert_eq!(map.slots.len(), 0);
+    assert_eq!(map.insert(1, 'a'), None);
+    setup::check(&map)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map: RobinHoodMap<i32, char, setup::Identity> = setup::ordered();
+    for key in 0..40 {
+      map.insert(key, 'x');
+    }
+    assert_eq!(map.slots.len(), 128);
+    for key in 3..40 {
+      map.remove(&key).unwrap();
+    }
+    map.reserve(1);
+    assert_eq!(map.slots.len(), 128);
+    map.shrink_to_fit();
+    assert_eq!(map.slots.len(), 8);
+    setup::check(&map);
+    assert_eq!(setup::entries(&map), vec![(0, 'x'), (1, 'x'), (2, 'x')])
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::robin_hood_map_empty().iter().count(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::robin_hood_map_filled();
+    map
+      .iter_mut()
+      .for_each(|(_, value)| *value = value.to_ascii_uppercase());
+    let mut op: Vec<(i32, char)> = map.into_iter().collect();
+    op.sort();
+    assert_eq!(op, vec![(0, 'A'), (1, 'B'), (2, 'C'), (3, 'D')])
+  }
+
+  #[test]
+  fn to_filled_ordered() {
+    let mut map: RobinHoodMap<i32, char, setup::Identity> = setup::ordered();
+    for (key, value) in [(40, 'a'), (3, 'b'), (17, 'c'), (8, 'd')] {
+      map.insert(key, value);
+    }
+    map.insert(3, 'z');
+    assert_eq!(
+      setup::entries(&map),
+      vec![(40, 'a'), (3, 'z'), (17, 'c'), (8, 'd')]
+    );
+    assert_eq!(format!("{map:?}"), "{40: 'a', 3: 'z', 17: 'c', 8: 'd'}")
+  }
+}
+
+#[cfg(test)]
+mod random {
+  use super::*;
+  use std::collections::HashMap;
+
+  // Replays random operations against `std::collections::HashMap`.
+  fn replay(mut map: RobinHoodMap<u64, u64, setup::Colliding>) {
+    let mut model = HashMap::new();
+    let mut order: Vec<u64> = vec![];
+    crate::test_support::replay(|_, rng| {
+      let key = rng.next_u64() % 200;
+      match rng.next_u64() % 3 {
+        0 => {
+          let removed = model.remove(&key).ok_or(RemoveError::ItemNotFound);
+          assert_eq!(map.remove(&key), removed);
+          order.retain(|other| *other != key);
+        }
+        _ => {
+          let value = rng.next_u64();
+          if model.insert(key, value).is_none() {
+            order.push(key);
+          }
+          map.insert(key, value);
+        }
+      }
+      setup::check(&map);
+      assert_eq!(map.len(), model.len() as i32);
+    });
+    assert!(model.iter().all(|(key, value)| map.get(key) == Some(value)));
+    if map.order() == IterOrder::Insertion {
+      assert_eq!(map.iter().map(|(key, _)| *key).collect::<Vec<_>>(), order)
+    }
+  }
+
+  #[test]
+  fn to_model() {
+    replay(RobinHoodMap::with_hasher(setup::Colliding::default()))
+  }
+
+  #[test]
+  fn to_model_ordered() {
+    replay(
+      RobinHoodMap::with_config(setup::Colliding::default(), 0.7, IterOrder::Insertion).unwrap(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod clone {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let map = setup::robin_hood_map_filled();
+    let mut op = map.clone();
+    assert_eq!(op, map);
+    op.insert(0, 'z');
+    assert_ne!(op, map);
+    assert_eq!(map.get(&0), Some(&'a'));
+    assert_eq!(
+      RobinHoodMap::from(vec![(1, 'b'), (1, 'c')]).get(&1),
+      Some(&'c')
+    )
+  }
+}
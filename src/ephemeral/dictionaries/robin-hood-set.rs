@@ -0,0 +1,175 @@
+use std::{
+  collections::hash_map::RandomState,
+  fmt,
+  hash::{BuildHasher, Hash},
+};
+
+use super::{IterOrder, LoadFactorError, RobinHoodIntoIter, RobinHoodIter, RobinHoodMap};
+use crate::ephemeral::lists::RemoveError;
+
+#[derive(Clone)]
+pub struct RobinHoodSet<T, S = RandomState> {
+  map: RobinHoodMap<T, (), S>,
+}
+
+pub struct RobinHoodSetIter<'a, T> {
+  entries: RobinHoodIter<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for RobinHoodSetIter<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.entries.next().map(|(item, _)| item)
+  }
+}
+
+pub struct RobinHoodSetIntoIter<T> {
+  entries: RobinHoodIntoIter<T, ()>,
+}
+
+impl<T> Iterator for RobinHoodSetIntoIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.entries.next().map(|(item, _)| item)
+  }
+}
+
+impl<T> RobinHoodSet<T, RandomState>
+where
+  T: Hash + Eq,
+{
+  pub fn new() -> Self {
+    Self {
+      map: RobinHoodMap::new(),
+    }
+  }
+}
+
+impl<T> Default for RobinHoodSet<T, RandomState>
+where
+  T: Hash + Eq,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, S> RobinHoodSet<T, S>
+where
+  T: Hash + Eq,
+  S: BuildHasher,
+{
+  pub fn with_hasher(hasher: S) -> Self {
+    Self {
+      map: RobinHoodMap::with_hasher(hasher),
+    }
+  }
+
+  pub fn with_config(
+    hasher: S,
+    load_factor: f64,
+    order: IterOrder,
+  ) -> Result<Self, LoadFactorError> {
+    RobinHoodMap::with_config(hasher, load_factor, order).map(|map| Self { map })
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.map.is_empty()
+  }
+
+  pub fn len(&self) -> i32 {
+    self.map.len()
+  }
+
+  pub fn capacity(&self) -> usize {
+    self.map.capacity()
+  }
+
+  pub fn contains(&self, item: &T) -> bool {
+    self.map.contains_key(item)
+  }
+
+  // Returns whether `item` was newly added.
+  pub fn insert(&mut self, item: T) -> bool {
+    self.map.insert(item, ()).is_none()
+  }
+
+  pub fn remove(&mut self, item: &T) -> Result<T, RemoveError> {
+    self.map.remove_entry(item).map(|(item, _)| item)
+  }
+
+  pub fn retain(&mut self, f: fn(&T) -> bool) {
+    let kept: Vec<T> = self.drain().filter(f).collect();
+    for item in kept {
+      self.insert(item);
+    }
+  }
+
+  pub fn drain(&mut self) -> RobinHoodSetIntoIter<T> {
+    RobinHoodSetIntoIter {
+      entries: self.map.drain(),
+    }
+  }
+
+  pub fn clear(&mut self) {
+    self.map.clear()
+  }
+
+  pub fn shrink_to_fit(&mut self) {
+    self.map.shrink_to_fit()
+  }
+
+  pub fn iter(&self) -> RobinHoodSetIter<'_, T> {
+    RobinHoodSetIter {
+      entries: self.map.iter(),
+    }
+  }
+}
+
+impl<T, S> IntoIterator for RobinHoodSet<T, S> {
+  type Item = T;
+  type IntoIter = RobinHoodSetIntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    RobinHoodSetIntoIter {
+      entries: self.map.into_iter(),
+    }
+  }
+}
+
+impl<T, S> PartialEq for RobinHoodSet<T, S>
+where
+  T: Hash + Eq,
+  S: BuildHasher,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.map == other.map
+  }
+}
+
+impl<T, S> fmt::Debug for RobinHoodSet<T, S>
+where
+  T: Hash + Eq + fmt::Debug,
+  S: BuildHasher,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
+
+impl<T> From<Vec<T>> for RobinHoodSet<T, RandomState>
+where
+  T: Hash + Eq,
+{
+  fn from(vec: Vec<T>) -> Self {
+    Self {
+      map: RobinHoodMap::from(vec.into_iter().map(|item| (item, ())).collect::<Vec<_>>()),
+    }
+  }
+}
+
+#[cfg(test)]
+#[path = "./robin-hood-set_test.rs"]
+mod test;
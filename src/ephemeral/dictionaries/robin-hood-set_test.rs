@@ -0,0 +1,116 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type RobinHoodSetT = RobinHoodSet<i32>;
+
+  pub fn robin_hood_set_empty() -> RobinHoodSetT {
+    RobinHoodSet::new()
+  }
+
+  pub fn robin_hood_set_filled() -> RobinHoodSetT {
+    RobinHoodSet::from(vec![0, 1, 2, 3])
+  }
+
+  pub fn sorted(set: &RobinHoodSetT) -> Vec<i32> {
+    let mut items: Vec<i32> = set.iter().copied().collect();
+    items.sort();
+    items
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut set = setup::robin_hood_set_empty();
+    assert!(set.insert(7));
+    assert!(set.contains(&7));
+    assert_eq!(set.len(), 1)
+  }
+
+  #[test]
+  fn to_filled_with_present_item() {
+    let mut set = setup::robin_hood_set_filled();
+    assert!(!set.insert(2));
+    assert_eq!(set.len(), 4)
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut set = setup::robin_hood_set_empty();
+    assert_eq!(set.remove(&0), Err(RemoveError::ItemNotFound))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut set = setup::robin_hood_set_filled();
+    assert_eq!(set.remove(&2), Ok(2));
+    assert!(!set.contains(&2));
+    assert_eq!(setup::sorted(&set), vec![0, 1, 3])
+  }
+}
+
+#[cfg(test)]
+mod retain {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut set = setup::robin_hood_set_empty();
+    set.retain(|_| true);
+    assert!(set.is_empty())
+  }
+
+  #[test]
+  fn to_filled_ordered() {
+    let mut set = RobinHoodSet::with_config(RandomState::new(), 0.5, IterOrder::Insertion).unwrap();
+    for item in [9, 4, 7, 2, 5] {
+      set.insert(item);
+    }
+    set.retain(|item| item % 2 == 1);
+    assert_eq!(set.iter().copied().collect::<Vec<_>>(), vec![9, 7, 5])
+  }
+}
+
+#[cfg(test)]
+mod drain {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let mut set = setup::robin_hood_set_filled();
+    let mut op: Vec<i32> = set.drain().collect();
+    op.sort();
+    assert_eq!(op, vec![0, 1, 2, 3]);
+    assert!(set.is_empty());
+    set.shrink_to_fit();
+    assert_eq!(set.capacity(), 0)
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::robin_hood_set_empty().iter().count(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let set = setup::robin_hood_set_filled();
+    assert_eq!(setup::sorted(&set), vec![0, 1, 2, 3]);
+    assert_eq!(set.clone(), set);
+    assert_eq!(set.into_iter().count(), 4)
+  }
+}
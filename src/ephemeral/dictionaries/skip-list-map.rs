@@ -0,0 +1,551 @@
+use std::{
+  fmt,
+  marker::PhantomData,
+  mem,
+  ops::{Bound, RangeBounds},
+  ptr::NonNull,
+};
+
+use crate::{ephemeral::lists::RemoveError, SplitMix64};
+
+const DEFAULT_MAX_LEVEL: usize = 32;
+const DEFAULT_BRANCHING: u64 = 2;
+
+type SkipLink<K, V> = Option<NonNull<SkipNode<K, V>>>;
+
+// `next[level]` is the following node on `level`, so a node's height is
+// `next.len()`.
+pub struct SkipNode<K, V> {
+  key: K,
+  value: V,
+  next: Vec<SkipLink<K, V>>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum LevelConfigError {
+  MaxLevel,
+  Branching,
+}
+
+// Invariants:
+//    - level 0 links every node in strictly increasing key order, and each
+//      higher level links a subsequence of the level below
+//    - a node of height `h` is linked on levels `0..h`, and `head` has one
+//      link per level up to the tallest node, but at least one
+//    - `tail` is the last node on level 0
+//    - every node is owned by exactly one list and was allocated by
+//      `private::new_link`, so it's freed with `Box::from_raw` once unlinked
+pub struct SkipListMap<K, V> {
+  head: Vec<SkipLink<K, V>>,
+  tail: SkipLink<K, V>,
+  len: usize,
+  rng: SplitMix64,
+  max_level: usize,
+  branching: u64,
+  marker: PhantomData<Box<SkipNode<K, V>>>,
+}
+
+// Owns its entries like a `BTreeMap` does, so it can cross threads on the
+// same terms.
+unsafe impl<K: Send, V: Send> Send for SkipListMap<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for SkipListMap<K, V> {}
+
+pub struct SkipIter<'a, K: 'a, V: 'a> {
+  current: SkipLink<K, V>,
+  marker: PhantomData<&'a SkipNode<K, V>>,
+}
+
+impl<'a, K, V> Iterator for SkipIter<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.current.map(|node| unsafe {
+      let node = &*node.as_ptr();
+      self.current = node.next[0];
+      (&node.key, &node.value)
+    })
+  }
+}
+
+unsafe impl<K: Sync, V: Sync> Send for SkipIter<'_, K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for SkipIter<'_, K, V> {}
+
+pub struct SkipIterMut<'a, K: 'a, V: 'a> {
+  current: SkipLink<K, V>,
+  marker: PhantomData<&'a mut SkipNode<K, V>>,
+}
+
+impl<'a, K, V> Iterator for SkipIterMut<'a, K, V> {
+  type Item = (&'a K, &'a mut V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.current.map(|node| unsafe {
+      let node = &mut *node.as_ptr();
+      self.current = node.next[0];
+      (&node.key, &mut node.value)
+    })
+  }
+}
+
+unsafe impl<K: Sync, V: Send> Send for SkipIterMut<'_, K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for SkipIterMut<'_, K, V> {}
+
+pub struct SkipRange<'a, K: 'a, V: 'a, R> {
+  current: SkipLink<K, V>,
+  range: R,
+  marker: PhantomData<&'a SkipNode<K, V>>,
+}
+
+impl<'a, K, V, R> Iterator for SkipRange<'a, K, V, R>
+where
+  K: Ord,
+  R: RangeBounds<K>,
+{
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let node = unsafe { &*self.current?.as_ptr() };
+    let inside = match self.range.end_bound() {
+      Bound::Included(end) => node.key <= *end,
+      Bound::Excluded(end) => node.key < *end,
+      Bound::Unbounded => true,
+    };
+    self.current = if inside { node.next[0] } else { None };
+    inside.then_some((&node.key, &node.value))
+  }
+}
+
+unsafe impl<K: Sync, V: Sync, R: Send> Send for SkipRange<'_, K, V, R> {}
+unsafe impl<K: Sync, V: Sync, R: Sync> Sync for SkipRange<'_, K, V, R> {}
+
+pub struct SkipIntoIter<K, V> {
+  list: SkipListMap<K, V>,
+}
+
+impl<K, V> Iterator for SkipIntoIter<K, V> {
+  type Item = (K, V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.list.pop_first().ok()
+  }
+}
+
+mod private {
+  use super::*;
+
+  pub fn new_link<K, V>(key: K, value: V, height: usize) -> NonNull<SkipNode<K, V>> {
+    unsafe {
+      NonNull::new_unchecked(Box::into_raw(Box::new(SkipNode {
+        key,
+        value,
+        next: vec![None; height],
+      })))
+    }
+  }
+
+  // Takes back ownership of an unlinked node.
+  pub fn free_node<K, V>(node: NonNull<SkipNode<K, V>>) -> (K, V) {
+    let node = unsafe { Box::from_raw(node.as_ptr()) };
+    (node.key, node.value)
+  }
+
+  // Link leaving `from` on `level`, where `None` stands for the head.
+  pub fn forward<K, V>(
+    list: &SkipListMap<K, V>,
+    from: SkipLink<K, V>,
+    level: usize,
+  ) -> SkipLink<K, V> {
+    match from {
+      None => list.head[level],
+      Some(node) => unsafe { (&(*node.as_ptr()).next)[level] },
+    }
+  }
+
+  pub fn set_forward<K, V>(
+    list: &mut SkipListMap<K, V>,
+    from: SkipLink<K, V>,
+    level: usize,
+    to: SkipLink<K, V>,
+  ) {
+    match from {
+      None => list.head[level] = to,
+      Some(node) => unsafe { (&mut (*node.as_ptr()).next)[level] = to },
+    }
+  }
+
+  // Walks down from the top level, stopping on each level at the last node
+  // whose key is `before` the target and handing it to `visit`. Returns the
+  // stop on level 0, so the target itself, if any, follows it.
+  pub fn descend<K, V, B, F>(list: &SkipListMap<K, V>, before: B, mut visit: F) -> SkipLink<K, V>
+  where
+    B: Fn(&K) -> bool,
+    F: FnMut(usize, SkipLink<K, V>),
+  {
+    let mut pred = None;
+    for level in (0..list.head.len()).rev() {
+      while let Some(next) = forward(list, pred, level) {
+        if !before(unsafe { &(*next.as_ptr()).key }) {
+          break;
+        }
+        pred = Some(next);
+      }
+      visit(level, pred);
+    }
+    pred
+  }
+
+  // Each extra level is kept with probability `1 / branching`.
+  pub fn random_height<K, V>(list: &mut SkipListMap<K, V>) -> usize {
+    let mut height = 1;
+    while height < list.max_level && list.rng.next_u64().is_multiple_of(list.branching) {
+      height += 1;
+    }
+    height
+  }
+
+  // Unlinks `node`, given its predecessor on every level it's linked on.
+  pub fn unlink<K, V>(
+    list: &mut SkipListMap<K, V>,
+    node: NonNull<SkipNode<K, V>>,
+    update: &[SkipLink<K, V>],
+  ) -> (K, V) {
+    let height = unsafe { (*node.as_ptr()).next.len() };
+    for (level, pred) in update.iter().enumerate().take(height) {
+      let next = unsafe { (&(*node.as_ptr()).next)[level] };
+      set_forward(list, *pred, level, next);
+    }
+    if list.tail == Some(node) {
+      list.tail = update[0];
+    }
+    while list.head.len() > 1 && list.head.last() == Some(&None) {
+      list.head.pop();
+    }
+    list.len -= 1;
+    free_node(node)
+  }
+}
+
+impl<K, V> SkipListMap<K, V> {
+  pub fn new() -> Self {
+    Self::with_rng(SplitMix64::default())
+  }
+
+  // Fixes the level generator's seed, so the same inserts always build the
+  // same structure.
+  pub fn with_seed(seed: u64) -> Self {
+    Self::with_rng(SplitMix64::new(seed))
+  }
+
+  // Nodes grow one level with probability `1 / branching`, up to `max_level`
+  // levels, which should be around `log(n) / log(branching)` for the largest
+  // expected `n`.
+  pub fn with_config(
+    seed: u64,
+    max_level: usize,
+    branching: u64,
+  ) -> Result<Self, LevelConfigError> {
+    if !(1..=64).contains(&max_level) {
+      return Err(LevelConfigError::MaxLevel);
+    }
+    if branching < 2 {
+      return Err(LevelConfigError::Branching);
+    }
+    let mut list = Self::with_seed(seed);
+    list.max_level = max_level;
+    list.branching = branching;
+    Ok(list)
+  }
+
+  fn with_rng(rng: SplitMix64) -> Self {
+    Self {
+      head: vec![None],
+      tail: None,
+      len: 0,
+      rng,
+      max_level: DEFAULT_MAX_LEVEL,
+      branching: DEFAULT_BRANCHING,
+      marker: PhantomData,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn len(&self) -> i32 {
+    self.len as i32
+  }
+
+  // Levels currently in use, at least one.
+  pub fn height(&self) -> usize {
+    self.head.len()
+  }
+
+  // How many nodes are linked on `level`.
+  pub fn level_len(&self, level: usize) -> usize {
+    let mut count = 0;
+    let mut current = self.head.get(level).copied().flatten();
+    while let Some(node) = current {
+      count += 1;
+      current = unsafe { (&(*node.as_ptr()).next)[level] };
+    }
+    count
+  }
+
+  pub fn first(&self) -> Option<(&K, &V)> {
+    self.head[0].map(|node| unsafe {
+      let node = &*node.as_ptr();
+      (&node.key, &node.value)
+    })
+  }
+
+  pub fn last(&self) -> Option<(&K, &V)> {
+    self.tail.map(|node| unsafe {
+      let node = &*node.as_ptr();
+      (&node.key, &node.value)
+    })
+  }
+
+  // The first node is first on every level it's linked on, so this needs
+  // no search.
+  pub fn pop_first(&mut self) -> Result<(K, V), RemoveError> {
+    let node = self.head[0].ok_or(RemoveError::EmptyList)?;
+    let update = vec![None; self.head.len()];
+    Ok(private::unlink(self, node, &update))
+  }
+
+  pub fn clear(&mut self) {
+    while let Some(node) = self.head[0] {
+      unsafe {
+        self.head[0] = (&(*node.as_ptr()).next)[0];
+      }
+      private::free_node(node);
+    }
+    self.head = vec![None];
+    self.tail = None;
+    self.len = 0;
+  }
+
+  pub fn iter(&self) -> SkipIter<'_, K, V> {
+    SkipIter {
+      current: self.head[0],
+      marker: PhantomData,
+    }
+  }
+
+  pub fn iter_mut(&mut self) -> SkipIterMut<'_, K, V> {
+    SkipIterMut {
+      current: self.head[0],
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<K, V> SkipListMap<K, V>
+where
+  K: Ord,
+{
+  pub fn get(&self, key: &K) -> Option<&V> {
+    let pred = private::descend(self, |other| other < key, |_, _| {});
+    private::forward(self, pred, 0)
+      .map(|node| unsafe { &*node.as_ptr() })
+      .filter(|node| node.key == *key)
+      .map(|node| &node.value)
+  }
+
+  pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    let pred = private::descend(self, |other| other < key, |_, _| {});
+    private::forward(self, pred, 0)
+      .map(|node| unsafe { &mut *node.as_ptr() })
+      .filter(|node| node.key == *key)
+      .map(|node| &mut node.value)
+  }
+
+  pub fn contains_key(&self, key: &K) -> bool {
+    self.get(key).is_some()
+  }
+
+  // Height of the node holding `key`.
+  pub fn node_height(&self, key: &K) -> Option<usize> {
+    let pred = private::descend(self, |other| other < key, |_, _| {});
+    private::forward(self, pred, 0)
+      .map(|node| unsafe { &*node.as_ptr() })
+      .filter(|node| node.key == *key)
+      .map(|node| node.next.len())
+  }
+
+  // Replaces the value when `key` is already present, keeping the node and
+  // its height.
+  pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+    let mut update = vec![None; self.head.len()];
+    let pred = private::descend(
+      self,
+      |other| *other < key,
+      |level, link| update[level] = link,
+    );
+    if let Some(node) = private::forward(self, pred, 0) {
+      let node = unsafe { &mut *node.as_ptr() };
+      if node.key == key {
+        return Some(mem::replace(&mut node.value, value));
+      }
+    }
+    let height = private::random_height(self);
+    while self.head.len() < height {
+      self.head.push(None);
+      update.push(None);
+    }
+    let node = private::new_link(key, value, height);
+    for (level, pred) in update.iter().enumerate().take(height) {
+      let next = private::forward(self, *pred, level);
+      unsafe { (&mut (*node.as_ptr()).next)[level] = next };
+      private::set_forward(self, *pred, level, Some(node));
+    }
+    if self.tail == update[0] {
+      self.tail = Some(node);
+    }
+    self.len += 1;
+    None
+  }
+
+  pub fn remove_entry(&mut self, key: &K) -> Result<(K, V), RemoveError> {
+    let mut update = vec![None; self.head.len()];
+    let pred = private::descend(
+      self,
+      |other| other < key,
+      |level, link| update[level] = link,
+    );
+    match private::forward(self, pred, 0) {
+      Some(node) if unsafe { &(*node.as_ptr()).key } == key => {
+        Ok(private::unlink(self, node, &update))
+      }
+      _ => Err(RemoveError::ItemNotFound),
+    }
+  }
+
+  pub fn remove(&mut self, key: &K) -> Result<V, RemoveError> {
+    self.remove_entry(key).map(|(_, value)| value)
+  }
+
+  // Finds the start in O(log n) expected, then walks level 0. A range that
+  // ends before it starts is empty.
+  pub fn range<R>(&self, range: R) -> SkipRange<'_, K, V, R>
+  where
+    R: RangeBounds<K>,
+  {
+    let current = match range.start_bound() {
+      Bound::Included(start) => private::forward(
+        self,
+        private::descend(self, |key| key < start, |_, _| {}),
+        0,
+      ),
+      Bound::Excluded(start) => private::forward(
+        self,
+        private::descend(self, |key| key <= start, |_, _| {}),
+        0,
+      ),
+      Bound::Unbounded => self.head[0],
+    };
+    SkipRange {
+      current,
+      range,
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<K, V> Default for SkipListMap<K, V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// Copies the structure node for node, heights and generator state included.
+impl<K, V> Clone for SkipListMap<K, V>
+where
+  K: Clone,
+  V: Clone,
+{
+  fn clone(&self) -> Self {
+    let mut list = Self::with_rng(self.rng);
+    list.max_level = self.max_level;
+    list.branching = self.branching;
+    list.head = vec![None; self.head.len()];
+    let mut last: Vec<SkipLink<K, V>> = vec![None; self.head.len()];
+    let mut current = self.head[0];
+    while let Some(source) = current {
+      let source = unsafe { &*source.as_ptr() };
+      current = source.next[0];
+      let height = source.next.len();
+      let node = private::new_link(source.key.clone(), source.value.clone(), height);
+      for (level, pred) in last.iter_mut().enumerate().take(height) {
+        private::set_forward(&mut list, *pred, level, Some(node));
+        *pred = Some(node);
+      }
+      list.len += 1;
+    }
+    list.tail = last[0];
+    list
+  }
+}
+
+// Frees the nodes one by one along level 0.
+impl<K, V> Drop for SkipListMap<K, V> {
+  fn drop(&mut self) {
+    self.clear();
+  }
+}
+
+impl<K, V> PartialEq for SkipListMap<K, V>
+where
+  K: PartialEq,
+  V: PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.len == other.len && self.iter().eq(other.iter())
+  }
+}
+
+impl<K, V> fmt::Debug for SkipListMap<K, V>
+where
+  K: fmt::Debug,
+  V: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<K, V> From<Vec<(K, V)>> for SkipListMap<K, V>
+where
+  K: Ord,
+{
+  fn from(vec: Vec<(K, V)>) -> Self {
+    let mut list = Self::new();
+    for (key, value) in vec {
+      list.insert(key, value);
+    }
+    list
+  }
+}
+
+impl<K, V> IntoIterator for SkipListMap<K, V> {
+  type Item = (K, V);
+  type IntoIter = SkipIntoIter<K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    SkipIntoIter { list: self }
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipListMap<K, V> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = SkipIter<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+#[cfg(test)]
+#[path = "./skip-list-map_test.rs"]
+mod test;
@@ -0,0 +1,356 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type SkipListMapT = SkipListMap<i32, char>;
+
+  pub fn skip_list_map_empty() -> SkipListMapT {
+    SkipListMap::with_seed(50)
+  }
+
+  pub fn skip_list_map_filled() -> SkipListMapT {
+    let mut map = skip_list_map_empty();
+    for (key, value) in [(3, 'd'), (0, 'a'), (2, 'c'), (1, 'b')] {
+      map.insert(key, value);
+    }
+    map
+  }
+
+  pub fn keys<V>(map: &SkipListMap<i32, V>) -> Vec<i32> {
+    map.iter().map(|(key, _)| *key).collect()
+  }
+
+  // Walks every level and checks it's sorted, a subsequence of the level
+  // below, and only links nodes tall enough for it.
+  pub fn check<V>(map: &SkipListMap<i32, V>) {
+    let mut below: Vec<i32> = vec![];
+    for level in 0..map.head.len() {
+      let mut keys = vec![];
+      let mut current = map.head[level];
+      while let Some(node) = current {
+        let node = unsafe { &*node.as_ptr() };
+        assert!(node.next.len() > level);
+        keys.push(node.key);
+        current = node.next[level];
+      }
+      assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+      if level == 0 {
+        assert_eq!(keys.len() as i32, map.len());
+        assert_eq!(map.last().map(|(key, _)| *key), keys.last().copied());
+      } else {
+        assert!(!keys.is_empty());
+        assert!(keys.iter().all(|key| below.binary_search(key).is_ok()));
+      }
+      assert_eq!(map.level_len(level), keys.len());
+      below = keys;
+    }
+  }
+}
+
+#[cfg(test)]
+mod with_config {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(
+      SkipListMap::<i32, i32>::with_config(0, 0, 2).err(),
+      Some(LevelConfigError::MaxLevel)
+    );
+    assert_eq!(
+      SkipListMap::<i32, i32>::with_config(0, 65, 2).err(),
+      Some(LevelConfigError::MaxLevel)
+    );
+    assert_eq!(
+      SkipListMap::<i32, i32>::with_config(0, 8, 1).err(),
+      Some(LevelConfigError::Branching)
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = SkipListMap::with_config(50, 3, 2).unwrap();
+    (0..200).for_each(|key| {
+      map.insert(key, ());
+    });
+    setup::check(&map);
+    assert!(map.height() <= 3);
+    let mut flat = SkipListMap::with_config(50, 1, 2).unwrap();
+    (0..20).for_each(|key| {
+      flat.insert(key, ());
+    });
+    setup::check(&flat);
+    assert_eq!(flat.height(), 1)
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::skip_list_map_empty();
+    assert_eq!(map.insert(7, 'x'), None);
+    assert_eq!(map.get(&7), Some(&'x'));
+    assert_eq!(map.first(), map.last());
+    setup::check(&map)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::skip_list_map_filled();
+    let height = map.node_height(&2);
+    assert_eq!(map.insert(2, 'z'), Some('c'));
+    assert_eq!(map.node_height(&2), height);
+    assert_eq!(map.insert(-1, 'y'), None);
+    assert_eq!(map.insert(9, 'w'), None);
+    setup::check(&map);
+    assert_eq!(setup::keys(&map), vec![-1, 0, 1, 2, 3, 9]);
+    assert_eq!(map.get(&2), Some(&'z'));
+    assert_eq!(map.last(), Some((&9, &'w')))
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::skip_list_map_empty();
+    assert_eq!(map.remove(&0), Err(RemoveError::ItemNotFound))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::skip_list_map_filled();
+    assert_eq!(map.remove(&3), Ok('d'));
+    assert_eq!(map.remove_entry(&1), Ok((1, 'b')));
+    assert_eq!(map.remove(&1), Err(RemoveError::ItemNotFound));
+    setup::check(&map);
+    assert_eq!(map.last(), Some((&2, &'c')));
+    assert_eq!(setup::keys(&map), vec![0, 2])
+  }
+
+  #[test]
+  fn to_filled_until_empty() {
+    let mut map = setup::skip_list_map_filled();
+    for key in [2, 0, 3, 1] {
+      map.remove(&key).unwrap();
+      setup::check(&map);
+    }
+    assert!(map.is_empty());
+    assert_eq!(map.height(), 1);
+    assert_eq!(map.last(), None)
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::skip_list_map_empty();
+    assert_eq!(map.get(&0), None);
+    assert_eq!(map.get_mut(&0), None);
+    assert_eq!(map.node_height(&0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::skip_list_map_filled();
+    *map.get_mut(&1).unwrap() = 'x';
+    assert_eq!(map.get(&1), Some(&'x'));
+    assert!(map.contains_key(&3));
+    assert!(!map.contains_key(&4));
+    assert!(map.node_height(&0).unwrap() >= 1)
+  }
+}
+
+#[cfg(test)]
+mod pop_first {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut map = setup::skip_list_map_empty();
+    assert_eq!(map.pop_first(), Err(RemoveError::EmptyList));
+    assert_eq!(map.first(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::skip_list_map_filled();
+    assert_eq!(map.first(), Some((&0, &'a')));
+    assert_eq!(map.pop_first(), Ok((0, 'a')));
+    assert_eq!(map.pop_first(), Ok((1, 'b')));
+    setup::check(&map);
+    assert_eq!(map.first(), Some((&2, &'c')));
+    map.pop_first().unwrap();
+    map.pop_first().unwrap();
+    assert_eq!(map.last(), None);
+    setup::check(&map)
+  }
+}
+
+#[cfg(test)]
+mod range {
+  use super::*;
+
+  fn keys<R: RangeBounds<i32>>(map: &SkipListMap<i32, ()>, range: R) -> Vec<i32> {
+    map.range(range).map(|(key, _)| *key).collect()
+  }
+
+  #[test]
+  fn to_empty() {
+    let map = SkipListMap::<i32, ()>::new();
+    assert_eq!(keys(&map, ..), vec![])
+  }
+
+  #[test]
+  fn to_filled() {
+    let map = SkipListMap::from((0..20).map(|key| (key * 2, ())).collect::<Vec<_>>());
+    assert_eq!(keys(&map, 3..9), vec![4, 6, 8]);
+    assert_eq!(keys(&map, 4..=8), vec![4, 6, 8]);
+    assert_eq!(keys(&map, 4..8), vec![4, 6]);
+    assert_eq!(
+      keys(&map, (Bound::Excluded(4), Bound::Included(8))),
+      vec![6, 8]
+    );
+    assert_eq!(keys(&map, 35..), vec![36, 38]);
+    assert_eq!(keys(&map, ..3), vec![0, 2]);
+    assert_eq!(keys(&map, 40..), vec![]);
+    assert_eq!(keys(&map, (Bound::Excluded(9), Bound::Excluded(6))), vec![]);
+    assert_eq!(keys(&map, ..).len(), 20)
+  }
+}
+
+#[cfg(test)]
+mod with_seed {
+  use super::*;
+
+  fn heights(seed: u64) -> Vec<usize> {
+    let mut map = SkipListMap::with_seed(seed);
+    (0..64).for_each(|key| {
+      map.insert(key, ());
+    });
+    (0..64).map(|key| map.node_height(&key).unwrap()).collect()
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(heights(7), heights(7));
+    assert_ne!(heights(7), heights(8))
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let mut map = setup::skip_list_map_filled();
+    assert_eq!(format!("{map:?}"), "{0: 'a', 1: 'b', 2: 'c', 3: 'd'}");
+    map
+      .iter_mut()
+      .for_each(|(_, value)| *value = value.to_ascii_uppercase());
+    let copy = map.clone();
+    setup::check(&copy);
+    assert_eq!(copy, map);
+    assert!((0..4).all(|key| copy.node_height(&key) == map.node_height(&key)));
+    assert_eq!(
+      (&map)
+        .into_iter()
+        .map(|(_, value)| *value)
+        .collect::<String>(),
+      "ABCD"
+    );
+    assert_eq!(
+      map.into_iter().collect::<Vec<_>>(),
+      vec![(0, 'A'), (1, 'B'), (2, 'C'), (3, 'D')]
+    )
+  }
+
+  #[test]
+  fn to_cleared() {
+    let mut map = setup::skip_list_map_filled();
+    map.clear();
+    setup::check(&map);
+    assert!(map.is_empty());
+    map.insert(5, 'e');
+    assert_eq!(setup::keys(&map), vec![5])
+  }
+}
+
+#[cfg(test)]
+mod drop {
+  use super::*;
+
+  #[test]
+  fn to_long_list() {
+    let mut map = SkipListMap::new();
+    let len = if cfg!(miri) { 1000 } else { 100000 };
+    (0..len).for_each(|key| {
+      map.insert(key, key);
+    });
+    assert!(map.height() > 1);
+    drop(map)
+  }
+}
+
+#[cfg(test)]
+mod send_sync {
+  use super::*;
+
+  fn is_send_sync<T: Send + Sync>(_: &T) {}
+
+  #[test]
+  fn to_filled() {
+    let map = setup::skip_list_map_filled();
+    is_send_sync(&map);
+    is_send_sync(&map.iter());
+    is_send_sync(&map.range(1..));
+    let op = std::thread::spawn(move || map.iter().map(|(key, _)| *key).sum::<i32>()).join();
+    assert_eq!(op.unwrap(), 6)
+  }
+}
+
+#[cfg(test)]
+mod random {
+  use super::*;
+  use std::collections::BTreeMap;
+
+  // Replays random operations against `std::collections::BTreeMap`.
+  #[test]
+  fn to_model() {
+    let mut map = SkipListMap::with_config(50, 8, 4).unwrap();
+    let mut model = BTreeMap::new();
+    crate::test_support::replay(|_, rng| {
+      let key = (rng.next_u64() % 300) as i32;
+      match rng.next_u64() % 4 {
+        0 => {
+          let removed = model.remove(&key).ok_or(RemoveError::ItemNotFound);
+          assert_eq!(map.remove(&key), removed);
+        }
+        1 => {
+          let popped = model.pop_first().ok_or(RemoveError::EmptyList);
+          assert_eq!(map.pop_first(), popped);
+        }
+        _ => {
+          let value = rng.next_u64();
+          assert_eq!(map.insert(key, value), model.insert(key, value));
+        }
+      }
+      assert_eq!(map.len(), model.len() as i32);
+      assert_eq!(map.last(), model.last_key_value());
+      let end = key + 40;
+      assert!(map.range(key..end).eq(model.range(key..end)));
+    });
+    setup::check(&map);
+    assert!(map.iter().eq(model.iter()))
+  }
+}
@@ -0,0 +1,181 @@
+use std::{fmt, ops::RangeBounds};
+
+use super::{LevelConfigError, SkipIntoIter, SkipIter, SkipListMap, SkipRange};
+use crate::ephemeral::lists::RemoveError;
+
+#[derive(Clone, PartialEq)]
+pub struct SkipListSet<T> {
+  map: SkipListMap<T, ()>,
+}
+
+pub struct SkipSetIter<'a, T> {
+  entries: SkipIter<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for SkipSetIter<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.entries.next().map(|(item, _)| item)
+  }
+}
+
+pub struct SkipSetRange<'a, T, R> {
+  entries: SkipRange<'a, T, (), R>,
+}
+
+impl<'a, T, R> Iterator for SkipSetRange<'a, T, R>
+where
+  T: Ord,
+  R: RangeBounds<T>,
+{
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.entries.next().map(|(item, _)| item)
+  }
+}
+
+pub struct SkipSetIntoIter<T> {
+  entries: SkipIntoIter<T, ()>,
+}
+
+impl<T> Iterator for SkipSetIntoIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.entries.next().map(|(item, _)| item)
+  }
+}
+
+impl<T> SkipListSet<T> {
+  pub fn new() -> Self {
+    Self {
+      map: SkipListMap::new(),
+    }
+  }
+
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      map: SkipListMap::with_seed(seed),
+    }
+  }
+
+  pub fn with_config(
+    seed: u64,
+    max_level: usize,
+    branching: u64,
+  ) -> Result<Self, LevelConfigError> {
+    SkipListMap::with_config(seed, max_level, branching).map(|map| Self { map })
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.map.is_empty()
+  }
+
+  pub fn len(&self) -> i32 {
+    self.map.len()
+  }
+
+  pub fn height(&self) -> usize {
+    self.map.height()
+  }
+
+  pub fn level_len(&self, level: usize) -> usize {
+    self.map.level_len(level)
+  }
+
+  pub fn first(&self) -> Option<&T> {
+    self.map.first().map(|(item, _)| item)
+  }
+
+  pub fn last(&self) -> Option<&T> {
+    self.map.last().map(|(item, _)| item)
+  }
+
+  pub fn pop_first(&mut self) -> Result<T, RemoveError> {
+    self.map.pop_first().map(|(item, _)| item)
+  }
+
+  pub fn clear(&mut self) {
+    self.map.clear()
+  }
+
+  pub fn iter(&self) -> SkipSetIter<'_, T> {
+    SkipSetIter {
+      entries: self.map.iter(),
+    }
+  }
+}
+
+impl<T> SkipListSet<T>
+where
+  T: Ord,
+{
+  pub fn contains(&self, item: &T) -> bool {
+    self.map.contains_key(item)
+  }
+
+  pub fn node_height(&self, item: &T) -> Option<usize> {
+    self.map.node_height(item)
+  }
+
+  // Returns whether `item` was newly added.
+  pub fn insert(&mut self, item: T) -> bool {
+    self.map.insert(item, ()).is_none()
+  }
+
+  pub fn remove(&mut self, item: &T) -> Result<T, RemoveError> {
+    self.map.remove_entry(item).map(|(item, _)| item)
+  }
+
+  pub fn range<R>(&self, range: R) -> SkipSetRange<'_, T, R>
+  where
+    R: RangeBounds<T>,
+  {
+    SkipSetRange {
+      entries: self.map.range(range),
+    }
+  }
+}
+
+impl<T> Default for SkipListSet<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> IntoIterator for SkipListSet<T> {
+  type Item = T;
+  type IntoIter = SkipSetIntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    SkipSetIntoIter {
+      entries: self.map.into_iter(),
+    }
+  }
+}
+
+impl<T> fmt::Debug for SkipListSet<T>
+where
+  T: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
+
+impl<T> From<Vec<T>> for SkipListSet<T>
+where
+  T: Ord,
+{
+  fn from(vec: Vec<T>) -> Self {
+    Self {
+      map: SkipListMap::from(vec.into_iter().map(|item| (item, ())).collect::<Vec<_>>()),
+    }
+  }
+}
+
+#[cfg(test)]
+#[path = "./skip-list-set_test.rs"]
+mod test;
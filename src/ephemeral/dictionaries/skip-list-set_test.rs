@@ -0,0 +1,89 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type SkipListSetT = SkipListSet<i32>;
+
+  pub fn skip_list_set_empty() -> SkipListSetT {
+    SkipListSet::with_seed(50)
+  }
+
+  pub fn skip_list_set_filled() -> SkipListSetT {
+    SkipListSet::from(vec![3, 0, 2, 1])
+  }
+
+  pub fn items(set: &SkipListSetT) -> Vec<i32> {
+    set.iter().copied().collect()
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut set = setup::skip_list_set_empty();
+    assert!(set.insert(7));
+    assert!(set.contains(&7));
+    assert_eq!(set.len(), 1)
+  }
+
+  #[test]
+  fn to_filled_with_present_item() {
+    let mut set = setup::skip_list_set_filled();
+    assert!(!set.insert(2));
+    assert!(set.insert(-1));
+    assert_eq!(setup::items(&set), vec![-1, 0, 1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut set = setup::skip_list_set_empty();
+    assert_eq!(set.remove(&0), Err(RemoveError::ItemNotFound));
+    assert_eq!(set.pop_first(), Err(RemoveError::EmptyList))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut set = setup::skip_list_set_filled();
+    assert_eq!(set.remove(&2), Ok(2));
+    assert_eq!(set.pop_first(), Ok(0));
+    assert_eq!((set.first(), set.last()), (Some(&1), Some(&3)));
+    assert_eq!(setup::items(&set), vec![1, 3])
+  }
+}
+
+#[cfg(test)]
+mod range {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let set = setup::skip_list_set_filled();
+    assert_eq!(set.range(1..3).copied().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(set.range(2..).copied().collect::<Vec<_>>(), vec![2, 3]);
+    assert_eq!(set.range(..=0).copied().collect::<Vec<_>>(), vec![0])
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let set = setup::skip_list_set_filled();
+    assert_eq!(format!("{set:?}"), "{0, 1, 2, 3}");
+    assert_eq!(set.clone(), set);
+    assert!(set.height() >= 1);
+    assert_eq!(set.level_len(0), 4);
+    assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3])
+  }
+}
@@ -0,0 +1,218 @@
+use std::{cmp::Ordering, collections::HashMap, fmt, hash::Hash};
+
+#[derive(PartialEq, Debug)]
+pub enum HeapError {
+  DuplicateKey,
+  KeyNotFound,
+}
+
+// Invariants:
+//    - no entry compares less than its parent under `compare`
+//    - `positions[key]` is the index of `key` in `entries`, for every entry
+pub struct IndexedHeap<K, P> {
+  entries: Vec<(K, P)>,
+  positions: HashMap<K, usize>,
+  compare: fn(&P, &P) -> Ordering,
+}
+
+mod private {
+  use super::*;
+
+  pub fn less<K, P>(heap: &IndexedHeap<K, P>, i: usize, j: usize) -> bool {
+    (heap.compare)(&heap.entries[i].1, &heap.entries[j].1) == Ordering::Less
+  }
+
+  pub fn swap<K, P>(heap: &mut IndexedHeap<K, P>, i: usize, j: usize)
+  where
+    K: Hash + Eq,
+  {
+    heap.entries.swap(i, j);
+    *heap.positions.get_mut(&heap.entries[i].0).unwrap() = i;
+    *heap.positions.get_mut(&heap.entries[j].0).unwrap() = j;
+  }
+
+  pub fn sift_up<K, P>(heap: &mut IndexedHeap<K, P>, mut index: usize)
+  where
+    K: Hash + Eq,
+  {
+    while index > 0 {
+      let parent = (index - 1) / 2;
+      if !less(heap, index, parent) {
+        break;
+      }
+      swap(heap, index, parent);
+      index = parent;
+    }
+  }
+
+  pub fn sift_down<K, P>(heap: &mut IndexedHeap<K, P>, mut index: usize)
+  where
+    K: Hash + Eq,
+  {
+    loop {
+      let (left, right) = (2 * index + 1, 2 * index + 2);
+      let mut smallest = index;
+      if left < heap.entries.len() && less(heap, left, smallest) {
+        smallest = left;
+      }
+      if right < heap.entries.len() && less(heap, right, smallest) {
+        smallest = right;
+      }
+      if smallest == index {
+        break;
+      }
+      swap(heap, index, smallest);
+      index = smallest;
+    }
+  }
+
+  // Takes the entry at `index` out, filling the hole with the last entry.
+  pub fn remove_at<K, P>(heap: &mut IndexedHeap<K, P>, index: usize) -> (K, P)
+  where
+    K: Hash + Eq,
+  {
+    let last = heap.entries.len() - 1;
+    swap(heap, index, last);
+    let (key, priority) = heap.entries.pop().unwrap();
+    heap.positions.remove(&key);
+    if index < heap.entries.len() {
+      sift_up(heap, index);
+      sift_down(heap, index);
+    }
+    (key, priority)
+  }
+}
+
+impl<K, P> IndexedHeap<K, P>
+where
+  K: Hash + Eq + Clone,
+{
+  // Pops the smallest priority first.
+  pub fn new() -> Self
+  where
+    P: Ord,
+  {
+    Self::with_comparator(P::cmp)
+  }
+
+  // Pops the largest priority first.
+  pub fn new_max() -> Self
+  where
+    P: Ord,
+  {
+    Self::with_comparator(|a, b| b.cmp(a))
+  }
+
+  // Pops first whatever `compare` orders first.
+  pub fn with_comparator(compare: fn(&P, &P) -> Ordering) -> Self {
+    Self {
+      entries: vec![],
+      positions: HashMap::new(),
+      compare,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn len(&self) -> i32 {
+    self.entries.len() as i32
+  }
+
+  pub fn contains(&self, key: &K) -> bool {
+    self.positions.contains_key(key)
+  }
+
+  pub fn priority(&self, key: &K) -> Option<&P> {
+    self.positions.get(key).map(|index| &self.entries[*index].1)
+  }
+
+  pub fn push(&mut self, key: K, priority: P) -> Result<(), HeapError> {
+    if self.positions.contains_key(&key) {
+      return Err(HeapError::DuplicateKey);
+    }
+    self.positions.insert(key.clone(), self.entries.len());
+    self.entries.push((key, priority));
+    private::sift_up(self, self.entries.len() - 1);
+    Ok(())
+  }
+
+  // The first entry under the heap's ordering, so the largest one for
+  // `new_max`.
+  pub fn peek_min(&self) -> Option<(&K, &P)> {
+    self.entries.first().map(|(key, priority)| (key, priority))
+  }
+
+  pub fn pop_min(&mut self) -> Option<(K, P)> {
+    if self.entries.is_empty() {
+      return None;
+    }
+    Some(private::remove_at(self, 0))
+  }
+
+  // Moves `key` either way, returning its old priority. Covers both
+  // `decrease_key` and `increase_key`.
+  pub fn change_priority(&mut self, key: &K, priority: P) -> Result<P, HeapError> {
+    let index = *self.positions.get(key).ok_or(HeapError::KeyNotFound)?;
+    let old = std::mem::replace(&mut self.entries[index].1, priority);
+    private::sift_up(self, index);
+    private::sift_down(self, self.positions[key]);
+    Ok(old)
+  }
+
+  pub fn remove(&mut self, key: &K) -> Result<P, HeapError> {
+    let index = *self.positions.get(key).ok_or(HeapError::KeyNotFound)?;
+    Ok(private::remove_at(self, index).1)
+  }
+
+  pub fn clear(&mut self) {
+    self.entries.clear();
+    self.positions.clear();
+  }
+
+  // Visits entries in heap order, which is not sorted.
+  pub fn iter(&self) -> impl Iterator<Item = (&K, &P)> {
+    self.entries.iter().map(|(key, priority)| (key, priority))
+  }
+}
+
+impl<K, P> Default for IndexedHeap<K, P>
+where
+  K: Hash + Eq + Clone,
+  P: Ord,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K, P> Clone for IndexedHeap<K, P>
+where
+  K: Clone,
+  P: Clone,
+{
+  fn clone(&self) -> Self {
+    Self {
+      entries: self.entries.clone(),
+      positions: self.positions.clone(),
+      compare: self.compare,
+    }
+  }
+}
+
+impl<K, P> fmt::Debug for IndexedHeap<K, P>
+where
+  K: fmt::Debug,
+  P: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map()
+      .entries(self.entries.iter().map(|(key, priority)| (key, priority)))
+      .finish()
+  }
+}
+
+#[cfg(test)]
+#[path = "./indexed-heap_test.rs"]
+mod test;
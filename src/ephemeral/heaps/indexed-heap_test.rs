@@ -0,0 +1,263 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type IndexedHeapT = IndexedHeap<char, i32>;
+
+  pub fn indexed_heap_empty() -> IndexedHeapT {
+    IndexedHeap::new()
+  }
+
+  pub fn indexed_heap_filled() -> IndexedHeapT {
+    let mut heap = IndexedHeap::new();
+    for (key, priority) in [('a', 5), ('b', 3), ('c', 8), ('d', 1), ('e', 4)] {
+      heap.push(key, priority).unwrap();
+    }
+    heap
+  }
+
+  // Checks the heap property and that every position points back at its key.
+  pub fn check<K, P>(heap: &IndexedHeap<K, P>)
+  where
+    K: Hash + Eq + Clone + fmt::Debug,
+  {
+    assert_eq!(heap.positions.len(), heap.entries.len());
+    for (index, (key, priority)) in heap.entries.iter().enumerate() {
+      assert_eq!(heap.positions.get(key), Some(&index), "{key:?}");
+      if index > 0 {
+        let parent = &heap.entries[(index - 1) / 2].1;
+        assert_ne!((heap.compare)(priority, parent), Ordering::Less);
+      }
+    }
+  }
+
+  pub fn drain<K, P>(heap: &mut IndexedHeap<K, P>) -> Vec<(K, P)>
+  where
+    K: Hash + Eq + Clone + fmt::Debug,
+  {
+    let mut items = vec![];
+    while let Some(item) = heap.pop_min() {
+      check(heap);
+      items.push(item);
+    }
+    items
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = IndexedHeap::<char, i32>::new();
+    assert!(op.is_empty());
+    assert_eq!(op.len(), 0)
+  }
+}
+
+#[cfg(test)]
+mod push {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut heap = setup::indexed_heap_empty();
+    assert_eq!(heap.push('a', 1), Ok(()));
+    assert_eq!(heap.peek_min(), Some((&'a', &1)));
+    assert_eq!(heap.len(), 1)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut heap = setup::indexed_heap_filled();
+    assert_eq!(heap.push('f', 0), Ok(()));
+    setup::check(&heap);
+    assert_eq!(heap.peek_min(), Some((&'f', &0)));
+    assert_eq!(heap.len(), 6)
+  }
+
+  #[test]
+  fn to_filled_duplicate() {
+    let mut heap = setup::indexed_heap_filled();
+    assert_eq!(heap.push('a', 0), Err(HeapError::DuplicateKey));
+    assert_eq!(heap.priority(&'a'), Some(&5));
+    setup::check(&heap)
+  }
+}
+
+#[cfg(test)]
+mod pop_min {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut heap = setup::indexed_heap_empty();
+    assert_eq!(heap.pop_min(), None);
+    assert_eq!(heap.peek_min(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut heap = setup::indexed_heap_filled();
+    assert_eq!(
+      setup::drain(&mut heap),
+      vec![('d', 1), ('b', 3), ('e', 4), ('a', 5), ('c', 8)]
+    );
+    assert!(heap.is_empty())
+  }
+
+  #[test]
+  fn to_filled_max() {
+    let mut heap = IndexedHeap::new_max();
+    for (key, priority) in [('a', 5), ('b', 3), ('c', 8)] {
+      heap.push(key, priority).unwrap();
+    }
+    assert_eq!(heap.peek_min(), Some((&'c', &8)));
+    assert_eq!(setup::drain(&mut heap), vec![('c', 8), ('a', 5), ('b', 3)])
+  }
+
+  #[test]
+  fn to_filled_comparator() {
+    let mut heap =
+      IndexedHeap::with_comparator(|a: &(i32, i32), b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)));
+    heap.push("x", (1, 2)).unwrap();
+    heap.push("y", (3, 2)).unwrap();
+    heap.push("z", (0, 1)).unwrap();
+    let op: Vec<&str> = setup::drain(&mut heap)
+      .into_iter()
+      .map(|(key, _)| key)
+      .collect();
+    assert_eq!(op, vec!["z", "y", "x"])
+  }
+}
+
+#[cfg(test)]
+mod change_priority {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut heap = setup::indexed_heap_empty();
+    assert_eq!(heap.change_priority(&'a', 1), Err(HeapError::KeyNotFound))
+  }
+
+  #[test]
+  fn to_filled_decrease() {
+    let mut heap = setup::indexed_heap_filled();
+    assert_eq!(heap.change_priority(&'c', 0), Ok(8));
+    setup::check(&heap);
+    assert_eq!(heap.peek_min(), Some((&'c', &0)))
+  }
+
+  #[test]
+  fn to_filled_increase() {
+    let mut heap = setup::indexed_heap_filled();
+    assert_eq!(heap.change_priority(&'d', 10), Ok(1));
+    setup::check(&heap);
+    assert_eq!(
+      setup::drain(&mut heap),
+      vec![('b', 3), ('e', 4), ('a', 5), ('c', 8), ('d', 10)]
+    )
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut heap = setup::indexed_heap_empty();
+    assert_eq!(heap.remove(&'a'), Err(HeapError::KeyNotFound))
+  }
+
+  #[test]
+  fn to_filled() {
+    for key in ['a', 'b', 'c', 'd', 'e'] {
+      let mut heap = setup::indexed_heap_filled();
+      let priority = *heap.priority(&key).unwrap();
+      assert_eq!(heap.remove(&key), Ok(priority));
+      setup::check(&heap);
+      assert!(!heap.contains(&key));
+      assert_eq!(heap.len(), 4)
+    }
+  }
+}
+
+#[cfg(test)]
+mod contains {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(!setup::indexed_heap_empty().contains(&'a'))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut heap = setup::indexed_heap_filled();
+    assert!(heap.contains(&'e'));
+    heap.pop_min();
+    assert!(!heap.contains(&'d'));
+    heap.clear();
+    assert!(!heap.contains(&'e'))
+  }
+}
+
+#[cfg(test)]
+mod random {
+  use super::*;
+
+  // Replays random operations against a plain vector as the model.
+  #[test]
+  fn to_model() {
+    let mut heap = IndexedHeap::new();
+    let mut model: Vec<(u64, u64)> = vec![];
+    crate::test_support::replay(|_, rng| {
+      let key = rng.next_u64() % 64;
+      let priority = rng.next_u64() % 100;
+      let found = model.iter().position(|(k, _)| *k == key);
+      match (rng.next_u64() % 4, found) {
+        (0, Some(index)) => {
+          assert_eq!(heap.remove(&key), Ok(model.remove(index).1));
+        }
+        (1, Some(index)) => {
+          let old = std::mem::replace(&mut model[index].1, priority);
+          assert_eq!(heap.change_priority(&key, priority), Ok(old));
+        }
+        (2, _) => {
+          let min = model.iter().map(|(_, p)| *p).min();
+          let op = heap.pop_min();
+          assert_eq!(op.map(|(_, p)| p), min);
+          if let Some((k, _)) = op {
+            model.retain(|(other, _)| *other != k);
+          }
+        }
+        (_, None) => {
+          heap.push(key, priority).unwrap();
+          model.push((key, priority));
+        }
+        (_, Some(_)) => assert_eq!(heap.push(key, priority), Err(HeapError::DuplicateKey)),
+      }
+      setup::check(&heap);
+      assert_eq!(heap.len(), model.len() as i32)
+    })
+  }
+}
+
+#[cfg(test)]
+mod clone {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let heap = setup::indexed_heap_filled();
+    let mut op = heap.clone();
+    op.change_priority(&'a', 0).unwrap();
+    assert_eq!(op.peek_min(), Some((&'a', &0)));
+    assert_eq!(heap.peek_min(), Some((&'d', &1)));
+    assert_eq!(format!("{:?}", setup::indexed_heap_empty()), "{}")
+  }
+}
@@ -0,0 +1,3 @@
+#[path = "./indexed-heap.rs"]
+pub mod indexed_heap;
+pub use indexed_heap::*;
@@ -0,0 +1,706 @@
+use std::{fmt, marker::PhantomData, ptr::NonNull};
+
+use super::{InsertError, RemoveError};
+use crate::IndexError;
+
+type DoublyLink<T> = Option<NonNull<DoublyNode<T>>>;
+
+#[derive(PartialEq, Debug)]
+pub struct DoublyNode<T> {
+  value: T,
+  prev: DoublyLink<T>,
+  next: DoublyLink<T>,
+}
+
+// Invariants:
+//    - `head` has no `prev`, `tail` has no `next`, and both are None exactly
+//      when the list is empty
+//    - for every node `n`, `n.next.prev == n` and `n.prev.next == n`
+//    - `len` is the number of nodes reachable from `head`
+//    - every node is owned by exactly one list and was allocated by
+//      `private::new_link`
+pub struct DoublyLinkedList<T> {
+  head: DoublyLink<T>,
+  tail: DoublyLink<T>,
+  len: usize,
+  marker: PhantomData<Box<DoublyNode<T>>>,
+}
+
+unsafe impl<T: Send> Send for DoublyLinkedList<T> {}
+unsafe impl<T: Sync> Sync for DoublyLinkedList<T> {}
+
+pub struct DoublyIter<'a, T: 'a> {
+  head: DoublyLink<T>,
+  tail: DoublyLink<T>,
+  len: usize,
+  marker: PhantomData<&'a DoublyNode<T>>,
+}
+
+unsafe impl<T: Sync> Send for DoublyIter<'_, T> {}
+unsafe impl<T: Sync> Sync for DoublyIter<'_, T> {}
+
+impl<'a, T> Iterator for DoublyIter<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.len == 0 {
+      return None;
+    }
+    self.head.map(|node| unsafe {
+      let node = &*node.as_ptr();
+      self.len -= 1;
+      self.head = node.next;
+      &node.value
+    })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.len, Some(self.len))
+  }
+}
+
+impl<T> DoubleEndedIterator for DoublyIter<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.len == 0 {
+      return None;
+    }
+    self.tail.map(|node| unsafe {
+      let node = &*node.as_ptr();
+      self.len -= 1;
+      self.tail = node.prev;
+      &node.value
+    })
+  }
+}
+
+pub struct DoublyIterMut<'a, T: 'a> {
+  head: DoublyLink<T>,
+  tail: DoublyLink<T>,
+  len: usize,
+  marker: PhantomData<&'a mut DoublyNode<T>>,
+}
+
+unsafe impl<T: Send> Send for DoublyIterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for DoublyIterMut<'_, T> {}
+
+impl<'a, T> Iterator for DoublyIterMut<'a, T> {
+  type Item = &'a mut T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.len == 0 {
+      return None;
+    }
+    self.head.map(|node| unsafe {
+      let node = &mut *node.as_ptr();
+      self.len -= 1;
+      self.head = node.next;
+      &mut node.value
+    })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.len, Some(self.len))
+  }
+}
+
+impl<T> DoubleEndedIterator for DoublyIterMut<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.len == 0 {
+      return None;
+    }
+    self.tail.map(|node| unsafe {
+      let node = &mut *node.as_ptr();
+      self.len -= 1;
+      self.tail = node.prev;
+      &mut node.value
+    })
+  }
+}
+
+#[derive(Clone)]
+pub struct DoublyIntoIter<T> {
+  list: DoublyLinkedList<T>,
+}
+
+impl<T> Iterator for DoublyIntoIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.list.pop_front().ok()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.list.len, Some(self.list.len))
+  }
+}
+
+impl<T> DoubleEndedIterator for DoublyIntoIter<T> {
+  fn next_back(&mut self) -> Option<T> {
+    self.list.pop_back().ok()
+  }
+}
+
+// Past the last node a cursor rests on a "ghost" position between the tail
+// and the head, so it can wrap around in both directions. The ghost's index is
+// the length of the list.
+pub struct DoublyCursor<'a, T: 'a> {
+  current: DoublyLink<T>,
+  index: usize,
+  list: &'a DoublyLinkedList<T>,
+}
+
+impl<T> Clone for DoublyCursor<'_, T> {
+  fn clone(&self) -> Self {
+    Self {
+      current: self.current,
+      index: self.index,
+      list: self.list,
+    }
+  }
+}
+
+impl<'a, T> DoublyCursor<'a, T> {
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  pub fn current(&self) -> Option<&'a T> {
+    self.current.map(|node| unsafe { &(*node.as_ptr()).value })
+  }
+
+  pub fn move_next(&mut self) {
+    (self.current, self.index) = private::next_position(self.list, self.current, self.index);
+  }
+
+  pub fn move_prev(&mut self) {
+    (self.current, self.index) = private::prev_position(self.list, self.current, self.index);
+  }
+
+  pub fn peek_next(&self) -> Option<&'a T> {
+    private::next_link(self.list, self.current).map(|node| unsafe { &(*node.as_ptr()).value })
+  }
+
+  pub fn peek_prev(&self) -> Option<&'a T> {
+    private::prev_link(self.list, self.current).map(|node| unsafe { &(*node.as_ptr()).value })
+  }
+}
+
+pub struct DoublyCursorMut<'a, T: 'a> {
+  current: DoublyLink<T>,
+  index: usize,
+  list: &'a mut DoublyLinkedList<T>,
+}
+
+impl<T> DoublyCursorMut<'_, T> {
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  pub fn current(&mut self) -> Option<&mut T> {
+    self
+      .current
+      .map(|node| unsafe { &mut (*node.as_ptr()).value })
+  }
+
+  pub fn move_next(&mut self) {
+    (self.current, self.index) = private::next_position(self.list, self.current, self.index);
+  }
+
+  pub fn move_prev(&mut self) {
+    (self.current, self.index) = private::prev_position(self.list, self.current, self.index);
+  }
+
+  pub fn peek_next(&mut self) -> Option<&mut T> {
+    private::next_link(self.list, self.current).map(|node| unsafe { &mut (*node.as_ptr()).value })
+  }
+
+  pub fn peek_prev(&mut self) -> Option<&mut T> {
+    private::prev_link(self.list, self.current).map(|node| unsafe { &mut (*node.as_ptr()).value })
+  }
+
+  // On the ghost, inserts at the front of the list.
+  pub fn insert_after(&mut self, item: T) {
+    let next = private::next_link(self.list, self.current);
+    private::link_between(self.list, item, self.current, next);
+    if self.current.is_none() {
+      self.index += 1;
+    }
+  }
+
+  // On the ghost, inserts at the end of the list.
+  pub fn insert_before(&mut self, item: T) {
+    let prev = private::prev_link(self.list, self.current);
+    private::link_between(self.list, item, prev, self.current);
+    self.index += 1;
+  }
+
+  // Removes the current node and moves to the next one.
+  pub fn remove_current(&mut self) -> Option<T> {
+    let node = self.current?;
+    self.current = unsafe { (*node.as_ptr()).next };
+    Some(private::unlink(self.list, node))
+  }
+
+  // Moves everything after the cursor into a new list. On the ghost, that's
+  // the whole list.
+  pub fn split_after(&mut self) -> DoublyLinkedList<T> {
+    let Some(node) = self.current else {
+      self.index = 0;
+      return std::mem::take(self.list);
+    };
+    let Some(next) = (unsafe { (*node.as_ptr()).next.take() }) else {
+      return DoublyLinkedList::new();
+    };
+    let list = DoublyLinkedList {
+      head: Some(next),
+      tail: self.list.tail,
+      len: self.list.len - self.index - 1,
+      marker: PhantomData,
+    };
+    unsafe {
+      (*next.as_ptr()).prev = None;
+    }
+    self.list.tail = Some(node);
+    self.list.len = self.index + 1;
+    list
+  }
+
+  // Moves the nodes of `list` right after the cursor.
+  pub fn splice_after(&mut self, mut list: DoublyLinkedList<T>) {
+    let (Some(head), Some(tail)) = (list.head.take(), list.tail.take()) else {
+      return;
+    };
+    let next = private::next_link(self.list, self.current);
+    unsafe {
+      (*head.as_ptr()).prev = self.current;
+      (*tail.as_ptr()).next = next;
+      match self.current {
+        None => self.list.head = Some(head),
+        Some(node) => (*node.as_ptr()).next = Some(head),
+      }
+      match next {
+        None => self.list.tail = Some(tail),
+        Some(node) => (*node.as_ptr()).prev = Some(tail),
+      }
+    }
+    self.list.len += list.len;
+    if self.current.is_none() {
+      self.index += list.len;
+    }
+    list.len = 0;
+  }
+}
+
+mod private {
+  use super::*;
+
+  pub fn new_link<T>(value: T, prev: DoublyLink<T>, next: DoublyLink<T>) -> NonNull<DoublyNode<T>> {
+    unsafe { NonNull::new_unchecked(Box::into_raw(Box::new(DoublyNode { value, prev, next }))) }
+  }
+
+  // Takes back ownership of an unlinked node.
+  pub fn free_node<T>(node: NonNull<DoublyNode<T>>) -> T {
+    unsafe { Box::from_raw(node.as_ptr()).value }
+  }
+
+  // Node after `current`, where the ghost is followed by the head.
+  pub fn next_link<T>(list: &DoublyLinkedList<T>, current: DoublyLink<T>) -> DoublyLink<T> {
+    match current {
+      None => list.head,
+      Some(node) => unsafe { (*node.as_ptr()).next },
+    }
+  }
+
+  // Node before `current`, where the ghost is preceded by the tail.
+  pub fn prev_link<T>(list: &DoublyLinkedList<T>, current: DoublyLink<T>) -> DoublyLink<T> {
+    match current {
+      None => list.tail,
+      Some(node) => unsafe { (*node.as_ptr()).prev },
+    }
+  }
+
+  pub fn next_position<T>(
+    list: &DoublyLinkedList<T>,
+    current: DoublyLink<T>,
+    index: usize,
+  ) -> (DoublyLink<T>, usize) {
+    match current {
+      None => (list.head, 0),
+      Some(_) => (next_link(list, current), index + 1),
+    }
+  }
+
+  pub fn prev_position<T>(
+    list: &DoublyLinkedList<T>,
+    current: DoublyLink<T>,
+    index: usize,
+  ) -> (DoublyLink<T>, usize) {
+    match prev_link(list, current) {
+      None => (None, list.len),
+      prev => (prev, index - 1),
+    }
+  }
+
+  // Walks from the nearer end.
+  pub fn node_at<T>(list: &DoublyLinkedList<T>, index: usize) -> NonNull<DoublyNode<T>> {
+    unsafe {
+      if index < list.len / 2 {
+        let mut node = list.head.unwrap();
+        for _ in 0..index {
+          node = (*node.as_ptr()).next.unwrap();
+        }
+        node
+      } else {
+        let mut node = list.tail.unwrap();
+        for _ in index + 1..list.len {
+          node = (*node.as_ptr()).prev.unwrap();
+        }
+        node
+      }
+    }
+  }
+
+  pub fn find_node<T>(list: &DoublyLinkedList<T>, item: &T) -> DoublyLink<T>
+  where
+    T: PartialEq,
+  {
+    let mut ptr = list.head;
+    while let Some(inner) = ptr {
+      unsafe {
+        if (*inner.as_ptr()).value == *item {
+          return ptr;
+        }
+        ptr = (*inner.as_ptr()).next;
+      }
+    }
+    None
+  }
+
+  // Links a new node between `prev` and `next`, which must be adjacent.
+  pub fn link_between<T>(
+    list: &mut DoublyLinkedList<T>,
+    item: T,
+    prev: DoublyLink<T>,
+    next: DoublyLink<T>,
+  ) {
+    let node = Some(new_link(item, prev, next));
+    unsafe {
+      match prev {
+        None => list.head = node,
+        Some(prev) => (*prev.as_ptr()).next = node,
+      }
+      match next {
+        None => list.tail = node,
+        Some(next) => (*next.as_ptr()).prev = node,
+      }
+    }
+    list.len += 1;
+  }
+
+  pub fn unlink<T>(list: &mut DoublyLinkedList<T>, node: NonNull<DoublyNode<T>>) -> T {
+    unsafe {
+      let DoublyNode { prev, next, .. } = *node.as_ptr();
+      match prev {
+        None => list.head = next,
+        Some(prev) => (*prev.as_ptr()).next = next,
+      }
+      match next {
+        None => list.tail = prev,
+        Some(next) => (*next.as_ptr()).prev = prev,
+      }
+    }
+    list.len -= 1;
+    free_node(node)
+  }
+}
+
+impl<T> DoublyLinkedList<T> {
+  pub const fn new() -> Self {
+    Self {
+      head: None,
+      tail: None,
+      len: 0,
+      marker: PhantomData,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  pub fn len(&self) -> i32 {
+    self.len as i32
+  }
+
+  pub fn front(&self) -> Option<&T> {
+    self.head.map(|node| unsafe { &(*node.as_ptr()).value })
+  }
+
+  pub fn back(&self) -> Option<&T> {
+    self.tail.map(|node| unsafe { &(*node.as_ptr()).value })
+  }
+
+  pub fn front_mut(&mut self) -> Option<&mut T> {
+    self.head.map(|node| unsafe { &mut (*node.as_ptr()).value })
+  }
+
+  pub fn back_mut(&mut self) -> Option<&mut T> {
+    self.tail.map(|node| unsafe { &mut (*node.as_ptr()).value })
+  }
+
+  pub fn push_front(&mut self, item: T) {
+    private::link_between(self, item, None, self.head)
+  }
+
+  pub fn push_back(&mut self, item: T) {
+    private::link_between(self, item, self.tail, None)
+  }
+
+  pub fn pop_front(&mut self) -> Result<T, RemoveError> {
+    match self.head {
+      None => Err(RemoveError::EmptyList),
+      Some(node) => Ok(private::unlink(self, node)),
+    }
+  }
+
+  pub fn pop_back(&mut self) -> Result<T, RemoveError> {
+    match self.tail {
+      None => Err(RemoveError::EmptyList),
+      Some(node) => Ok(private::unlink(self, node)),
+    }
+  }
+
+  pub fn insert_before(&mut self, item: T, before: T) -> Result<(), InsertError>
+  where
+    T: PartialEq,
+  {
+    let node = private::find_node(self, &before).ok_or(InsertError::BeforeItemNotFound)?;
+    let prev = unsafe { (*node.as_ptr()).prev };
+    private::link_between(self, item, prev, Some(node));
+    Ok(())
+  }
+
+  pub fn insert_after(&mut self, item: T, after: T) -> Result<(), InsertError>
+  where
+    T: PartialEq,
+  {
+    let node = private::find_node(self, &after).ok_or(InsertError::AfterItemNotFound)?;
+    let next = unsafe { (*node.as_ptr()).next };
+    private::link_between(self, item, Some(node), next);
+    Ok(())
+  }
+
+  pub fn remove_item(&mut self, item: T) -> Result<T, RemoveError>
+  where
+    T: PartialEq,
+  {
+    if self.is_empty() {
+      return Err(RemoveError::EmptyList);
+    }
+    let node = private::find_node(self, &item).ok_or(RemoveError::ItemNotFound)?;
+    Ok(private::unlink(self, node))
+  }
+
+  // Moves every node of `list` to the end of this one.
+  pub fn append(&mut self, mut list: Self) {
+    let (Some(head), Some(tail)) = (list.head.take(), list.tail.take()) else {
+      return;
+    };
+    unsafe {
+      match self.tail {
+        None => self.head = Some(head),
+        Some(tail) => {
+          (*tail.as_ptr()).next = Some(head);
+          (*head.as_ptr()).prev = Some(tail);
+        }
+      }
+    }
+    self.tail = Some(tail);
+    self.len += list.len;
+    list.len = 0;
+  }
+
+  // Keeps the first `at` items and returns the rest. Constant time at either
+  // end, otherwise walks from the nearer one.
+  pub fn split_off(&mut self, at: usize) -> Result<Self, IndexError> {
+    if at > self.len {
+      return Err(IndexError::OutOfBounds);
+    }
+    if at == self.len {
+      return Ok(Self::new());
+    }
+    if at == 0 {
+      return Ok(std::mem::take(self));
+    }
+    let node = private::node_at(self, at);
+    let prev = unsafe { (*node.as_ptr()).prev.take() };
+    unsafe {
+      (*prev.unwrap().as_ptr()).next = None;
+    }
+    let list = Self {
+      head: Some(node),
+      tail: self.tail,
+      len: self.len - at,
+      marker: PhantomData,
+    };
+    self.tail = prev;
+    self.len = at;
+    Ok(list)
+  }
+
+  pub fn clear(&mut self) {
+    *self = Self::new();
+  }
+
+  pub fn cursor_front(&self) -> DoublyCursor<'_, T> {
+    DoublyCursor {
+      current: self.head,
+      index: 0,
+      list: self,
+    }
+  }
+
+  pub fn cursor_back(&self) -> DoublyCursor<'_, T> {
+    DoublyCursor {
+      current: self.tail,
+      index: self.len.saturating_sub(1),
+      list: self,
+    }
+  }
+
+  pub fn cursor_front_mut(&mut self) -> DoublyCursorMut<'_, T> {
+    DoublyCursorMut {
+      current: self.head,
+      index: 0,
+      list: self,
+    }
+  }
+
+  pub fn cursor_back_mut(&mut self) -> DoublyCursorMut<'_, T> {
+    DoublyCursorMut {
+      current: self.tail,
+      index: self.len.saturating_sub(1),
+      list: self,
+    }
+  }
+
+  // Cursor on the first node holding `item`, for editing around it without
+  // searching again.
+  pub fn find_cursor_mut(&mut self, item: T) -> Option<DoublyCursorMut<'_, T>>
+  where
+    T: PartialEq,
+  {
+    let mut cursor = self.cursor_front_mut();
+    while cursor.current.is_some() {
+      if cursor.current().is_some_and(|value| *value == item) {
+        return Some(cursor);
+      }
+      cursor.move_next();
+    }
+    None
+  }
+
+  pub fn iter(&self) -> DoublyIter<'_, T> {
+    DoublyIter {
+      head: self.head,
+      tail: self.tail,
+      len: self.len,
+      marker: PhantomData,
+    }
+  }
+
+  pub fn iter_mut(&mut self) -> DoublyIterMut<'_, T> {
+    DoublyIterMut {
+      head: self.head,
+      tail: self.tail,
+      len: self.len,
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> From<Vec<T>> for DoublyLinkedList<T> {
+  fn from(items: Vec<T>) -> Self {
+    let mut list = Self::new();
+    items.into_iter().for_each(|item| list.push_back(item));
+    list
+  }
+}
+
+// Copies every node, so the clone never aliases the original.
+impl<T> Clone for DoublyLinkedList<T>
+where
+  T: Clone,
+{
+  fn clone(&self) -> Self {
+    let mut list = Self::new();
+    self.iter().for_each(|item| list.push_back(item.clone()));
+    list
+  }
+}
+
+impl<T> Drop for DoublyLinkedList<T> {
+  fn drop(&mut self) {
+    while let Some(node) = self.head {
+      unsafe {
+        self.head = (*node.as_ptr()).next;
+      }
+      private::free_node(node);
+    }
+  }
+}
+
+impl<T> PartialEq for DoublyLinkedList<T>
+where
+  T: PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.len == other.len && self.iter().eq(other.iter())
+  }
+}
+
+impl<T> fmt::Debug for DoublyLinkedList<T>
+where
+  T: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+  type Item = T;
+  type IntoIter = DoublyIntoIter<T>;
+
+  fn into_iter(self) -> DoublyIntoIter<T> {
+    DoublyIntoIter { list: self }
+  }
+}
+
+impl<'a, T> IntoIterator for &'a DoublyLinkedList<T> {
+  type Item = &'a T;
+  type IntoIter = DoublyIter<'a, T>;
+
+  fn into_iter(self) -> DoublyIter<'a, T> {
+    self.iter()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a mut DoublyLinkedList<T> {
+  type Item = &'a mut T;
+  type IntoIter = DoublyIterMut<'a, T>;
+
+  fn into_iter(self) -> DoublyIterMut<'a, T> {
+    self.iter_mut()
+  }
+}
+
+#[cfg(test)]
+#[path = "./doubly-linked-list_test.rs"]
+mod test;
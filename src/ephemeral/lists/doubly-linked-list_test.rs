@@ -0,0 +1,547 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type DoublyLinkedListT = DoublyLinkedList<i32>;
+
+  pub fn doubly_list_empty() -> DoublyLinkedListT {
+    DoublyLinkedList::new()
+  }
+
+  pub fn doubly_list_filled() -> DoublyLinkedListT {
+    DoublyLinkedList::from(vec![0, 1, 2, 3])
+  }
+
+  // Walks the links both ways and compares against `expected`.
+  pub fn test_list<T: PartialEq + Clone + std::fmt::Debug>(
+    list: &DoublyLinkedList<T>,
+    expected: Vec<T>,
+  ) {
+    let mut forward = vec![];
+    let mut prev: DoublyLink<T> = None;
+    let mut ptr = list.head;
+    while let Some(node) = ptr {
+      unsafe {
+        assert_eq!((*node.as_ptr()).prev, prev);
+        forward.push((*node.as_ptr()).value.clone());
+        prev = ptr;
+        ptr = (*node.as_ptr()).next;
+      }
+    }
+    assert_eq!(list.tail, prev);
+    let mut backward = vec![];
+    let mut ptr = list.tail;
+    while let Some(node) = ptr {
+      unsafe {
+        backward.push((*node.as_ptr()).value.clone());
+        ptr = (*node.as_ptr()).prev;
+      }
+    }
+    backward.reverse();
+    assert_eq!(forward, expected);
+    assert_eq!(backward, expected);
+    assert_eq!(list.len, expected.len())
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = DoublyLinkedList::<i32>::new();
+    assert!(op.is_empty());
+    setup::test_list(&op, vec![])
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::doubly_list_empty().len(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(setup::doubly_list_filled().len(), 4)
+  }
+}
+
+#[cfg(test)]
+mod front_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    assert_eq!(list.front(), None);
+    assert_eq!(list.back(), None);
+    assert_eq!(list.front_mut(), None);
+    assert_eq!(list.back_mut(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut list = setup::doubly_list_filled();
+    assert_eq!(list.front(), Some(&0));
+    assert_eq!(list.back(), Some(&3));
+    *list.front_mut().unwrap() = 10;
+    *list.back_mut().unwrap() = 13;
+    setup::test_list(&list, vec![10, 1, 2, 13])
+  }
+}
+
+#[cfg(test)]
+mod push_front {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    list.push_front(0);
+    setup::test_list(&list, vec![0])
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut list = setup::doubly_list_filled();
+    list.push_front(-1);
+    setup::test_list(&list, vec![-1, 0, 1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod push_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    list.push_back(0);
+    setup::test_list(&list, vec![0])
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut list = setup::doubly_list_filled();
+    list.push_back(4);
+    setup::test_list(&list, vec![0, 1, 2, 3, 4])
+  }
+}
+
+#[cfg(test)]
+mod pop_front {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    assert_eq!(list.pop_front(), Err(RemoveError::EmptyList))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut list = setup::doubly_list_filled();
+    assert_eq!(list.pop_front(), Ok(0));
+    setup::test_list(&list, vec![1, 2, 3])
+  }
+
+  #[test]
+  fn to_filled_just_one_node() {
+    let mut list = DoublyLinkedList::from(vec![1]);
+    assert_eq!(list.pop_front(), Ok(1));
+    setup::test_list(&list, vec![])
+  }
+}
+
+#[cfg(test)]
+mod pop_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    assert_eq!(list.pop_back(), Err(RemoveError::EmptyList))
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut list = setup::doubly_list_filled();
+    assert_eq!(list.pop_back(), Ok(3));
+    setup::test_list(&list, vec![0, 1, 2])
+  }
+
+  #[test]
+  fn to_filled_just_one_node() {
+    let mut list = DoublyLinkedList::from(vec![1]);
+    assert_eq!(list.pop_back(), Ok(1));
+    setup::test_list(&list, vec![])
+  }
+}
+
+#[cfg(test)]
+mod insert_before {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    let op = list.insert_before(0, 4);
+    assert_eq!(op, Err(InsertError::BeforeItemNotFound))
+  }
+
+  #[test]
+  fn to_filled_with_found_item() {
+    let mut list = setup::doubly_list_filled();
+    assert_eq!(list.insert_before(4, 2), Ok(()));
+    assert_eq!(list.insert_before(-1, 0), Ok(()));
+    setup::test_list(&list, vec![-1, 0, 1, 4, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod insert_after {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    let op = list.insert_after(0, 4);
+    assert_eq!(op, Err(InsertError::AfterItemNotFound))
+  }
+
+  #[test]
+  fn to_filled_with_found_item() {
+    let mut list = setup::doubly_list_filled();
+    assert_eq!(list.insert_after(4, 1), Ok(()));
+    assert_eq!(list.insert_after(5, 3), Ok(()));
+    setup::test_list(&list, vec![0, 1, 4, 2, 3, 5])
+  }
+}
+
+#[cfg(test)]
+mod remove_item {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    assert_eq!(list.remove_item(1), Err(RemoveError::EmptyList))
+  }
+
+  #[test]
+  fn to_filled_without_found_item() {
+    let mut list = setup::doubly_list_filled();
+    assert_eq!(list.remove_item(4), Err(RemoveError::ItemNotFound))
+  }
+
+  #[test]
+  fn to_filled_with_found_item() {
+    let mut list = setup::doubly_list_filled();
+    assert_eq!(list.remove_item(2), Ok(2));
+    assert_eq!(list.remove_item(0), Ok(0));
+    assert_eq!(list.remove_item(3), Ok(3));
+    setup::test_list(&list, vec![1])
+  }
+}
+
+#[cfg(test)]
+mod append {
+  use super::*;
+
+  #[test]
+  fn to_both_empty() {
+    let mut list = setup::doubly_list_empty();
+    list.append(setup::doubly_list_empty());
+    setup::test_list(&list, vec![])
+  }
+
+  #[test]
+  fn to_first_empty_second_filled() {
+    let mut list = setup::doubly_list_empty();
+    list.append(setup::doubly_list_filled());
+    setup::test_list(&list, vec![0, 1, 2, 3])
+  }
+
+  #[test]
+  fn to_first_filled_second_empty() {
+    let mut list = setup::doubly_list_filled();
+    list.append(setup::doubly_list_empty());
+    setup::test_list(&list, vec![0, 1, 2, 3])
+  }
+
+  #[test]
+  fn to_both_filled() {
+    let mut list = setup::doubly_list_filled();
+    list.append(DoublyLinkedList::from(vec![4, 5]));
+    setup::test_list(&list, vec![0, 1, 2, 3, 4, 5])
+  }
+}
+
+#[cfg(test)]
+mod split_off {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    let op = list.split_off(0).unwrap();
+    setup::test_list(&op, vec![]);
+    assert_eq!(list.split_off(1).map(|_| ()), Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    for at in 0..=4 {
+      let mut list = setup::doubly_list_filled();
+      let op = list.split_off(at).unwrap();
+      setup::test_list(&list, (0..at as i32).collect());
+      setup::test_list(&op, (at as i32..4).collect())
+    }
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let mut list = setup::doubly_list_filled();
+    assert_eq!(list.split_off(5).map(|_| ()), Err(IndexError::OutOfBounds));
+    setup::test_list(&list, vec![0, 1, 2, 3])
+  }
+
+  #[test]
+  fn reverses_append() {
+    let mut list = setup::doubly_list_filled();
+    let op = list.split_off(3).unwrap();
+    list.append(op);
+    setup::test_list(&list, vec![0, 1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::doubly_list_empty();
+    assert_eq!(list.iter().next(), None);
+    assert_eq!(list.iter().next_back(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::doubly_list_filled();
+    let mut iter = list.iter();
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next_back(), Some(&3));
+    assert_eq!(iter.next_back(), Some(&2));
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(
+      list.iter().rev().copied().collect::<Vec<_>>(),
+      vec![3, 2, 1, 0]
+    )
+  }
+}
+
+#[cfg(test)]
+mod iter_mut {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    assert_eq!(list.iter_mut().next(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut list = setup::doubly_list_filled();
+    for (index, item) in list.iter_mut().rev().enumerate() {
+      *item += 10 * index as i32;
+    }
+    setup::test_list(&list, vec![30, 21, 12, 3])
+  }
+}
+
+#[cfg(test)]
+mod into_iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut iter = setup::doubly_list_empty().into_iter();
+    assert_eq!(iter.next(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut iter = setup::doubly_list_filled().into_iter();
+    assert_eq!(iter.next_back(), Some(3));
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.collect::<Vec<_>>(), vec![1, 2])
+  }
+}
+
+#[cfg(test)]
+mod clone {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let list = setup::doubly_list_filled();
+    let mut op = list.clone();
+    assert_eq!(op, list);
+    assert_eq!(op.pop_back(), Ok(3));
+    assert_ne!(op, list);
+    setup::test_list(&list, vec![0, 1, 2, 3]);
+    assert_eq!(format!("{op:?}"), "[0, 1, 2]")
+  }
+}
+
+#[cfg(test)]
+mod cursor {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::doubly_list_empty();
+    let mut cursor = list.cursor_back();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.index(), 0);
+    cursor.move_prev();
+    assert_eq!(cursor.index(), 0);
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(cursor.peek_prev(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::doubly_list_filled();
+    let mut cursor = list.cursor_back();
+    assert_eq!((cursor.current(), cursor.index()), (Some(&3), 3));
+    cursor.move_next();
+    assert_eq!((cursor.current(), cursor.index()), (None, 4));
+    assert_eq!(cursor.peek_next(), Some(&0));
+    assert_eq!(cursor.peek_prev(), Some(&3));
+    cursor.move_next();
+    assert_eq!((cursor.current(), cursor.index()), (Some(&0), 0));
+    cursor.move_prev();
+    assert_eq!((cursor.current(), cursor.index()), (None, 4));
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!((cursor.current(), cursor.index()), (Some(&2), 2));
+    assert_eq!(list.cursor_front().peek_prev(), None)
+  }
+}
+
+#[cfg(test)]
+mod cursor_mut {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let mut list = setup::doubly_list_empty();
+    let mut cursor = list.cursor_front_mut();
+    assert_eq!(cursor.remove_current(), None);
+    cursor.insert_after(1);
+    cursor.insert_before(2);
+    cursor.insert_after(0);
+    assert_eq!(cursor.index(), 3);
+    setup::test_list(&list, vec![0, 1, 2])
+  }
+
+  #[test]
+  fn insert_around_current() {
+    let mut list = setup::doubly_list_filled();
+    let mut cursor = list.cursor_back_mut();
+    cursor.move_prev();
+    cursor.insert_before(5);
+    cursor.insert_after(6);
+    assert_eq!(cursor.index(), 3);
+    assert_eq!(cursor.current(), Some(&mut 2));
+    assert_eq!(cursor.peek_prev(), Some(&mut 5));
+    assert_eq!(cursor.peek_next(), Some(&mut 6));
+    setup::test_list(&list, vec![0, 1, 5, 2, 6, 3])
+  }
+
+  #[test]
+  fn remove_current() {
+    let mut list = setup::doubly_list_filled();
+    let mut cursor = list.cursor_back_mut();
+    assert_eq!(cursor.remove_current(), Some(3));
+    assert_eq!((cursor.index(), cursor.current()), (3, None));
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(cursor.remove_current(), Some(1));
+    assert_eq!((cursor.index(), cursor.current()), (1, Some(&mut 2)));
+    setup::test_list(&list, vec![0, 2])
+  }
+
+  #[test]
+  fn split_after() {
+    for at in 0..4 {
+      let mut list = setup::doubly_list_filled();
+      let mut cursor = list.cursor_front_mut();
+      (0..at).for_each(|_| cursor.move_next());
+      let op = cursor.split_after();
+      assert_eq!(cursor.index(), at);
+      cursor.move_next();
+      assert_eq!(cursor.index(), at + 1);
+      setup::test_list(&op, (at as i32 + 1..4).collect());
+      setup::test_list(&list, (0..=at as i32).collect())
+    }
+  }
+
+  #[test]
+  fn split_after_on_ghost() {
+    let mut list = setup::doubly_list_filled();
+    let mut cursor = list.cursor_back_mut();
+    cursor.move_next();
+    let op = cursor.split_after();
+    assert_eq!(cursor.index(), 0);
+    setup::test_list(&op, vec![0, 1, 2, 3]);
+    setup::test_list(&list, vec![])
+  }
+
+  #[test]
+  fn splice_after() {
+    let mut list = setup::doubly_list_filled();
+    let mut cursor = list.cursor_front_mut();
+    cursor.move_next();
+    cursor.splice_after(setup::doubly_list_empty());
+    cursor.splice_after(DoublyLinkedList::from(vec![10, 11]));
+    assert_eq!(cursor.index(), 1);
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.splice_after(DoublyLinkedList::from(vec![-2, -1]));
+    assert_eq!(cursor.index(), 8);
+    setup::test_list(&list, vec![-2, -1, 0, 1, 10, 11, 2, 3])
+  }
+
+  #[test]
+  fn splice_after_at_tail() {
+    let mut list = setup::doubly_list_filled();
+    let mut cursor = list.cursor_back_mut();
+    cursor.splice_after(DoublyLinkedList::from(vec![4, 5]));
+    cursor.move_next();
+    assert_eq!((cursor.index(), cursor.current()), (4, Some(&mut 4)));
+    setup::test_list(&list, vec![0, 1, 2, 3, 4, 5])
+  }
+
+  #[test]
+  fn find_cursor_mut() {
+    let mut list = setup::doubly_list_filled();
+    assert!(list.find_cursor_mut(4).is_none());
+    let mut cursor = list.find_cursor_mut(2).unwrap();
+    assert_eq!(cursor.index(), 2);
+    assert_eq!(cursor.remove_current(), Some(2));
+    cursor.insert_before(20);
+    setup::test_list(&list, vec![0, 1, 20, 3])
+  }
+}
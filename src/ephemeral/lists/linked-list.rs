@@ -1,74 +1,259 @@
-use std::{marker::PhantomData, ptr::NonNull};
+use std::{
+  cmp::Ordering,
+  mem,
+  ops::{Bound, RangeBounds},
+};
 
-type Link<T> = Option<NonNull<ListNode<T>>>;
+use crate::{
+  ephemeral::arenas::{Arena, ArenaWalkMut, Handle, HandleError},
+  IndexError,
+};
 
-#[derive(Clone, PartialEq, Debug)]
+// Resolves to a node only while it's still in the arena, so a removed node
+// leaves a stale handle behind instead of a dangling one.
+pub type ListHandle<T> = Handle<ListNode<T>>;
+
+// Holds the nodes of any number of lists. Moving nodes between lists of one
+// arena only relinks them, so it takes O(1) and keeps their handles valid.
+pub type ListArena<T> = Arena<ListNode<T>>;
+
+type Link<T> = Option<ListHandle<T>>;
+
+#[derive(Clone, Debug)]
 pub struct ListNode<T> {
   value: T,
+  prev: Link<T>,
   next: Link<T>,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+// A doubly linked list over nodes in a `ListArena`, which every method takes
+// and which may hold other lists too. Handing a list another arena, or a
+// handle to a node of another list, scrambles the lists involved, though
+// never unsafely. Dropping a list leaves its nodes in the arena until the
+// arena goes; `clear` frees them right away.
+//
+// Invariants:
+//    - `head` has no `prev`, `tail` has no `next`, and `prev`/`next` agree
+//    - `len` counts the nodes reachable from `head`
 pub struct LinkedList<T> {
-  root: Link<T>,
-  marker: PhantomData<T>,
+  head: Link<T>,
+  tail: Link<T>,
+  len: usize,
 }
 
 pub struct Iter<'a, T: 'a> {
-  root: &'a Link<T>,
-  marker: PhantomData<&'a T>,
+  front: Link<T>,
+  back: Link<T>,
+  len: usize,
+  nodes: &'a ListArena<T>,
 }
 
 impl<'a, T> Iterator for Iter<'a, T> {
   type Item = &'a T;
 
   fn next(&mut self) -> Option<Self::Item> {
-    if let None = self.root {
+    if self.len == 0 {
       return None;
     }
-    self.root.map(|node| unsafe {
-      let node = &*node.as_ptr();
-      self.root = &node.next;
-      &node.value
-    })
+    let node = &self.nodes[self.front?];
+    self.front = node.next;
+    self.len -= 1;
+    Some(&node.value)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.len, Some(self.len))
+  }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.len == 0 {
+      return None;
+    }
+    let node = &self.nodes[self.back?];
+    self.back = node.prev;
+    self.len -= 1;
+    Some(&node.value)
   }
 }
 
 pub struct IterMut<'a, T: 'a> {
-  root: Link<T>,
-  marker: PhantomData<&'a ListNode<T>>,
+  current: Link<T>,
+  nodes: ArenaWalkMut<'a, ListNode<T>>,
 }
 
 impl<'a, T> Iterator for IterMut<'a, T> {
   type Item = &'a mut T;
 
   fn next(&mut self) -> Option<&'a mut T> {
-    if let None = self.root {
-      return None;
-    }
-    self.root.map(|node| unsafe {
-      let node = &mut *node.as_ptr();
-      self.root = node.next;
-      &mut node.value
-    })
+    let node = self.nodes.get_mut(self.current?)?;
+    self.current = node.next;
+    Some(&mut node.value)
   }
 }
 
-#[derive(Clone)]
-pub struct IntoIter<T> {
+// Frees whatever it didn't yield once dropped.
+pub struct Drain<'a, T: 'a> {
   list: LinkedList<T>,
+  nodes: &'a mut ListArena<T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.list.remove_at_beginning(self.nodes).ok()
+  }
+}
+
+impl<T> Drop for Drain<'_, T> {
+  fn drop(&mut self) {
+    self.list.clear(self.nodes);
+  }
+}
+
+// Past the last node a cursor rests on a "ghost" position, whose next node is
+// the first one, so it can wrap around.
+pub struct Cursor<'a, T: 'a> {
+  current: Link<T>,
+  list: &'a LinkedList<T>,
+  nodes: &'a ListArena<T>,
+}
+
+impl<T> Clone for Cursor<'_, T> {
+  fn clone(&self) -> Self {
+    Self {
+      current: self.current,
+      list: self.list,
+      nodes: self.nodes,
+    }
+  }
+}
+
+impl<'a, T> Cursor<'a, T> {
+  pub fn current(&self) -> Option<&'a T> {
+    let nodes = self.nodes;
+    self.current.map(|handle| &nodes[handle].value)
+  }
+
+  pub fn move_next(&mut self) {
+    self.current = private::next_link(self.list, self.nodes, self.current);
+  }
+
+  pub fn peek_next(&self) -> Option<&'a T> {
+    let nodes = self.nodes;
+    private::next_link(self.list, nodes, self.current).map(|handle| &nodes[handle].value)
+  }
+}
+
+// Keeps the index of `current` (the length when on the ghost), so splitting
+// after it knows how long both halves are.
+pub struct CursorMut<'a, T: 'a> {
+  current: Link<T>,
+  index: usize,
+  list: &'a mut LinkedList<T>,
+  nodes: &'a mut ListArena<T>,
+}
+
+impl<T> CursorMut<'_, T> {
+  pub fn current(&mut self) -> Option<&mut T> {
+    self.current.map(|handle| &mut self.nodes[handle].value)
+  }
+
+  pub fn move_next(&mut self) {
+    match self.current {
+      None => self.index = 0,
+      Some(_) => self.index += 1,
+    }
+    self.current = private::next_link(self.list, self.nodes, self.current);
+  }
+
+  pub fn peek_next(&mut self) -> Option<&mut T> {
+    private::next_link(self.list, self.nodes, self.current)
+      .map(|handle| &mut self.nodes[handle].value)
+  }
+
+  // On the ghost, inserts at the front of the list.
+  pub fn insert_after(&mut self, item: T) -> ListHandle<T> {
+    let next = private::next_link(self.list, self.nodes, self.current);
+    let handle = private::link_between(self.list, self.nodes, item, self.current, next);
+    if self.current.is_none() {
+      self.index = self.list.len;
+    }
+    handle
+  }
+
+  // On the ghost, inserts at the end of the list.
+  pub fn insert_before(&mut self, item: T) -> ListHandle<T> {
+    let prev = private::prev_link(self.list, self.nodes, self.current);
+    self.index += 1;
+    private::link_between(self.list, self.nodes, item, prev, self.current)
+  }
+
+  // Removes the current node and moves to the next one.
+  pub fn remove_current(&mut self) -> Option<T> {
+    let handle = self.current?;
+    self.current = self.nodes[handle].next;
+    Some(private::remove_node(self.list, self.nodes, handle))
+  }
+
+  // Moves everything after the cursor into a new list in the same arena, in
+  // O(1). On the ghost, that's the whole list.
+  pub fn split_after(&mut self) -> LinkedList<T> {
+    let Some(first) = private::next_link(self.list, self.nodes, self.current) else {
+      return LinkedList::new();
+    };
+    let len = match self.current {
+      None => self.list.len,
+      Some(_) => self.list.len - self.index - 1,
+    };
+    let rest = LinkedList {
+      head: Some(first),
+      tail: self.list.tail,
+      len,
+    };
+    self.nodes[first].prev = None;
+    match self.current {
+      None => self.list.head = None,
+      Some(handle) => self.nodes[handle].next = None,
+    }
+    self.list.tail = self.current;
+    self.list.len -= len;
+    if self.current.is_none() {
+      self.index = 0;
+    }
+    rest
+  }
+
+  // Moves the nodes of `list`, which must share the arena, right after the
+  // cursor in O(1).
+  pub fn splice_after(&mut self, list: LinkedList<T>) {
+    let next = private::next_link(self.list, self.nodes, self.current);
+    private::splice_between(self.list, self.nodes, list, self.current, next);
+    if self.current.is_none() {
+      self.index = self.list.len;
+    }
+  }
 }
 
-impl<T> Iterator for IntoIter<T> {
+// Lazily unlinks and yields the items matching `pred`, keeping the rest.
+pub struct ExtractIf<'a, T: 'a> {
+  cursor: CursorMut<'a, T>,
+  pred: fn(&mut T) -> bool,
+}
+
+impl<T> Iterator for ExtractIf<'_, T> {
   type Item = T;
 
   fn next(&mut self) -> Option<T> {
-    self.list.root.map(|node| unsafe {
-      let node = &mut *node.as_ptr();
-      self.list.root = node.next;
-      let node = Box::from_raw(node);
-      node.value
-    })
+    while let Some(value) = self.cursor.current() {
+      if (self.pred)(value) {
+        return self.cursor.remove_current();
+      }
+      self.cursor.move_next();
+    }
+    None
   }
 }
 
@@ -87,22 +272,206 @@ pub enum RemoveError {
 mod private {
   use super::*;
 
-  pub fn new_link<T>(value: T, next: Link<T>) -> Link<T> {
-    unsafe {
-      Some(NonNull::new_unchecked(Box::into_raw(Box::new(ListNode {
-        value,
-        next,
-      }))))
+  // Links a detached node between `prev` and `next`, which must be adjacent.
+  pub fn attach<T>(
+    list: &mut LinkedList<T>,
+    nodes: &mut ListArena<T>,
+    handle: ListHandle<T>,
+    prev: Link<T>,
+    next: Link<T>,
+  ) {
+    let node = &mut nodes[handle];
+    (node.prev, node.next) = (prev, next);
+    match prev {
+      Some(prev) => nodes[prev].next = Some(handle),
+      None => list.head = Some(handle),
+    }
+    match next {
+      Some(next) => nodes[next].prev = Some(handle),
+      None => list.tail = Some(handle),
+    }
+    list.len += 1;
+  }
+
+  // Unlinks `handle` from its neighbours, leaving it in the arena.
+  pub fn detach<T>(list: &mut LinkedList<T>, nodes: &mut ListArena<T>, handle: ListHandle<T>) {
+    let (prev, next) = (nodes[handle].prev, nodes[handle].next);
+    match prev {
+      Some(prev) => nodes[prev].next = next,
+      None => list.head = next,
+    }
+    match next {
+      Some(next) => nodes[next].prev = prev,
+      None => list.tail = prev,
+    }
+    list.len -= 1;
+  }
+
+  pub fn link_between<T>(
+    list: &mut LinkedList<T>,
+    nodes: &mut ListArena<T>,
+    value: T,
+    prev: Link<T>,
+    next: Link<T>,
+  ) -> ListHandle<T> {
+    let handle = nodes.insert(ListNode {
+      value,
+      prev: None,
+      next: None,
+    });
+    attach(list, nodes, handle, prev, next);
+    handle
+  }
+
+  // Handles kept by the list always resolve, so taking the node out of the
+  // arena can't fail.
+  pub fn remove_node<T>(
+    list: &mut LinkedList<T>,
+    nodes: &mut ListArena<T>,
+    handle: ListHandle<T>,
+  ) -> T {
+    detach(list, nodes, handle);
+    nodes.remove(handle).expect("linked node").value
+  }
+
+  // Links the nodes of `other` between `prev` and `next`, which must be
+  // adjacent.
+  pub fn splice_between<T>(
+    list: &mut LinkedList<T>,
+    nodes: &mut ListArena<T>,
+    other: LinkedList<T>,
+    prev: Link<T>,
+    next: Link<T>,
+  ) {
+    let (Some(first), Some(last)) = (other.head, other.tail) else {
+      return;
+    };
+    nodes[first].prev = prev;
+    nodes[last].next = next;
+    match prev {
+      Some(prev) => nodes[prev].next = Some(first),
+      None => list.head = Some(first),
+    }
+    match next {
+      Some(next) => nodes[next].prev = Some(last),
+      None => list.tail = Some(last),
+    }
+    list.len += other.len;
+  }
+
+  pub fn check<T>(nodes: &ListArena<T>, handle: ListHandle<T>) -> Result<(), HandleError> {
+    match nodes.contains(handle) {
+      true => Ok(()),
+      false => Err(HandleError::Stale),
+    }
+  }
+
+  // Node after `current`, where the ghost is followed by the first node.
+  pub fn next_link<T>(list: &LinkedList<T>, nodes: &ListArena<T>, current: Link<T>) -> Link<T> {
+    match current {
+      None => list.head,
+      Some(handle) => nodes[handle].next,
     }
   }
 
-  pub fn get_next<T>(node: Link<T>) -> Link<T> {
-    unsafe {
-      if let Some(inner_node) = node {
-        (*inner_node.as_ptr()).next
+  // Node before `current`, where the ghost comes after the last node.
+  pub fn prev_link<T>(list: &LinkedList<T>, nodes: &ListArena<T>, current: Link<T>) -> Link<T> {
+    match current {
+      None => list.tail,
+      Some(handle) => nodes[handle].prev,
+    }
+  }
+
+  // Cuts the chain after `len` nodes and returns the rest. Sorting only
+  // follows `next`, so `prev` is left for `relink_prev` to redo.
+  pub fn split_link<T>(nodes: &mut ListArena<T>, node: Link<T>, len: usize) -> Link<T> {
+    let mut ptr = node;
+    for _ in 1..len {
+      ptr = ptr.and_then(|handle| nodes[handle].next);
+    }
+    ptr.and_then(|handle| nodes[handle].next.take())
+  }
+
+  // Relinks the nodes of both sorted chains, taking from `n1` on ties.
+  pub fn merge_link<T, F>(
+    nodes: &mut ListArena<T>,
+    mut n1: Link<T>,
+    mut n2: Link<T>,
+    f: &mut F,
+  ) -> Link<T>
+  where
+    F: FnMut(&T, &T) -> Ordering,
+  {
+    let mut root: Link<T> = None;
+    let mut tail: Link<T> = None;
+    while let (Some(handle1), Some(handle2)) = (n1, n2) {
+      let handle = if f(&nodes[handle2].value, &nodes[handle1].value) == Ordering::Less {
+        n2 = nodes[handle2].next;
+        handle2
       } else {
-        None
+        n1 = nodes[handle1].next;
+        handle1
+      };
+      match tail {
+        None => root = Some(handle),
+        Some(inner) => nodes[inner].next = Some(handle),
       }
+      tail = Some(handle);
+    }
+    match tail {
+      None => root = n1.or(n2),
+      Some(inner) => nodes[inner].next = n1.or(n2),
+    }
+    root
+  }
+
+  pub fn sort_link<T, F>(nodes: &mut ListArena<T>, node: Link<T>, len: usize, f: &mut F) -> Link<T>
+  where
+    F: FnMut(&T, &T) -> Ordering,
+  {
+    if len < 2 {
+      return node;
+    }
+    let half = len / 2;
+    let right = split_link(nodes, node, half);
+    let left = sort_link(nodes, node, half, f);
+    let right = sort_link(nodes, right, len - half, f);
+    merge_link(nodes, left, right, f)
+  }
+
+  // Rebuilds `prev` and `tail` from the `next` links.
+  pub fn relink_prev<T>(list: &mut LinkedList<T>, nodes: &mut ListArena<T>) {
+    let mut prev = None;
+    let mut ptr = list.head;
+    while let Some(handle) = ptr {
+      nodes[handle].prev = prev;
+      prev = Some(handle);
+      ptr = nodes[handle].next;
+    }
+    list.tail = prev;
+  }
+
+  pub fn sort_by<T, F>(list: &mut LinkedList<T>, nodes: &mut ListArena<T>, mut f: F)
+  where
+    F: FnMut(&T, &T) -> Ordering,
+  {
+    list.head = sort_link(nodes, list.head, list.len, &mut f);
+    relink_prev(list, nodes);
+  }
+
+  pub fn dedup_by<T, F>(list: &mut LinkedList<T>, nodes: &mut ListArena<T>, mut same: F)
+  where
+    F: FnMut(&T, &T) -> bool,
+  {
+    let mut ptr = list.head;
+    while let Some(handle) = ptr {
+      while let Some(next) = nodes[handle].next {
+        if !same(&nodes[next].value, &nodes[handle].value) {
+          break;
+        }
+        remove_node(list, nodes, next);
+      }
+      ptr = nodes[handle].next;
     }
   }
 }
@@ -110,224 +479,439 @@ mod private {
 impl<T> LinkedList<T> {
   pub const fn new() -> Self {
     Self {
-      root: None,
-      marker: PhantomData,
+      head: None,
+      tail: None,
+      len: 0,
     }
   }
 
   pub fn is_empty(&self) -> bool {
-    matches!(self.root, None)
+    self.head.is_none()
+  }
+
+  pub fn len(&self) -> i32 {
+    self.len as i32
   }
 
-  pub fn insert_at_beginning(&mut self, item: T) {
-    let new_root = private::new_link(item, self.root);
-    self.root = new_root;
+  pub fn front<'a>(&self, nodes: &'a ListArena<T>) -> Option<&'a T> {
+    self.resolve(nodes, self.head?)
   }
 
-  pub fn insert_at_end(&mut self, item: T) {
-    unsafe {
-      let new_node = private::new_link(item, None);
-      let mut ptr = self.root;
-      while let Some(in_next) = private::get_next(ptr) {
-        ptr = Some(in_next);
-      }
-      match ptr {
-        None => {
-          self.root = new_node;
-        }
-        Some(inner) => {
-          (*inner.as_ptr()).next = new_node;
-        }
-      }
-    }
+  pub fn back<'a>(&self, nodes: &'a ListArena<T>) -> Option<&'a T> {
+    self.resolve(nodes, self.tail?)
   }
 
-  pub fn insert_before(&mut self, item: T, before: T) -> Result<(), InsertError>
+  pub fn front_handle(&self) -> Option<ListHandle<T>> {
+    self.head
+  }
+
+  pub fn back_handle(&self) -> Option<ListHandle<T>> {
+    self.tail
+  }
+
+  // `None` both for the last node and for a stale handle.
+  pub fn next(&self, nodes: &ListArena<T>, handle: ListHandle<T>) -> Option<ListHandle<T>> {
+    nodes.get(handle)?.next
+  }
+
+  pub fn prev(&self, nodes: &ListArena<T>, handle: ListHandle<T>) -> Option<ListHandle<T>> {
+    nodes.get(handle)?.prev
+  }
+
+  pub fn resolve<'a>(&self, nodes: &'a ListArena<T>, handle: ListHandle<T>) -> Option<&'a T> {
+    nodes.get(handle).map(|node| &node.value)
+  }
+
+  pub fn resolve_mut<'a>(
+    &mut self,
+    nodes: &'a mut ListArena<T>,
+    handle: ListHandle<T>,
+  ) -> Option<&'a mut T> {
+    nodes.get_mut(handle).map(|node| &mut node.value)
+  }
+
+  pub fn insert_at_beginning(&mut self, nodes: &mut ListArena<T>, item: T) -> ListHandle<T> {
+    private::link_between(self, nodes, item, None, self.head)
+  }
+
+  pub fn insert_at_end(&mut self, nodes: &mut ListArena<T>, item: T) -> ListHandle<T> {
+    private::link_between(self, nodes, item, self.tail, None)
+  }
+
+  pub fn insert_before(
+    &mut self,
+    nodes: &mut ListArena<T>,
+    item: T,
+    before: T,
+  ) -> Result<(), InsertError>
   where
     T: PartialEq,
   {
-    unsafe {
-      let mut ptr = self.root;
-      let mut preptr: Link<T> = None;
-      while let Some(inner) = ptr {
-        let inner_ptr = inner.as_ptr();
-        if (*inner_ptr).value == before {
-          let new_node = private::new_link(item, ptr);
-          match preptr {
-            None => self.root = new_node,
-            Some(inner_preptr) => (*inner_preptr.as_ptr()).next = new_node,
-          }
-          return Ok(());
-        }
-        preptr = ptr;
-        ptr = (*inner_ptr).next;
-      }
-      return Err(InsertError::BeforeItemNotFound);
-    }
+    let mut cursor = self
+      .find_cursor_mut(nodes, before)
+      .ok_or(InsertError::BeforeItemNotFound)?;
+    cursor.insert_before(item);
+    Ok(())
   }
 
-  pub fn insert_after(&mut self, item: T, after: T) -> Result<(), InsertError>
+  pub fn insert_after(
+    &mut self,
+    nodes: &mut ListArena<T>,
+    item: T,
+    after: T,
+  ) -> Result<(), InsertError>
   where
     T: PartialEq,
   {
-    unsafe {
-      let mut ptr = self.root.and_then(|node| (*node.as_ptr()).next);
-      let mut preptr = self.root;
-      while let Some(inner_preptr) = preptr {
-        let inner_ptr = inner_preptr.as_ptr();
-        if (*inner_ptr).value == after {
-          (*inner_ptr).next = private::new_link(item, ptr);
-          return Ok(());
-        }
-        preptr = ptr;
-        ptr = ptr.and_then(|node| (*node.as_ptr()).next);
-      }
-      return Err(InsertError::AfterItemNotFound);
+    let mut cursor = self
+      .find_cursor_mut(nodes, after)
+      .ok_or(InsertError::AfterItemNotFound)?;
+    cursor.insert_after(item);
+    Ok(())
+  }
+
+  pub fn insert_before_handle(
+    &mut self,
+    nodes: &mut ListArena<T>,
+    handle: ListHandle<T>,
+    item: T,
+  ) -> Result<ListHandle<T>, HandleError> {
+    private::check(nodes, handle)?;
+    let prev = nodes[handle].prev;
+    Ok(private::link_between(self, nodes, item, prev, Some(handle)))
+  }
+
+  pub fn insert_after_handle(
+    &mut self,
+    nodes: &mut ListArena<T>,
+    handle: ListHandle<T>,
+    item: T,
+  ) -> Result<ListHandle<T>, HandleError> {
+    private::check(nodes, handle)?;
+    let next = nodes[handle].next;
+    Ok(private::link_between(self, nodes, item, Some(handle), next))
+  }
+
+  pub fn remove_at_beginning(&mut self, nodes: &mut ListArena<T>) -> Result<T, RemoveError> {
+    let head = self.head.ok_or(RemoveError::EmptyList)?;
+    Ok(private::remove_node(self, nodes, head))
+  }
+
+  pub fn remove_at_end(&mut self, nodes: &mut ListArena<T>) -> Result<T, RemoveError> {
+    let tail = self.tail.ok_or(RemoveError::EmptyList)?;
+    Ok(private::remove_node(self, nodes, tail))
+  }
+
+  pub fn remove_item(&mut self, nodes: &mut ListArena<T>, item: T) -> Result<T, RemoveError>
+  where
+    T: PartialEq,
+  {
+    if self.is_empty() {
+      return Err(RemoveError::EmptyList);
     }
+    self
+      .find_cursor_mut(nodes, item)
+      .and_then(|mut cursor| cursor.remove_current())
+      .ok_or(RemoveError::ItemNotFound)
   }
 
-  pub fn remove_at_beginning(&mut self) -> Result<(), RemoveError> {
-    unsafe {
-      if let None = self.root {
-        return Err(RemoveError::EmptyList);
-      }
-      self.root = (*self.root.unwrap().as_ptr()).next;
-      Ok(())
+  pub fn remove(
+    &mut self,
+    nodes: &mut ListArena<T>,
+    handle: ListHandle<T>,
+  ) -> Result<T, HandleError> {
+    private::check(nodes, handle)?;
+    Ok(private::remove_node(self, nodes, handle))
+  }
+
+  // Relinks the node at the front; its handle stays valid.
+  pub fn move_to_front(
+    &mut self,
+    nodes: &mut ListArena<T>,
+    handle: ListHandle<T>,
+  ) -> Result<(), HandleError> {
+    private::check(nodes, handle)?;
+    if self.head != Some(handle) {
+      private::detach(self, nodes, handle);
+      private::attach(self, nodes, handle, None, self.head);
     }
+    Ok(())
   }
 
-  pub fn remove_at_end(&mut self) -> Result<(), RemoveError> {
-    unsafe {
-      if let None = self.root {
-        return Err(RemoveError::EmptyList);
-      }
-      let mut ptr = self.root;
-      let mut preptr: Link<T> = None;
-      while let Some(inner_next) = private::get_next(ptr) {
-        preptr = ptr;
-        ptr = Some(inner_next);
-      }
-      (*preptr.unwrap().as_ptr()).next = None;
-      Ok(())
+  // Relinks the node at the back; its handle stays valid.
+  pub fn move_to_back(
+    &mut self,
+    nodes: &mut ListArena<T>,
+    handle: ListHandle<T>,
+  ) -> Result<(), HandleError> {
+    private::check(nodes, handle)?;
+    if self.tail != Some(handle) {
+      private::detach(self, nodes, handle);
+      private::attach(self, nodes, handle, self.tail, None);
     }
+    Ok(())
   }
 
-  pub fn remove_item(&mut self, item: T) -> Result<(), RemoveError>
+  // Handle to the first node holding `item`, which goes stale once that node
+  // is removed.
+  pub fn search(&self, nodes: &ListArena<T>, item: T) -> Option<ListHandle<T>>
   where
     T: PartialEq,
   {
-    unsafe {
-      if let None = self.root {
-        return Err(RemoveError::EmptyList);
-      }
-      let mut ptr = self.root;
-      let mut preptr: Link<T> = None;
-      while let Some(inner) = ptr {
-        let in_ptr = inner.as_ptr();
-        if (*in_ptr).value == item {
-          match preptr {
-            None => self.root = private::get_next(ptr),
-            Some(_) => (*preptr.unwrap().as_ptr()).next = private::get_next(ptr),
-          }
-          return Ok(());
-        }
-        preptr = ptr;
-        ptr = (*in_ptr).next;
+    let mut ptr = self.head;
+    while let Some(handle) = ptr {
+      if nodes[handle].value == item {
+        return Some(handle);
       }
-      return Err(RemoveError::ItemNotFound);
+      ptr = nodes[handle].next;
     }
+    None
   }
 
-  pub fn len(&self) -> i32 {
-    let mut count = 0;
-    let mut ptr = self.root;
-    while let Some(inner) = ptr {
-      count += 1;
-      ptr = unsafe { (*inner.as_ptr()).next };
+  pub fn rev(&mut self, nodes: &mut ListArena<T>) {
+    let mut ptr = self.head;
+    while let Some(handle) = ptr {
+      let node = &mut nodes[handle];
+      mem::swap(&mut node.prev, &mut node.next);
+      ptr = node.prev;
     }
-    count
+    mem::swap(&mut self.head, &mut self.tail);
   }
 
-  pub fn search(&self, item: T) -> Link<T>
+  // Links the nodes of `list`, which must share the arena, after the last
+  // node in O(1).
+  pub fn concat(&mut self, nodes: &mut ListArena<T>, list: Self) {
+    private::splice_between(self, nodes, list, self.tail, None);
+  }
+
+  pub fn get<'a>(&self, nodes: &'a ListArena<T>, index: usize) -> Option<&'a T> {
+    self.iter(nodes).nth(index)
+  }
+
+  pub fn get_mut<'a>(&mut self, nodes: &'a mut ListArena<T>, index: usize) -> Option<&'a mut T> {
+    self.iter_mut(nodes).nth(index)
+  }
+
+  pub fn position(&self, nodes: &ListArena<T>, f: fn(&T) -> bool) -> Option<usize> {
+    self.iter(nodes).position(f)
+  }
+
+  pub fn contains(&self, nodes: &ListArena<T>, item: &T) -> bool
   where
     T: PartialEq,
   {
-    unsafe {
-      let mut ptr = self.root;
-      while let Some(inner) = ptr {
-        let in_ptr = inner.as_ptr();
-        if (*in_ptr).value == item {
-          return ptr;
-        }
-        ptr = (*in_ptr).next;
+    self.iter(nodes).any(|value| value == item)
+  }
+
+  // Stable merge sort that relinks the nodes in place, without allocating.
+  pub fn sort(&mut self, nodes: &mut ListArena<T>)
+  where
+    T: Ord,
+  {
+    private::sort_by(self, nodes, T::cmp)
+  }
+
+  pub fn sort_by(&mut self, nodes: &mut ListArena<T>, f: fn(&T, &T) -> Ordering) {
+    private::sort_by(self, nodes, f)
+  }
+
+  pub fn sort_by_key<K>(&mut self, nodes: &mut ListArena<T>, f: fn(&T) -> K)
+  where
+    K: Ord,
+  {
+    private::sort_by(self, nodes, |a, b| f(a).cmp(&f(b)))
+  }
+
+  // Removes consecutive repeated items, keeping the first of each run.
+  pub fn dedup(&mut self, nodes: &mut ListArena<T>)
+  where
+    T: PartialEq,
+  {
+    private::dedup_by(self, nodes, |a, b| a == b)
+  }
+
+  pub fn dedup_by_key<K>(&mut self, nodes: &mut ListArena<T>, f: fn(&T) -> K)
+  where
+    K: PartialEq,
+  {
+    private::dedup_by(self, nodes, |a, b| f(a) == f(b))
+  }
+
+  pub fn retain(&mut self, nodes: &mut ListArena<T>, f: fn(&T) -> bool) {
+    let mut cursor = self.cursor_front_mut(nodes);
+    while let Some(value) = cursor.current() {
+      if f(value) {
+        cursor.move_next();
+      } else {
+        cursor.remove_current();
       }
-      return None;
     }
   }
 
-  pub fn rev(&mut self) {
-    let mut current = self.root;
-    let mut prev: Link<T> = None;
+  // Items the iterator doesn't reach stay in the list.
+  pub fn extract_if<'a>(
+    &'a mut self,
+    nodes: &'a mut ListArena<T>,
+    pred: fn(&mut T) -> bool,
+  ) -> ExtractIf<'a, T> {
+    ExtractIf {
+      cursor: self.cursor_front_mut(nodes),
+      pred,
+    }
+  }
 
-    while let Some(current_inner) = current {
-      let next = unsafe { (*current_inner.as_ptr()).next };
-      unsafe {
-        (*current_inner.as_ptr()).next = prev;
+  // Returns how many items were removed.
+  pub fn remove_all(&mut self, nodes: &mut ListArena<T>, item: T) -> i32
+  where
+    T: PartialEq,
+  {
+    let mut count = 0;
+    let mut cursor = self.cursor_front_mut(nodes);
+    while let Some(value) = cursor.current() {
+      if *value == item {
+        cursor.remove_current();
+        count += 1;
+      } else {
+        cursor.move_next();
       }
-      prev = Some(current_inner);
-      current = next;
     }
+    count
+  }
 
-    self.root = prev;
+  // Reverses the items in `range`, relinking the nodes in place.
+  pub fn reverse_range<R>(&mut self, nodes: &mut ListArena<T>, range: R) -> Result<(), IndexError>
+  where
+    R: RangeBounds<usize>,
+  {
+    let start = match range.start_bound() {
+      Bound::Included(start) => *start,
+      Bound::Excluded(start) => start + 1,
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(end) => end + 1,
+      Bound::Excluded(end) => *end,
+      Bound::Unbounded => self.len,
+    };
+    if start > end || end > self.len {
+      return Err(IndexError::OutOfBounds);
+    }
+    if end - start < 2 {
+      return Ok(());
+    }
+    let mut before: Link<T> = None;
+    for _ in 0..start {
+      before = private::next_link(self, nodes, before);
+    }
+    let first = private::next_link(self, nodes, before).unwrap();
+    let mut last = first;
+    for _ in 1..end - start {
+      last = nodes[last].next.unwrap();
+    }
+    let after = nodes[last].next;
+    let mut ptr = Some(first);
+    while let Some(handle) = ptr {
+      let node = &mut nodes[handle];
+      mem::swap(&mut node.prev, &mut node.next);
+      ptr = if handle == last { None } else { node.prev };
+    }
+    (nodes[first].next, nodes[last].prev) = (after, before);
+    match before {
+      Some(before) => nodes[before].next = Some(last),
+      None => self.head = Some(last),
+    }
+    match after {
+      Some(after) => nodes[after].prev = Some(first),
+      None => self.tail = Some(first),
+    }
+    Ok(())
   }
 
-  pub fn concat(&mut self, list: Self) {
-    let mut tmp_node = &mut self.root;
-    while let Some(inner_node) = tmp_node {
-      tmp_node = unsafe { &mut (*inner_node.as_ptr()).next };
+  // Frees the nodes of this list, leaving those of other lists alone.
+  pub fn clear(&mut self, nodes: &mut ListArena<T>) {
+    while let Some(head) = self.head {
+      private::remove_node(self, nodes, head);
     }
-    *tmp_node = list.root
   }
 
-  pub fn iter(&self) -> Iter<'_, T> {
-    Iter {
-      root: &self.root,
-      marker: PhantomData,
+  // Copies the items into new nodes of the same arena.
+  pub fn clone_in(&self, nodes: &mut ListArena<T>) -> Self
+  where
+    T: Clone,
+  {
+    let mut list = Self::new();
+    let mut ptr = self.head;
+    while let Some(handle) = ptr {
+      let value = nodes[handle].value.clone();
+      list.insert_at_end(nodes, value);
+      ptr = nodes[handle].next;
     }
+    list
   }
 
-  pub fn iter_mut(&mut self) -> IterMut<'_, T> {
-    IterMut {
-      root: self.root,
-      marker: PhantomData,
+  pub fn cursor_front<'a>(&'a self, nodes: &'a ListArena<T>) -> Cursor<'a, T> {
+    Cursor {
+      current: self.head,
+      list: self,
+      nodes,
     }
   }
-}
 
-impl<T> IntoIterator for LinkedList<T> {
-  type Item = T;
-  type IntoIter = IntoIter<T>;
+  pub fn cursor_front_mut<'a>(&'a mut self, nodes: &'a mut ListArena<T>) -> CursorMut<'a, T> {
+    CursorMut {
+      current: self.head,
+      index: 0,
+      list: self,
+      nodes,
+    }
+  }
 
-  fn into_iter(self) -> IntoIter<T> {
-    IntoIter { list: self }
+  // Cursor on the first node holding `item`, for editing around it without
+  // searching again.
+  pub fn find_cursor_mut<'a>(
+    &'a mut self,
+    nodes: &'a mut ListArena<T>,
+    item: T,
+  ) -> Option<CursorMut<'a, T>>
+  where
+    T: PartialEq,
+  {
+    let mut cursor = self.cursor_front_mut(nodes);
+    while cursor.current.is_some() {
+      if cursor.current().is_some_and(|value| *value == item) {
+        return Some(cursor);
+      }
+      cursor.move_next();
+    }
+    None
   }
-}
 
-impl<'a, T> IntoIterator for &'a LinkedList<T> {
-  type Item = &'a T;
-  type IntoIter = Iter<'a, T>;
+  pub fn iter<'a>(&self, nodes: &'a ListArena<T>) -> Iter<'a, T> {
+    Iter {
+      front: self.head,
+      back: self.tail,
+      len: self.len,
+      nodes,
+    }
+  }
 
-  fn into_iter(self) -> Iter<'a, T> {
-    self.iter()
+  pub fn iter_mut<'a>(&mut self, nodes: &'a mut ListArena<T>) -> IterMut<'a, T> {
+    IterMut {
+      current: self.head,
+      nodes: nodes.walk_mut(),
+    }
   }
-}
 
-impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
-  type Item = &'a mut T;
-  type IntoIter = IterMut<'a, T>;
+  // Takes the items out from the front; whatever is left gets freed once the
+  // iterator is dropped.
+  pub fn drain(self, nodes: &mut ListArena<T>) -> Drain<'_, T> {
+    Drain { list: self, nodes }
+  }
+}
 
-  fn into_iter(self) -> IterMut<'a, T> {
-    self.iter_mut()
+impl<T> Default for LinkedList<T> {
+  fn default() -> Self {
+    Self::new()
   }
 }
 
@@ -2,55 +2,84 @@ use super::*;
 
 mod setup {
   use super::*;
+  use std::fmt::Debug;
 
   pub type LinkedListT = LinkedList<i32>;
-
-  pub fn node<T>(value: T, next: Link<T>) -> Link<T> {
-    unsafe {
-      Some(NonNull::new_unchecked(Box::into_raw(Box::new(ListNode {
-        value,
-        next,
-      }))))
+  pub type ListArenaT = ListArena<i32>;
+
+  pub fn handles<T>(nodes: &ListArena<T>, list: &LinkedList<T>) -> Vec<ListHandle<T>> {
+    let mut handles = vec![];
+    let mut ptr = list.head;
+    while let Some(handle) = ptr {
+      handles.push(handle);
+      ptr = nodes[handle].next;
     }
+    handles
   }
 
-  pub fn linked_list_empty() -> LinkedListT {
-    LinkedListT {
-      root: None,
-      marker: PhantomData,
-    }
+  pub fn list_of<T>(nodes: &mut ListArena<T>, items: Vec<T>) -> LinkedList<T> {
+    let mut list = LinkedList::new();
+    items.into_iter().rev().for_each(|item| {
+      list.insert_at_beginning(nodes, item);
+    });
+    list
   }
 
-  pub fn linked_list_filled() -> LinkedListT {
-    LinkedListT {
-      root: node(0, node(1, node(2, node(3, None)))),
-      marker: PhantomData,
-    }
+  pub fn linked_list_empty() -> (ListArenaT, LinkedListT) {
+    (ListArena::new(), LinkedList::new())
+  }
+
+  pub fn list_of_range(len: i32) -> (ListArenaT, LinkedListT) {
+    let mut nodes = ListArena::new();
+    let list = list_of(&mut nodes, (0..len).collect());
+    (nodes, list)
   }
 
-  pub fn test_list<T: PartialEq + std::fmt::Debug>(list: LinkedList<T>, expected: Vec<T>) {
-    let mut index = 0;
-    let mut tmp_node = list.root;
-    while let Some(node) = tmp_node {
-      unsafe {
-        dbg!(&(*node.as_ptr()).value);
-        dbg!(&expected[index]);
-        assert_eq!((*node.as_ptr()).value, expected[index]);
-        index += 1;
-        tmp_node = (*node.as_ptr()).next;
-      }
+  pub fn linked_list_filled() -> (ListArenaT, LinkedListT) {
+    let mut nodes = ListArena::new();
+    let list = list_of(&mut nodes, vec![0, 1, 2, 3]);
+    (nodes, list)
+  }
+
+  // Walks the list both ways, checking the links agree and hold `expected`.
+  pub fn test_links<T: PartialEq + Debug>(
+    nodes: &ListArena<T>,
+    list: &LinkedList<T>,
+    expected: Vec<T>,
+  ) {
+    let forward = handles(nodes, list);
+    let mut backward = vec![];
+    let mut ptr = list.tail;
+    while let Some(handle) = ptr {
+      backward.push(handle);
+      ptr = nodes[handle].prev;
     }
+    backward.reverse();
+    assert_eq!(forward, backward, "prev and next links disagree");
+    let items: Vec<&T> = forward.iter().map(|handle| &nodes[*handle].value).collect();
+    assert_eq!(items, expected.iter().collect::<Vec<_>>());
     assert_eq!(
-      expected.len(),
-      index,
-      "List size isn't same as expected, list size: {}, expected size: {}",
-      expected.len(),
-      index
-    );
+      list.len() as usize,
+      forward.len(),
+      "List size isn't same as expected, list size: {}, reachable nodes: {}",
+      list.len(),
+      forward.len()
+    )
+  }
+
+  // Same as `test_links`, for a list alone in its arena.
+  pub fn test_list<T: PartialEq + Debug>(
+    nodes: &ListArena<T>,
+    list: &LinkedList<T>,
+    expected: Vec<T>,
+  ) {
+    test_links(nodes, list, expected);
     assert_eq!(
-      tmp_node, None,
-      "Last node isn't None as expected, last node: {:#?}",
-      tmp_node,
+      nodes.len(),
+      list.len(),
+      "Arena holds unlinked nodes, arena size: {}, list size: {}",
+      nodes.len(),
+      list.len()
     )
   }
 }
@@ -62,8 +91,9 @@ mod new {
   #[test]
   fn single_case() {
     let op = LinkedList::<i32>::new();
-    let expected = setup::linked_list_empty();
-    assert_eq!(op, expected)
+    let (nodes, _) = setup::linked_list_empty();
+    assert_eq!((op.front_handle(), op.back_handle()), (None, None));
+    setup::test_list(&nodes, &op, vec![])
   }
 }
 
@@ -73,14 +103,14 @@ mod is_empty {
 
   #[test]
   fn to_empty() {
-    let list = setup::linked_list_empty();
+    let (_, list) = setup::linked_list_empty();
     let op = LinkedList::is_empty(&list);
     assert_eq!(op, true)
   }
 
   #[test]
   fn to_filled() {
-    let list = setup::linked_list_filled();
+    let (_, list) = setup::linked_list_filled();
     let op = LinkedList::is_empty(&list);
     assert_eq!(op, false)
   }
@@ -92,18 +122,21 @@ mod insert_at_beginning {
 
   #[test]
   fn to_empty() {
-    let mut list = setup::linked_list_empty();
-    list.insert_at_beginning(0);
-    let expected = vec![0];
-    setup::test_list(list, expected)
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let handle = list.insert_at_beginning(&mut nodes, 0);
+    assert_eq!(
+      (list.front_handle(), list.back_handle()),
+      (Some(handle), Some(handle))
+    );
+    setup::test_list(&nodes, &list, vec![0])
   }
 
   #[test]
   fn to_filled() {
-    let mut list = setup::linked_list_filled();
-    list.insert_at_beginning(-1);
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    list.insert_at_beginning(&mut nodes, -1);
     let expected = vec![-1, 0, 1, 2, 3];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 }
 
@@ -113,18 +146,19 @@ mod insert_at_end {
 
   #[test]
   fn to_empty() {
-    let mut list = setup::linked_list_empty();
-    list.insert_at_end(0);
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    list.insert_at_end(&mut nodes, 0);
     let expected = vec![0];
-    setup::test_list(list, expected);
+    setup::test_list(&nodes, &list, expected);
   }
 
   #[test]
   fn to_filled() {
-    let mut list = setup::linked_list_filled();
-    list.insert_at_end(4);
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let handle = list.insert_at_end(&mut nodes, 4);
+    assert_eq!(list.back_handle(), Some(handle));
     let expected = vec![0, 1, 2, 3, 4];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 }
 
@@ -134,36 +168,36 @@ mod insert_before {
 
   #[test]
   fn to_empty() {
-    let mut list = setup::linked_list_empty();
-    let op = list.insert_before(0, 4);
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let op = list.insert_before(&mut nodes, 0, 4);
     let expected = Err(InsertError::BeforeItemNotFound);
     assert_eq!(op, expected)
   }
 
   #[test]
   fn to_filled_without_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.insert_before(6, 5);
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.insert_before(&mut nodes, 6, 5);
     let expected = Err(InsertError::BeforeItemNotFound);
     assert_eq!(op, expected)
   }
 
   #[test]
   fn to_filled_with_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.insert_before(4, 2);
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.insert_before(&mut nodes, 4, 2);
     assert_eq!(op, Ok(()));
     let expected = vec![0, 1, 4, 2, 3];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 
   #[test]
   fn to_filled_with_first_as_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.insert_before(-1, 0);
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.insert_before(&mut nodes, -1, 0);
     assert_eq!(op, Ok(()));
     let expected = vec![-1, 0, 1, 2, 3];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 }
 
@@ -173,36 +207,62 @@ mod insert_after {
 
   #[test]
   fn to_empty() {
-    let mut list = setup::linked_list_empty();
-    let op = list.insert_after(0, 4);
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let op = list.insert_after(&mut nodes, 0, 4);
     let expected = Err(InsertError::AfterItemNotFound);
     assert_eq!(op, expected)
   }
 
   #[test]
   fn to_filled_without_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.insert_after(6, 5);
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.insert_after(&mut nodes, 6, 5);
     let expected = Err(InsertError::AfterItemNotFound);
     assert_eq!(op, expected)
   }
 
   #[test]
   fn to_filled_with_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.insert_after(4, 1);
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.insert_after(&mut nodes, 4, 1);
     assert_eq!(op, Ok(()));
     let expected = vec![0, 1, 4, 2, 3];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 
   #[test]
   fn to_filled_with_last_as_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.insert_after(4, 3);
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.insert_after(&mut nodes, 4, 3);
     assert_eq!(op, Ok(()));
     let expected = vec![0, 1, 2, 3, 4];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
+  }
+}
+
+#[cfg(test)]
+mod insert_handle {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (_, filled) = setup::linked_list_filled();
+    let handle = filled.front_handle().unwrap();
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let op = list.insert_after_handle(&mut nodes, handle, 9);
+    assert_eq!(op, Err(HandleError::Stale))
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let last = list.back_handle().unwrap();
+    let first = list.front_handle().unwrap();
+    let op = list.insert_after_handle(&mut nodes, last, 4).unwrap();
+    list.insert_before_handle(&mut nodes, first, -1).unwrap();
+    list.insert_before_handle(&mut nodes, op, 35).unwrap();
+    assert_eq!(list.back_handle(), Some(op));
+    setup::test_list(&nodes, &list, vec![-1, 0, 1, 2, 3, 35, 4])
   }
 }
 
@@ -212,30 +272,28 @@ mod remove_at_beginning {
 
   #[test]
   fn to_empty() {
-    let mut list = setup::linked_list_empty();
-    let op = list.remove_at_beginning();
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let op = list.remove_at_beginning(&mut nodes);
     assert_eq!(op, Err(RemoveError::EmptyList))
   }
 
   #[test]
   fn to_filled() {
-    let mut list = setup::linked_list_filled();
-    let op = list.remove_at_beginning();
-    assert_eq!(op, Ok(()));
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.remove_at_beginning(&mut nodes);
+    assert_eq!(op, Ok(0));
     let expected = vec![1, 2, 3];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 
   #[test]
   fn to_filled_just_one_node() {
-    let mut list = LinkedList {
-      root: setup::node(1, None),
-      marker: PhantomData,
-    };
-    let op = list.remove_at_beginning();
-    assert_eq!(op, Ok(()));
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, vec![1]);
+    let op = list.remove_at_beginning(&mut nodes);
+    assert_eq!(op, Ok(1));
     let expected = vec![];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 }
 
@@ -245,30 +303,28 @@ mod remove_at_end {
 
   #[test]
   fn to_empty() {
-    let mut list = setup::linked_list_empty();
-    let op = list.remove_at_end();
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let op = list.remove_at_end(&mut nodes);
     assert_eq!(op, Err(RemoveError::EmptyList))
   }
 
   #[test]
   fn to_filled() {
-    let mut list = setup::linked_list_filled();
-    let op = list.remove_at_end();
-    assert_eq!(op, Ok(()));
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.remove_at_end(&mut nodes);
+    assert_eq!(op, Ok(3));
     let expected = vec![0, 1, 2];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 
   #[test]
   fn to_filled_just_one_node() {
-    let mut list = LinkedList {
-      root: setup::node(1, None),
-      marker: PhantomData,
-    };
-    let op = list.remove_at_beginning();
-    assert_eq!(op, Ok(()));
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, vec![1]);
+    let op = list.remove_at_end(&mut nodes);
+    assert_eq!(op, Ok(1));
     let expected = vec![];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 }
 
@@ -278,43 +334,101 @@ mod remove_item {
 
   #[test]
   fn to_empty() {
-    let mut list = setup::linked_list_empty();
-    let op = list.remove_item(1);
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let op = list.remove_item(&mut nodes, 1);
     assert_eq!(op, Err(RemoveError::EmptyList))
   }
 
   #[test]
   fn to_filled_without_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.remove_item(4);
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.remove_item(&mut nodes, 4);
     assert_eq!(op, Err(RemoveError::ItemNotFound))
   }
 
   #[test]
   fn to_filled_with_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.remove_item(2);
-    assert_eq!(op, Ok(()));
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.remove_item(&mut nodes, 2);
+    assert_eq!(op, Ok(2));
     let expected = vec![0, 1, 3];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 
   #[test]
   fn to_filled_with_first_as_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.remove_item(0);
-    assert_eq!(op, Ok(()));
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.remove_item(&mut nodes, 0);
+    assert_eq!(op, Ok(0));
     let expected = vec![1, 2, 3];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 
   #[test]
   fn to_filled_with_last_as_found_item() {
-    let mut list = setup::linked_list_filled();
-    let op = list.remove_item(3);
-    assert_eq!(op, Ok(()));
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.remove_item(&mut nodes, 3);
+    assert_eq!(op, Ok(3));
     let expected = vec![0, 1, 2];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let handle = list.search(&nodes, 2).unwrap();
+    assert_eq!(list.remove(&mut nodes, handle), Ok(2));
+    assert_eq!(list.remove(&mut nodes, handle), Err(HandleError::Stale));
+    setup::test_list(&nodes, &list, vec![0, 1, 3])
+  }
+
+  #[test]
+  fn to_filled_with_reused_slot() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let stale = list.search(&nodes, 1).unwrap();
+    list.remove(&mut nodes, stale).unwrap();
+    let fresh = list.insert_at_end(&mut nodes, 10);
+    assert_eq!(fresh.index(), stale.index());
+    assert_eq!(list.resolve(&nodes, stale), None);
+    let op = list.insert_before_handle(&mut nodes, stale, 5);
+    assert_eq!(op, Err(HandleError::Stale));
+    let op = list.move_to_front(&mut nodes, stale);
+    assert_eq!(op, Err(HandleError::Stale));
+    setup::test_list(&nodes, &list, vec![0, 2, 3, 10])
+  }
+}
+
+#[cfg(test)]
+mod move_to_front {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let handles = setup::handles(&nodes, &list);
+    list.move_to_front(&mut nodes, handles[2]).unwrap();
+    list.move_to_front(&mut nodes, handles[3]).unwrap();
+    list.move_to_front(&mut nodes, handles[3]).unwrap();
+    setup::test_list(&nodes, &list, vec![3, 2, 0, 1]);
+    list.move_to_back(&mut nodes, handles[3]).unwrap();
+    list.move_to_back(&mut nodes, handles[0]).unwrap();
+    setup::test_list(&nodes, &list, vec![2, 1, 3, 0]);
+    *list.resolve_mut(&mut nodes, handles[1]).unwrap() = 10;
+    assert_eq!(list.resolve(&nodes, handles[1]), Some(&10))
+  }
+
+  #[test]
+  fn to_single() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let handle = list.insert_at_end(&mut nodes, 0);
+    list.move_to_back(&mut nodes, handle).unwrap();
+    list.move_to_front(&mut nodes, handle).unwrap();
+    setup::test_list(&nodes, &list, vec![0])
   }
 }
 
@@ -324,36 +438,83 @@ mod len {
 
   #[test]
   fn to_empty() {
-    let list = setup::linked_list_empty();
+    let (_, list) = setup::linked_list_empty();
     let len = list.len();
     assert_eq!(len, 0)
   }
 
   #[test]
   fn to_filled() {
-    let list = setup::linked_list_filled();
+    let (_, list) = setup::linked_list_filled();
     let len = list.len();
     assert_eq!(len, 4)
   }
 }
 
+#[cfg(test)]
+mod front {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (nodes, list) = setup::linked_list_empty();
+    assert_eq!((list.front(&nodes), list.back(&nodes)), (None, None))
+  }
+
+  #[test]
+  fn to_filled() {
+    let (nodes, list) = setup::linked_list_filled();
+    assert_eq!(
+      (list.front(&nodes), list.back(&nodes)),
+      (Some(&0), Some(&3))
+    );
+    let second = list.next(&nodes, list.front_handle().unwrap()).unwrap();
+    assert_eq!(list.resolve(&nodes, second), Some(&1));
+    assert_eq!(list.prev(&nodes, second), list.front_handle());
+    assert_eq!(list.next(&nodes, list.back_handle().unwrap()), None)
+  }
+}
+
 #[cfg(test)]
 mod search {
   use super::*;
 
   #[test]
   fn to_empty() {
-    let list = setup::linked_list_empty();
-    let item = list.search(4);
+    let (nodes, list) = setup::linked_list_empty();
+    let item = list.search(&nodes, 4);
     assert_eq!(item, None)
   }
 
   #[test]
   fn to_filled() {
-    let list = setup::linked_list_filled();
-    let item = list.search(2);
-    let value = unsafe { (*item.unwrap().as_ptr()).value };
-    assert_eq!(value, 2)
+    let (nodes, list) = setup::linked_list_filled();
+    let handle = list.search(&nodes, 2).unwrap();
+    assert_eq!(list.resolve(&nodes, handle), Some(&2));
+    assert_eq!(list.search(&nodes, 7), None)
+  }
+
+  #[test]
+  fn to_filled_moved() {
+    let (nodes, list) = setup::linked_list_filled();
+    let moved = Box::new((nodes, list));
+    let handle = moved.1.search(&moved.0, 3).unwrap();
+    let (nodes, list) = *moved;
+    assert_eq!(list.resolve(&nodes, handle), Some(&3));
+    assert_eq!(list.back_handle(), Some(handle))
+  }
+
+  #[test]
+  fn to_removed_item() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let handle = list.search(&nodes, 2).unwrap();
+    list.sort_by(&mut nodes, |a, b| b.cmp(a));
+    *list.resolve_mut(&mut nodes, handle).unwrap() = 20;
+    assert_eq!(list.remove_item(&mut nodes, 20), Ok(20));
+    assert_eq!(list.resolve(&nodes, handle), None);
+    list.insert_at_end(&mut nodes, 4);
+    assert_eq!(list.resolve(&nodes, handle), None);
+    setup::test_list(&nodes, &list, vec![3, 1, 0, 4])
   }
 }
 
@@ -363,18 +524,18 @@ mod rev {
 
   #[test]
   fn to_empty() {
-    let mut list = setup::linked_list_empty();
-    list.rev();
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    list.rev(&mut nodes);
     let expected = vec![];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 
   #[test]
   fn to_filled() {
-    let mut list = setup::linked_list_filled();
-    list.rev();
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    list.rev(&mut nodes);
     let expected = vec![3, 2, 1, 0];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
   }
 }
 
@@ -384,44 +545,41 @@ mod concat {
 
   #[test]
   fn to_both_empty() {
-    let mut l1 = setup::linked_list_empty();
-    let l2 = setup::linked_list_empty();
-    l1.concat(l2);
+    let (mut nodes, mut l1) = setup::linked_list_empty();
+    let l2 = LinkedList::new();
+    l1.concat(&mut nodes, l2);
     let expect = vec![];
-    setup::test_list(l1, expect)
+    setup::test_list(&nodes, &l1, expect)
   }
 
   #[test]
   fn to_first_filled_second_empty() {
-    let mut l1 = setup::linked_list_filled();
-    let l2 = setup::linked_list_empty();
-    l1.concat(l2);
+    let (mut nodes, mut l1) = setup::linked_list_filled();
+    let l2 = LinkedList::new();
+    l1.concat(&mut nodes, l2);
     let expect = vec![0, 1, 2, 3];
-    setup::test_list(l1, expect)
+    setup::test_list(&nodes, &l1, expect)
   }
 
   #[test]
   fn to_first_empty_second_filled() {
-    let mut l1 = setup::linked_list_empty();
-    let l2 = setup::linked_list_filled();
-    l1.concat(l2);
+    let (mut nodes, l2) = setup::linked_list_filled();
+    let mut l1 = LinkedList::new();
+    l1.concat(&mut nodes, l2);
     let expect = vec![0, 1, 2, 3];
-    setup::test_list(l1, expect)
+    setup::test_list(&nodes, &l1, expect)
   }
 
   #[test]
   fn to_both_filled() {
-    let mut l1 = LinkedList {
-      root: setup::node(0, setup::node(1, setup::node(2, setup::node(3, None)))),
-      marker: PhantomData,
-    };
-    let l2 = LinkedList {
-      root: setup::node(4, setup::node(5, setup::node(6, setup::node(7, None)))),
-      marker: PhantomData,
-    };
-    l1.concat(l2);
+    let mut nodes = ListArena::new();
+    let mut l1 = setup::list_of(&mut nodes, vec![0, 1, 2, 3]);
+    let l2 = setup::list_of(&mut nodes, vec![4, 5, 6, 7]);
+    let handles = [setup::handles(&nodes, &l1), setup::handles(&nodes, &l2)].concat();
+    l1.concat(&mut nodes, l2);
+    assert_eq!(setup::handles(&nodes, &l1), handles);
     let expect = vec![0, 1, 2, 3, 4, 5, 6, 7];
-    setup::test_list(l1, expect)
+    setup::test_list(&nodes, &l1, expect)
   }
 }
 
@@ -431,21 +589,29 @@ mod iter {
 
   #[test]
   fn to_empty() {
-    let list = setup::linked_list_empty();
-    let mut iter = list.iter();
+    let (nodes, list) = setup::linked_list_empty();
+    let mut iter = list.iter(&nodes);
     assert_eq!(iter.next(), None)
   }
 
   #[test]
   fn to_filled() {
-    let list = setup::linked_list_filled();
-    let mut iter = list.iter();
+    let (nodes, list) = setup::linked_list_filled();
+    let mut iter = list.iter(&nodes);
     assert_eq!(iter.next(), Some(&0));
     assert_eq!(iter.next(), Some(&1));
     assert_eq!(iter.next(), Some(&2));
     assert_eq!(iter.next(), Some(&3));
     assert_eq!(iter.next(), None)
   }
+
+  #[test]
+  fn to_filled_from_both_ends() {
+    let (nodes, list) = setup::linked_list_filled();
+    let mut op = list.iter(&nodes);
+    assert_eq!((op.next(), op.next_back()), (Some(&0), Some(&3)));
+    assert_eq!(op.rev().collect::<Vec<_>>(), vec![&2, &1])
+  }
 }
 
 #[cfg(test)]
@@ -454,47 +620,564 @@ mod iter_mut {
 
   #[test]
   fn to_empty() {
-    let mut list = setup::linked_list_empty();
-    let mut iter = list.iter_mut();
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let mut iter = list.iter_mut(&mut nodes);
     assert_eq!(iter.next(), None)
   }
 
   #[test]
   fn to_filled() {
-    let mut list = setup::linked_list_filled();
-    for element in list.iter_mut() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    for element in list.iter_mut(&mut nodes) {
       *element += 10;
     }
-    let mut iter = list.iter();
+    let mut iter = list.iter(&nodes);
     assert_eq!(iter.next(), Some(&10));
     assert_eq!(iter.next(), Some(&11));
     assert_eq!(iter.next(), Some(&12));
     assert_eq!(iter.next(), Some(&13));
     assert_eq!(iter.next(), None);
     let expected = vec![10, 11, 12, 13];
-    setup::test_list(list, expected)
+    setup::test_list(&nodes, &list, expected)
+  }
+
+  #[test]
+  fn to_filled_held_at_once() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let mut op: Vec<&mut i32> = list.iter_mut(&mut nodes).collect();
+    op.swap(0, 3);
+    *op[0] += 30;
+    *op[3] -= 10;
+    setup::test_list(&nodes, &list, vec![-10, 1, 2, 33])
   }
 }
 
 #[cfg(test)]
-mod into_inter {
+mod drain {
   use super::*;
 
   #[test]
   fn to_empty() {
-    let list = setup::linked_list_empty();
-    let mut iter = list.into_iter();
+    let (mut nodes, list) = setup::linked_list_empty();
+    let mut iter = list.drain(&mut nodes);
     assert_eq!(iter.next(), None)
   }
 
   #[test]
   fn to_filled() {
-    let list = setup::linked_list_filled();
-    let mut iter = list.into_iter();
+    let (mut nodes, list) = setup::linked_list_filled();
+    let mut iter = list.drain(&mut nodes);
     assert_eq!(iter.next(), Some(0));
     assert_eq!(iter.next(), Some(1));
     assert_eq!(iter.next(), Some(2));
     assert_eq!(iter.next(), Some(3));
     assert_eq!(iter.next(), None)
   }
+
+  #[test]
+  fn to_filled_partially_consumed() {
+    let (mut nodes, list) = setup::linked_list_filled();
+    let other = setup::list_of(&mut nodes, vec![7]);
+    assert_eq!(list.drain(&mut nodes).next(), Some(0));
+    setup::test_list(&nodes, &other, vec![7])
+  }
+}
+
+#[cfg(test)]
+mod clone_in {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut nodes, list) = setup::linked_list_empty();
+    let op = list.clone_in(&mut nodes);
+    setup::test_list(&nodes, &op, vec![])
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, list) = setup::linked_list_filled();
+    let mut op = list.clone_in(&mut nodes);
+    op.iter_mut(&mut nodes).for_each(|item| *item *= 10);
+    assert_eq!(op.remove_at_beginning(&mut nodes), Ok(0));
+    setup::test_links(&nodes, &op, vec![10, 20, 30]);
+    setup::test_links(&nodes, &list, vec![0, 1, 2, 3]);
+    assert_eq!(nodes.len(), 7)
+  }
+}
+
+#[cfg(test)]
+mod clear {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let other = setup::list_of(&mut nodes, vec![7]);
+    let handle = list.front_handle().unwrap();
+    list.clear(&mut nodes);
+    assert!(list.is_empty());
+    assert!(!nodes.contains(handle));
+    setup::test_list(&nodes, &other, vec![7])
+  }
+}
+
+#[cfg(test)]
+mod drop {
+  use super::*;
+
+  #[test]
+  fn to_long_list() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let len = if cfg!(miri) { 1000 } else { 100000 };
+    (0..len).for_each(|item| {
+      list.insert_at_beginning(&mut nodes, item);
+    });
+    assert_eq!(list.len(), len);
+    drop(nodes)
+  }
+
+  // Heap-owning items through every path that frees or moves nodes, so a
+  // Miri run flags any leak, double free or overlapping borrow.
+  #[test]
+  fn to_owned_values() {
+    let mut nodes = ListArena::new();
+    let items = (0..8).map(|item| format!("item-{item}")).collect();
+    let mut list = setup::list_of(&mut nodes, items);
+    assert_eq!(
+      list.remove_at_beginning(&mut nodes),
+      Ok(String::from("item-0"))
+    );
+    assert_eq!(list.remove_at_end(&mut nodes), Ok(String::from("item-7")));
+    let handle = list.search(&nodes, String::from("item-3")).unwrap();
+    assert_eq!(list.remove(&mut nodes, handle), Ok(String::from("item-3")));
+    let mut held: Vec<&mut String> = list.iter_mut(&mut nodes).collect();
+    held.iter_mut().for_each(|item| item.push('!'));
+    let mut other = list.clone_in(&mut nodes);
+    let mut cursor = other.cursor_front_mut(&mut nodes);
+    cursor.move_next();
+    let rest = cursor.split_after();
+    list.concat(&mut nodes, rest);
+    list.cursor_front_mut(&mut nodes).splice_after(other);
+    list.retain(&mut nodes, |item| !item.starts_with("item-2"));
+    list.sort(&mut nodes);
+    assert_eq!(list.drain(&mut nodes).next(), Some(String::from("item-1!")));
+    assert!(nodes.is_empty())
+  }
+}
+
+#[cfg(test)]
+mod send_sync {
+  use super::*;
+
+  fn is_send_sync<T: Send + Sync>(_: &T) {}
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    is_send_sync(&list);
+    is_send_sync(&list.iter(&nodes));
+    is_send_sync(&list.iter_mut(&mut nodes));
+    let op = std::thread::spawn(move || list.iter(&nodes).sum::<i32>()).join();
+    assert_eq!(op.unwrap(), 6)
+  }
+}
+
+#[cfg(test)]
+mod cursor {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (nodes, list) = setup::linked_list_empty();
+    let mut cursor = list.cursor_front(&nodes);
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), None);
+    cursor.move_next();
+    assert_eq!(cursor.current(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let (nodes, list) = setup::linked_list_filled();
+    let mut cursor = list.cursor_front(&nodes);
+    assert_eq!(cursor.current(), Some(&0));
+    assert_eq!(cursor.peek_next(), Some(&1));
+    (0..3).for_each(|_| cursor.move_next());
+    assert_eq!(cursor.current(), Some(&3));
+    assert_eq!(cursor.peek_next(), None);
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&0));
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&0))
+  }
+}
+
+#[cfg(test)]
+mod cursor_mut {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    assert_eq!(cursor.remove_current(), None);
+    cursor.insert_after(1);
+    cursor.insert_before(2);
+    cursor.insert_after(0);
+    setup::test_list(&nodes, &list, vec![0, 1, 2])
+  }
+
+  #[test]
+  fn insert_around_current() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    cursor.insert_before(-1);
+    cursor.move_next();
+    cursor.insert_after(10);
+    cursor.insert_before(5);
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(cursor.peek_next(), Some(&mut 10));
+    setup::test_list(&nodes, &list, vec![-1, 0, 5, 1, 10, 2, 3])
+  }
+
+  #[test]
+  fn insert_on_ghost() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    (0..4).for_each(|_| cursor.move_next());
+    assert_eq!(cursor.current(), None);
+    cursor.insert_before(4);
+    cursor.insert_after(-1);
+    cursor.insert_before(5);
+    setup::test_list(&nodes, &list, vec![-1, 0, 1, 2, 3, 4, 5])
+  }
+
+  #[test]
+  fn remove_current() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    assert_eq!(cursor.remove_current(), Some(0));
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(2));
+    assert_eq!(cursor.remove_current(), Some(3));
+    assert_eq!(cursor.remove_current(), None);
+    cursor.insert_before(4);
+    setup::test_list(&nodes, &list, vec![1, 4])
+  }
+
+  #[test]
+  fn split_after() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let handles = setup::handles(&nodes, &list);
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    cursor.move_next();
+    let op = cursor.split_after();
+    cursor.insert_after(7);
+    assert_eq!(setup::handles(&nodes, &op), handles[2..]);
+    setup::test_links(&nodes, &op, vec![2, 3]);
+    setup::test_links(&nodes, &list, vec![0, 1, 7]);
+    assert_eq!(nodes.len(), 5)
+  }
+
+  #[test]
+  fn split_after_on_ghost() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let handles = setup::handles(&nodes, &list);
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    (0..4).for_each(|_| cursor.move_next());
+    let op = cursor.split_after();
+    cursor.insert_before(9);
+    assert_eq!(setup::handles(&nodes, &op), handles);
+    setup::test_links(&nodes, &op, vec![0, 1, 2, 3]);
+    setup::test_links(&nodes, &list, vec![9])
+  }
+
+  #[test]
+  fn split_after_then_splice_back() {
+    let (mut nodes, mut list) = setup::list_of_range(6);
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    (0..3).for_each(|_| cursor.move_next());
+    let mut rest = cursor.split_after();
+    assert_eq!(rest.len(), 2);
+    let mut back = rest.cursor_front_mut(&mut nodes);
+    back.move_next();
+    let tail = back.split_after();
+    assert_eq!(tail.len(), 0);
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    cursor.splice_after(rest);
+    cursor.move_next();
+    cursor.move_next();
+    let op = cursor.split_after();
+    setup::test_links(&nodes, &op, vec![1, 2, 3]);
+    setup::test_links(&nodes, &list, vec![0, 4, 5]);
+    assert_eq!(nodes.len(), 6)
+  }
+
+  #[test]
+  fn splice_after() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let other = setup::list_of(&mut nodes, vec![10, 11]);
+    let handles = setup::handles(&nodes, &other);
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    cursor.move_next();
+    cursor.splice_after(LinkedList::new());
+    cursor.splice_after(other);
+    assert_eq!(cursor.peek_next(), Some(&mut 10));
+    assert_eq!(setup::handles(&nodes, &list)[2..4], handles);
+    setup::test_list(&nodes, &list, vec![0, 1, 10, 11, 2, 3])
+  }
+
+  #[test]
+  fn splice_after_on_ghost() {
+    let (mut nodes, other) = setup::linked_list_filled();
+    let mut list = LinkedList::new();
+    let mut cursor = list.cursor_front_mut(&mut nodes);
+    cursor.splice_after(other);
+    cursor.insert_before(4);
+    setup::test_list(&nodes, &list, vec![0, 1, 2, 3, 4])
+  }
+
+  #[test]
+  fn find_cursor_mut() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    assert!(list.find_cursor_mut(&mut nodes, 4).is_none());
+    let mut cursor = list.find_cursor_mut(&mut nodes, 1).unwrap();
+    assert_eq!(cursor.remove_current(), Some(1));
+    cursor.insert_before(20);
+    let op = cursor.split_after();
+    setup::test_links(&nodes, &op, vec![3]);
+    setup::test_links(&nodes, &list, vec![0, 20, 2])
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    assert_eq!(list.get(&nodes, 0), None);
+    assert_eq!(list.get_mut(&mut nodes, 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    assert_eq!(list.get(&nodes, 2), Some(&2));
+    assert_eq!(list.get(&nodes, 4), None);
+    *list.get_mut(&mut nodes, 3).unwrap() = 30;
+    setup::test_list(&nodes, &list, vec![0, 1, 2, 30])
+  }
+}
+
+#[cfg(test)]
+mod position {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (nodes, list) = setup::linked_list_empty();
+    assert_eq!(list.position(&nodes, |item| *item == 0), None);
+    assert!(!list.contains(&nodes, &0))
+  }
+
+  #[test]
+  fn to_filled() {
+    let (nodes, list) = setup::linked_list_filled();
+    assert_eq!(list.position(&nodes, |item| *item > 1), Some(2));
+    assert_eq!(list.position(&nodes, |item| *item > 3), None);
+    assert!(list.contains(&nodes, &3));
+    assert!(!list.contains(&nodes, &4))
+  }
+}
+
+#[cfg(test)]
+mod sort {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    list.sort(&mut nodes);
+    setup::test_list(&nodes, &list, vec![])
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, vec![5, 3, 9, 1, 3, 0, 7, 2]);
+    let mut handles = setup::handles(&nodes, &list);
+    list.sort(&mut nodes);
+    let mut sorted = setup::handles(&nodes, &list);
+    handles.sort_by_key(|handle| handle.index());
+    sorted.sort_by_key(|handle| handle.index());
+    assert_eq!(handles, sorted);
+    setup::test_list(&nodes, &list, vec![0, 1, 2, 3, 3, 5, 7, 9])
+  }
+
+  #[test]
+  fn to_long_list() {
+    let mut nodes = ListArena::new();
+    let items = (0..1000).map(|item| (item * 7919) % 1000).collect();
+    let mut list = setup::list_of(&mut nodes, items);
+    list.sort(&mut nodes);
+    setup::test_list(&nodes, &list, (0..1000).collect())
+  }
+
+  #[test]
+  fn sort_by_is_stable() {
+    let mut nodes = ListArena::new();
+    let items = vec![(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd'), (1, 'e')];
+    let mut list = setup::list_of(&mut nodes, items);
+    list.sort_by(&mut nodes, |a, b| a.0.cmp(&b.0));
+    let expected = vec![(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c'), (1, 'e')];
+    setup::test_list(&nodes, &list, expected)
+  }
+
+  #[test]
+  fn sort_by_key() {
+    let mut nodes = ListArena::new();
+    let mut list: LinkedList<i32> = setup::list_of(&mut nodes, vec![-3, 1, -2, 0, 2]);
+    list.sort_by_key(&mut nodes, |item| i32::abs(*item));
+    setup::test_list(&nodes, &list, vec![0, 1, -2, 2, -3])
+  }
+}
+
+#[cfg(test)]
+mod dedup {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    list.dedup(&mut nodes);
+    setup::test_list(&nodes, &list, vec![])
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, vec![0, 0, 1, 2, 2, 2, 1, 3, 3]);
+    list.dedup(&mut nodes);
+    setup::test_list(&nodes, &list, vec![0, 1, 2, 1, 3])
+  }
+
+  #[test]
+  fn dedup_by_key() {
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, vec![10, 11, 20, 35, 31, 12]);
+    list.dedup_by_key(&mut nodes, |item| item / 10);
+    setup::test_list(&nodes, &list, vec![10, 20, 35, 12])
+  }
+}
+
+#[cfg(test)]
+mod retain {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    list.retain(&mut nodes, |item| *item > 0);
+    setup::test_list(&nodes, &list, vec![])
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, mut list) = setup::list_of_range(6);
+    list.retain(&mut nodes, |item| item % 2 == 1);
+    setup::test_list(&nodes, &list, vec![1, 3, 5])
+  }
+}
+
+#[cfg(test)]
+mod extract_if {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    assert_eq!(list.extract_if(&mut nodes, |_| true).next(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, mut list) = setup::list_of_range(6);
+    let op: Vec<i32> = list.extract_if(&mut nodes, |item| *item % 2 == 0).collect();
+    assert_eq!(op, vec![0, 2, 4]);
+    setup::test_list(&nodes, &list, vec![1, 3, 5])
+  }
+
+  #[test]
+  fn to_filled_partially_consumed() {
+    let (mut nodes, mut list) = setup::list_of_range(6);
+    let mut iter = list.extract_if(&mut nodes, |item| {
+      *item += 10;
+      *item > 11
+    });
+    assert_eq!(iter.next(), Some(12));
+    setup::test_list(&nodes, &list, vec![10, 11, 3, 4, 5])
+  }
+}
+
+#[cfg(test)]
+mod remove_all {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    assert_eq!(list.remove_all(&mut nodes, 0), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, vec![1, 0, 1, 1, 2, 1]);
+    assert_eq!(list.remove_all(&mut nodes, 1), 4);
+    assert_eq!(list.remove_all(&mut nodes, 3), 0);
+    setup::test_list(&nodes, &list, vec![0, 2])
+  }
+}
+
+#[cfg(test)]
+mod reverse_range {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let (mut nodes, mut list) = setup::linked_list_empty();
+    assert_eq!(list.reverse_range(&mut nodes, ..), Ok(()));
+    let op = list.reverse_range(&mut nodes, 0..1);
+    assert_eq!(op, Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    let (mut nodes, mut list) = setup::list_of_range(6);
+    assert_eq!(list.reverse_range(&mut nodes, 1..4), Ok(()));
+    setup::test_list(&nodes, &list, vec![0, 3, 2, 1, 4, 5])
+  }
+
+  #[test]
+  fn to_filled_at_both_ends() {
+    let (mut nodes, mut list) = setup::list_of_range(6);
+    assert_eq!(list.reverse_range(&mut nodes, ..=2), Ok(()));
+    assert_eq!(list.reverse_range(&mut nodes, 4..), Ok(()));
+    assert_eq!(list.reverse_range(&mut nodes, 3..3), Ok(()));
+    setup::test_list(&nodes, &list, vec![2, 1, 0, 3, 5, 4]);
+    assert_eq!(list.reverse_range(&mut nodes, ..), Ok(()));
+    setup::test_list(&nodes, &list, vec![4, 5, 3, 0, 1, 2])
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let (mut nodes, mut list) = setup::linked_list_filled();
+    let op = list.reverse_range(&mut nodes, 2..5);
+    assert_eq!(op, Err(IndexError::OutOfBounds));
+    setup::test_list(&nodes, &list, vec![0, 1, 2, 3])
+  }
 }
@@ -1,3 +1,7 @@
 #[path = "./linked-list.rs"]
 pub mod linked_list;
 pub use linked_list::*;
+
+#[path = "./doubly-linked-list.rs"]
+pub mod doubly_linked_list;
+pub use doubly_linked_list::*;
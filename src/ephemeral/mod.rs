@@ -1,2 +1,17 @@
+pub mod arenas;
+pub use arenas::*;
+
+pub mod caches;
+pub use caches::*;
+
+pub mod deques;
+pub use deques::*;
+
+pub mod dictionaries;
+pub use dictionaries::*;
+
+pub mod heaps;
+pub use heaps::*;
+
 pub mod lists;
 pub use lists::*;
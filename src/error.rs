@@ -0,0 +1,5 @@
+// Shared by every structure addressed by position.
+#[derive(PartialEq, Debug)]
+pub enum IndexError {
+  OutOfBounds,
+}
@@ -1,7 +1,14 @@
 pub mod ephemeral;
 pub use ephemeral::*;
 
+pub mod error;
+pub use error::*;
+
 pub mod persistent;
 pub use persistent::*;
 
-// page 267
+pub mod random;
+pub use random::*;
+
+#[cfg(test)]
+mod test_support;
@@ -1,4 +1,10 @@
-type Link<T> = Option<Box<ListNode<T>>>;
+use std::{
+  cmp::Ordering,
+  iter::{Product, Sum},
+  rc::Rc,
+};
+
+type Link<T> = Option<Rc<ListNode<T>>>;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct ListNode<T> {
@@ -11,6 +17,20 @@ pub struct LinkedList<T> {
   root: Link<T>,
 }
 
+// Invariants:
+//    - left holds the items before the focus, nearest first
+//    - right holds the items after the focus, nearest first
+#[derive(Clone, PartialEq, Debug)]
+pub struct ListZipper<T> {
+  left: Link<T>,
+  focus: T,
+  right: Link<T>,
+}
+
+pub struct ListIter<'a, T> {
+  next: Option<&'a ListNode<T>>,
+}
+
 #[derive(PartialEq, Debug)]
 pub enum InsertError {
   BeforeItemNotFound,
@@ -30,7 +50,7 @@ mod private {
   where
     T: Clone,
   {
-    Some(Box::new(ListNode {
+    Some(Rc::new(ListNode {
       value: item,
       next: node.clone(),
     }))
@@ -38,15 +58,15 @@ mod private {
 
   pub fn insert_at_end_node_aux<T>(node: &Link<T>, item: T) -> Link<T>
   where
-    T: Copy,
+    T: Clone,
   {
     match node {
-      None => Some(Box::new(ListNode {
+      None => Some(Rc::new(ListNode {
         value: item,
         next: None,
       })),
-      Some(node) => Some(Box::new(ListNode {
-        value: node.value,
+      Some(node) => Some(Rc::new(ListNode {
+        value: node.value.clone(),
         next: insert_at_end_node_aux(&node.next, item),
       })),
     }
@@ -58,22 +78,22 @@ mod private {
     before: &T,
   ) -> Result<Link<T>, InsertError>
   where
-    T: PartialEq + Copy,
+    T: PartialEq + Clone,
   {
     match node {
       Some(inner_node) => {
         if &inner_node.value == before {
-          Ok(Some(Box::new(ListNode {
+          Ok(Some(Rc::new(ListNode {
             value: item,
-            next: Some(Box::new(ListNode {
-              value: inner_node.value,
+            next: Some(Rc::new(ListNode {
+              value: inner_node.value.clone(),
               next: inner_node.next.clone(),
             })),
           })))
         } else {
           match insert_before_node_aux(&inner_node.next, item, before) {
-            Ok(link) => Ok(Some(Box::new(ListNode {
-              value: inner_node.value,
+            Ok(link) => Ok(Some(Rc::new(ListNode {
+              value: inner_node.value.clone(),
               next: link,
             }))),
             Err(err) => Err(err),
@@ -90,22 +110,22 @@ mod private {
     after: &T,
   ) -> Result<Link<T>, InsertError>
   where
-    T: PartialEq + Copy,
+    T: PartialEq + Clone,
   {
     match node {
       Some(inner_node) => {
         if &inner_node.value == after {
-          Ok(Some(Box::new(ListNode {
-            value: inner_node.value,
-            next: Some(Box::new(ListNode {
+          Ok(Some(Rc::new(ListNode {
+            value: inner_node.value.clone(),
+            next: Some(Rc::new(ListNode {
               value: item,
               next: inner_node.next.clone(),
             })),
           })))
         } else {
           match insert_after_node_aux(&inner_node.next, item, after) {
-            Ok(link) => Ok(Some(Box::new(ListNode {
-              value: inner_node.value,
+            Ok(link) => Ok(Some(Rc::new(ListNode {
+              value: inner_node.value.clone(),
               next: link,
             }))),
             Err(err) => Err(err),
@@ -118,7 +138,7 @@ mod private {
 
   pub fn remove_item_node_aux<T>(node: &Link<T>, item: T) -> Result<Link<T>, RemoveError>
   where
-    T: PartialEq + Copy,
+    T: PartialEq + Clone,
   {
     match node {
       None => Err(RemoveError::ItemNotFound),
@@ -127,8 +147,8 @@ mod private {
           Ok(inner_node.next.clone())
         } else {
           match remove_item_node_aux(&inner_node.next, item) {
-            Ok(link) => Ok(Some(Box::new(ListNode {
-              value: inner_node.value,
+            Ok(link) => Ok(Some(Rc::new(ListNode {
+              value: inner_node.value.clone(),
               next: link,
             }))),
             Err(err) => Err(err),
@@ -150,13 +170,13 @@ mod private {
 
   pub fn remove_at_end_node_aux<T>(node: &Link<T>) -> Link<T>
   where
-    T: PartialEq + Copy,
+    T: PartialEq + Clone,
   {
     match node {
       None => None,
       Some(inner_node) => match inner_node.next {
-        Some(_) => Some(Box::new(ListNode {
-          value: inner_node.value,
+        Some(_) => Some(Rc::new(ListNode {
+          value: inner_node.value.clone(),
           next: remove_at_end_node_aux(&inner_node.next),
         })),
         None => None,
@@ -173,31 +193,32 @@ mod private {
 
   pub fn rev_node_aux<T>(node: &Link<T>, acc: Link<T>) -> Link<T>
   where
-    T: Copy,
+    T: Clone,
   {
     match node {
       None => acc,
-      Some(inner_node) => {
-        insert_at_end_node_aux(&rev_node_aux(&inner_node.next, acc), inner_node.value)
-      }
+      Some(inner_node) => insert_at_end_node_aux(
+        &rev_node_aux(&inner_node.next, acc),
+        inner_node.value.clone(),
+      ),
     }
   }
 
   pub fn concat_nodes_aux<T>(n1: &Link<T>, n2: &Link<T>, acc: Link<T>) -> Link<T>
   where
-    T: Copy,
+    T: Clone,
   {
     match (n1, n2) {
       (None, None) => acc,
       (_, Some(inner_node)) => concat_nodes_aux(
         n1,
         &inner_node.next,
-        insert_at_end_node_aux(&acc, inner_node.value),
+        insert_at_end_node_aux(&acc, inner_node.value.clone()),
       ),
       (Some(inner_node), _) => concat_nodes_aux(
         &inner_node.next,
         n2,
-        insert_at_end_node_aux(&acc, inner_node.value),
+        insert_at_end_node_aux(&acc, inner_node.value.clone()),
       ),
     }
   }
@@ -209,7 +230,7 @@ mod private {
     acc2: Link<T>,
   ) -> (Link<T>, Link<T>)
   where
-    T: Copy,
+    T: Clone,
   {
     match node {
       None => (acc1, acc2),
@@ -219,13 +240,13 @@ mod private {
             &inner_node.next,
             f,
             acc1,
-            insert_at_end_node_aux(&acc2, inner_node.value),
+            insert_at_end_node_aux(&acc2, inner_node.value.clone()),
           )
         } else {
           split_node_aux(
             &inner_node.next,
             f,
-            insert_at_end_node_aux(&acc1, inner_node.value),
+            insert_at_end_node_aux(&acc1, inner_node.value.clone()),
             acc2,
           )
         }
@@ -261,13 +282,13 @@ mod private {
 
   pub fn find_node_aux<T>(node: &Link<T>, f: fn(&T) -> bool) -> Option<T>
   where
-    T: Copy,
+    T: Clone,
   {
     match node {
       None => None,
       Some(inner_node) => {
         if f(&inner_node.value) {
-          Some(inner_node.value)
+          Some(inner_node.value.clone())
         } else {
           find_node_aux(&inner_node.next, f)
         }
@@ -277,13 +298,13 @@ mod private {
 
   pub fn find_r_node_aux<T>(node: &Link<T>, f: fn(&T) -> bool, acc: Option<T>) -> Option<T>
   where
-    T: Copy,
+    T: Clone,
   {
     match node {
       None => acc,
       Some(inner_node) => {
         if f(&inner_node.value) {
-          find_r_node_aux(&inner_node.next, f, Some(inner_node.value))
+          find_r_node_aux(&inner_node.next, f, Some(inner_node.value.clone()))
         } else {
           find_r_node_aux(&inner_node.next, f, acc)
         }
@@ -294,7 +315,7 @@ mod private {
   pub fn map_node_aux<T, U>(node: &Link<T>, f: fn(&T) -> U) -> Link<U> {
     match node {
       None => None,
-      Some(inner_node) => Some(Box::new(ListNode {
+      Some(inner_node) => Some(Rc::new(ListNode {
         value: f(&inner_node.value),
         next: map_node_aux(&inner_node.next, f),
       })),
@@ -303,7 +324,7 @@ mod private {
 
   pub fn filter_node_aux<T>(node: &Link<T>, f: fn(&T) -> bool, acc: Link<T>) -> Link<T>
   where
-    T: Clone + Copy,
+    T: Clone,
   {
     match node {
       None => acc,
@@ -312,7 +333,7 @@ mod private {
           filter_node_aux(
             &inner_node.next,
             f,
-            insert_at_end_node_aux(&acc, inner_node.value),
+            insert_at_end_node_aux(&acc, inner_node.value.clone()),
           )
         } else {
           filter_node_aux(&inner_node.next, f, acc)
@@ -327,11 +348,353 @@ mod private {
       Some(inner_node) => reduce_node_aux(&inner_node.next, f, f(&inner_node.value, acc)),
     }
   }
+
+  // Pushes every item of `node` onto `acc`, so a nearest-first prefix
+  // comes out in list order.
+  pub fn rev_onto_node_aux<T>(node: &Link<T>, acc: Link<T>) -> Link<T>
+  where
+    T: Clone,
+  {
+    match node {
+      None => acc,
+      Some(inner_node) => rev_onto_node_aux(
+        &inner_node.next,
+        insert_at_beginning_node_aux(&acc, inner_node.value.clone()),
+      ),
+    }
+  }
+
+  pub fn cons<T>(item: T, next: Link<T>) -> Link<T> {
+    Some(Rc::new(ListNode { value: item, next }))
+  }
+
+  // Reuses `node` when its tail came back untouched, so unchanged suffixes
+  // stay shared with the input.
+  pub fn relink<T>(node: &Rc<ListNode<T>>, next: Link<T>) -> Link<T>
+  where
+    T: Clone,
+  {
+    let same = match (&node.next, &next) {
+      (None, None) => true,
+      (Some(n1), Some(n2)) => Rc::ptr_eq(n1, n2),
+      _ => false,
+    };
+    if same {
+      Some(node.clone())
+    } else {
+      cons(node.value.clone(), next)
+    }
+  }
+
+  pub fn zip_node_aux<T, U>(n1: &Link<T>, n2: &Link<U>) -> Link<(T, U)>
+  where
+    T: Clone,
+    U: Clone,
+  {
+    match (n1, n2) {
+      (Some(inner1), Some(inner2)) => cons(
+        (inner1.value.clone(), inner2.value.clone()),
+        zip_node_aux(&inner1.next, &inner2.next),
+      ),
+      _ => None,
+    }
+  }
+
+  pub fn unzip_node_aux<T, U>(node: &Link<(T, U)>) -> (Link<T>, Link<U>)
+  where
+    T: Clone,
+    U: Clone,
+  {
+    match node {
+      None => (None, None),
+      Some(inner_node) => {
+        let (n1, n2) = unzip_node_aux(&inner_node.next);
+        let (v1, v2) = inner_node.value.clone();
+        (cons(v1, n1), cons(v2, n2))
+      }
+    }
+  }
+
+  // Copies `n1` in front of `n2`, sharing `n2`, or `n1` when `n2` is empty.
+  pub fn append_node_aux<T>(n1: &Link<T>, n2: Link<T>) -> Link<T>
+  where
+    T: Clone,
+  {
+    match (n1, &n2) {
+      (None, _) => n2,
+      (_, None) => n1.clone(),
+      (Some(inner_node), _) => cons(
+        inner_node.value.clone(),
+        append_node_aux(&inner_node.next, n2),
+      ),
+    }
+  }
+
+  pub fn flat_map_node_aux<T, U>(node: &Link<T>, f: fn(&T) -> LinkedList<U>) -> Link<U>
+  where
+    U: Clone,
+  {
+    match node {
+      None => None,
+      Some(inner_node) => append_node_aux(
+        &f(&inner_node.value).root,
+        flat_map_node_aux(&inner_node.next, f),
+      ),
+    }
+  }
+
+  pub fn scan_node_aux<T, U>(node: &Link<T>, f: fn(&T, U) -> U, acc: U) -> Link<U>
+  where
+    U: Clone,
+  {
+    match node {
+      None => None,
+      Some(inner_node) => {
+        let acc = f(&inner_node.value, acc);
+        cons(acc.clone(), scan_node_aux(&inner_node.next, f, acc))
+      }
+    }
+  }
+
+  pub fn take_while_node_aux<T>(node: &Link<T>, f: fn(&T) -> bool) -> Link<T>
+  where
+    T: Clone,
+  {
+    match node {
+      Some(inner_node) if f(&inner_node.value) => {
+        relink(inner_node, take_while_node_aux(&inner_node.next, f))
+      }
+      _ => None,
+    }
+  }
+
+  pub fn drop_while_node_aux<T>(node: &Link<T>, f: fn(&T) -> bool) -> Link<T> {
+    match node {
+      Some(inner_node) if f(&inner_node.value) => drop_while_node_aux(&inner_node.next, f),
+      _ => node.clone(),
+    }
+  }
+
+  pub fn partition_map_node_aux<T, L, R>(
+    node: &Link<T>,
+    f: fn(&T) -> Result<L, R>,
+  ) -> (Link<L>, Link<R>) {
+    match node {
+      None => (None, None),
+      Some(inner_node) => {
+        let (n1, n2) = partition_map_node_aux(&inner_node.next, f);
+        match f(&inner_node.value) {
+          Ok(value) => (cons(value, n1), n2),
+          Err(value) => (n1, cons(value, n2)),
+        }
+      }
+    }
+  }
+
+  // The first `index` items, copied unless they reach the end of the list,
+  // and the shared rest.
+  pub fn split_at_node_aux<T>(node: &Link<T>, index: usize) -> (Link<T>, Link<T>)
+  where
+    T: Clone,
+  {
+    match node {
+      Some(inner_node) if index > 0 => {
+        let (prefix, rest) = split_at_node_aux(&inner_node.next, index - 1);
+        (relink(inner_node, prefix), rest)
+      }
+      _ => (None, node.clone()),
+    }
+  }
+
+  pub fn chunks_node_aux<T>(node: &Link<T>, size: usize) -> Link<LinkedList<T>>
+  where
+    T: Clone,
+  {
+    match node {
+      None => None,
+      Some(_) => {
+        let (chunk, rest) = split_at_node_aux(node, size);
+        cons(LinkedList { root: chunk }, chunks_node_aux(&rest, size))
+      }
+    }
+  }
+
+  pub fn windows_node_aux<T>(node: &Link<T>, size: usize, count: i32) -> Link<LinkedList<T>>
+  where
+    T: Clone,
+  {
+    match node {
+      Some(inner_node) if count > 0 => cons(
+        LinkedList {
+          root: split_at_node_aux(node, size).0,
+        },
+        windows_node_aux(&inner_node.next, size, count - 1),
+      ),
+      _ => None,
+    }
+  }
+
+  pub fn intersperse_node_aux<T>(node: &Link<T>, item: T) -> Link<T>
+  where
+    T: Clone,
+  {
+    match node {
+      None => None,
+      Some(inner_node) => match inner_node.next {
+        None => node.clone(),
+        Some(_) => cons(
+          inner_node.value.clone(),
+          cons(item.clone(), intersperse_node_aux(&inner_node.next, item)),
+        ),
+      },
+    }
+  }
+
+  pub fn skip_same_node_aux<'a, T>(
+    node: &'a Link<T>,
+    item: &T,
+    f: fn(&T, &T) -> bool,
+  ) -> &'a Link<T> {
+    match node {
+      Some(inner_node) if f(item, &inner_node.value) => {
+        skip_same_node_aux(&inner_node.next, item, f)
+      }
+      _ => node,
+    }
+  }
+
+  pub fn dedup_by_node_aux<T>(node: &Link<T>, f: fn(&T, &T) -> bool) -> Link<T>
+  where
+    T: Clone,
+  {
+    match node {
+      None => None,
+      Some(inner_node) => {
+        let rest = skip_same_node_aux(&inner_node.next, &inner_node.value, f);
+        relink(inner_node, dedup_by_node_aux(rest, f))
+      }
+    }
+  }
+
+  // The leading run of items where each one matches its predecessor, and the
+  // shared rest.
+  pub fn span_node_aux<T>(node: &Rc<ListNode<T>>, f: fn(&T, &T) -> bool) -> (Link<T>, Link<T>)
+  where
+    T: Clone,
+  {
+    match &node.next {
+      Some(next) if f(&node.value, &next.value) => {
+        let (group, rest) = span_node_aux(next, f);
+        (relink(node, group), rest)
+      }
+      _ => (relink(node, None), node.next.clone()),
+    }
+  }
+
+  pub fn group_by_node_aux<T>(node: &Link<T>, f: fn(&T, &T) -> bool) -> Link<LinkedList<T>>
+  where
+    T: Clone,
+  {
+    match node {
+      None => None,
+      Some(inner_node) => {
+        let (group, rest) = span_node_aux(inner_node, f);
+        cons(LinkedList { root: group }, group_by_node_aux(&rest, f))
+      }
+    }
+  }
+
+  pub fn fold_right_node_aux<T, U>(node: &Link<T>, f: fn(&T, U) -> U, acc: U) -> U {
+    match node {
+      None => acc,
+      Some(inner_node) => f(
+        &inner_node.value,
+        fold_right_node_aux(&inner_node.next, f, acc),
+      ),
+    }
+  }
+
+  pub fn last_node_aux<T>(node: &Link<T>) -> Option<&T> {
+    match node {
+      None => None,
+      Some(inner_node) => match inner_node.next {
+        None => Some(&inner_node.value),
+        Some(_) => last_node_aux(&inner_node.next),
+      },
+    }
+  }
+
+  pub fn nth_node_aux<T>(node: &Link<T>, index: usize) -> Option<&T> {
+    match node {
+      None => None,
+      Some(inner_node) => match index {
+        0 => Some(&inner_node.value),
+        _ => nth_node_aux(&inner_node.next, index - 1),
+      },
+    }
+  }
+
+  pub fn position_node_aux<T>(node: &Link<T>, f: fn(&T) -> bool, acc: usize) -> Option<usize> {
+    match node {
+      None => None,
+      Some(inner_node) => {
+        if f(&inner_node.value) {
+          Some(acc)
+        } else {
+          position_node_aux(&inner_node.next, f, acc + 1)
+        }
+      }
+    }
+  }
+
+  pub fn contains_node_aux<T>(node: &Link<T>, item: &T) -> bool
+  where
+    T: PartialEq,
+  {
+    match node {
+      None => false,
+      Some(inner_node) => &inner_node.value == item || contains_node_aux(&inner_node.next, item),
+    }
+  }
+
+  // Takes from `n1` on ties to keep the sort stable, and shares whichever
+  // side is left over.
+  pub fn merge_node_aux<T>(n1: &Link<T>, n2: &Link<T>, f: fn(&T, &T) -> Ordering) -> Link<T>
+  where
+    T: Clone,
+  {
+    match (n1, n2) {
+      (None, _) => n2.clone(),
+      (_, None) => n1.clone(),
+      (Some(inner1), Some(inner2)) => {
+        if f(&inner2.value, &inner1.value) == Ordering::Less {
+          cons(inner2.value.clone(), merge_node_aux(n1, &inner2.next, f))
+        } else {
+          cons(inner1.value.clone(), merge_node_aux(&inner1.next, n2, f))
+        }
+      }
+    }
+  }
+
+  pub fn sort_by_node_aux<T>(node: &Link<T>, len: usize, f: fn(&T, &T) -> Ordering) -> Link<T>
+  where
+    T: Clone,
+  {
+    if len <= 1 {
+      return node.clone();
+    }
+    let (left, right) = split_at_node_aux(node, len / 2);
+    merge_node_aux(
+      &sort_by_node_aux(&left, len / 2, f),
+      &sort_by_node_aux(&right, len - len / 2, f),
+      f,
+    )
+  }
 }
 
 impl<T> LinkedList<T>
 where
-  T: PartialEq + Clone + Copy,
+  T: PartialEq + Clone,
 {
   pub fn new() -> Self {
     Self { root: None }
@@ -432,7 +795,7 @@ where
 
   pub fn map<U>(list: &Self, f: fn(&T) -> U) -> LinkedList<U>
   where
-    U: Clone + PartialEq + Copy,
+    U: PartialEq + Clone,
   {
     LinkedList::<U> {
       root: private::map_node_aux(&list.root, f),
@@ -448,6 +811,263 @@ where
   pub fn reduce<U>(list: &Self, f: fn(&T, U) -> U, acc: U) -> U {
     private::reduce_node_aux(&list.root, f, acc)
   }
+
+  pub fn iter(list: &Self) -> ListIter<'_, T> {
+    ListIter {
+      next: list.root.as_deref(),
+    }
+  }
+
+  // Stops at the end of the shorter list.
+  pub fn zip<U>(l1: &Self, l2: &LinkedList<U>) -> LinkedList<(T, U)>
+  where
+    U: PartialEq + Clone,
+  {
+    LinkedList::<(T, U)> {
+      root: private::zip_node_aux(&l1.root, &l2.root),
+    }
+  }
+
+  pub fn flat_map<U>(list: &Self, f: fn(&T) -> LinkedList<U>) -> LinkedList<U>
+  where
+    U: PartialEq + Clone,
+  {
+    LinkedList::<U> {
+      root: private::flat_map_node_aux(&list.root, f),
+    }
+  }
+
+  // Every intermediate accumulator of a left fold, without the initial one.
+  pub fn scan<U>(list: &Self, f: fn(&T, U) -> U, acc: U) -> LinkedList<U>
+  where
+    U: PartialEq + Clone,
+  {
+    LinkedList::<U> {
+      root: private::scan_node_aux(&list.root, f, acc),
+    }
+  }
+
+  pub fn take_while(list: &Self, f: fn(&T) -> bool) -> Self {
+    Self {
+      root: private::take_while_node_aux(&list.root, f),
+    }
+  }
+
+  pub fn drop_while(list: &Self, f: fn(&T) -> bool) -> Self {
+    Self {
+      root: private::drop_while_node_aux(&list.root, f),
+    }
+  }
+
+  pub fn partition_map<L, R>(
+    list: &Self,
+    f: fn(&T) -> Result<L, R>,
+  ) -> (LinkedList<L>, LinkedList<R>)
+  where
+    L: PartialEq + Clone,
+    R: PartialEq + Clone,
+  {
+    let (n1, n2) = private::partition_map_node_aux(&list.root, f);
+    (LinkedList::<L> { root: n1 }, LinkedList::<R> { root: n2 })
+  }
+
+  // Consecutive runs of `size` items, the last one may be shorter. Empty when
+  // `size` is 0.
+  pub fn chunks(list: &Self, size: usize) -> LinkedList<Self> {
+    if size == 0 {
+      return LinkedList::<Self>::new();
+    }
+    LinkedList::<Self> {
+      root: private::chunks_node_aux(&list.root, size),
+    }
+  }
+
+  // Every run of `size` consecutive items. Empty when `size` is 0 or larger
+  // than the list.
+  pub fn windows(list: &Self, size: usize) -> LinkedList<Self> {
+    if size == 0 {
+      return LinkedList::<Self>::new();
+    }
+    let count = Self::len(list) - size as i32 + 1;
+    LinkedList::<Self> {
+      root: private::windows_node_aux(&list.root, size, count),
+    }
+  }
+
+  pub fn intersperse(list: &Self, item: T) -> Self {
+    Self {
+      root: private::intersperse_node_aux(&list.root, item),
+    }
+  }
+
+  // Removes consecutive repeated items.
+  pub fn dedup(list: &Self) -> Self {
+    Self::dedup_by(list, |a, b| a == b)
+  }
+
+  // Removes every item for which `f(kept, item)` holds, `kept` being the
+  // last item kept before it.
+  pub fn dedup_by(list: &Self, f: fn(&T, &T) -> bool) -> Self {
+    Self {
+      root: private::dedup_by_node_aux(&list.root, f),
+    }
+  }
+
+  // Splits into runs where `f(previous, item)` holds for every pair of
+  // neighbours.
+  pub fn group_by(list: &Self, f: fn(&T, &T) -> bool) -> LinkedList<Self> {
+    LinkedList::<Self> {
+      root: private::group_by_node_aux(&list.root, f),
+    }
+  }
+
+  pub fn fold_right<U>(list: &Self, f: fn(&T, U) -> U, acc: U) -> U {
+    private::fold_right_node_aux(&list.root, f, acc)
+  }
+
+  pub fn last(list: &Self) -> Option<T> {
+    private::last_node_aux(&list.root).cloned()
+  }
+
+  pub fn nth(list: &Self, index: usize) -> Option<T> {
+    private::nth_node_aux(&list.root, index).cloned()
+  }
+
+  pub fn position(list: &Self, f: fn(&T) -> bool) -> Option<usize> {
+    private::position_node_aux(&list.root, f, 0)
+  }
+
+  pub fn contains(list: &Self, item: &T) -> bool {
+    private::contains_node_aux(&list.root, item)
+  }
+
+  pub fn sum(list: &Self) -> T
+  where
+    T: Sum,
+  {
+    Self::iter(list).cloned().sum()
+  }
+
+  pub fn product(list: &Self) -> T
+  where
+    T: Product,
+  {
+    Self::iter(list).cloned().product()
+  }
+
+  // Stable merge sort.
+  pub fn sort_by(list: &Self, f: fn(&T, &T) -> Ordering) -> Self {
+    Self {
+      root: private::sort_by_node_aux(&list.root, Self::len(list) as usize, f),
+    }
+  }
+}
+
+impl<T, U> LinkedList<(T, U)>
+where
+  T: PartialEq + Clone,
+  U: PartialEq + Clone,
+{
+  pub fn unzip(list: &Self) -> (LinkedList<T>, LinkedList<U>) {
+    let (n1, n2) = private::unzip_node_aux(&list.root);
+    (LinkedList::<T> { root: n1 }, LinkedList::<U> { root: n2 })
+  }
+}
+
+impl<'a, T> Iterator for ListIter<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.next.map(|node| {
+      self.next = node.next.as_deref();
+      &node.value
+    })
+  }
+}
+
+impl<T> ListZipper<T>
+where
+  T: PartialEq + Clone,
+{
+  // Focuses the first item, `None` for an empty list.
+  pub fn from_list(list: &LinkedList<T>) -> Option<Self> {
+    list.root.as_ref().map(|node| Self {
+      left: None,
+      focus: node.value.clone(),
+      right: node.next.clone(),
+    })
+  }
+
+  pub fn focus(zipper: &Self) -> T {
+    zipper.focus.clone()
+  }
+
+  pub fn left(zipper: &Self) -> Option<Self> {
+    zipper.left.as_ref().map(|node| Self {
+      left: node.next.clone(),
+      focus: node.value.clone(),
+      right: private::insert_at_beginning_node_aux(&zipper.right, zipper.focus.clone()),
+    })
+  }
+
+  pub fn right(zipper: &Self) -> Option<Self> {
+    zipper.right.as_ref().map(|node| Self {
+      left: private::insert_at_beginning_node_aux(&zipper.left, zipper.focus.clone()),
+      focus: node.value.clone(),
+      right: node.next.clone(),
+    })
+  }
+
+  pub fn replace(zipper: &Self, item: T) -> Self {
+    Self {
+      left: zipper.left.clone(),
+      focus: item,
+      right: zipper.right.clone(),
+    }
+  }
+
+  pub fn insert_left(zipper: &Self, item: T) -> Self {
+    Self {
+      left: private::insert_at_beginning_node_aux(&zipper.left, item),
+      focus: zipper.focus.clone(),
+      right: zipper.right.clone(),
+    }
+  }
+
+  pub fn insert_right(zipper: &Self, item: T) -> Self {
+    Self {
+      left: zipper.left.clone(),
+      focus: zipper.focus.clone(),
+      right: private::insert_at_beginning_node_aux(&zipper.right, item),
+    }
+  }
+
+  // Drops the focused item and focuses its right neighbour, or the left one
+  // at the end of the list. `None` once the list would be empty.
+  pub fn delete(zipper: &Self) -> Option<Self> {
+    match (&zipper.left, &zipper.right) {
+      (_, Some(node)) => Some(Self {
+        left: zipper.left.clone(),
+        focus: node.value.clone(),
+        right: node.next.clone(),
+      }),
+      (Some(node), None) => Some(Self {
+        left: node.next.clone(),
+        focus: node.value.clone(),
+        right: None,
+      }),
+      (None, None) => None,
+    }
+  }
+
+  pub fn to_list(zipper: &Self) -> LinkedList<T> {
+    LinkedList {
+      root: private::rev_onto_node_aux(
+        &zipper.left,
+        private::insert_at_beginning_node_aux(&zipper.right, zipper.focus.clone()),
+      ),
+    }
+  }
 }
 
 #[cfg(test)]
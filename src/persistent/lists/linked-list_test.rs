@@ -6,7 +6,7 @@ mod setup {
   pub type LinkedListT = LinkedList<i32>;
 
   pub fn node<T>(value: T, next: Link<T>) -> Link<T> {
-    Some(Box::new(ListNode { value, next: next }))
+    Some(Rc::new(ListNode { value, next: next }))
   }
 
   pub fn linked_list_empty() -> LinkedListT {
@@ -18,6 +18,29 @@ mod setup {
       root: node(0, node(1, node(2, node(3, None)))),
     }
   }
+
+  pub fn list_of<T>(items: &[T]) -> LinkedList<T>
+  where
+    T: Clone,
+  {
+    LinkedList {
+      root: items
+        .iter()
+        .rev()
+        .fold(None, |acc, item| node(item.clone(), acc)),
+    }
+  }
+
+  pub fn node_at<T>(list: &LinkedList<T>, index: usize) -> &Link<T> {
+    (0..index).fold(&list.root, |acc, _| &acc.as_ref().unwrap().next)
+  }
+
+  pub fn same<T>(n1: &Link<T>, n2: &Link<T>) -> bool {
+    match (n1, n2) {
+      (Some(n1), Some(n2)) => Rc::ptr_eq(n1, n2),
+      _ => false,
+    }
+  }
 }
 
 #[cfg(test)]
@@ -535,3 +558,644 @@ mod reduce {
     assert_eq!(op, 6)
   }
 }
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    assert_eq!(LinkedList::iter(&list).next(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op: Vec<i32> = LinkedList::iter(&list).copied().collect();
+    assert_eq!(op, vec![0, 1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod zip {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::zip(&list, &setup::linked_list_filled());
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::zip(&list, &setup::list_of(&['a', 'b', 'c']));
+    assert_eq!(op, setup::list_of(&[(0, 'a'), (1, 'b'), (2, 'c')]))
+  }
+}
+
+#[cfg(test)]
+mod unzip {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::list_of::<(i32, char)>(&[]);
+    let (op1, op2) = LinkedList::unzip(&list);
+    assert!(LinkedList::is_empty(&op1));
+    assert!(LinkedList::is_empty(&op2))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::list_of(&[(0, 'a'), (1, 'b'), (2, 'c')]);
+    let (op1, op2) = LinkedList::unzip(&list);
+    assert_eq!(op1, setup::list_of(&[0, 1, 2]));
+    assert_eq!(op2, setup::list_of(&['a', 'b', 'c']))
+  }
+}
+
+#[cfg(test)]
+mod flat_map {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::flat_map(&list, |item| setup::list_of(&[*item, *item]));
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::flat_map(&list, |item| setup::list_of(&vec![*item; *item as usize]));
+    assert_eq!(op, setup::list_of(&[1, 2, 2, 3, 3, 3]))
+  }
+}
+
+#[cfg(test)]
+mod scan {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::scan(&list, |item, acc| item + acc, 0);
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::scan(&list, |item, acc| item + acc, 10);
+    assert_eq!(op, setup::list_of(&[10, 11, 13, 16]))
+  }
+}
+
+#[cfg(test)]
+mod take_while {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::take_while(&list, |_| true);
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::take_while(&list, |item| item < &2);
+    assert_eq!(op, setup::list_of(&[0, 1]))
+  }
+
+  #[test]
+  fn to_filled_taking_everything() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::take_while(&list, |_| true);
+    assert!(setup::same(&op.root, &list.root))
+  }
+}
+
+#[cfg(test)]
+mod drop_while {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::drop_while(&list, |_| true);
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::drop_while(&list, |item| item < &2);
+    assert_eq!(op, setup::list_of(&[2, 3]));
+    assert!(setup::same(&op.root, setup::node_at(&list, 2)))
+  }
+}
+
+#[cfg(test)]
+mod partition_map {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let (op1, op2) = LinkedList::partition_map(&list, |item| Ok::<i32, i32>(*item));
+    assert!(LinkedList::is_empty(&op1));
+    assert!(LinkedList::is_empty(&op2))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let (op1, op2) = LinkedList::partition_map(&list, |item| match item % 2 {
+      0 => Ok(item * 10),
+      _ => Err(item.to_string()),
+    });
+    assert_eq!(op1, setup::list_of(&[0, 20]));
+    assert_eq!(op2, setup::list_of(&[String::from("1"), String::from("3")]))
+  }
+}
+
+#[cfg(test)]
+mod chunks {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::chunks(&list, 2);
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::chunks(&list, 3);
+    let expected = setup::list_of(&[setup::list_of(&[0, 1, 2]), setup::list_of(&[3])]);
+    assert_eq!(op, expected);
+    let last = LinkedList::last(&op).unwrap();
+    assert!(setup::same(&last.root, setup::node_at(&list, 3)))
+  }
+
+  #[test]
+  fn to_filled_with_zero_size() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::chunks(&list, 0);
+    assert!(LinkedList::is_empty(&op))
+  }
+}
+
+#[cfg(test)]
+mod windows {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::windows(&list, 2);
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::windows(&list, 2);
+    let expected = setup::list_of(&[
+      setup::list_of(&[0, 1]),
+      setup::list_of(&[1, 2]),
+      setup::list_of(&[2, 3]),
+    ]);
+    assert_eq!(op, expected);
+    let last = LinkedList::last(&op).unwrap();
+    assert!(setup::same(&last.root, setup::node_at(&list, 2)))
+  }
+
+  #[test]
+  fn to_filled_with_larger_size() {
+    let list = setup::linked_list_filled();
+    assert!(LinkedList::is_empty(&LinkedList::windows(&list, 5)));
+    assert!(LinkedList::is_empty(&LinkedList::windows(&list, 0)))
+  }
+}
+
+#[cfg(test)]
+mod intersperse {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::intersperse(&list, 9);
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::intersperse(&list, 9);
+    assert_eq!(op, setup::list_of(&[0, 9, 1, 9, 2, 9, 3]))
+  }
+}
+
+#[cfg(test)]
+mod dedup {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::dedup(&list);
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::list_of(&[0, 0, 1, 1, 1, 0, 2, 3]);
+    let op = LinkedList::dedup(&list);
+    assert_eq!(op, setup::list_of(&[0, 1, 0, 2, 3]));
+    assert!(setup::same(
+      setup::node_at(&op, 2),
+      setup::node_at(&list, 5)
+    ))
+  }
+
+  #[test]
+  fn to_filled_without_repeats() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::dedup(&list);
+    assert!(setup::same(&op.root, &list.root))
+  }
+}
+
+#[cfg(test)]
+mod dedup_by {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::dedup_by(&list, |_, _| true);
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::list_of(&[0, 1, 2, 5, 6, 10]);
+    let op = LinkedList::dedup_by(&list, |kept, item| item - kept < 3);
+    assert_eq!(op, setup::list_of(&[0, 5, 10]))
+  }
+}
+
+#[cfg(test)]
+mod group_by {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::group_by(&list, |_, _| true);
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::list_of(&[0, 1, 2, 5, 6, 10]);
+    let op = LinkedList::group_by(&list, |previous, item| item - previous == 1);
+    let expected = setup::list_of(&[
+      setup::list_of(&[0, 1, 2]),
+      setup::list_of(&[5, 6]),
+      setup::list_of(&[10]),
+    ]);
+    assert_eq!(op, expected);
+    let last = LinkedList::last(&op).unwrap();
+    assert!(setup::same(&last.root, setup::node_at(&list, 5)))
+  }
+}
+
+#[cfg(test)]
+mod fold_right {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::fold_right(&list, |_, _| 4, 0);
+    assert_eq!(op, 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = LinkedList::fold_right(
+      &list,
+      |item, acc: Vec<i32>| [acc, vec![*item]].concat(),
+      vec![],
+    );
+    assert_eq!(op, vec![3, 2, 1, 0])
+  }
+}
+
+#[cfg(test)]
+mod last {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(LinkedList::last(&setup::linked_list_empty()), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(LinkedList::last(&setup::linked_list_filled()), Some(3))
+  }
+}
+
+#[cfg(test)]
+mod nth {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(LinkedList::nth(&setup::linked_list_empty(), 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    assert_eq!(LinkedList::nth(&list, 0), Some(0));
+    assert_eq!(LinkedList::nth(&list, 2), Some(2));
+    assert_eq!(LinkedList::nth(&list, 4), None)
+  }
+}
+
+#[cfg(test)]
+mod position {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    assert_eq!(LinkedList::position(&list, |_| true), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    assert_eq!(LinkedList::position(&list, |item| item > &1), Some(2));
+    assert_eq!(LinkedList::position(&list, |item| item > &3), None)
+  }
+}
+
+#[cfg(test)]
+mod contains {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(!LinkedList::contains(&setup::linked_list_empty(), &0))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    assert!(LinkedList::contains(&list, &3));
+    assert!(!LinkedList::contains(&list, &4))
+  }
+}
+
+#[cfg(test)]
+mod sum {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(LinkedList::sum(&setup::linked_list_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(LinkedList::sum(&setup::linked_list_filled()), 6)
+  }
+}
+
+#[cfg(test)]
+mod product {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(LinkedList::product(&setup::linked_list_empty()), 1)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::list_of(&[1, 2, 3, 4]);
+    assert_eq!(LinkedList::product(&list), 24)
+  }
+}
+
+#[cfg(test)]
+mod sort_by {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = LinkedList::sort_by(&list, |a, b| a.cmp(b));
+    assert!(LinkedList::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::list_of(&[5, 3, 9, 0, 3, 1, 8]);
+    let op = LinkedList::sort_by(&list, |a, b| a.cmp(b));
+    assert_eq!(op, setup::list_of(&[0, 1, 3, 3, 5, 8, 9]))
+  }
+
+  #[test]
+  fn to_filled_stable() {
+    let list = setup::list_of(&[(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd'), (1, 'e')]);
+    let op = LinkedList::sort_by(&list, |a, b| a.0.cmp(&b.0));
+    let expected = setup::list_of(&[(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c'), (1, 'e')]);
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_sorted_tail() {
+    let list = setup::list_of(&[3, 0, 1, 4, 5]);
+    let op = LinkedList::sort_by(&list, |a, b| a.cmp(b));
+    assert_eq!(op, setup::list_of(&[0, 1, 3, 4, 5]));
+    assert!(setup::same(
+      setup::node_at(&op, 4),
+      setup::node_at(&list, 4)
+    ))
+  }
+}
+
+#[cfg(test)]
+mod zipper_from_list {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::linked_list_empty();
+    let op = ListZipper::from_list(&list);
+    assert_eq!(op, None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let op = ListZipper::from_list(&list).unwrap();
+    assert_eq!(ListZipper::focus(&op), 0);
+    assert_eq!(ListZipper::to_list(&op), list)
+  }
+}
+
+#[cfg(test)]
+mod zipper_left_right {
+  use super::*;
+
+  #[test]
+  fn to_single() {
+    let list = LinkedList::insert_at_end(&setup::linked_list_empty(), 7);
+    let zipper = ListZipper::from_list(&list).unwrap();
+    assert_eq!(ListZipper::left(&zipper), None);
+    assert_eq!(ListZipper::right(&zipper), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let zipper = ListZipper::from_list(&list).unwrap();
+    let last = (0..3).fold(zipper.clone(), |acc, _| ListZipper::right(&acc).unwrap());
+    assert_eq!(ListZipper::focus(&last), 3);
+    assert_eq!(ListZipper::right(&last), None);
+    let back = ListZipper::left(&ListZipper::left(&last).unwrap()).unwrap();
+    assert_eq!(ListZipper::focus(&back), 1);
+    assert_eq!(ListZipper::to_list(&back), list);
+    assert_eq!(ListZipper::left(&ListZipper::left(&back).unwrap()), None)
+  }
+}
+
+#[cfg(test)]
+mod zipper_replace {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let zipper = ListZipper::right(&ListZipper::from_list(&list).unwrap()).unwrap();
+    let op = ListZipper::replace(&zipper, 9);
+    let expected = setup::LinkedListT {
+      root: setup::node(0, setup::node(9, setup::node(2, setup::node(3, None)))),
+    };
+    assert_eq!(ListZipper::focus(&op), 9);
+    assert_eq!(ListZipper::to_list(&op), expected);
+    assert_eq!(ListZipper::to_list(&zipper), list)
+  }
+}
+
+#[cfg(test)]
+mod zipper_insert_left {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let zipper = ListZipper::right(&ListZipper::from_list(&list).unwrap()).unwrap();
+    let op = ListZipper::insert_left(&ListZipper::insert_left(&zipper, 8), 9);
+    let expected = setup::LinkedListT {
+      root: setup::node(
+        0,
+        setup::node(
+          8,
+          setup::node(9, setup::node(1, setup::node(2, setup::node(3, None)))),
+        ),
+      ),
+    };
+    assert_eq!(ListZipper::focus(&op), 1);
+    assert_eq!(ListZipper::to_list(&op), expected)
+  }
+
+  #[test]
+  fn to_first() {
+    let list = setup::linked_list_filled();
+    let zipper = ListZipper::from_list(&list).unwrap();
+    let op = ListZipper::insert_left(&zipper, 9);
+    assert_eq!(
+      ListZipper::to_list(&op),
+      LinkedList::insert_at_beginning(&list, 9)
+    )
+  }
+}
+
+#[cfg(test)]
+mod zipper_insert_right {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let zipper = ListZipper::right(&ListZipper::from_list(&list).unwrap()).unwrap();
+    let op = ListZipper::insert_right(&ListZipper::insert_right(&zipper, 8), 9);
+    let expected = setup::LinkedListT {
+      root: setup::node(
+        0,
+        setup::node(
+          1,
+          setup::node(9, setup::node(8, setup::node(2, setup::node(3, None)))),
+        ),
+      ),
+    };
+    assert_eq!(ListZipper::focus(&op), 1);
+    assert_eq!(ListZipper::to_list(&op), expected)
+  }
+}
+
+#[cfg(test)]
+mod zipper_delete {
+  use super::*;
+
+  #[test]
+  fn to_single() {
+    let list = LinkedList::insert_at_end(&setup::linked_list_empty(), 7);
+    let zipper = ListZipper::from_list(&list).unwrap();
+    assert_eq!(ListZipper::delete(&zipper), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::linked_list_filled();
+    let zipper = ListZipper::right(&ListZipper::from_list(&list).unwrap()).unwrap();
+    let op = ListZipper::delete(&zipper).unwrap();
+    assert_eq!(ListZipper::focus(&op), 2);
+    assert_eq!(
+      ListZipper::to_list(&op),
+      LinkedList::remove_item(&list, 1).unwrap()
+    )
+  }
+
+  #[test]
+  fn to_last() {
+    let list = setup::linked_list_filled();
+    let zipper = (0..3).fold(ListZipper::from_list(&list).unwrap(), |acc, _| {
+      ListZipper::right(&acc).unwrap()
+    });
+    let op = ListZipper::delete(&zipper).unwrap();
+    assert_eq!(ListZipper::focus(&op), 2);
+    assert_eq!(
+      ListZipper::to_list(&op),
+      LinkedList::remove_at_end(&list).unwrap()
+    )
+  }
+}
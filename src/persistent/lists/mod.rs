@@ -1,3 +1,18 @@
 #[path = "./linked-list.rs"]
 pub mod linked_list;
 pub use linked_list::*;
+
+#[path = "./random-access-list.rs"]
+pub mod random_access_list;
+pub use random_access_list::*;
+
+#[path = "./persistent-vector.rs"]
+pub mod persistent_vector;
+pub use persistent_vector::*;
+
+pub mod stream;
+pub use stream::*;
+
+#[path = "./persistent-array.rs"]
+pub mod persistent_array;
+pub use persistent_array::*;
@@ -0,0 +1,225 @@
+use std::{cell::RefCell, fmt, mem, rc::Rc};
+
+type Node<T> = Rc<RefCell<ArrayData<T>>>;
+
+#[derive(PartialEq, Debug)]
+pub enum ArrayError {
+  OutOfBounds,
+  // The version was given up by `backtrack`.
+  Discarded,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ArrayData<T> {
+  Array(Vec<T>),
+  Diff(usize, T, Node<T>),
+  Discarded,
+}
+
+// Invariants:
+//    - following the diffs from any live version reaches the single node
+//      holding the array, which is the current version
+//    - a diff `(index, item, next)` reads as `next` with `item` at `index`
+//    - only versions given up by `backtrack` are discarded
+pub struct PersistentArray<T> {
+  data: Node<T>,
+  len: usize,
+}
+
+pub struct PersistentArrayIter<T> {
+  array: PersistentArray<T>,
+  index: usize,
+}
+
+mod private {
+  use super::*;
+
+  // Nodes from `node` to the one holding the array, both included. A
+  // discarded version leads nowhere, and neither does anything diffed
+  // against it.
+  pub fn path_aux<T>(node: &Node<T>) -> Result<Vec<Node<T>>, ArrayError> {
+    let mut path = vec![node.clone()];
+    loop {
+      let next = match &*path.last().unwrap().borrow() {
+        ArrayData::Array(_) => None,
+        ArrayData::Diff(_, _, next) => Some(next.clone()),
+        ArrayData::Discarded => return Err(ArrayError::Discarded),
+      };
+      match next {
+        Some(next) => path.push(next),
+        None => return Ok(path),
+      }
+    }
+  }
+
+  // Moves the array to `node`, reversing the diffs on the way when `keep` is
+  // set and discarding them otherwise.
+  pub fn reroot<T>(node: &Node<T>, keep: bool) -> Result<(), ArrayError> {
+    let path = path_aux(node)?;
+    for pair in path.windows(2).rev() {
+      let (node, root) = (&pair[0], &pair[1]);
+      let (index, item) = match node.replace(ArrayData::Discarded) {
+        ArrayData::Diff(index, item, _) => (index, item),
+        _ => unreachable!(),
+      };
+      let mut items = match root.replace(ArrayData::Discarded) {
+        ArrayData::Array(items) => items,
+        _ => unreachable!(),
+      };
+      let old = mem::replace(&mut items[index], item);
+      if keep {
+        *root.borrow_mut() = ArrayData::Diff(index, old, node.clone());
+      }
+      *node.borrow_mut() = ArrayData::Array(items);
+    }
+    Ok(())
+  }
+
+  pub fn take_next<T>(node: &Node<T>) -> Option<Node<T>> {
+    if Rc::strong_count(node) != 1 {
+      return None;
+    }
+    match node.replace(ArrayData::Discarded) {
+      ArrayData::Diff(_, _, next) => Some(next),
+      _ => None,
+    }
+  }
+}
+
+impl<T> Iterator for PersistentArrayIter<T>
+where
+  T: Clone,
+{
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let item = PersistentArray::get(&self.array, self.index)?;
+    self.index += 1;
+    Some(item)
+  }
+}
+
+impl<T> From<Vec<T>> for PersistentArray<T> {
+  fn from(items: Vec<T>) -> Self {
+    Self {
+      len: items.len(),
+      data: Rc::new(RefCell::new(ArrayData::Array(items))),
+    }
+  }
+}
+
+impl<T> From<PersistentArray<T>> for Vec<T>
+where
+  T: Clone,
+{
+  fn from(array: PersistentArray<T>) -> Self {
+    PersistentArray::iter(&array).collect()
+  }
+}
+
+impl<T> Default for PersistentArray<T> {
+  fn default() -> Self {
+    Self::from(vec![])
+  }
+}
+
+impl<T> Clone for PersistentArray<T> {
+  fn clone(&self) -> Self {
+    Self {
+      data: self.data.clone(),
+      len: self.len,
+    }
+  }
+}
+
+// Unlinks the diff chain one node at a time, so long histories don't
+// overflow the stack.
+impl<T> Drop for PersistentArray<T> {
+  fn drop(&mut self) {
+    let mut next = private::take_next(&self.data);
+    while let Some(node) = next {
+      next = private::take_next(&node);
+    }
+  }
+}
+
+impl<T> fmt::Debug for PersistentArray<T>
+where
+  T: Clone + fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_list().entries(Self::iter(self)).finish()
+  }
+}
+
+impl<T> PersistentArray<T>
+where
+  T: Clone,
+{
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn is_empty(array: &Self) -> bool {
+    array.len == 0
+  }
+
+  pub fn len(array: &Self) -> i32 {
+    array.len as i32
+  }
+
+  // O(1) on the current version, otherwise reroots to `array` first. `None`
+  // for a discarded version too.
+  pub fn get(array: &Self, index: usize) -> Option<T> {
+    if index >= array.len {
+      return None;
+    }
+    private::reroot(&array.data, true).ok()?;
+    match &*array.data.borrow() {
+      ArrayData::Array(items) => Some(items[index].clone()),
+      _ => unreachable!(),
+    }
+  }
+
+  // Writes in place and leaves a diff behind, so `array` stays readable.
+  pub fn set(array: &Self, index: usize, item: T) -> Result<Self, ArrayError> {
+    if index >= array.len {
+      return Err(ArrayError::OutOfBounds);
+    }
+    private::reroot(&array.data, true)?;
+    let mut items = match array.data.replace(ArrayData::Discarded) {
+      ArrayData::Array(items) => items,
+      _ => unreachable!(),
+    };
+    let old = mem::replace(&mut items[index], item);
+    let data = Rc::new(RefCell::new(ArrayData::Array(items)));
+    *array.data.borrow_mut() = ArrayData::Diff(index, old, data.clone());
+    Ok(Self {
+      data,
+      len: array.len,
+    })
+  }
+
+  // Semi-persistent fast path: makes `array` current again by undoing the
+  // newer writes instead of keeping them as diffs. Every version that is not
+  // an ancestor of `array` may be discarded; `get` then finds nothing in it
+  // and `set` fails with `Discarded`.
+  pub fn backtrack(array: &Self) -> Result<(), ArrayError> {
+    private::reroot(&array.data, false)
+  }
+
+  pub fn is_discarded(array: &Self) -> bool {
+    private::path_aux(&array.data).is_err()
+  }
+
+  pub fn iter(array: &Self) -> PersistentArrayIter<T> {
+    PersistentArrayIter {
+      array: array.clone(),
+      index: 0,
+    }
+  }
+}
+
+#[cfg(test)]
+#[path = "./persistent-array_test.rs"]
+mod test;
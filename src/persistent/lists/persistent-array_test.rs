@@ -0,0 +1,198 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type PersistentArrayT = PersistentArray<i32>;
+
+  pub fn array_empty() -> PersistentArrayT {
+    PersistentArray::new()
+  }
+
+  pub fn array_filled() -> PersistentArrayT {
+    PersistentArray::from(vec![0, 1, 2, 3])
+  }
+
+  pub fn is_current<T>(array: &PersistentArray<T>) -> bool {
+    matches!(*array.data.borrow(), ArrayData::Array(_))
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = PersistentArray::<i32>::new();
+    assert!(PersistentArray::is_empty(&op));
+    assert!(setup::is_current(&op))
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(PersistentArray::len(&setup::array_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(PersistentArray::len(&setup::array_filled()), 4)
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(PersistentArray::get(&setup::array_empty(), 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let array = setup::array_filled();
+    assert_eq!(PersistentArray::get(&array, 2), Some(2));
+    assert_eq!(PersistentArray::get(&array, 4), None)
+  }
+
+  #[test]
+  fn reroots_to_old_version() {
+    let array = setup::array_filled();
+    let op = PersistentArray::set(&array, 1, 10).unwrap();
+    assert!(setup::is_current(&op));
+    assert_eq!(PersistentArray::get(&array, 1), Some(1));
+    assert!(setup::is_current(&array));
+    assert!(!setup::is_current(&op));
+    assert_eq!(PersistentArray::get(&op, 1), Some(10));
+    assert!(setup::is_current(&op))
+  }
+}
+
+#[cfg(test)]
+mod set {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = PersistentArray::set(&setup::array_empty(), 0, 7);
+    assert_eq!(op.map(|_| ()), Err(ArrayError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    let array = setup::array_filled();
+    let op = PersistentArray::set(&array, 3, 30).unwrap();
+    assert_eq!(Vec::from(op), vec![0, 1, 2, 30]);
+    assert_eq!(Vec::from(array), vec![0, 1, 2, 3])
+  }
+
+  #[test]
+  fn to_branching_versions() {
+    let array = setup::array_filled();
+    let v1 = PersistentArray::set(&array, 0, 10).unwrap();
+    let v2 = PersistentArray::set(&v1, 1, 11).unwrap();
+    let v3 = PersistentArray::set(&v1, 2, 12).unwrap();
+    let v4 = PersistentArray::set(&array, 3, 13).unwrap();
+    assert_eq!(Vec::from(v2.clone()), vec![10, 11, 2, 3]);
+    assert_eq!(Vec::from(v4.clone()), vec![0, 1, 2, 13]);
+    assert_eq!(Vec::from(v3.clone()), vec![10, 1, 12, 3]);
+    assert_eq!(Vec::from(v1.clone()), vec![10, 1, 2, 3]);
+    assert_eq!(Vec::from(array.clone()), vec![0, 1, 2, 3]);
+    assert_eq!(Vec::from(v2), vec![10, 11, 2, 3])
+  }
+
+  #[test]
+  fn to_long_history() {
+    let array = PersistentArray::from(vec![0; 8]);
+    let versions = (0..100000).fold(vec![array], |mut acc, item| {
+      let op = PersistentArray::set(acc.last().unwrap(), item % 8, item).unwrap();
+      acc.push(op);
+      acc
+    });
+    assert_eq!(PersistentArray::get(&versions[0], 0), Some(0));
+    assert_eq!(PersistentArray::get(&versions[100000], 7), Some(99999));
+    assert_eq!(PersistentArray::get(&versions[9], 0), Some(8));
+    drop(versions)
+  }
+}
+
+#[cfg(test)]
+mod backtrack {
+  use super::*;
+
+  #[test]
+  fn to_current() {
+    let array = setup::array_filled();
+    assert_eq!(PersistentArray::backtrack(&array), Ok(()));
+    assert!(setup::is_current(&array));
+    assert_eq!(Vec::from(array), vec![0, 1, 2, 3])
+  }
+
+  #[test]
+  fn to_filled() {
+    let array = setup::array_filled();
+    let v1 = PersistentArray::set(&array, 0, 10).unwrap();
+    let v2 = PersistentArray::set(&v1, 1, 11).unwrap();
+    PersistentArray::backtrack(&array).unwrap();
+    assert!(setup::is_current(&array));
+    assert!(PersistentArray::is_discarded(&v1));
+    assert!(PersistentArray::is_discarded(&v2));
+    assert_eq!(Vec::from(array.clone()), vec![0, 1, 2, 3]);
+    let op = PersistentArray::set(&array, 2, 12).unwrap();
+    assert_eq!(Vec::from(op), vec![0, 1, 12, 3])
+  }
+
+  #[test]
+  fn keeps_ancestors() {
+    let array = setup::array_filled();
+    let v1 = PersistentArray::set(&array, 0, 10).unwrap();
+    let v2 = PersistentArray::set(&v1, 1, 11).unwrap();
+    PersistentArray::backtrack(&v1).unwrap();
+    assert!(PersistentArray::is_discarded(&v2));
+    assert!(!PersistentArray::is_discarded(&array));
+    assert_eq!(Vec::from(v1), vec![10, 1, 2, 3]);
+    assert_eq!(Vec::from(array), vec![0, 1, 2, 3])
+  }
+
+  #[test]
+  fn to_discarded() {
+    let array = setup::array_filled();
+    let v1 = PersistentArray::set(&array, 0, 10).unwrap();
+    let v2 = PersistentArray::set(&v1, 1, 11).unwrap();
+    PersistentArray::backtrack(&array).unwrap();
+    assert_eq!(PersistentArray::get(&v1, 0), None);
+    assert_eq!(PersistentArray::get(&v2, 0), None);
+    assert_eq!(
+      PersistentArray::set(&v2, 0, 20).map(|_| ()),
+      Err(ArrayError::Discarded)
+    );
+    assert_eq!(PersistentArray::backtrack(&v1), Err(ArrayError::Discarded));
+    assert_eq!(PersistentArray::iter(&v2).count(), 0);
+    assert_eq!(Vec::from(array), vec![0, 1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(PersistentArray::iter(&setup::array_empty()).count(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let array = setup::array_filled();
+    let op = PersistentArray::set(&array, 1, 10).unwrap();
+    let items: Vec<i32> = PersistentArray::iter(&array).collect();
+    assert_eq!(items, vec![0, 1, 2, 3]);
+    assert_eq!(format!("{op:?}"), "[0, 10, 2, 3]")
+  }
+}
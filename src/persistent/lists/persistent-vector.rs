@@ -0,0 +1,648 @@
+use std::{
+  ops::{Bound, RangeBounds},
+  rc::Rc,
+};
+
+use crate::IndexError;
+
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+// How many nodes over the optimal count a concatenation tolerates on each
+// level before it redistributes them.
+const EXTRAS: usize = 2;
+
+type Link<T> = Option<Rc<VectorNode<T>>>;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum VectorNode<T> {
+  Leaf(Vec<T>),
+  Branch {
+    children: Vec<Rc<VectorNode<T>>>,
+    sizes: Vec<usize>,
+  },
+}
+
+// A relaxed radix balanced (RRB) trie: a 32-way trie whose nodes keep the
+// cumulative sizes of their children, so after a concat or a split they don't
+// need to be full for indexing to work. The last items live in a tail buffer
+// outside the trie, which makes push_back/pop_back touch the trie only once
+// every 32 calls.
+//
+// Invariants:
+//    - every leaf is at depth height and holds at most 32 items
+//    - every branch holds between 1 and 32 children, none of them empty
+//    - sizes[i] is the number of items in children[0..=i]
+//    - the tail holds at most 32 items and comes after every item in the trie
+#[derive(Clone, PartialEq, Debug)]
+pub struct PersistentVector<T> {
+  root: Rc<VectorNode<T>>,
+  height: usize,
+  tail: Vec<T>,
+}
+
+mod private {
+  use super::*;
+
+  pub fn size<T>(node: &VectorNode<T>) -> usize {
+    match node {
+      VectorNode::Leaf(items) => items.len(),
+      VectorNode::Branch { sizes, .. } => sizes.last().copied().unwrap_or(0),
+    }
+  }
+
+  // How many items a leaf or children a branch holds.
+  fn slots<T>(node: &VectorNode<T>) -> usize {
+    match node {
+      VectorNode::Leaf(items) => items.len(),
+      VectorNode::Branch { children, .. } => children.len(),
+    }
+  }
+
+  fn children<T>(node: &VectorNode<T>) -> &[Rc<VectorNode<T>>] {
+    match node {
+      VectorNode::Leaf(_) => &[],
+      VectorNode::Branch { children, .. } => children,
+    }
+  }
+
+  fn items<T>(node: &VectorNode<T>) -> &[T] {
+    match node {
+      VectorNode::Leaf(items) => items,
+      VectorNode::Branch { .. } => &[],
+    }
+  }
+
+  pub fn empty<T>() -> Rc<VectorNode<T>> {
+    Rc::new(VectorNode::Leaf(vec![]))
+  }
+
+  pub fn leaf<T>(items: Vec<T>) -> Rc<VectorNode<T>> {
+    Rc::new(VectorNode::Leaf(items))
+  }
+
+  pub fn branch<T>(children: Vec<Rc<VectorNode<T>>>) -> Rc<VectorNode<T>> {
+    let sizes = children
+      .iter()
+      .scan(0, |acc, child| {
+        *acc += size(child);
+        Some(*acc)
+      })
+      .collect();
+    Rc::new(VectorNode::Branch { children, sizes })
+  }
+
+  // Wraps `node` in single child branches until it reaches `to`.
+  fn lift<T>(node: Rc<VectorNode<T>>, from: usize, to: usize) -> Rc<VectorNode<T>> {
+    (from..to).fold(node, |acc, _| branch(vec![acc]))
+  }
+
+  // Drops the single child branches left on top of the root by a split or a
+  // concat.
+  pub fn normalize<T>(node: Link<T>, height: usize) -> (Rc<VectorNode<T>>, usize) {
+    let mut node = match node {
+      Some(node) if size(&node) > 0 => node,
+      _ => return (empty(), 0),
+    };
+    let mut height = height;
+    while height > 0 && slots(&node) == 1 {
+      node = children(&node)[0].clone();
+      height -= 1;
+    }
+    (node, height)
+  }
+
+  // The slot holding `index` in a branch at `height`. Every child holds at
+  // most 32^height items, so the radix guess is never past the right slot and
+  // only relaxed nodes need to walk forward from it.
+  fn slot_of(sizes: &[usize], height: usize, index: usize) -> (usize, usize) {
+    let guess = index.checked_shr((BITS * height) as u32).unwrap_or(0);
+    let mut slot = guess.min(sizes.len() - 1);
+    while sizes[slot] <= index {
+      slot += 1;
+    }
+    let offset = if slot == 0 { 0 } else { sizes[slot - 1] };
+    (slot, index - offset)
+  }
+
+  pub fn get_node_aux<T>(node: &VectorNode<T>, height: usize, index: usize) -> &T {
+    match node {
+      VectorNode::Leaf(items) => &items[index],
+      VectorNode::Branch { children, sizes } => {
+        let (slot, index) = slot_of(sizes, height, index);
+        get_node_aux(&children[slot], height - 1, index)
+      }
+    }
+  }
+
+  pub fn set_node_aux<T>(
+    node: &VectorNode<T>,
+    height: usize,
+    index: usize,
+    item: T,
+  ) -> Rc<VectorNode<T>>
+  where
+    T: Clone,
+  {
+    match node {
+      VectorNode::Leaf(items) => {
+        let mut items = items.clone();
+        items[index] = item;
+        leaf(items)
+      }
+      VectorNode::Branch { children, sizes } => {
+        let (slot, index) = slot_of(sizes, height, index);
+        let mut children = children.clone();
+        children[slot] = set_node_aux(&children[slot], height - 1, index, item);
+        Rc::new(VectorNode::Branch {
+          children,
+          sizes: sizes.clone(),
+        })
+      }
+    }
+  }
+
+  // `None` when there's no free slot left on the right edge of `node`.
+  fn push_leaf_node_aux<T>(
+    node: &VectorNode<T>,
+    height: usize,
+    new_leaf: &Rc<VectorNode<T>>,
+  ) -> Link<T> {
+    let children = children(node);
+    if height > 1 {
+      if let Some(last) = children.last() {
+        if let Some(new_last) = push_leaf_node_aux(last, height - 1, new_leaf) {
+          let mut children = children.to_vec();
+          *children.last_mut().unwrap() = new_last;
+          return Some(branch(children));
+        }
+      }
+    }
+    if children.len() < WIDTH {
+      let mut children = children.to_vec();
+      children.push(lift(new_leaf.clone(), 0, height - 1));
+      Some(branch(children))
+    } else {
+      None
+    }
+  }
+
+  pub fn push_leaf_aux<T>(
+    root: &Rc<VectorNode<T>>,
+    height: usize,
+    new_leaf: Rc<VectorNode<T>>,
+  ) -> (Rc<VectorNode<T>>, usize) {
+    if size(root) == 0 {
+      return (new_leaf, 0);
+    }
+    if height == 0 {
+      return (branch(vec![root.clone(), new_leaf]), 1);
+    }
+    match push_leaf_node_aux(root, height, &new_leaf) {
+      Some(root) => (root, height),
+      None => (
+        branch(vec![root.clone(), lift(new_leaf, 0, height)]),
+        height + 1,
+      ),
+    }
+  }
+
+  // Takes the rightmost leaf out of `node`, `None` being what's left when it
+  // was the only one.
+  pub fn pop_leaf_aux<T>(node: &VectorNode<T>) -> (Vec<T>, Link<T>)
+  where
+    T: Clone,
+  {
+    match node {
+      VectorNode::Leaf(items) => (items.clone(), None),
+      VectorNode::Branch { children, .. } => {
+        let (items, rest) = pop_leaf_aux(children.last().unwrap());
+        let mut children = children[..children.len() - 1].to_vec();
+        children.extend(rest);
+        if children.is_empty() {
+          (items, None)
+        } else {
+          (items, Some(branch(children)))
+        }
+      }
+    }
+  }
+
+  // Splits into the first `index` items and the rest, `None` standing for an
+  // empty half.
+  pub fn split_node_aux<T>(
+    node: &Rc<VectorNode<T>>,
+    height: usize,
+    index: usize,
+  ) -> (Link<T>, Link<T>)
+  where
+    T: Clone,
+  {
+    if index == 0 {
+      return (None, Some(node.clone()));
+    }
+    if index >= size(node) {
+      return (Some(node.clone()), None);
+    }
+    match node.as_ref() {
+      VectorNode::Leaf(items) => (
+        Some(leaf(items[..index].to_vec())),
+        Some(leaf(items[index..].to_vec())),
+      ),
+      VectorNode::Branch { children, sizes } => {
+        let (slot, index) = slot_of(sizes, height, index);
+        let (lower, upper) = split_node_aux(&children[slot], height - 1, index);
+        let mut left = children[..slot].to_vec();
+        left.extend(lower);
+        let mut right: Vec<Rc<VectorNode<T>>> = upper.into_iter().collect();
+        right.extend_from_slice(&children[slot + 1..]);
+        (Some(branch(left)), Some(branch(right)))
+      }
+    }
+  }
+
+  // How many slots each node should end up with so that there are at most
+  // EXTRAS nodes more than the optimal count. Slots move from the first
+  // node that isn't nearly full into the ones after it, keeping their order.
+  fn concat_plan(counts: &[usize]) -> Vec<usize> {
+    let total: usize = counts.iter().sum();
+    let optimal = total.div_ceil(WIDTH);
+    let mut counts = counts.to_vec();
+    let mut i = 0;
+    while counts.len() > optimal + EXTRAS {
+      while i < counts.len() && counts[i] >= WIDTH - 1 {
+        i += 1;
+      }
+      if i == counts.len() {
+        break;
+      }
+      let mut remaining = counts.remove(i);
+      let mut j = i;
+      while remaining > 0 && j < counts.len() {
+        let moved = (WIDTH - counts[j]).min(remaining);
+        counts[j] += moved;
+        remaining -= moved;
+        j += 1;
+      }
+      if remaining > 0 {
+        counts.push(remaining);
+      }
+    }
+    counts
+  }
+
+  fn chunks<U>(slots: Vec<U>, plan: &[usize]) -> Vec<Vec<U>> {
+    let mut slots = slots.into_iter();
+    plan
+      .iter()
+      .map(|count| slots.by_ref().take(*count).collect())
+      .collect()
+  }
+
+  // Redistributes the contents of `nodes`, all at `height`, when there are
+  // too many of them for what they hold.
+  fn rebalance<T>(nodes: Vec<Rc<VectorNode<T>>>, height: usize) -> Vec<Rc<VectorNode<T>>>
+  where
+    T: Clone,
+  {
+    let counts: Vec<usize> = nodes.iter().map(|node| slots(node)).collect();
+    let plan = concat_plan(&counts);
+    if plan.len() == nodes.len() {
+      return nodes;
+    }
+    if height == 0 {
+      let items: Vec<T> = nodes.iter().flat_map(|node| items(node).to_vec()).collect();
+      chunks(items, &plan).into_iter().map(leaf).collect()
+    } else {
+      let children: Vec<Rc<VectorNode<T>>> = nodes
+        .iter()
+        .flat_map(|node| children(node).to_vec())
+        .collect();
+      chunks(children, &plan).into_iter().map(branch).collect()
+    }
+  }
+
+  // Concatenates two nodes at the same height into one or two nodes at
+  // that height. Only the right edge of `left` and the left edge of `right`
+  // are rebuilt.
+  fn merge_node_aux<T>(
+    left: &VectorNode<T>,
+    right: &VectorNode<T>,
+    height: usize,
+  ) -> Vec<Rc<VectorNode<T>>>
+  where
+    T: Clone,
+  {
+    if height == 0 {
+      let mut joined = items(left).to_vec();
+      joined.extend_from_slice(items(right));
+      return joined
+        .chunks(WIDTH)
+        .map(|chunk| leaf(chunk.to_vec()))
+        .collect();
+    }
+    let (lc, rc) = (children(left), children(right));
+    let middle = merge_node_aux(lc.last().unwrap(), rc.first().unwrap(), height - 1);
+    let mut nodes = lc[..lc.len() - 1].to_vec();
+    nodes.extend(middle);
+    nodes.extend_from_slice(&rc[1..]);
+    let nodes = rebalance(nodes, height - 1);
+    nodes
+      .chunks(WIDTH)
+      .map(|chunk| branch(chunk.to_vec()))
+      .collect()
+  }
+
+  pub fn concat_node_aux<T>(
+    left: &Rc<VectorNode<T>>,
+    left_height: usize,
+    right: &Rc<VectorNode<T>>,
+    right_height: usize,
+  ) -> (Rc<VectorNode<T>>, usize)
+  where
+    T: Clone,
+  {
+    if size(left) == 0 {
+      return (right.clone(), right_height);
+    }
+    if size(right) == 0 {
+      return (left.clone(), left_height);
+    }
+    let height = left_height.max(right_height);
+    let left = lift(left.clone(), left_height, height);
+    let right = lift(right.clone(), right_height, height);
+    let nodes = merge_node_aux(&left, &right, height);
+    if nodes.len() == 1 {
+      normalize(nodes.into_iter().next(), height)
+    } else {
+      normalize(Some(branch(nodes)), height + 1)
+    }
+  }
+
+  pub fn any_node_aux<T>(node: &VectorNode<T>, f: fn(&T) -> bool) -> bool {
+    match node {
+      VectorNode::Leaf(items) => items.iter().any(f),
+      VectorNode::Branch { children, .. } => children.iter().any(|child| any_node_aux(child, f)),
+    }
+  }
+
+  pub fn all_node_aux<T>(node: &VectorNode<T>, f: fn(&T) -> bool) -> bool {
+    match node {
+      VectorNode::Leaf(items) => items.iter().all(f),
+      VectorNode::Branch { children, .. } => children.iter().all(|child| all_node_aux(child, f)),
+    }
+  }
+
+  pub fn find_node_aux<T>(node: &VectorNode<T>, f: fn(&T) -> bool) -> Option<&T> {
+    match node {
+      VectorNode::Leaf(items) => items.iter().find(|item| f(item)),
+      VectorNode::Branch { children, .. } => {
+        children.iter().find_map(|child| find_node_aux(child, f))
+      }
+    }
+  }
+
+  pub fn map_node_aux<T, U>(node: &VectorNode<T>, f: fn(&T) -> U) -> Rc<VectorNode<U>> {
+    match node {
+      VectorNode::Leaf(items) => leaf(items.iter().map(f).collect()),
+      VectorNode::Branch { children, sizes } => Rc::new(VectorNode::Branch {
+        children: children
+          .iter()
+          .map(|child| map_node_aux(child, f))
+          .collect(),
+        sizes: sizes.clone(),
+      }),
+    }
+  }
+
+  pub fn reduce_node_aux<T, U>(node: &VectorNode<T>, f: fn(&T, U) -> U, acc: U) -> U {
+    match node {
+      VectorNode::Leaf(items) => items.iter().fold(acc, |acc, item| f(item, acc)),
+      VectorNode::Branch { children, .. } => children
+        .iter()
+        .fold(acc, |acc, child| reduce_node_aux(child, f, acc)),
+    }
+  }
+}
+
+impl<T> From<Vec<T>> for PersistentVector<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  fn from(value: Vec<T>) -> Self {
+    value
+      .into_iter()
+      .fold(Self::new(), |acc, item| Self::push_back(&acc, item))
+  }
+}
+
+impl<T> From<PersistentVector<T>> for Vec<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  fn from(value: PersistentVector<T>) -> Self {
+    PersistentVector::reduce(
+      &value,
+      |item, mut acc: Vec<T>| {
+        acc.push(*item);
+        acc
+      },
+      vec![],
+    )
+  }
+}
+
+impl<T> Default for PersistentVector<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> PersistentVector<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  pub fn new() -> Self {
+    Self {
+      root: private::empty(),
+      height: 0,
+      tail: vec![],
+    }
+  }
+
+  pub fn is_empty(vector: &Self) -> bool {
+    private::size(&vector.root) == 0 && vector.tail.is_empty()
+  }
+
+  pub fn len(vector: &Self) -> i32 {
+    (private::size(&vector.root) + vector.tail.len()) as i32
+  }
+
+  pub fn get(vector: &Self, index: usize) -> Option<&T> {
+    let tree_len = private::size(&vector.root);
+    if index < tree_len {
+      Some(private::get_node_aux(&vector.root, vector.height, index))
+    } else {
+      vector.tail.get(index - tree_len)
+    }
+  }
+
+  pub fn set(vector: &Self, index: usize, item: T) -> Result<Self, IndexError> {
+    let tree_len = private::size(&vector.root);
+    if index < tree_len {
+      Ok(Self {
+        root: private::set_node_aux(&vector.root, vector.height, index, item),
+        height: vector.height,
+        tail: vector.tail.clone(),
+      })
+    } else if index - tree_len < vector.tail.len() {
+      let mut tail = vector.tail.clone();
+      tail[index - tree_len] = item;
+      Ok(Self {
+        root: vector.root.clone(),
+        height: vector.height,
+        tail,
+      })
+    } else {
+      Err(IndexError::OutOfBounds)
+    }
+  }
+
+  pub fn push_back(vector: &Self, item: T) -> Self {
+    if vector.tail.len() < WIDTH {
+      let mut tail = vector.tail.clone();
+      tail.push(item);
+      return Self {
+        root: vector.root.clone(),
+        height: vector.height,
+        tail,
+      };
+    }
+    let (root, height) = private::push_leaf_aux(
+      &vector.root,
+      vector.height,
+      private::leaf(vector.tail.clone()),
+    );
+    Self {
+      root,
+      height,
+      tail: vec![item],
+    }
+  }
+
+  pub fn pop_back(vector: &Self) -> Option<(T, Self)> {
+    let (root, height, mut tail) = if !vector.tail.is_empty() {
+      (vector.root.clone(), vector.height, vector.tail.clone())
+    } else if private::size(&vector.root) > 0 {
+      let (items, rest) = private::pop_leaf_aux(&vector.root);
+      let (root, height) = private::normalize(rest, vector.height);
+      (root, height, items)
+    } else {
+      return None;
+    };
+    let item = tail.pop()?;
+    Some((item, Self { root, height, tail }))
+  }
+
+  pub fn concat(v1: &Self, v2: &Self) -> Self {
+    let (root, height) =
+      private::concat_node_aux(&v1.root, v1.height, &private::leaf(v1.tail.clone()), 0);
+    let (root, height) = private::concat_node_aux(&root, height, &v2.root, v2.height);
+    Self {
+      root,
+      height,
+      tail: v2.tail.clone(),
+    }
+  }
+
+  // Splits into the first `index` items and the rest; an index past the end
+  // leaves the second half empty.
+  pub fn split_at(vector: &Self, index: usize) -> (Self, Self) {
+    let tree_len = private::size(&vector.root);
+    if index <= tree_len {
+      let (lower, upper) = private::split_node_aux(&vector.root, vector.height, index);
+      let (lower_root, lower_height) = private::normalize(lower, vector.height);
+      let (upper_root, upper_height) = private::normalize(upper, vector.height);
+      (
+        Self {
+          root: lower_root,
+          height: lower_height,
+          tail: vec![],
+        },
+        Self {
+          root: upper_root,
+          height: upper_height,
+          tail: vector.tail.clone(),
+        },
+      )
+    } else {
+      let index = (index - tree_len).min(vector.tail.len());
+      (
+        Self {
+          root: vector.root.clone(),
+          height: vector.height,
+          tail: vector.tail[..index].to_vec(),
+        },
+        Self {
+          root: private::empty(),
+          height: 0,
+          tail: vector.tail[index..].to_vec(),
+        },
+      )
+    }
+  }
+
+  // The items in `range`, clamped to the bounds of the vector.
+  pub fn slice<R>(vector: &Self, range: R) -> Self
+  where
+    R: RangeBounds<usize>,
+  {
+    let start = match range.start_bound() {
+      Bound::Included(start) => *start,
+      Bound::Excluded(start) => start + 1,
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(end) => end + 1,
+      Bound::Excluded(end) => *end,
+      Bound::Unbounded => usize::MAX,
+    };
+    let (_, upper) = Self::split_at(vector, start);
+    let (middle, _) = Self::split_at(&upper, end.saturating_sub(start));
+    middle
+  }
+
+  pub fn any(vector: &Self, f: fn(&T) -> bool) -> bool {
+    private::any_node_aux(&vector.root, f) || vector.tail.iter().any(f)
+  }
+
+  pub fn all(vector: &Self, f: fn(&T) -> bool) -> bool {
+    private::all_node_aux(&vector.root, f) && vector.tail.iter().all(f)
+  }
+
+  pub fn find(vector: &Self, f: fn(&T) -> bool) -> Option<&T> {
+    private::find_node_aux(&vector.root, f).or_else(|| vector.tail.iter().find(|item| f(item)))
+  }
+
+  pub fn map<U>(vector: &Self, f: fn(&T) -> U) -> PersistentVector<U>
+  where
+    U: PartialEq + Clone + Copy,
+  {
+    PersistentVector::<U> {
+      root: private::map_node_aux(&vector.root, f),
+      height: vector.height,
+      tail: vector.tail.iter().map(f).collect(),
+    }
+  }
+
+  pub fn reduce<U>(vector: &Self, f: fn(&T, U) -> U, acc: U) -> U {
+    let acc = private::reduce_node_aux(&vector.root, f, acc);
+    vector.tail.iter().fold(acc, |acc, item| f(item, acc))
+  }
+}
+
+#[cfg(test)]
+#[path = "./persistent-vector_test.rs"]
+mod test;
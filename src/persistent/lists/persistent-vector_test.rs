@@ -0,0 +1,525 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type PersistentVectorT = PersistentVector<i32>;
+
+  pub fn persistent_vector_empty() -> PersistentVectorT {
+    PersistentVectorT {
+      root: Rc::new(VectorNode::Leaf(vec![])),
+      height: 0,
+      tail: vec![],
+    }
+  }
+
+  pub fn persistent_vector_filled() -> PersistentVectorT {
+    PersistentVectorT {
+      root: Rc::new(VectorNode::Leaf((0..32).collect())),
+      height: 0,
+      tail: vec![32, 33, 34],
+    }
+  }
+
+  pub fn from_range(start: i32, end: i32) -> PersistentVectorT {
+    PersistentVector::from((start..end).collect::<Vec<i32>>())
+  }
+
+  pub fn to_vec(vector: &PersistentVectorT) -> Vec<i32> {
+    Vec::from(vector.clone())
+  }
+
+  // Checks the invariants of the trie, returning how many items it holds.
+  fn check_node_aux(node: &VectorNode<i32>, height: usize, is_root: bool) -> usize {
+    match node {
+      VectorNode::Leaf(items) => {
+        assert_eq!(height, 0);
+        assert!(items.len() <= WIDTH);
+        assert!(is_root || !items.is_empty());
+        items.len()
+      }
+      VectorNode::Branch { children, sizes } => {
+        assert!(height > 0);
+        assert!(!children.is_empty() && children.len() <= WIDTH);
+        assert!(!is_root || children.len() > 1);
+        let mut acc = 0;
+        for (child, size) in children.iter().zip(sizes) {
+          acc += check_node_aux(child, height - 1, false);
+          assert_eq!(acc, *size);
+        }
+        acc
+      }
+    }
+  }
+
+  pub fn check(vector: &PersistentVectorT) {
+    check_node_aux(&vector.root, vector.height, true);
+    assert!(vector.tail.len() <= WIDTH);
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = PersistentVector::<i32>::new();
+    let expected = setup::persistent_vector_empty();
+    assert_eq!(op, expected)
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    assert!(PersistentVector::is_empty(&vector))
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    assert!(!PersistentVector::is_empty(&vector))
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    assert_eq!(PersistentVector::len(&vector), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    assert_eq!(PersistentVector::len(&vector), 35)
+  }
+}
+
+#[cfg(test)]
+mod push_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    let op = PersistentVector::push_back(&vector, 0);
+    let expected = PersistentVector {
+      root: Rc::new(VectorNode::Leaf(vec![])),
+      height: 0,
+      tail: vec![0],
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    let op = PersistentVector::push_back(&vector, 35);
+    assert!(Rc::ptr_eq(&op.root, &vector.root));
+    assert_eq!(op.tail, vec![32, 33, 34, 35]);
+    assert_eq!(vector.tail, vec![32, 33, 34])
+  }
+
+  #[test]
+  fn to_filled_flushing_the_tail() {
+    let op = setup::from_range(0, 65);
+    let expected = PersistentVector {
+      root: private::branch(vec![
+        Rc::new(VectorNode::Leaf((0..32).collect())),
+        Rc::new(VectorNode::Leaf((32..64).collect())),
+      ]),
+      height: 1,
+      tail: vec![64],
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_growing_levels() {
+    let op = setup::from_range(0, 40_000);
+    setup::check(&op);
+    assert_eq!(op.height, 3);
+    assert_eq!(setup::to_vec(&op), (0..40_000).collect::<Vec<i32>>())
+  }
+}
+
+#[cfg(test)]
+mod pop_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    assert_eq!(PersistentVector::pop_back(&vector), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    let (item, op) = PersistentVector::pop_back(&vector).unwrap();
+    assert_eq!(item, 34);
+    assert_eq!(op.tail, vec![32, 33]);
+    assert_eq!(PersistentVector::len(&vector), 35)
+  }
+
+  #[test]
+  fn to_filled_refilling_the_tail() {
+    let vector = setup::from_range(0, 64);
+    let op = (0..33).fold(vector, |acc, _| PersistentVector::pop_back(&acc).unwrap().1);
+    let expected = PersistentVector {
+      root: Rc::new(VectorNode::Leaf(vec![])),
+      height: 0,
+      tail: (0..31).collect(),
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_until_empty() {
+    let mut vector = setup::from_range(0, 2000);
+    for expected in (0..2000).rev() {
+      let (item, rest) = PersistentVector::pop_back(&vector).unwrap();
+      assert_eq!(item, expected);
+      vector = rest;
+    }
+    assert_eq!(vector, setup::persistent_vector_empty())
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    assert_eq!(PersistentVector::get(&vector, 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    assert_eq!(PersistentVector::get(&vector, 5), Some(&5));
+    assert_eq!(PersistentVector::get(&vector, 33), Some(&33));
+    assert_eq!(PersistentVector::get(&vector, 35), None)
+  }
+
+  #[test]
+  fn to_filled_long() {
+    let vector = setup::from_range(0, 5000);
+    assert!((0..5000).all(|i| PersistentVector::get(&vector, i as usize) == Some(&i)))
+  }
+}
+
+#[cfg(test)]
+mod set {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    let op = PersistentVector::set(&vector, 0, 10);
+    assert_eq!(op, Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    let op = PersistentVector::set(&vector, 1, 10).unwrap();
+    assert_eq!(PersistentVector::get(&op, 1), Some(&10));
+    assert_eq!(PersistentVector::get(&vector, 1), Some(&1))
+  }
+
+  #[test]
+  fn to_filled_tail() {
+    let vector = setup::persistent_vector_filled();
+    let op = PersistentVector::set(&vector, 34, 10).unwrap();
+    assert!(Rc::ptr_eq(&op.root, &vector.root));
+    assert_eq!(op.tail, vec![32, 33, 10])
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let vector = setup::persistent_vector_filled();
+    let op = PersistentVector::set(&vector, 35, 10);
+    assert_eq!(op, Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn shares_untouched_leaves() {
+    let vector = setup::from_range(0, 100);
+    let op = PersistentVector::set(&vector, 40, -1).unwrap();
+    match (vector.root.as_ref(), op.root.as_ref()) {
+      (VectorNode::Branch { children: c1, .. }, VectorNode::Branch { children: c2, .. }) => {
+        assert!(Rc::ptr_eq(&c1[0], &c2[0]));
+        assert!(!Rc::ptr_eq(&c1[1], &c2[1]));
+        assert!(Rc::ptr_eq(&c1[2], &c2[2]))
+      }
+      _ => panic!("expected a branch"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod concat {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    let op = PersistentVector::concat(&vector, &vector);
+    assert_eq!(op, setup::persistent_vector_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let v1 = setup::persistent_vector_filled();
+    let v2 = setup::from_range(35, 100);
+    let op = PersistentVector::concat(&v1, &v2);
+    setup::check(&op);
+    assert_eq!(setup::to_vec(&op), (0..100).collect::<Vec<i32>>())
+  }
+
+  #[test]
+  fn to_filled_with_empty() {
+    let vector = setup::persistent_vector_filled();
+    let empty = setup::persistent_vector_empty();
+    let op1 = PersistentVector::concat(&vector, &empty);
+    let op2 = PersistentVector::concat(&empty, &vector);
+    assert_eq!(setup::to_vec(&op1), (0..35).collect::<Vec<i32>>());
+    assert_eq!(setup::to_vec(&op2), (0..35).collect::<Vec<i32>>())
+  }
+
+  #[test]
+  fn to_filled_different_heights() {
+    let v1 = setup::from_range(0, 3);
+    let v2 = setup::from_range(3, 40_000);
+    let op1 = PersistentVector::concat(&v1, &v2);
+    let op2 = PersistentVector::concat(&v2, &v1);
+    setup::check(&op1);
+    setup::check(&op2);
+    assert_eq!(setup::to_vec(&op1), (0..40_000).collect::<Vec<i32>>());
+    assert!((0..40_000).all(|i| PersistentVector::get(&op1, i as usize) == Some(&i)));
+    assert_eq!(PersistentVector::get(&op2, 39_997), Some(&0))
+  }
+
+  #[test]
+  fn to_many_small_pieces() {
+    let op = (0..500).fold(PersistentVector::new(), |acc, i| {
+      PersistentVector::concat(&acc, &setup::from_range(i * 7, i * 7 + 7))
+    });
+    setup::check(&op);
+    assert!(op.height <= 3);
+    assert!((0..3500).all(|i| PersistentVector::get(&op, i as usize) == Some(&i)));
+    let op = PersistentVector::push_back(&op, 3500);
+    assert_eq!(setup::to_vec(&op), (0..3501).collect::<Vec<i32>>())
+  }
+}
+
+#[cfg(test)]
+mod split_at {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    let (op1, op2) = PersistentVector::split_at(&vector, 0);
+    assert_eq!(op1, setup::persistent_vector_empty());
+    assert_eq!(op2, setup::persistent_vector_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    let (op1, op2) = PersistentVector::split_at(&vector, 10);
+    assert_eq!(setup::to_vec(&op1), (0..10).collect::<Vec<i32>>());
+    assert_eq!(setup::to_vec(&op2), (10..35).collect::<Vec<i32>>())
+  }
+
+  #[test]
+  fn to_filled_in_the_tail() {
+    let vector = setup::persistent_vector_filled();
+    let (op1, op2) = PersistentVector::split_at(&vector, 33);
+    assert!(Rc::ptr_eq(&op1.root, &vector.root));
+    assert_eq!(op1.tail, vec![32]);
+    assert_eq!(setup::to_vec(&op2), vec![33, 34])
+  }
+
+  #[test]
+  fn to_filled_past_the_end() {
+    let vector = setup::persistent_vector_filled();
+    let (op1, op2) = PersistentVector::split_at(&vector, 100);
+    assert_eq!(op1, vector);
+    assert!(PersistentVector::is_empty(&op2))
+  }
+
+  #[test]
+  fn to_filled_long() {
+    let vector = setup::from_range(0, 40_000);
+    for index in [1, 31, 32, 1000, 1024, 33_333, 39_999] {
+      let (op1, op2) = PersistentVector::split_at(&vector, index);
+      setup::check(&op1);
+      setup::check(&op2);
+      assert_eq!(PersistentVector::len(&op1), index as i32);
+      assert_eq!(PersistentVector::get(&op2, 0), Some(&(index as i32)));
+      let op = PersistentVector::concat(&op1, &op2);
+      assert_eq!(setup::to_vec(&op), (0..40_000).collect::<Vec<i32>>())
+    }
+  }
+}
+
+#[cfg(test)]
+mod slice {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    let op = PersistentVector::slice(&vector, 2..5);
+    assert!(PersistentVector::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::from_range(0, 5000);
+    let op = PersistentVector::slice(&vector, 1000..=2500);
+    setup::check(&op);
+    assert_eq!(setup::to_vec(&op), (1000..2501).collect::<Vec<i32>>())
+  }
+
+  #[test]
+  fn to_filled_unbounded() {
+    let vector = setup::persistent_vector_filled();
+    assert_eq!(
+      setup::to_vec(&PersistentVector::slice(&vector, 30..)),
+      (30..35).collect::<Vec<i32>>()
+    );
+    assert_eq!(
+      setup::to_vec(&PersistentVector::slice(&vector, ..3)),
+      vec![0, 1, 2]
+    )
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let vector = setup::persistent_vector_filled();
+    assert!(PersistentVector::is_empty(&PersistentVector::slice(
+      &vector,
+      40..50
+    )));
+    assert!(PersistentVector::is_empty(&PersistentVector::slice(
+      &vector,
+      (Bound::Included(10), Bound::Excluded(5))
+    )))
+  }
+}
+
+#[cfg(test)]
+mod any {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    assert!(!PersistentVector::any(&vector, |item| *item == 3))
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    assert!(PersistentVector::any(&vector, |item| *item == 34));
+    assert!(!PersistentVector::any(&vector, |item| *item > 34))
+  }
+}
+
+#[cfg(test)]
+mod all {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    assert!(PersistentVector::all(&vector, |item| *item > 0))
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    assert!(PersistentVector::all(&vector, |item| *item >= 0));
+    assert!(!PersistentVector::all(&vector, |item| *item < 34))
+  }
+}
+
+#[cfg(test)]
+mod find {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    assert_eq!(PersistentVector::find(&vector, |item| *item > 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    assert_eq!(
+      PersistentVector::find(&vector, |item| *item > 31),
+      Some(&32)
+    )
+  }
+}
+
+#[cfg(test)]
+mod map {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    let op = PersistentVector::map(&vector, |item| item * 2);
+    assert_eq!(op, setup::persistent_vector_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    let op = PersistentVector::map(&vector, |item| item * 2);
+    assert_eq!(
+      setup::to_vec(&op),
+      (0..35).map(|item| item * 2).collect::<Vec<i32>>()
+    )
+  }
+}
+
+#[cfg(test)]
+mod reduce {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let vector = setup::persistent_vector_empty();
+    assert_eq!(
+      PersistentVector::reduce(&vector, |item, acc| item + acc, 0),
+      0
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let vector = setup::persistent_vector_filled();
+    assert_eq!(
+      PersistentVector::reduce(&vector, |item, acc| item + acc, 0),
+      595
+    )
+  }
+}
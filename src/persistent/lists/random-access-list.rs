@@ -0,0 +1,360 @@
+use std::rc::Rc;
+
+use crate::IndexError;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum SkewTree<T> {
+  Leaf(T),
+  Node(T, Rc<SkewTree<T>>, Rc<SkewTree<T>>),
+}
+
+type Digit<T> = Option<Rc<SkewDigit<T>>>;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SkewDigit<T> {
+  weight: usize,
+  tree: Rc<SkewTree<T>>,
+  next: Digit<T>,
+}
+
+// Okasaki's skew-binary random-access list: the list is split into complete
+// binary trees whose sizes follow the skew-binary digits of its length, each
+// tree holding its elements in pre-order.
+//
+// Invariants:
+//    - weight is the number of elements in tree, always of the form 2^k - 1
+//    - weights are increasing, only the first two of them may be equal
+#[derive(Clone, PartialEq, Debug)]
+pub struct RandomAccessList<T> {
+  root: Digit<T>,
+}
+
+mod private {
+  use super::*;
+
+  pub fn digit<T>(weight: usize, tree: Rc<SkewTree<T>>, next: Digit<T>) -> Digit<T> {
+    Some(Rc::new(SkewDigit { weight, tree, next }))
+  }
+
+  pub fn cons_digit_aux<T>(node: &Digit<T>, item: T) -> Digit<T> {
+    if let Some(first) = node {
+      if let Some(second) = &first.next {
+        if first.weight == second.weight {
+          return digit(
+            1 + first.weight + second.weight,
+            Rc::new(SkewTree::Node(
+              item,
+              first.tree.clone(),
+              second.tree.clone(),
+            )),
+            second.next.clone(),
+          );
+        }
+      }
+    }
+    digit(1, Rc::new(SkewTree::Leaf(item)), node.clone())
+  }
+
+  pub fn tail_digit_aux<T>(node: &SkewDigit<T>) -> Digit<T> {
+    match node.tree.as_ref() {
+      SkewTree::Leaf(_) => node.next.clone(),
+      SkewTree::Node(_, left, right) => {
+        let weight = node.weight / 2;
+        digit(
+          weight,
+          left.clone(),
+          digit(weight, right.clone(), node.next.clone()),
+        )
+      }
+    }
+  }
+
+  pub fn root_value<T>(tree: &SkewTree<T>) -> &T {
+    match tree {
+      SkewTree::Leaf(value) => value,
+      SkewTree::Node(value, _, _) => value,
+    }
+  }
+
+  pub fn get_tree_aux<T>(tree: &SkewTree<T>, weight: usize, index: usize) -> &T {
+    match tree {
+      SkewTree::Leaf(value) => value,
+      SkewTree::Node(value, left, right) => {
+        let half = weight / 2;
+        if index == 0 {
+          value
+        } else if index <= half {
+          get_tree_aux(left, half, index - 1)
+        } else {
+          get_tree_aux(right, half, index - 1 - half)
+        }
+      }
+    }
+  }
+
+  pub fn get_digit_aux<T>(node: &Digit<T>, index: usize) -> Option<&T> {
+    match node {
+      None => None,
+      Some(inner_node) => {
+        if index < inner_node.weight {
+          Some(get_tree_aux(&inner_node.tree, inner_node.weight, index))
+        } else {
+          get_digit_aux(&inner_node.next, index - inner_node.weight)
+        }
+      }
+    }
+  }
+
+  pub fn update_tree_aux<T>(
+    tree: &SkewTree<T>,
+    weight: usize,
+    index: usize,
+    item: T,
+  ) -> Rc<SkewTree<T>>
+  where
+    T: Copy,
+  {
+    match tree {
+      SkewTree::Leaf(_) => Rc::new(SkewTree::Leaf(item)),
+      SkewTree::Node(value, left, right) => {
+        let half = weight / 2;
+        if index == 0 {
+          Rc::new(SkewTree::Node(item, left.clone(), right.clone()))
+        } else if index <= half {
+          Rc::new(SkewTree::Node(
+            *value,
+            update_tree_aux(left, half, index - 1, item),
+            right.clone(),
+          ))
+        } else {
+          Rc::new(SkewTree::Node(
+            *value,
+            left.clone(),
+            update_tree_aux(right, half, index - 1 - half, item),
+          ))
+        }
+      }
+    }
+  }
+
+  pub fn update_digit_aux<T>(node: &Digit<T>, index: usize, item: T) -> Result<Digit<T>, IndexError>
+  where
+    T: Copy,
+  {
+    match node {
+      None => Err(IndexError::OutOfBounds),
+      Some(inner_node) => {
+        if index < inner_node.weight {
+          Ok(digit(
+            inner_node.weight,
+            update_tree_aux(&inner_node.tree, inner_node.weight, index, item),
+            inner_node.next.clone(),
+          ))
+        } else {
+          let next = update_digit_aux(&inner_node.next, index - inner_node.weight, item)?;
+          Ok(digit(inner_node.weight, inner_node.tree.clone(), next))
+        }
+      }
+    }
+  }
+
+  pub fn len_aux<T>(node: &Digit<T>, acc: usize) -> usize {
+    match node {
+      None => acc,
+      Some(inner_node) => len_aux(&inner_node.next, acc + inner_node.weight),
+    }
+  }
+
+  pub fn any_tree_aux<T>(tree: &SkewTree<T>, f: fn(&T) -> bool) -> bool {
+    match tree {
+      SkewTree::Leaf(value) => f(value),
+      SkewTree::Node(value, left, right) => {
+        f(value) || any_tree_aux(left, f) || any_tree_aux(right, f)
+      }
+    }
+  }
+
+  pub fn all_tree_aux<T>(tree: &SkewTree<T>, f: fn(&T) -> bool) -> bool {
+    match tree {
+      SkewTree::Leaf(value) => f(value),
+      SkewTree::Node(value, left, right) => {
+        f(value) && all_tree_aux(left, f) && all_tree_aux(right, f)
+      }
+    }
+  }
+
+  pub fn find_tree_aux<T>(tree: &SkewTree<T>, f: fn(&T) -> bool) -> Option<T>
+  where
+    T: Copy,
+  {
+    match tree {
+      SkewTree::Leaf(value) => f(value).then_some(*value),
+      SkewTree::Node(value, left, right) => {
+        if f(value) {
+          Some(*value)
+        } else {
+          find_tree_aux(left, f).or_else(|| find_tree_aux(right, f))
+        }
+      }
+    }
+  }
+
+  pub fn map_tree_aux<T, U>(tree: &SkewTree<T>, f: fn(&T) -> U) -> Rc<SkewTree<U>> {
+    match tree {
+      SkewTree::Leaf(value) => Rc::new(SkewTree::Leaf(f(value))),
+      SkewTree::Node(value, left, right) => Rc::new(SkewTree::Node(
+        f(value),
+        map_tree_aux(left, f),
+        map_tree_aux(right, f),
+      )),
+    }
+  }
+
+  pub fn map_digit_aux<T, U>(node: &Digit<T>, f: fn(&T) -> U) -> Digit<U> {
+    match node {
+      None => None,
+      Some(inner_node) => digit(
+        inner_node.weight,
+        map_tree_aux(&inner_node.tree, f),
+        map_digit_aux(&inner_node.next, f),
+      ),
+    }
+  }
+
+  pub fn reduce_tree_aux<T, U>(tree: &SkewTree<T>, f: fn(&T, U) -> U, acc: U) -> U {
+    match tree {
+      SkewTree::Leaf(value) => f(value, acc),
+      SkewTree::Node(value, left, right) => {
+        reduce_tree_aux(right, f, reduce_tree_aux(left, f, f(value, acc)))
+      }
+    }
+  }
+
+  pub fn reduce_digit_aux<T, U>(node: &Digit<T>, f: fn(&T, U) -> U, acc: U) -> U {
+    match node {
+      None => acc,
+      Some(inner_node) => reduce_digit_aux(
+        &inner_node.next,
+        f,
+        reduce_tree_aux(&inner_node.tree, f, acc),
+      ),
+    }
+  }
+}
+
+impl<T> Default for RandomAccessList<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> RandomAccessList<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  pub fn new() -> Self {
+    Self { root: None }
+  }
+
+  pub fn is_empty(list: &Self) -> bool {
+    list.root.is_none()
+  }
+
+  pub fn cons(list: &Self, item: T) -> Self {
+    Self {
+      root: private::cons_digit_aux(&list.root, item),
+    }
+  }
+
+  pub fn head(list: &Self) -> Option<&T> {
+    list
+      .root
+      .as_ref()
+      .map(|inner_node| private::root_value(&inner_node.tree))
+  }
+
+  pub fn tail(list: &Self) -> Option<Self> {
+    list.root.as_ref().map(|inner_node| Self {
+      root: private::tail_digit_aux(inner_node),
+    })
+  }
+
+  pub fn get(list: &Self, index: usize) -> Option<&T> {
+    private::get_digit_aux(&list.root, index)
+  }
+
+  pub fn update(list: &Self, index: usize, item: T) -> Result<Self, IndexError> {
+    Ok(Self {
+      root: private::update_digit_aux(&list.root, index, item)?,
+    })
+  }
+
+  // O(log n): sums the weights of the trees.
+  pub fn len(list: &Self) -> i32 {
+    private::len_aux(&list.root, 0) as i32
+  }
+
+  pub fn any(list: &Self, f: fn(&T) -> bool) -> bool {
+    Self::trees(list)
+      .into_iter()
+      .any(|tree| private::any_tree_aux(tree, f))
+  }
+
+  pub fn all(list: &Self, f: fn(&T) -> bool) -> bool {
+    Self::trees(list)
+      .into_iter()
+      .all(|tree| private::all_tree_aux(tree, f))
+  }
+
+  pub fn find(list: &Self, f: fn(&T) -> bool) -> Option<T> {
+    Self::trees(list)
+      .into_iter()
+      .find_map(|tree| private::find_tree_aux(tree, f))
+  }
+
+  pub fn map<U>(list: &Self, f: fn(&T) -> U) -> RandomAccessList<U>
+  where
+    U: PartialEq + Clone + Copy,
+  {
+    RandomAccessList::<U> {
+      root: private::map_digit_aux(&list.root, f),
+    }
+  }
+
+  pub fn filter(list: &Self, f: fn(&T) -> bool) -> Self {
+    let kept = Self::reduce(
+      list,
+      |item, mut acc: Vec<T>| {
+        acc.push(*item);
+        acc
+      },
+      vec![],
+    );
+    kept
+      .into_iter()
+      .rev()
+      .filter(f)
+      .fold(Self::new(), |acc, item| Self::cons(&acc, item))
+  }
+
+  pub fn reduce<U>(list: &Self, f: fn(&T, U) -> U, acc: U) -> U {
+    private::reduce_digit_aux(&list.root, f, acc)
+  }
+
+  fn trees(list: &Self) -> Vec<&SkewTree<T>> {
+    let mut trees = vec![];
+    let mut node = &list.root;
+    while let Some(inner_node) = node {
+      trees.push(inner_node.tree.as_ref());
+      node = &inner_node.next;
+    }
+    trees
+  }
+}
+
+#[cfg(test)]
+#[path = "./random-access-list_test.rs"]
+mod test;
@@ -0,0 +1,379 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type RandomAccessListT = RandomAccessList<i32>;
+
+  pub fn leaf<T>(value: T) -> Rc<SkewTree<T>> {
+    Rc::new(SkewTree::Leaf(value))
+  }
+
+  pub fn node<T>(value: T, left: Rc<SkewTree<T>>, right: Rc<SkewTree<T>>) -> Rc<SkewTree<T>> {
+    Rc::new(SkewTree::Node(value, left, right))
+  }
+
+  pub fn digit<T>(weight: usize, tree: Rc<SkewTree<T>>, next: Digit<T>) -> Digit<T> {
+    Some(Rc::new(SkewDigit { weight, tree, next }))
+  }
+
+  pub fn random_access_list_empty() -> RandomAccessListT {
+    RandomAccessListT { root: None }
+  }
+
+  pub fn random_access_list_filled() -> RandomAccessListT {
+    RandomAccessListT {
+      root: digit(1, leaf(0), digit(3, node(1, leaf(2), leaf(3)), None)),
+    }
+  }
+
+  pub fn from_range(n: i32) -> RandomAccessListT {
+    (0..n).rev().fold(RandomAccessList::new(), |list, item| {
+      RandomAccessList::cons(&list, item)
+    })
+  }
+
+  pub fn to_vec(list: &RandomAccessListT) -> Vec<i32> {
+    RandomAccessList::reduce(
+      list,
+      |item, mut acc: Vec<i32>| {
+        acc.push(*item);
+        acc
+      },
+      vec![],
+    )
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = RandomAccessList::<i32>::new();
+    let expected = setup::random_access_list_empty();
+    assert_eq!(op, expected)
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    assert!(RandomAccessList::is_empty(&list))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    assert!(!RandomAccessList::is_empty(&list))
+  }
+}
+
+#[cfg(test)]
+mod cons {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    let op = RandomAccessList::cons(&list, 0);
+    let expected = RandomAccessList {
+      root: setup::digit(1, setup::leaf(0), None),
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    let op = RandomAccessList::cons(&list, -1);
+    let expected = RandomAccessList {
+      root: setup::digit(1, setup::leaf(-1), list.root.clone()),
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_linking_trees() {
+    let list = RandomAccessList::cons(&setup::random_access_list_filled(), -1);
+    let op = RandomAccessList::cons(&list, -2);
+    let expected = RandomAccessList {
+      root: setup::digit(
+        3,
+        setup::node(-2, setup::leaf(-1), setup::leaf(0)),
+        setup::digit(3, setup::node(1, setup::leaf(2), setup::leaf(3)), None),
+      ),
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn shares_the_old_version() {
+    let list = setup::random_access_list_filled();
+    let op = RandomAccessList::cons(&list, -1);
+    assert!(Rc::ptr_eq(
+      op.root.as_ref().unwrap().next.as_ref().unwrap(),
+      list.root.as_ref().unwrap()
+    ))
+  }
+}
+
+#[cfg(test)]
+mod head {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    assert_eq!(RandomAccessList::head(&list), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    assert_eq!(RandomAccessList::head(&list), Some(&0))
+  }
+}
+
+#[cfg(test)]
+mod tail {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    assert_eq!(RandomAccessList::tail(&list), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    let op = RandomAccessList::tail(&list);
+    let expected = RandomAccessList {
+      root: setup::digit(3, setup::node(1, setup::leaf(2), setup::leaf(3)), None),
+    };
+    assert_eq!(op, Some(expected))
+  }
+
+  #[test]
+  fn to_filled_splitting_a_tree() {
+    let list = RandomAccessList::tail(&setup::random_access_list_filled()).unwrap();
+    let op = RandomAccessList::tail(&list);
+    let expected = RandomAccessList {
+      root: setup::digit(1, setup::leaf(2), setup::digit(1, setup::leaf(3), None)),
+    };
+    assert_eq!(op, Some(expected))
+  }
+
+  #[test]
+  fn undoes_cons() {
+    let list = setup::from_range(20);
+    let op = RandomAccessList::tail(&RandomAccessList::cons(&list, -1));
+    assert_eq!(op, Some(list))
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    assert_eq!(RandomAccessList::get(&list, 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    let op: Vec<Option<&i32>> = (0..5).map(|i| RandomAccessList::get(&list, i)).collect();
+    assert_eq!(op, vec![Some(&0), Some(&1), Some(&2), Some(&3), None])
+  }
+
+  #[test]
+  fn to_filled_long() {
+    let list = setup::from_range(100);
+    assert!((0..100).all(|i| RandomAccessList::get(&list, i as usize) == Some(&i)));
+    assert_eq!(RandomAccessList::get(&list, 100), None)
+  }
+}
+
+#[cfg(test)]
+mod update {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    let op = RandomAccessList::update(&list, 0, 10);
+    assert_eq!(op, Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    let op = RandomAccessList::update(&list, 3, 30);
+    let expected = RandomAccessList {
+      root: setup::digit(
+        1,
+        setup::leaf(0),
+        setup::digit(3, setup::node(1, setup::leaf(2), setup::leaf(30)), None),
+      ),
+    };
+    assert_eq!(op, Ok(expected))
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let list = setup::random_access_list_filled();
+    let op = RandomAccessList::update(&list, 4, 40);
+    assert_eq!(op, Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled_long() {
+    let list = setup::from_range(100);
+    let op = RandomAccessList::update(&list, 57, -1).unwrap();
+    assert_eq!(RandomAccessList::get(&op, 57), Some(&-1));
+    assert_eq!(RandomAccessList::get(&list, 57), Some(&57));
+    assert_eq!(RandomAccessList::len(&op), 100)
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    assert_eq!(RandomAccessList::len(&list), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    assert_eq!(RandomAccessList::len(&list), 4)
+  }
+}
+
+#[cfg(test)]
+mod any {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    assert!(!RandomAccessList::any(&list, |item| *item == 3))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    assert!(RandomAccessList::any(&list, |item| *item == 3));
+    assert!(!RandomAccessList::any(&list, |item| *item > 3))
+  }
+}
+
+#[cfg(test)]
+mod all {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    assert!(RandomAccessList::all(&list, |item| *item > 0))
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    assert!(RandomAccessList::all(&list, |item| *item >= 0));
+    assert!(!RandomAccessList::all(&list, |item| *item > 0))
+  }
+}
+
+#[cfg(test)]
+mod find {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    assert_eq!(RandomAccessList::find(&list, |item| *item > 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    assert_eq!(RandomAccessList::find(&list, |item| *item > 1), Some(2))
+  }
+}
+
+#[cfg(test)]
+mod map {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    let op = RandomAccessList::map(&list, |item| item * 2);
+    assert_eq!(op, setup::random_access_list_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::random_access_list_filled();
+    let op = RandomAccessList::map(&list, |item| item * 2);
+    assert_eq!(setup::to_vec(&op), vec![0, 2, 4, 6])
+  }
+}
+
+#[cfg(test)]
+mod filter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    let op = RandomAccessList::filter(&list, |item| item % 2 == 0);
+    assert_eq!(op, setup::random_access_list_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::from_range(10);
+    let op = RandomAccessList::filter(&list, |item| item % 2 == 0);
+    assert_eq!(setup::to_vec(&op), vec![0, 2, 4, 6, 8]);
+    assert_eq!(RandomAccessList::get(&op, 3), Some(&6))
+  }
+}
+
+#[cfg(test)]
+mod reduce {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let list = setup::random_access_list_empty();
+    assert_eq!(
+      RandomAccessList::reduce(&list, |item, acc| item + acc, 0),
+      0
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let list = setup::from_range(20);
+    assert_eq!(setup::to_vec(&list), (0..20).collect::<Vec<i32>>());
+    assert_eq!(
+      RandomAccessList::reduce(&list, |item, acc| item + acc, 0),
+      190
+    )
+  }
+}
@@ -0,0 +1,420 @@
+use std::{cell::RefCell, fmt, mem, rc::Rc};
+
+type Thunk<T> = Box<dyn FnOnce() -> StreamCell<T>>;
+
+#[derive(Clone)]
+pub enum StreamCell<T> {
+  Nil,
+  Cons(T, Stream<T>),
+}
+
+enum Suspension<T> {
+  Pending(Thunk<T>),
+  Forcing,
+  // The thunk panicked, so there is no cell to memoize.
+  Poisoned,
+  Forced(StreamCell<T>),
+}
+
+// Invariants:
+//    - a suspension is evaluated at most once, every clone of the stream
+//      sees the memoized cell afterwards
+//    - forcing a stream never forces more than its first cell
+pub struct Stream<T> {
+  node: Rc<RefCell<Suspension<T>>>,
+}
+
+pub struct StreamIter<T> {
+  next: Stream<T>,
+}
+
+mod private {
+  use super::*;
+
+  pub fn suspend<T>(thunk: impl FnOnce() -> StreamCell<T> + 'static) -> Stream<T> {
+    Stream {
+      node: Rc::new(RefCell::new(Suspension::Pending(Box::new(thunk)))),
+    }
+  }
+
+  pub fn forced<T>(cell: StreamCell<T>) -> Stream<T> {
+    Stream {
+      node: Rc::new(RefCell::new(Suspension::Forced(cell))),
+    }
+  }
+
+  pub fn force<T>(stream: &Stream<T>) -> StreamCell<T>
+  where
+    T: Clone,
+  {
+    if let Suspension::Forced(cell) = &*stream.node.borrow() {
+      return cell.clone();
+    }
+    let suspension = mem::replace(&mut *stream.node.borrow_mut(), Suspension::Forcing);
+    let thunk = match suspension {
+      Suspension::Pending(thunk) => thunk,
+      Suspension::Poisoned => {
+        *stream.node.borrow_mut() = Suspension::Poisoned;
+        panic!("stream suspension panicked while being evaluated earlier")
+      }
+      _ => panic!("stream forced itself while being evaluated"),
+    };
+    let guard = PoisonGuard { node: &stream.node };
+    let cell = thunk();
+    mem::forget(guard);
+    *stream.node.borrow_mut() = Suspension::Forced(cell.clone());
+    cell
+  }
+
+  // Only dropped when the thunk unwinds, leaving the suspension poisoned
+  // rather than stuck in `Forcing`.
+  struct PoisonGuard<'a, T> {
+    node: &'a RefCell<Suspension<T>>,
+  }
+
+  impl<T> Drop for PoisonGuard<'_, T> {
+    fn drop(&mut self) {
+      if let Ok(mut node) = self.node.try_borrow_mut() {
+        *node = Suspension::Poisoned;
+      }
+    }
+  }
+
+  // Detaches the tail of a uniquely owned, forced cell so long streams are
+  // dropped in a loop instead of recursively.
+  pub fn take_tail<T>(stream: &Stream<T>) -> Option<Stream<T>> {
+    if Rc::strong_count(&stream.node) != 1 {
+      return None;
+    }
+    let mut node = stream.node.borrow_mut();
+    match &*node {
+      Suspension::Forced(StreamCell::Cons(..)) => {
+        match mem::replace(&mut *node, Suspension::Forced(StreamCell::Nil)) {
+          Suspension::Forced(StreamCell::Cons(_, tail)) => Some(tail),
+          _ => None,
+        }
+      }
+      _ => None,
+    }
+  }
+
+  pub fn take_aux<T>(stream: Stream<T>, count: usize) -> StreamCell<T>
+  where
+    T: Clone + 'static,
+  {
+    if count == 0 {
+      return StreamCell::Nil;
+    }
+    match force(&stream) {
+      StreamCell::Nil => StreamCell::Nil,
+      StreamCell::Cons(head, tail) => {
+        StreamCell::Cons(head, suspend(move || take_aux(tail, count - 1)))
+      }
+    }
+  }
+
+  pub fn skip_aux<T>(stream: Stream<T>, count: usize) -> StreamCell<T>
+  where
+    T: Clone,
+  {
+    let mut cell = force(&stream);
+    for _ in 0..count {
+      cell = match cell {
+        StreamCell::Nil => return StreamCell::Nil,
+        StreamCell::Cons(_, tail) => force(&tail),
+      };
+    }
+    cell
+  }
+
+  pub fn append_aux<T>(s1: Stream<T>, s2: Stream<T>) -> StreamCell<T>
+  where
+    T: Clone + 'static,
+  {
+    match force(&s1) {
+      StreamCell::Nil => force(&s2),
+      StreamCell::Cons(head, tail) => StreamCell::Cons(head, suspend(move || append_aux(tail, s2))),
+    }
+  }
+
+  pub fn reverse_aux<T>(stream: Stream<T>) -> StreamCell<T>
+  where
+    T: Clone,
+  {
+    let mut acc = StreamCell::Nil;
+    let mut cell = force(&stream);
+    while let StreamCell::Cons(head, tail) = cell {
+      cell = force(&tail);
+      acc = StreamCell::Cons(head, forced(acc));
+    }
+    acc
+  }
+
+  pub fn map_aux<T, U>(stream: Stream<T>, f: fn(&T) -> U) -> StreamCell<U>
+  where
+    T: Clone + 'static,
+    U: 'static,
+  {
+    match force(&stream) {
+      StreamCell::Nil => StreamCell::Nil,
+      StreamCell::Cons(head, tail) => StreamCell::Cons(f(&head), suspend(move || map_aux(tail, f))),
+    }
+  }
+
+  // Skips rejected items in a loop, only the next match is forced.
+  pub fn filter_aux<T>(stream: Stream<T>, f: fn(&T) -> bool) -> StreamCell<T>
+  where
+    T: Clone + 'static,
+  {
+    let mut cell = force(&stream);
+    loop {
+      match cell {
+        StreamCell::Nil => return StreamCell::Nil,
+        StreamCell::Cons(head, tail) => {
+          if f(&head) {
+            return StreamCell::Cons(head, suspend(move || filter_aux(tail, f)));
+          }
+          cell = force(&tail);
+        }
+      }
+    }
+  }
+
+  pub fn zip_aux<T, U>(s1: Stream<T>, s2: Stream<U>) -> StreamCell<(T, U)>
+  where
+    T: Clone + 'static,
+    U: Clone + 'static,
+  {
+    match (force(&s1), force(&s2)) {
+      (StreamCell::Cons(h1, t1), StreamCell::Cons(h2, t2)) => {
+        StreamCell::Cons((h1, h2), suspend(move || zip_aux(t1, t2)))
+      }
+      _ => StreamCell::Nil,
+    }
+  }
+
+  pub fn iterate_aux<T>(item: T, f: fn(&T) -> T) -> StreamCell<T>
+  where
+    T: Clone + 'static,
+  {
+    let next = item.clone();
+    StreamCell::Cons(item, suspend(move || iterate_aux(f(&next), f)))
+  }
+
+  pub fn unfold_aux<T, S>(seed: S, f: fn(&S) -> Option<(T, S)>) -> StreamCell<T>
+  where
+    T: Clone + 'static,
+    S: 'static,
+  {
+    match f(&seed) {
+      None => StreamCell::Nil,
+      Some((item, seed)) => StreamCell::Cons(item, suspend(move || unfold_aux(seed, f))),
+    }
+  }
+
+  pub fn cycle_aux<T>(stream: Stream<T>) -> StreamCell<T>
+  where
+    T: Clone + 'static,
+  {
+    if let StreamCell::Nil = force(&stream) {
+      return StreamCell::Nil;
+    }
+    let again = stream.clone();
+    append_aux(stream, suspend(move || cycle_aux(again)))
+  }
+}
+
+impl<T> Clone for Stream<T> {
+  fn clone(&self) -> Self {
+    Self {
+      node: Rc::clone(&self.node),
+    }
+  }
+}
+
+impl<T> Drop for Stream<T> {
+  fn drop(&mut self) {
+    let mut next = private::take_tail(self);
+    while let Some(stream) = next {
+      next = private::take_tail(&stream);
+    }
+  }
+}
+
+// Only prints the cells forced so far.
+impl<T> fmt::Debug for Stream<T>
+where
+  T: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut list = f.debug_list();
+    let mut node = Rc::clone(&self.node);
+    loop {
+      let next = match &*node.borrow() {
+        Suspension::Forced(StreamCell::Cons(head, tail)) => {
+          list.entry(head);
+          Rc::clone(&tail.node)
+        }
+        Suspension::Forced(StreamCell::Nil) => return list.finish(),
+        _ => return list.finish_non_exhaustive(),
+      };
+      node = next;
+    }
+  }
+}
+
+impl<T> Default for Stream<T> {
+  fn default() -> Self {
+    private::forced(StreamCell::Nil)
+  }
+}
+
+impl<T> From<Vec<T>> for Stream<T> {
+  fn from(items: Vec<T>) -> Self {
+    items
+      .into_iter()
+      .rev()
+      .fold(private::forced(StreamCell::Nil), |acc, item| {
+        private::forced(StreamCell::Cons(item, acc))
+      })
+  }
+}
+
+impl<T> Iterator for StreamIter<T>
+where
+  T: Clone,
+{
+  type Item = T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match private::force(&self.next) {
+      StreamCell::Nil => None,
+      StreamCell::Cons(head, tail) => {
+        self.next = tail;
+        Some(head)
+      }
+    }
+  }
+}
+
+impl<T> Stream<T>
+where
+  T: Clone + 'static,
+{
+  pub fn new() -> Self {
+    private::forced(StreamCell::Nil)
+  }
+
+  pub fn cons(item: T, stream: &Self) -> Self {
+    private::forced(StreamCell::Cons(item, stream.clone()))
+  }
+
+  // Evaluates the first cell, memoizing it for every clone of `stream`.
+  pub fn force(stream: &Self) -> StreamCell<T> {
+    private::force(stream)
+  }
+
+  pub fn is_forced(stream: &Self) -> bool {
+    matches!(&*stream.node.borrow(), Suspension::Forced(_))
+  }
+
+  pub fn is_empty(stream: &Self) -> bool {
+    matches!(private::force(stream), StreamCell::Nil)
+  }
+
+  pub fn head(stream: &Self) -> Option<T> {
+    match private::force(stream) {
+      StreamCell::Nil => None,
+      StreamCell::Cons(head, _) => Some(head),
+    }
+  }
+
+  pub fn tail(stream: &Self) -> Option<Self> {
+    match private::force(stream) {
+      StreamCell::Nil => None,
+      StreamCell::Cons(_, tail) => Some(tail),
+    }
+  }
+
+  // `item`, `f(item)`, `f(f(item))`, ...
+  pub fn iterate(item: T, f: fn(&T) -> T) -> Self {
+    private::suspend(move || private::iterate_aux(item, f))
+  }
+
+  pub fn repeat(item: T) -> Self {
+    Self::iterate(item, |item| item.clone())
+  }
+
+  // Yields items from `f` until it returns `None`.
+  pub fn unfold<S>(seed: S, f: fn(&S) -> Option<(T, S)>) -> Self
+  where
+    S: 'static,
+  {
+    private::suspend(move || private::unfold_aux(seed, f))
+  }
+
+  // Repeats `stream` forever, an empty stream stays empty.
+  pub fn cycle(stream: &Self) -> Self {
+    let stream = stream.clone();
+    private::suspend(move || private::cycle_aux(stream))
+  }
+
+  pub fn take(stream: &Self, count: usize) -> Self {
+    let stream = stream.clone();
+    private::suspend(move || private::take_aux(stream, count))
+  }
+
+  pub fn skip(stream: &Self, count: usize) -> Self {
+    let stream = stream.clone();
+    private::suspend(move || private::skip_aux(stream, count))
+  }
+
+  pub fn append(s1: &Self, s2: &Self) -> Self {
+    let (s1, s2) = (s1.clone(), s2.clone());
+    private::suspend(move || private::append_aux(s1, s2))
+  }
+
+  // Monolithic: forcing the result forces all of `stream`, which must be
+  // finite.
+  pub fn reverse(stream: &Self) -> Self {
+    let stream = stream.clone();
+    private::suspend(move || private::reverse_aux(stream))
+  }
+
+  pub fn map<U>(stream: &Self, f: fn(&T) -> U) -> Stream<U>
+  where
+    U: Clone + 'static,
+  {
+    let stream = stream.clone();
+    private::suspend(move || private::map_aux(stream, f))
+  }
+
+  pub fn filter(stream: &Self, f: fn(&T) -> bool) -> Self {
+    let stream = stream.clone();
+    private::suspend(move || private::filter_aux(stream, f))
+  }
+
+  // Stops at the end of the shorter stream.
+  pub fn zip<U>(s1: &Self, s2: &Stream<U>) -> Stream<(T, U)>
+  where
+    U: Clone + 'static,
+  {
+    let (s1, s2) = (s1.clone(), s2.clone());
+    private::suspend(move || private::zip_aux(s1, s2))
+  }
+
+  pub fn iter(stream: &Self) -> StreamIter<T> {
+    StreamIter {
+      next: stream.clone(),
+    }
+  }
+
+  // Forces every cell, `stream` must be finite.
+  pub fn to_vec(stream: &Self) -> Vec<T> {
+    Self::iter(stream).collect()
+  }
+}
+
+#[cfg(test)]
+#[path = "./stream_test.rs"]
+mod test;
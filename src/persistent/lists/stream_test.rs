@@ -0,0 +1,437 @@
+use super::*;
+
+mod setup {
+  use super::*;
+  use std::cell::Cell;
+
+  pub type StreamT = Stream<i32>;
+
+  thread_local! {
+    static CALLS: Cell<usize> = const { Cell::new(0) };
+  }
+
+  pub fn calls() -> usize {
+    CALLS.with(|calls| calls.get())
+  }
+
+  // Successor function that counts how many times it was evaluated.
+  pub fn counted_succ(item: &i32) -> i32 {
+    CALLS.with(|calls| calls.set(calls.get() + 1));
+    item + 1
+  }
+
+  pub fn stream_empty() -> StreamT {
+    Stream::new()
+  }
+
+  pub fn stream_filled() -> StreamT {
+    Stream::from(vec![0, 1, 2, 3])
+  }
+
+  pub fn naturals() -> StreamT {
+    Stream::iterate(0, |item| item + 1)
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = Stream::<i32>::new();
+    assert!(Stream::is_forced(&op));
+    assert!(Stream::is_empty(&op))
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(Stream::is_empty(&setup::stream_empty()))
+  }
+
+  #[test]
+  fn to_filled() {
+    assert!(!Stream::is_empty(&setup::stream_filled()))
+  }
+
+  #[test]
+  fn to_infinite() {
+    assert!(!Stream::is_empty(&setup::naturals()))
+  }
+}
+
+#[cfg(test)]
+mod cons {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = Stream::cons(7, &setup::stream_empty());
+    assert_eq!(Stream::to_vec(&op), vec![7])
+  }
+
+  #[test]
+  fn to_filled() {
+    let stream = setup::stream_filled();
+    let op = Stream::cons(7, &stream);
+    assert_eq!(Stream::to_vec(&op), vec![7, 0, 1, 2, 3]);
+    assert_eq!(Stream::to_vec(&stream), vec![0, 1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod head_tail {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let stream = setup::stream_empty();
+    assert_eq!(Stream::head(&stream), None);
+    assert!(Stream::tail(&stream).is_none())
+  }
+
+  #[test]
+  fn to_filled() {
+    let stream = setup::stream_filled();
+    assert_eq!(Stream::head(&stream), Some(0));
+    let tail = Stream::tail(&stream).unwrap();
+    assert_eq!(Stream::to_vec(&tail), vec![1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod force {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = Stream::force(&setup::stream_empty());
+    assert!(matches!(op, StreamCell::Nil))
+  }
+
+  #[test]
+  fn to_infinite() {
+    let stream = Stream::iterate(0, setup::counted_succ);
+    assert!(!Stream::is_forced(&stream));
+    let tail = match Stream::force(&stream) {
+      StreamCell::Cons(head, tail) => {
+        assert_eq!(head, 0);
+        tail
+      }
+      StreamCell::Nil => unreachable!(),
+    };
+    assert!(Stream::is_forced(&stream));
+    assert!(!Stream::is_forced(&tail));
+    assert_eq!(setup::calls(), 0)
+  }
+
+  #[test]
+  fn memoized_between_versions() {
+    let stream = Stream::iterate(0, setup::counted_succ);
+    let v1 = Stream::map(&stream, |item| item * 2);
+    let v2 = Stream::filter(&stream, |item| item % 2 == 1);
+    assert_eq!(
+      Stream::to_vec(&Stream::take(&stream, 10)),
+      (0..10).collect::<Vec<_>>()
+    );
+    assert_eq!(setup::calls(), 9);
+    assert_eq!(
+      Stream::to_vec(&Stream::take(&v1, 10)),
+      (0..20).step_by(2).collect::<Vec<_>>()
+    );
+    assert_eq!(Stream::to_vec(&Stream::take(&v2, 5)), vec![1, 3, 5, 7, 9]);
+    assert_eq!(
+      Stream::to_vec(&Stream::take(&stream, 10)),
+      (0..10).collect::<Vec<_>>()
+    );
+    assert_eq!(setup::calls(), 9)
+  }
+
+  #[test]
+  #[should_panic(expected = "panicked while being evaluated earlier")]
+  fn to_poisoned() {
+    let stream = Stream::map(&setup::stream_filled(), |item| match item {
+      0 => panic!("thunk failed"),
+      _ => *item,
+    });
+    let op = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+      Stream::force(&stream);
+    }));
+    assert!(op.is_err());
+    assert!(!Stream::is_forced(&stream));
+    Stream::force(&stream);
+  }
+}
+
+#[cfg(test)]
+mod iterate {
+  use super::*;
+
+  #[test]
+  fn to_infinite() {
+    let op = Stream::iterate(1, |item| item * 2);
+    assert_eq!(Stream::to_vec(&Stream::take(&op, 5)), vec![1, 2, 4, 8, 16])
+  }
+
+  #[test]
+  fn to_infinite_is_lazy() {
+    let op = Stream::iterate(0, setup::counted_succ);
+    assert_eq!(setup::calls(), 0);
+    assert_eq!(Stream::head(&op), Some(0));
+    assert_eq!(setup::calls(), 0)
+  }
+}
+
+#[cfg(test)]
+mod repeat {
+  use super::*;
+
+  #[test]
+  fn to_infinite() {
+    let op = Stream::repeat('x');
+    assert_eq!(Stream::to_vec(&Stream::take(&op, 3)), vec!['x', 'x', 'x'])
+  }
+}
+
+#[cfg(test)]
+mod unfold {
+  use super::*;
+
+  #[test]
+  fn to_finite() {
+    let op = Stream::unfold(3, |n| if *n > 0 { Some((*n, n - 1)) } else { None });
+    assert_eq!(Stream::to_vec(&op), vec![3, 2, 1])
+  }
+
+  #[test]
+  fn to_infinite() {
+    let op = Stream::unfold((0u64, 1u64), |(a, b)| Some((*a, (*b, a + b))));
+    let expected = vec![0, 1, 1, 2, 3, 5, 8, 13];
+    assert_eq!(Stream::to_vec(&Stream::take(&op, 8)), expected)
+  }
+}
+
+#[cfg(test)]
+mod cycle {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = Stream::cycle(&setup::stream_empty());
+    assert!(Stream::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let stream = Stream::from(vec![1, 2, 3]);
+    let op = Stream::cycle(&stream);
+    assert_eq!(
+      Stream::to_vec(&Stream::take(&op, 7)),
+      vec![1, 2, 3, 1, 2, 3, 1]
+    )
+  }
+}
+
+#[cfg(test)]
+mod take {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = Stream::take(&setup::stream_empty(), 3);
+    assert!(Stream::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let stream = setup::stream_filled();
+    assert_eq!(Stream::to_vec(&Stream::take(&stream, 2)), vec![0, 1]);
+    assert_eq!(Stream::to_vec(&Stream::take(&stream, 9)), vec![0, 1, 2, 3]);
+    assert!(Stream::is_empty(&Stream::take(&stream, 0)))
+  }
+
+  #[test]
+  fn to_infinite_is_lazy() {
+    let stream = Stream::iterate(0, setup::counted_succ);
+    let op = Stream::take(&stream, 1000);
+    assert!(!Stream::is_forced(&op));
+    assert_eq!(Stream::to_vec(&Stream::take(&op, 3)), vec![0, 1, 2]);
+    assert_eq!(setup::calls(), 2)
+  }
+}
+
+#[cfg(test)]
+mod skip {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = Stream::skip(&setup::stream_empty(), 3);
+    assert!(Stream::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let stream = setup::stream_filled();
+    assert_eq!(Stream::to_vec(&Stream::skip(&stream, 1)), vec![1, 2, 3]);
+    assert!(Stream::is_empty(&Stream::skip(&stream, 9)))
+  }
+
+  #[test]
+  fn to_infinite() {
+    let stream = setup::naturals();
+    let op = Stream::skip(&stream, 100_000);
+    assert!(!Stream::is_forced(&op));
+    assert_eq!(Stream::head(&op), Some(100_000))
+  }
+}
+
+#[cfg(test)]
+mod append {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let stream = setup::stream_empty();
+    let op = Stream::append(&stream, &setup::stream_filled());
+    assert_eq!(Stream::to_vec(&op), vec![0, 1, 2, 3])
+  }
+
+  #[test]
+  fn to_filled() {
+    let stream = setup::stream_filled();
+    let op = Stream::append(&stream, &setup::naturals());
+    assert_eq!(
+      Stream::to_vec(&Stream::take(&op, 6)),
+      vec![0, 1, 2, 3, 0, 1]
+    )
+  }
+
+  #[test]
+  fn to_infinite() {
+    let op = Stream::append(&setup::naturals(), &setup::stream_filled());
+    assert_eq!(Stream::to_vec(&Stream::take(&op, 3)), vec![0, 1, 2])
+  }
+}
+
+#[cfg(test)]
+mod reverse {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = Stream::reverse(&setup::stream_empty());
+    assert!(Stream::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let op = Stream::reverse(&setup::stream_filled());
+    assert!(!Stream::is_forced(&op));
+    assert_eq!(Stream::to_vec(&op), vec![3, 2, 1, 0])
+  }
+}
+
+#[cfg(test)]
+mod map {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = Stream::map(&setup::stream_empty(), |item| item * 2);
+    assert!(Stream::is_empty(&op))
+  }
+
+  #[test]
+  fn to_infinite() {
+    let op = Stream::map(&setup::naturals(), |item| item.to_string());
+    assert_eq!(Stream::to_vec(&Stream::take(&op, 3)), vec!["0", "1", "2"])
+  }
+}
+
+#[cfg(test)]
+mod filter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = Stream::filter(&setup::stream_empty(), |_| true);
+    assert!(Stream::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let op = Stream::filter(&setup::stream_filled(), |item| item > &3);
+    assert!(Stream::is_empty(&op))
+  }
+
+  #[test]
+  fn to_infinite() {
+    let op = Stream::filter(&setup::naturals(), |item| item % 3 == 0);
+    assert_eq!(Stream::to_vec(&Stream::take(&op, 4)), vec![0, 3, 6, 9])
+  }
+}
+
+#[cfg(test)]
+mod zip {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = Stream::zip(&setup::stream_empty(), &setup::naturals());
+    assert!(Stream::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let op = Stream::zip(&Stream::repeat('x'), &setup::stream_filled());
+    let expected = vec![('x', 0), ('x', 1), ('x', 2), ('x', 3)];
+    assert_eq!(Stream::to_vec(&op), expected)
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_infinite() {
+    let op: i32 = Stream::iter(&setup::naturals()).take(5).sum();
+    assert_eq!(op, 10)
+  }
+}
+
+#[cfg(test)]
+mod fmt {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let stream = setup::stream_filled();
+    assert_eq!(format!("{:?}", stream), "[0, 1, 2, 3]")
+  }
+
+  #[test]
+  fn to_partially_forced() {
+    let stream = setup::naturals();
+    Stream::to_vec(&Stream::take(&stream, 2));
+    assert_eq!(format!("{:?}", stream), "[0, 1, ..]")
+  }
+}
+
+#[cfg(test)]
+mod drop_long {
+  use super::*;
+
+  #[test]
+  fn to_forced() {
+    let stream = setup::naturals();
+    assert_eq!(Stream::iter(&stream).nth(500_000), Some(500_000));
+    std::mem::drop(stream)
+  }
+}
@@ -0,0 +1,399 @@
+use std::{
+  collections::hash_map::RandomState,
+  fmt,
+  hash::{BuildHasher, Hash},
+  rc::Rc,
+};
+
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum HamtNode<K, V> {
+  Branch {
+    bitmap: u32,
+    children: Vec<Rc<HamtNode<K, V>>>,
+  },
+  Leaf {
+    hash: u64,
+    key: K,
+    value: V,
+  },
+  Collision {
+    hash: u64,
+    entries: Vec<(K, V)>,
+  },
+}
+
+// Invariants:
+//    - a branch stores one child per bit set in its bitmap, in bit order
+//    - only the root branch may be empty, and no other branch holds a lone
+//      leaf or collision child
+//    - a collision holds at least two entries, all sharing the same hash
+#[derive(Clone)]
+pub struct HashTrieMap<K, V, S = RandomState> {
+  root: Rc<HamtNode<K, V>>,
+  len: i32,
+  hasher: S,
+}
+
+pub struct HashTrieIter<'a, K, V> {
+  stack: Vec<&'a HamtNode<K, V>>,
+  entries: std::slice::Iter<'a, (K, V)>,
+}
+
+enum Removal<K, V> {
+  NotFound,
+  Removed(Option<Rc<HamtNode<K, V>>>),
+}
+
+mod private {
+  use super::*;
+
+  pub fn empty<K, V>() -> Rc<HamtNode<K, V>> {
+    Rc::new(HamtNode::Branch {
+      bitmap: 0,
+      children: vec![],
+    })
+  }
+
+  pub fn slot(hash: u64, shift: u32) -> u32 {
+    1 << ((hash >> shift) & MASK)
+  }
+
+  pub fn position(bitmap: u32, bit: u32) -> usize {
+    (bitmap & (bit - 1)).count_ones() as usize
+  }
+
+  pub fn node_hash<K, V>(node: &HamtNode<K, V>) -> u64 {
+    match node {
+      HamtNode::Leaf { hash, .. } | HamtNode::Collision { hash, .. } => *hash,
+      HamtNode::Branch { .. } => unreachable!(),
+    }
+  }
+
+  // Branches out until the hashes of `n1` and `n2` land on different slots.
+  pub fn fork<K, V>(
+    n1: Rc<HamtNode<K, V>>,
+    n2: Rc<HamtNode<K, V>>,
+    shift: u32,
+  ) -> Rc<HamtNode<K, V>> {
+    let (b1, b2) = (slot(node_hash(&n1), shift), slot(node_hash(&n2), shift));
+    let (bitmap, children) = if b1 == b2 {
+      (b1, vec![fork(n1, n2, shift + BITS)])
+    } else if b1 < b2 {
+      (b1 | b2, vec![n1, n2])
+    } else {
+      (b1 | b2, vec![n2, n1])
+    };
+    Rc::new(HamtNode::Branch { bitmap, children })
+  }
+
+  pub fn get_node_aux<'a, K, V>(
+    node: &'a HamtNode<K, V>,
+    hash: u64,
+    shift: u32,
+    key: &K,
+  ) -> Option<&'a V>
+  where
+    K: Eq,
+  {
+    match node {
+      HamtNode::Branch { bitmap, children } => {
+        let bit = slot(hash, shift);
+        if bitmap & bit == 0 {
+          return None;
+        }
+        get_node_aux(&children[position(*bitmap, bit)], hash, shift + BITS, key)
+      }
+      HamtNode::Leaf {
+        hash: h,
+        key: k,
+        value,
+      } => (*h == hash && k == key).then_some(value),
+      HamtNode::Collision { hash: h, entries } => {
+        if *h != hash {
+          return None;
+        }
+        entries
+          .iter()
+          .find(|(k, _)| k == key)
+          .map(|(_, value)| value)
+      }
+    }
+  }
+
+  // Returns the new node and whether `key` was not in the trie before.
+  pub fn insert_node_aux<K, V>(
+    node: &Rc<HamtNode<K, V>>,
+    hash: u64,
+    shift: u32,
+    key: K,
+    value: V,
+  ) -> (Rc<HamtNode<K, V>>, bool)
+  where
+    K: Eq + Clone,
+    V: Clone,
+  {
+    match &**node {
+      HamtNode::Branch { bitmap, children } => {
+        let bit = slot(hash, shift);
+        let index = position(*bitmap, bit);
+        let mut children = children.clone();
+        let added = if bitmap & bit == 0 {
+          children.insert(index, Rc::new(HamtNode::Leaf { hash, key, value }));
+          true
+        } else {
+          let (child, added) = insert_node_aux(&children[index], hash, shift + BITS, key, value);
+          children[index] = child;
+          added
+        };
+        let node = HamtNode::Branch {
+          bitmap: bitmap | bit,
+          children,
+        };
+        (Rc::new(node), added)
+      }
+      HamtNode::Leaf {
+        hash: h,
+        key: k,
+        value: v,
+      } => {
+        if *h != hash {
+          let leaf = Rc::new(HamtNode::Leaf { hash, key, value });
+          (fork(node.clone(), leaf, shift), true)
+        } else if *k == key {
+          (Rc::new(HamtNode::Leaf { hash, key, value }), false)
+        } else {
+          let entries = vec![(k.clone(), v.clone()), (key, value)];
+          (Rc::new(HamtNode::Collision { hash, entries }), true)
+        }
+      }
+      HamtNode::Collision { hash: h, entries } => {
+        if *h != hash {
+          let leaf = Rc::new(HamtNode::Leaf { hash, key, value });
+          return (fork(node.clone(), leaf, shift), true);
+        }
+        let mut entries = entries.clone();
+        let added = match entries.iter().position(|(k, _)| k == &key) {
+          Some(index) => {
+            entries[index] = (key, value);
+            false
+          }
+          None => {
+            entries.push((key, value));
+            true
+          }
+        };
+        (Rc::new(HamtNode::Collision { hash, entries }), added)
+      }
+    }
+  }
+
+  pub fn remove_node_aux<K, V>(
+    node: &Rc<HamtNode<K, V>>,
+    hash: u64,
+    shift: u32,
+    key: &K,
+  ) -> Removal<K, V>
+  where
+    K: Eq + Clone,
+    V: Clone,
+  {
+    match &**node {
+      HamtNode::Branch { bitmap, children } => {
+        let bit = slot(hash, shift);
+        if bitmap & bit == 0 {
+          return Removal::NotFound;
+        }
+        let index = position(*bitmap, bit);
+        let child = match remove_node_aux(&children[index], hash, shift + BITS, key) {
+          Removal::NotFound => return Removal::NotFound,
+          Removal::Removed(child) => child,
+        };
+        let mut children = children.clone();
+        let bitmap = match child {
+          Some(child) => {
+            children[index] = child;
+            *bitmap
+          }
+          None => {
+            children.remove(index);
+            bitmap & !bit
+          }
+        };
+        match children.as_slice() {
+          [] => Removal::Removed(None),
+          [child] if !matches!(**child, HamtNode::Branch { .. }) => {
+            Removal::Removed(Some(child.clone()))
+          }
+          _ => Removal::Removed(Some(Rc::new(HamtNode::Branch { bitmap, children }))),
+        }
+      }
+      HamtNode::Leaf {
+        hash: h, key: k, ..
+      } => {
+        if *h == hash && k == key {
+          Removal::Removed(None)
+        } else {
+          Removal::NotFound
+        }
+      }
+      HamtNode::Collision { hash: h, entries } => {
+        let index = match entries.iter().position(|(k, _)| k == key) {
+          Some(index) if *h == hash => index,
+          _ => return Removal::NotFound,
+        };
+        let mut entries = entries.clone();
+        entries.remove(index);
+        if entries.len() == 1 {
+          let (key, value) = entries.pop().unwrap();
+          Removal::Removed(Some(Rc::new(HamtNode::Leaf { hash, key, value })))
+        } else {
+          Removal::Removed(Some(Rc::new(HamtNode::Collision { hash, entries })))
+        }
+      }
+    }
+  }
+}
+
+impl<'a, K, V> Iterator for HashTrieIter<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some((key, value)) = self.entries.next() {
+        return Some((key, value));
+      }
+      match self.stack.pop()? {
+        HamtNode::Branch { children, .. } => {
+          self
+            .stack
+            .extend(children.iter().rev().map(|child| &**child));
+        }
+        HamtNode::Leaf { key, value, .. } => return Some((key, value)),
+        HamtNode::Collision { entries, .. } => self.entries = entries.iter(),
+      }
+    }
+  }
+}
+
+impl<K, V> HashTrieMap<K, V, RandomState>
+where
+  K: Hash + Eq + Clone,
+  V: Clone,
+{
+  pub fn new() -> Self {
+    Self::with_hasher(RandomState::new())
+  }
+}
+
+impl<K, V> Default for HashTrieMap<K, V, RandomState>
+where
+  K: Hash + Eq + Clone,
+  V: Clone,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K, V, S> HashTrieMap<K, V, S>
+where
+  K: Hash + Eq + Clone,
+  V: Clone,
+  S: BuildHasher + Clone,
+{
+  pub fn with_hasher(hasher: S) -> Self {
+    Self {
+      root: private::empty(),
+      len: 0,
+      hasher,
+    }
+  }
+
+  pub fn is_empty(map: &Self) -> bool {
+    map.len == 0
+  }
+
+  pub fn len(map: &Self) -> i32 {
+    map.len
+  }
+
+  pub fn get<'a>(map: &'a Self, key: &K) -> Option<&'a V> {
+    private::get_node_aux(&map.root, map.hasher.hash_one(key), 0, key)
+  }
+
+  pub fn contains_key(map: &Self, key: &K) -> bool {
+    Self::get(map, key).is_some()
+  }
+
+  // Replaces the value when `key` is already present.
+  pub fn insert(map: &Self, key: K, value: V) -> Self {
+    let hash = map.hasher.hash_one(&key);
+    let (root, added) = private::insert_node_aux(&map.root, hash, 0, key, value);
+    Self {
+      root,
+      len: map.len + added as i32,
+      hasher: map.hasher.clone(),
+    }
+  }
+
+  // Returns an unchanged copy when `key` is missing.
+  pub fn remove(map: &Self, key: &K) -> Self {
+    let hash = map.hasher.hash_one(key);
+    match private::remove_node_aux(&map.root, hash, 0, key) {
+      Removal::NotFound => map.clone(),
+      Removal::Removed(root) => {
+        let root = match root {
+          Some(root) if matches!(*root, HamtNode::Branch { .. }) => root,
+          Some(root) => Rc::new(HamtNode::Branch {
+            bitmap: private::slot(private::node_hash(&root), 0),
+            children: vec![root],
+          }),
+          None => private::empty(),
+        };
+        Self {
+          root,
+          len: map.len - 1,
+          hasher: map.hasher.clone(),
+        }
+      }
+    }
+  }
+
+  // Visits entries in hash order, which is unspecified.
+  pub fn iter(map: &Self) -> HashTrieIter<'_, K, V> {
+    HashTrieIter {
+      stack: vec![&map.root],
+      entries: [].iter(),
+    }
+  }
+}
+
+impl<K, V, S> PartialEq for HashTrieMap<K, V, S>
+where
+  K: Hash + Eq + Clone,
+  V: Clone + PartialEq,
+  S: BuildHasher + Clone,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.len == other.len
+      && Self::iter(self).all(|(key, value)| Self::get(other, key) == Some(value))
+  }
+}
+
+impl<K, V, S> fmt::Debug for HashTrieMap<K, V, S>
+where
+  K: Hash + Eq + Clone + fmt::Debug,
+  V: Clone + fmt::Debug,
+  S: BuildHasher + Clone,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_map().entries(Self::iter(self)).finish()
+  }
+}
+
+#[cfg(test)]
+#[path = "./hash-trie-map_test.rs"]
+mod test;
@@ -0,0 +1,334 @@
+use super::*;
+
+mod setup {
+  use super::*;
+  use std::hash::{BuildHasherDefault, Hasher};
+
+  // Hashes non-negative integers to themselves, so tests can pick slots.
+  #[derive(Default)]
+  pub struct IdentityHasher(u64);
+
+  impl Hasher for IdentityHasher {
+    fn finish(&self) -> u64 {
+      self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+      for (index, byte) in bytes.iter().enumerate() {
+        self.0 |= (*byte as u64) << (8 * index);
+      }
+    }
+  }
+
+  // Only keeps the two lowest bits, so most keys collide.
+  #[derive(Default)]
+  pub struct CollidingHasher(IdentityHasher);
+
+  impl Hasher for CollidingHasher {
+    fn finish(&self) -> u64 {
+      self.0.finish() & 0b11
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+      self.0.write(bytes)
+    }
+  }
+
+  pub type Identity = BuildHasherDefault<IdentityHasher>;
+  pub type Colliding = BuildHasherDefault<CollidingHasher>;
+  pub type HashTrieMapT = HashTrieMap<i32, char, Identity>;
+
+  pub fn hash_map_empty() -> HashTrieMapT {
+    HashTrieMap::with_hasher(Identity::default())
+  }
+
+  pub fn hash_map_filled() -> HashTrieMapT {
+    ['a', 'b', 'c', 'd']
+      .iter()
+      .enumerate()
+      .fold(hash_map_empty(), |acc, (key, value)| {
+        HashTrieMap::insert(&acc, key as i32, *value)
+      })
+  }
+
+  pub fn children<K, V>(node: &HamtNode<K, V>) -> &Vec<Rc<HamtNode<K, V>>> {
+    match node {
+      HamtNode::Branch { children, .. } => children,
+      _ => panic!("not a branch"),
+    }
+  }
+
+  fn check_node_aux<K, V>(node: &HamtNode<K, V>, is_root: bool) -> i32 {
+    match node {
+      HamtNode::Branch { bitmap, children } => {
+        assert_eq!(bitmap.count_ones() as usize, children.len());
+        if !is_root {
+          assert!(!children.is_empty());
+          if let [child] = children.as_slice() {
+            assert!(matches!(**child, HamtNode::Branch { .. }));
+          }
+        }
+        children
+          .iter()
+          .map(|child| check_node_aux(child, false))
+          .sum()
+      }
+      HamtNode::Leaf { .. } => 1,
+      HamtNode::Collision { entries, .. } => {
+        assert!(entries.len() >= 2);
+        entries.len() as i32
+      }
+    }
+  }
+
+  pub fn check<K, V, S>(map: &HashTrieMap<K, V, S>) {
+    assert_eq!(check_node_aux(&map.root, true), map.len);
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = HashTrieMap::<i32, char>::new();
+    assert!(HashTrieMap::is_empty(&op));
+    assert_eq!(*op.root, *private::empty())
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(HashTrieMap::is_empty(&setup::hash_map_empty()))
+  }
+
+  #[test]
+  fn to_filled() {
+    assert!(!HashTrieMap::is_empty(&setup::hash_map_filled()))
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(HashTrieMap::len(&setup::hash_map_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(HashTrieMap::len(&setup::hash_map_filled()), 4)
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(HashTrieMap::get(&setup::hash_map_empty(), &0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let map = setup::hash_map_filled();
+    assert_eq!(HashTrieMap::get(&map, &2), Some(&'c'));
+    assert_eq!(HashTrieMap::get(&map, &4), None);
+    assert!(HashTrieMap::contains_key(&map, &0));
+    assert!(!HashTrieMap::contains_key(&map, &32))
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let map = setup::hash_map_empty();
+    let op = HashTrieMap::insert(&map, 1, 'a');
+    assert_eq!(HashTrieMap::get(&op, &1), Some(&'a'));
+    assert_eq!(HashTrieMap::len(&op), 1);
+    assert!(HashTrieMap::is_empty(&map))
+  }
+
+  #[test]
+  fn to_filled_replacing() {
+    let map = setup::hash_map_filled();
+    let op = HashTrieMap::insert(&map, 1, 'z');
+    assert_eq!(HashTrieMap::get(&op, &1), Some(&'z'));
+    assert_eq!(HashTrieMap::get(&map, &1), Some(&'b'));
+    assert_eq!(HashTrieMap::len(&op), 4)
+  }
+
+  #[test]
+  fn to_filled_on_taken_slot() {
+    // 1, 33 and 1057 share their lowest five bits, 1 and 1057 the next five.
+    let map = setup::hash_map_filled();
+    let op = HashTrieMap::insert(&map, 33, 'x');
+    let op = HashTrieMap::insert(&op, 1057, 'y');
+    setup::check(&op);
+    assert_eq!(HashTrieMap::get(&op, &1), Some(&'b'));
+    assert_eq!(HashTrieMap::get(&op, &33), Some(&'x'));
+    assert_eq!(HashTrieMap::get(&op, &1057), Some(&'y'));
+    assert_eq!(HashTrieMap::len(&op), 6)
+  }
+
+  #[test]
+  fn shares_untouched_nodes() {
+    let map = HashTrieMap::insert(&setup::hash_map_filled(), 33, 'x');
+    let op = HashTrieMap::insert(&map, 65, 'y');
+    let (old, new) = (setup::children(&map.root), setup::children(&op.root));
+    assert!(Rc::ptr_eq(&old[0], &new[0]));
+    assert!(Rc::ptr_eq(&old[2], &new[2]));
+    assert!(!Rc::ptr_eq(&old[1], &new[1]))
+  }
+
+  #[test]
+  fn to_colliding_keys() {
+    let map = HashTrieMap::<i32, i32, setup::Colliding>::with_hasher(Default::default());
+    let op = (0..20).fold(map, |acc, key| HashTrieMap::insert(&acc, key, key * 10));
+    setup::check(&op);
+    assert_eq!(HashTrieMap::len(&op), 20);
+    assert!((0..20).all(|key| HashTrieMap::get(&op, &key) == Some(&(key * 10))));
+    let op = HashTrieMap::insert(&op, 7, 0);
+    assert_eq!(HashTrieMap::get(&op, &7), Some(&0));
+    assert_eq!(HashTrieMap::len(&op), 20)
+  }
+
+  #[test]
+  fn to_string_keys() {
+    let map = HashTrieMap::<String, i32>::new();
+    let op = (0..1000).fold(map, |acc, key| {
+      HashTrieMap::insert(&acc, format!("key-{key}"), key)
+    });
+    setup::check(&op);
+    assert_eq!(HashTrieMap::len(&op), 1000);
+    assert_eq!(HashTrieMap::get(&op, &String::from("key-777")), Some(&777))
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let map = setup::hash_map_empty();
+    let op = HashTrieMap::remove(&map, &1);
+    assert!(HashTrieMap::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let map = setup::hash_map_filled();
+    let op = HashTrieMap::remove(&map, &1);
+    setup::check(&op);
+    assert_eq!(HashTrieMap::get(&op, &1), None);
+    assert_eq!(HashTrieMap::len(&op), 3);
+    assert_eq!(HashTrieMap::get(&map, &1), Some(&'b'))
+  }
+
+  #[test]
+  fn to_filled_without_found_key() {
+    let map = setup::hash_map_filled();
+    let op = HashTrieMap::remove(&map, &33);
+    assert!(Rc::ptr_eq(&op.root, &map.root));
+    assert_eq!(HashTrieMap::len(&op), 4)
+  }
+
+  #[test]
+  fn to_filled_collapsing_branches() {
+    let map = HashTrieMap::insert(&setup::hash_map_filled(), 1057, 'y');
+    let op = HashTrieMap::remove(&map, &1);
+    setup::check(&op);
+    assert!(matches!(
+      *setup::children(&op.root)[1],
+      HamtNode::Leaf { .. }
+    ));
+    assert_eq!(HashTrieMap::get(&op, &1057), Some(&'y'))
+  }
+
+  #[test]
+  fn to_filled_everything() {
+    let map = setup::hash_map_filled();
+    let op = (0..4).fold(map, |acc, key| HashTrieMap::remove(&acc, &key));
+    setup::check(&op);
+    assert!(HashTrieMap::is_empty(&op));
+    assert_eq!(op.root, private::empty())
+  }
+
+  #[test]
+  fn to_colliding_keys() {
+    let map = HashTrieMap::<i32, i32, setup::Colliding>::with_hasher(Default::default());
+    let map = (0..20).fold(map, |acc, key| HashTrieMap::insert(&acc, key, key));
+    let op = (0..20)
+      .filter(|key| key % 3 != 0)
+      .fold(map, |acc, key| HashTrieMap::remove(&acc, &key));
+    setup::check(&op);
+    assert_eq!(HashTrieMap::len(&op), 7);
+    assert!((0..20).all(|key| HashTrieMap::contains_key(&op, &key) == (key % 3 == 0)))
+  }
+
+  #[test]
+  fn to_random_keys() {
+    let map = HashTrieMap::<i32, i32>::new();
+    let map = (0..5000).fold(map, |acc, key| HashTrieMap::insert(&acc, key, key));
+    let op = (0..5000)
+      .step_by(2)
+      .fold(map, |acc, key| HashTrieMap::remove(&acc, &key));
+    setup::check(&op);
+    assert_eq!(HashTrieMap::len(&op), 2500);
+    assert!((0..5000).all(|key| HashTrieMap::contains_key(&op, &key) == (key % 2 == 1)))
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(HashTrieMap::iter(&setup::hash_map_empty()).count(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let map = setup::hash_map_filled();
+    let op: Vec<(i32, char)> = HashTrieMap::iter(&map).map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(op, vec![(0, 'a'), (1, 'b'), (2, 'c'), (3, 'd')])
+  }
+
+  #[test]
+  fn to_colliding_keys() {
+    let map = HashTrieMap::<i32, i32, setup::Colliding>::with_hasher(Default::default());
+    let map = (0..20).fold(map, |acc, key| HashTrieMap::insert(&acc, key, key));
+    let mut op: Vec<i32> = HashTrieMap::iter(&map).map(|(k, _)| *k).collect();
+    op.sort();
+    assert_eq!(op, (0..20).collect::<Vec<_>>())
+  }
+}
+
+#[cfg(test)]
+mod eq {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let map = setup::hash_map_filled();
+    let other = (0..4).rev().fold(setup::hash_map_empty(), |acc, key| {
+      HashTrieMap::insert(&acc, key, HashTrieMap::get(&map, &key).copied().unwrap())
+    });
+    assert_eq!(map, other);
+    assert_ne!(map, HashTrieMap::insert(&other, 0, 'z'));
+    assert_ne!(map, HashTrieMap::remove(&other, &0))
+  }
+}
@@ -0,0 +1,110 @@
+use std::{
+  collections::hash_map::RandomState,
+  fmt,
+  hash::{BuildHasher, Hash},
+};
+
+use super::{HashTrieIter, HashTrieMap};
+
+#[derive(Clone)]
+pub struct HashTrieSet<T, S = RandomState> {
+  map: HashTrieMap<T, (), S>,
+}
+
+pub struct HashTrieSetIter<'a, T> {
+  entries: HashTrieIter<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for HashTrieSetIter<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.entries.next().map(|(item, _)| item)
+  }
+}
+
+impl<T> HashTrieSet<T, RandomState>
+where
+  T: Hash + Eq + Clone,
+{
+  pub fn new() -> Self {
+    Self {
+      map: HashTrieMap::new(),
+    }
+  }
+}
+
+impl<T> Default for HashTrieSet<T, RandomState>
+where
+  T: Hash + Eq + Clone,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, S> HashTrieSet<T, S>
+where
+  T: Hash + Eq + Clone,
+  S: BuildHasher + Clone,
+{
+  pub fn with_hasher(hasher: S) -> Self {
+    Self {
+      map: HashTrieMap::with_hasher(hasher),
+    }
+  }
+
+  pub fn is_empty(set: &Self) -> bool {
+    HashTrieMap::is_empty(&set.map)
+  }
+
+  pub fn len(set: &Self) -> i32 {
+    HashTrieMap::len(&set.map)
+  }
+
+  pub fn contains(set: &Self, item: &T) -> bool {
+    HashTrieMap::contains_key(&set.map, item)
+  }
+
+  pub fn insert(set: &Self, item: T) -> Self {
+    Self {
+      map: HashTrieMap::insert(&set.map, item, ()),
+    }
+  }
+
+  pub fn remove(set: &Self, item: &T) -> Self {
+    Self {
+      map: HashTrieMap::remove(&set.map, item),
+    }
+  }
+
+  pub fn iter(set: &Self) -> HashTrieSetIter<'_, T> {
+    HashTrieSetIter {
+      entries: HashTrieMap::iter(&set.map),
+    }
+  }
+}
+
+impl<T, S> PartialEq for HashTrieSet<T, S>
+where
+  T: Hash + Eq + Clone,
+  S: BuildHasher + Clone,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.map == other.map
+  }
+}
+
+impl<T, S> fmt::Debug for HashTrieSet<T, S>
+where
+  T: Hash + Eq + Clone + fmt::Debug,
+  S: BuildHasher + Clone,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_set().entries(Self::iter(self)).finish()
+  }
+}
+
+#[cfg(test)]
+#[path = "./hash-trie-set_test.rs"]
+mod test;
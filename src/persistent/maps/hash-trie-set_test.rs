@@ -0,0 +1,119 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type HashTrieSetT = HashTrieSet<i32>;
+
+  pub fn hash_set_empty() -> HashTrieSetT {
+    HashTrieSet::new()
+  }
+
+  pub fn hash_set_filled() -> HashTrieSetT {
+    (0..4).fold(hash_set_empty(), |acc, item| {
+      HashTrieSet::insert(&acc, item)
+    })
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = HashTrieSet::<i32>::new();
+    assert!(HashTrieSet::is_empty(&op))
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(HashTrieSet::len(&setup::hash_set_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(HashTrieSet::len(&setup::hash_set_filled()), 4)
+  }
+}
+
+#[cfg(test)]
+mod contains {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(!HashTrieSet::contains(&setup::hash_set_empty(), &0))
+  }
+
+  #[test]
+  fn to_filled() {
+    let set = setup::hash_set_filled();
+    assert!(HashTrieSet::contains(&set, &3));
+    assert!(!HashTrieSet::contains(&set, &4))
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let set = setup::hash_set_empty();
+    let op = HashTrieSet::insert(&set, 7);
+    assert!(HashTrieSet::contains(&op, &7));
+    assert!(HashTrieSet::is_empty(&set))
+  }
+
+  #[test]
+  fn to_filled_with_present_item() {
+    let set = setup::hash_set_filled();
+    let op = HashTrieSet::insert(&set, 2);
+    assert_eq!(HashTrieSet::len(&op), 4);
+    assert_eq!(op, set)
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = HashTrieSet::remove(&setup::hash_set_empty(), &0);
+    assert!(HashTrieSet::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled() {
+    let set = setup::hash_set_filled();
+    let op = HashTrieSet::remove(&set, &2);
+    assert!(!HashTrieSet::contains(&op, &2));
+    assert_eq!(HashTrieSet::len(&op), 3);
+    assert!(HashTrieSet::contains(&set, &2))
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(HashTrieSet::iter(&setup::hash_set_empty()).count(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let set = setup::hash_set_filled();
+    let mut op: Vec<i32> = HashTrieSet::iter(&set).copied().collect();
+    op.sort();
+    assert_eq!(op, vec![0, 1, 2, 3])
+  }
+}
@@ -0,0 +1,7 @@
+#[path = "./hash-trie-map.rs"]
+pub mod hash_trie_map;
+pub use hash_trie_map::*;
+
+#[path = "./hash-trie-set.rs"]
+pub mod hash_trie_set;
+pub use hash_trie_set::*;
@@ -10,3 +10,6 @@ pub use lists::*;
 
 pub mod trees;
 pub use trees::*;
+
+pub mod maps;
+pub use maps::*;
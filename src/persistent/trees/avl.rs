@@ -0,0 +1,79 @@
+use std::rc::Rc;
+
+// Value and children of an inner node.
+pub type AvlParts<'a, V, N> = (&'a V, &'a Rc<N>, &'a Rc<N>);
+
+// The rotations shared by the height-balanced persistent trees. A tree only
+// says how to read the height and the parts of a node and how to build an
+// inner node, which is where it refreshes whatever else the node caches.
+pub trait Avl {
+  type Node;
+  type Value: Clone;
+
+  fn height(node: &Self::Node) -> i32;
+
+  // `None` for the nodes a rotation can't go through, like empty nodes or
+  // rope leaves.
+  fn parts(node: &Self::Node) -> Option<AvlParts<'_, Self::Value, Self::Node>>;
+
+  fn node(value: Self::Value, left: Rc<Self::Node>, right: Rc<Self::Node>) -> Rc<Self::Node>;
+
+  fn rotate_right(
+    value: Self::Value,
+    left: &Rc<Self::Node>,
+    right: Rc<Self::Node>,
+  ) -> Rc<Self::Node> {
+    match Self::parts(left) {
+      None => Self::node(value, left.clone(), right),
+      Some((left_value, left_left, left_right)) => Self::node(
+        left_value.clone(),
+        left_left.clone(),
+        Self::node(value, left_right.clone(), right),
+      ),
+    }
+  }
+
+  fn rotate_left(
+    value: Self::Value,
+    left: Rc<Self::Node>,
+    right: &Rc<Self::Node>,
+  ) -> Rc<Self::Node> {
+    match Self::parts(right) {
+      None => Self::node(value, left, right.clone()),
+      Some((right_value, right_left, right_right)) => Self::node(
+        right_value.clone(),
+        Self::node(value, left, right_left.clone()),
+        right_right.clone(),
+      ),
+    }
+  }
+
+  // Builds a node out of children whose heights differ by at most two,
+  // rotating it back into an AVL shape.
+  fn balance(value: Self::Value, left: Rc<Self::Node>, right: Rc<Self::Node>) -> Rc<Self::Node> {
+    let diff = Self::height(&left) - Self::height(&right);
+    if diff > 1 {
+      let left = match Self::parts(&left) {
+        Some((left_value, left_left, left_right))
+          if Self::height(left_left) < Self::height(left_right) =>
+        {
+          Self::rotate_left(left_value.clone(), left_left.clone(), left_right)
+        }
+        _ => left,
+      };
+      Self::rotate_right(value, &left, right)
+    } else if diff < -1 {
+      let right = match Self::parts(&right) {
+        Some((right_value, right_left, right_right))
+          if Self::height(right_right) < Self::height(right_left) =>
+        {
+          Self::rotate_right(right_value.clone(), right_left, right_right.clone())
+        }
+        _ => right,
+      };
+      Self::rotate_left(value, left, &right)
+    } else {
+      Self::node(value, left, right)
+    }
+  }
+}
@@ -1,47 +1,127 @@
+use std::rc::Rc;
+
+use super::{Count, MeasuredTree};
+
+// The node layout shared by the persistent search trees: `measure` caches the
+// combined measure of the subtree and `height` its height.
 #[derive(Clone, PartialEq, Debug)]
-pub enum TreeNode<T> {
+pub enum TreeNode<T, V = i32> {
   Empty,
   Node {
     value: T,
-    left: Box<TreeNode<T>>,
-    right: Box<TreeNode<T>>,
+    measure: V,
+    height: i32,
+    left: Rc<TreeNode<T, V>>,
+    right: Rc<TreeNode<T, V>>,
   },
 }
 
 // Invariants:
 //    - the left_node value is always <= node value
 //    - the right_node value is always > node value
+//    - measure is the `Count` of the subtree, its size
 #[derive(Clone, PartialEq, Debug)]
 pub struct BinarySearchTree<T> {
   root: TreeNode<T>,
 }
 
+type Trail<T> = Option<Rc<Crumb<T>>>;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Side {
+  Left,
+  Right,
+}
+
+// A step down from a parent: its value, the subtree not taken and the bounds
+// the parent was focused with.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Crumb<T> {
+  value: T,
+  side: Side,
+  sibling: Rc<TreeNode<T>>,
+  low: Option<T>,
+  high: Option<T>,
+  next: Trail<T>,
+}
+
+// Walks the nodes of a `BinarySearchTree` directly: moving up only rebuilds
+// the path back to the root, every subtree off that path stays shared.
+//
+// Invariants:
+//    - every value under the focus is > low and <= high
+//    - trail holds the parents of the focus, nearest first
+#[derive(Clone, PartialEq, Debug)]
+pub struct TreeZipper<T> {
+  focus: Rc<TreeNode<T>>,
+  trail: Trail<T>,
+  low: Option<T>,
+  high: Option<T>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum ZipperError {
+  EmptyFocus,
+  OutOfOrder,
+}
+
 mod private {
   use super::*;
 
+  pub fn size<T>(node: &TreeNode<T>) -> i32 {
+    match node {
+      TreeNode::Empty => 0,
+      TreeNode::Node { measure, .. } => *measure,
+    }
+  }
+
+  pub fn height<T>(node: &TreeNode<T>) -> i32 {
+    match node {
+      TreeNode::Empty => 0,
+      TreeNode::Node { height, .. } => *height,
+    }
+  }
+
+  // The same nodes seen as a tree measured by `Count`, which answers the
+  // order statistics.
+  pub fn counted<T>(tree: &BinarySearchTree<T>) -> MeasuredTree<T, Count>
+  where
+    T: PartialEq + PartialOrd + Clone + Copy,
+  {
+    MeasuredTree::from_root(Rc::new(tree.root.clone()))
+  }
+
+  pub fn new_node<T>(value: T, left: Rc<TreeNode<T>>, right: Rc<TreeNode<T>>) -> TreeNode<T> {
+    TreeNode::Node {
+      value,
+      measure: size(&left) + size(&right) + 1,
+      height: std::cmp::max(height(&left), height(&right)) + 1,
+      left,
+      right,
+    }
+  }
+
   pub fn insert_node_aux<T>(node: &TreeNode<T>, item: T) -> TreeNode<T>
   where
     T: Clone + PartialOrd,
   {
     match node {
-      TreeNode::Empty => TreeNode::Node {
-        value: item,
-        left: Box::new(TreeNode::Empty),
-        right: Box::new(TreeNode::Empty),
-      },
-      TreeNode::Node { value, left, right } => {
+      TreeNode::Empty => new_node(item, Rc::new(TreeNode::Empty), Rc::new(TreeNode::Empty)),
+      TreeNode::Node {
+        value, left, right, ..
+      } => {
         if &item > value {
-          TreeNode::Node {
-            value: value.clone(),
-            left: left.clone(),
-            right: Box::new(insert_node_aux(right, item)),
-          }
+          new_node(
+            value.clone(),
+            left.clone(),
+            Rc::new(insert_node_aux(right, item)),
+          )
         } else {
-          TreeNode::Node {
-            value: value.clone(),
-            left: Box::new(insert_node_aux(left, item)),
-            right: right.clone(),
-          }
+          new_node(
+            value.clone(),
+            Rc::new(insert_node_aux(left, item)),
+            right.clone(),
+          )
         }
       }
     }
@@ -53,11 +133,7 @@ mod private {
   {
     match node {
       TreeNode::Empty => acc,
-      TreeNode::Node {
-        value,
-        left,
-        right: _,
-      } => match *left.clone() {
+      TreeNode::Node { value, left, .. } => match left.as_ref() {
         TreeNode::Empty => value.clone(),
         TreeNode::Node { .. } => in_order_sucessor(left, value.clone()),
       },
@@ -70,36 +146,36 @@ mod private {
   {
     match node {
       TreeNode::Empty => TreeNode::Empty,
-      TreeNode::Node { value, left, right } => {
-        match (value.clone() == item, *left.clone(), *right.clone()) {
-          (false, _, _) => {
-            if &item > value {
-              TreeNode::Node {
-                value: value.clone(),
-                left: left.clone(),
-                right: Box::new(remove_node_aux(right, item)),
-              }
-            } else {
-              TreeNode::Node {
-                value: value.clone(),
-                left: Box::new(remove_node_aux(left, item)),
-                right: right.clone(),
-              }
-            }
-          }
-          (true, TreeNode::Empty, TreeNode::Empty) => TreeNode::Empty,
-          (true, TreeNode::Empty, _) => *right.clone(),
-          (true, _, TreeNode::Empty) => *left.clone(),
-          (true, _, _) => {
-            let rightmost = in_order_sucessor(right, item);
-            TreeNode::Node {
-              value: rightmost.clone(),
-              left: left.to_owned(),
-              right: Box::new(remove_node_aux(right, rightmost.clone())),
-            }
+      TreeNode::Node {
+        value, left, right, ..
+      } => match (value.clone() == item, left.as_ref(), right.as_ref()) {
+        (false, _, _) => {
+          if &item > value {
+            new_node(
+              value.clone(),
+              left.clone(),
+              Rc::new(remove_node_aux(right, item)),
+            )
+          } else {
+            new_node(
+              value.clone(),
+              Rc::new(remove_node_aux(left, item)),
+              right.clone(),
+            )
           }
         }
-      }
+        (true, TreeNode::Empty, TreeNode::Empty) => TreeNode::Empty,
+        (true, TreeNode::Empty, _) => right.as_ref().clone(),
+        (true, _, TreeNode::Empty) => left.as_ref().clone(),
+        (true, _, _) => {
+          let rightmost = in_order_sucessor(right, item);
+          new_node(
+            rightmost.clone(),
+            left.to_owned(),
+            Rc::new(remove_node_aux(right, rightmost.clone())),
+          )
+        }
+      },
     }
   }
 
@@ -110,7 +186,9 @@ mod private {
     let _ = item;
     match node {
       TreeNode::Empty => None,
-      TreeNode::Node { value, left, right } => {
+      TreeNode::Node {
+        value, left, right, ..
+      } => {
         if value == &item {
           Some(item)
         } else if value > &item {
@@ -122,32 +200,12 @@ mod private {
     }
   }
 
-  pub fn len_node_aux<T>(node: &TreeNode<T>, acc: i32) -> i32 {
-    match node {
-      TreeNode::Empty => acc,
-      TreeNode::Node {
-        value: _,
-        left,
-        right,
-      } => len_node_aux(right, len_node_aux(left, acc + 1)),
-    }
-  }
-
-  pub fn height_node_aux<T>(node: &TreeNode<T>) -> i32 {
-    match node {
-      TreeNode::Empty => 0,
-      TreeNode::Node {
-        value: _,
-        left,
-        right,
-      } => std::cmp::max(height_node_aux(left), height_node_aux(right)) + 1,
-    }
-  }
-
   pub fn any_node_aux<T>(node: &TreeNode<T>, f: fn(&T) -> bool) -> bool {
     match node {
       TreeNode::Empty => false,
-      TreeNode::Node { value, left, right } => {
+      TreeNode::Node {
+        value, left, right, ..
+      } => {
         if f(value) {
           true
         } else {
@@ -160,7 +218,9 @@ mod private {
   pub fn all_node_aux<T>(node: &TreeNode<T>, f: fn(&T) -> bool) -> bool {
     match node {
       TreeNode::Empty => true,
-      TreeNode::Node { value, left, right } => {
+      TreeNode::Node {
+        value, left, right, ..
+      } => {
         if f(value) {
           all_node_aux(left, f) && all_node_aux(right, f)
         } else {
@@ -173,7 +233,9 @@ mod private {
   pub fn find_node_aux<'a, T>(node: &'a TreeNode<T>, f: fn(&T) -> bool) -> Option<&'a T> {
     match node {
       TreeNode::Empty => None,
-      TreeNode::Node { value, left, right } => {
+      TreeNode::Node {
+        value, left, right, ..
+      } => {
         if f(value) {
           Some(value)
         } else {
@@ -192,10 +254,18 @@ mod private {
   pub fn map_node_aux<T, U>(node: &TreeNode<T>, f: fn(&T) -> U) -> TreeNode<U> {
     match node {
       TreeNode::Empty => TreeNode::Empty,
-      TreeNode::Node { value, left, right } => TreeNode::Node {
+      TreeNode::Node {
+        value,
+        measure,
+        height,
+        left,
+        right,
+      } => TreeNode::Node {
         value: f(value),
-        left: Box::new(map_node_aux(left, f)),
-        right: Box::new(map_node_aux(right, f)),
+        measure: *measure,
+        height: *height,
+        left: Rc::new(map_node_aux(left, f)),
+        right: Rc::new(map_node_aux(right, f)),
       },
     }
   }
@@ -203,9 +273,69 @@ mod private {
   pub fn reduce_node_aux<T, U>(node: &TreeNode<T>, f: fn(&T, U) -> U, acc: U) -> U {
     match node {
       TreeNode::Empty => acc,
-      TreeNode::Node { value, left, right } => {
-        reduce_node_aux(right, f, f(value, reduce_node_aux(left, f, acc)))
-      }
+      TreeNode::Node {
+        value, left, right, ..
+      } => reduce_node_aux(right, f, f(value, reduce_node_aux(left, f, acc))),
+    }
+  }
+
+  pub fn fits<T>(item: &T, low: &Option<T>, high: &Option<T>) -> bool
+  where
+    T: PartialOrd,
+  {
+    low.as_ref().is_none_or(|low| item > low) && high.as_ref().is_none_or(|high| item <= high)
+  }
+
+  pub fn min_node_aux<T>(node: &TreeNode<T>) -> Option<&T> {
+    match node {
+      TreeNode::Empty => None,
+      TreeNode::Node { value, left, .. } => min_node_aux(left).or(Some(value)),
+    }
+  }
+
+  pub fn max_node_aux<T>(node: &TreeNode<T>) -> Option<&T> {
+    match node {
+      TreeNode::Empty => None,
+      TreeNode::Node { value, right, .. } => max_node_aux(right).or(Some(value)),
+    }
+  }
+
+  pub fn remove_max_node_aux<T>(node: &TreeNode<T>) -> TreeNode<T>
+  where
+    T: Clone,
+  {
+    match node {
+      TreeNode::Empty => TreeNode::Empty,
+      TreeNode::Node {
+        value, left, right, ..
+      } => match right.as_ref() {
+        TreeNode::Empty => left.as_ref().clone(),
+        TreeNode::Node { .. } => new_node(
+          value.clone(),
+          left.clone(),
+          Rc::new(remove_max_node_aux(right)),
+        ),
+      },
+    }
+  }
+
+  // Replaces the root by its in-order predecessor, which keeps duplicates,
+  // always stored on the left, in order.
+  pub fn remove_root_aux<T>(node: &TreeNode<T>) -> TreeNode<T>
+  where
+    T: Clone,
+  {
+    match node {
+      TreeNode::Empty => TreeNode::Empty,
+      TreeNode::Node { left, right, .. } => match (left.as_ref(), right.as_ref()) {
+        (TreeNode::Empty, _) => right.as_ref().clone(),
+        (_, TreeNode::Empty) => left.as_ref().clone(),
+        _ => new_node(
+          max_node_aux(left).unwrap().clone(),
+          Rc::new(remove_max_node_aux(left)),
+          right.clone(),
+        ),
+      },
     }
   }
 }
@@ -241,11 +371,38 @@ where
   }
 
   pub fn len(tree: &Self) -> i32 {
-    private::len_node_aux(&tree.root, 0)
+    private::size(&tree.root)
+  }
+
+  // Number of values strictly smaller than `item`.
+  pub fn rank(tree: &Self, item: T) -> i32 {
+    MeasuredTree::measure_range(&private::counted(tree), ..item)
+  }
+
+  // The `index`-th smallest value, starting from 0.
+  pub fn select(tree: &Self, index: i32) -> Option<T> {
+    if index < 0 {
+      return None;
+    }
+    MeasuredTree::search_by(&private::counted(tree), |count| *count > index)
+  }
+
+  // Lower median for trees with an even number of values.
+  pub fn median(tree: &Self) -> Option<T> {
+    Self::select(tree, (Self::len(tree) - 1) / 2)
+  }
+
+  // Nearest-rank percentile, `percent` goes from 0 to 100.
+  pub fn percentile(tree: &Self, percent: f64) -> Option<T> {
+    if !(0.0..=100.0).contains(&percent) {
+      return None;
+    }
+    let rank = (percent / 100.0 * Self::len(tree) as f64).ceil() as i32;
+    Self::select(tree, std::cmp::max(rank, 1) - 1)
   }
 
   pub fn height(tree: &Self) -> i32 {
-    private::height_node_aux(&tree.root)
+    private::height(&tree.root)
   }
 
   pub fn any(tree: &Self, f: fn(&T) -> bool) -> bool {
@@ -271,6 +428,164 @@ where
   }
 }
 
+impl<T> TreeZipper<T>
+where
+  T: PartialEq + PartialOrd + Clone,
+{
+  // Focuses the root of `tree`, sharing all of its nodes.
+  pub fn from_tree(tree: &BinarySearchTree<T>) -> Self {
+    Self {
+      focus: Rc::new(tree.root.clone()),
+      trail: None,
+      low: None,
+      high: None,
+    }
+  }
+
+  pub fn to_tree(zipper: &Self) -> BinarySearchTree<T> {
+    BinarySearchTree {
+      root: Self::top(zipper).focus.as_ref().clone(),
+    }
+  }
+
+  // Value of the focused node, `None` when focused on an empty slot.
+  pub fn focus(zipper: &Self) -> Option<T> {
+    match zipper.focus.as_ref() {
+      TreeNode::Empty => None,
+      TreeNode::Node { value, .. } => Some(value.clone()),
+    }
+  }
+
+  pub fn is_root(zipper: &Self) -> bool {
+    zipper.trail.is_none()
+  }
+
+  pub fn left(zipper: &Self) -> Option<Self> {
+    match zipper.focus.as_ref() {
+      TreeNode::Empty => None,
+      TreeNode::Node {
+        value, left, right, ..
+      } => Some(Self {
+        focus: left.clone(),
+        trail: Some(Rc::new(Crumb {
+          value: value.clone(),
+          side: Side::Left,
+          sibling: right.clone(),
+          low: zipper.low.clone(),
+          high: zipper.high.clone(),
+          next: zipper.trail.clone(),
+        })),
+        low: zipper.low.clone(),
+        high: Some(value.clone()),
+      }),
+    }
+  }
+
+  pub fn right(zipper: &Self) -> Option<Self> {
+    match zipper.focus.as_ref() {
+      TreeNode::Empty => None,
+      TreeNode::Node {
+        value, left, right, ..
+      } => Some(Self {
+        focus: right.clone(),
+        trail: Some(Rc::new(Crumb {
+          value: value.clone(),
+          side: Side::Right,
+          sibling: left.clone(),
+          low: zipper.low.clone(),
+          high: zipper.high.clone(),
+          next: zipper.trail.clone(),
+        })),
+        low: Some(value.clone()),
+        high: zipper.high.clone(),
+      }),
+    }
+  }
+
+  pub fn up(zipper: &Self) -> Option<Self> {
+    zipper.trail.as_ref().map(|crumb| {
+      let (left, right) = match crumb.side {
+        Side::Left => (zipper.focus.clone(), crumb.sibling.clone()),
+        Side::Right => (crumb.sibling.clone(), zipper.focus.clone()),
+      };
+      Self {
+        focus: Rc::new(private::new_node(crumb.value.clone(), left, right)),
+        trail: crumb.next.clone(),
+        low: crumb.low.clone(),
+        high: crumb.high.clone(),
+      }
+    })
+  }
+
+  pub fn top(zipper: &Self) -> Self {
+    match Self::up(zipper) {
+      None => zipper.clone(),
+      Some(parent) => Self::top(&parent),
+    }
+  }
+
+  // Moves to the node holding `item`, or to the empty slot where it would be
+  // inserted. Only climbs as far as needed instead of restarting at the root.
+  pub fn seek(zipper: &Self, item: T) -> Self {
+    if !private::fits(&item, &zipper.low, &zipper.high) {
+      if let Some(parent) = Self::up(zipper) {
+        return Self::seek(&parent, item);
+      }
+    }
+    match zipper.focus.as_ref() {
+      TreeNode::Empty => zipper.clone(),
+      TreeNode::Node { value, .. } => {
+        if value == &item {
+          zipper.clone()
+        } else if &item > value {
+          Self::seek(&Self::right(zipper).unwrap(), item)
+        } else {
+          Self::seek(&Self::left(zipper).unwrap(), item)
+        }
+      }
+    }
+  }
+
+  // Swaps the focused value, as long as the tree stays ordered.
+  pub fn replace(zipper: &Self, item: T) -> Result<Self, ZipperError> {
+    let TreeNode::Node { left, right, .. } = zipper.focus.as_ref() else {
+      return Err(ZipperError::EmptyFocus);
+    };
+    let ordered = private::fits(&item, &zipper.low, &zipper.high)
+      && private::max_node_aux(left).is_none_or(|max| max <= &item)
+      && private::min_node_aux(right).is_none_or(|min| min > &item);
+    if !ordered {
+      return Err(ZipperError::OutOfOrder);
+    }
+    Ok(Self {
+      focus: Rc::new(private::new_node(item, left.clone(), right.clone())),
+      ..zipper.clone()
+    })
+  }
+
+  // Inserts `item` under the focus, which must be able to hold it.
+  pub fn insert(zipper: &Self, item: T) -> Result<Self, ZipperError> {
+    if !private::fits(&item, &zipper.low, &zipper.high) {
+      return Err(ZipperError::OutOfOrder);
+    }
+    Ok(Self {
+      focus: Rc::new(private::insert_node_aux(&zipper.focus, item)),
+      ..zipper.clone()
+    })
+  }
+
+  // Removes the focused node, the focus moves to the subtree replacing it.
+  pub fn delete(zipper: &Self) -> Result<Self, ZipperError> {
+    if let TreeNode::Empty = zipper.focus.as_ref() {
+      return Err(ZipperError::EmptyFocus);
+    }
+    Ok(Self {
+      focus: Rc::new(private::remove_root_aux(&zipper.focus)),
+      ..zipper.clone()
+    })
+  }
+}
+
 #[cfg(test)]
 #[path = "./binary-search-tree_test.rs"]
 mod test;
@@ -6,11 +6,7 @@ mod setup {
   pub type BinaryTreeT = BinarySearchTree<i32>;
 
   pub fn node<T>(value: T, left: TreeNode<T>, right: TreeNode<T>) -> TreeNode<T> {
-    TreeNode::Node {
-      value: value,
-      left: Box::new(left),
-      right: Box::new(right),
-    }
+    private::new_node(value, Rc::new(left), Rc::new(right))
   }
 
   pub fn binary_tree_empty() -> BinaryTreeT {
@@ -257,6 +253,16 @@ mod len {
     let op = BinarySearchTree::len(&tree);
     assert_eq!(op, 7);
   }
+
+  #[test]
+  fn to_filled_after_insert_and_remove() {
+    let tree = setup::binary_tree_filled();
+    let inserted = BinarySearchTree::insert(&tree, 7);
+    let removed = BinarySearchTree::remove(&inserted, 3);
+    assert_eq!(BinarySearchTree::len(&inserted), 8);
+    assert_eq!(BinarySearchTree::len(&removed), 7);
+    assert_eq!(BinarySearchTree::len(&tree), 7);
+  }
 }
 
 #[cfg(test)]
@@ -415,3 +421,368 @@ mod reduce {
     assert_eq!(op, 21)
   }
 }
+
+#[cfg(test)]
+mod rank {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::binary_tree_empty();
+    assert_eq!(BinarySearchTree::rank(&tree, 3), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::binary_tree_filled();
+    assert_eq!(BinarySearchTree::rank(&tree, 0), 0);
+    assert_eq!(BinarySearchTree::rank(&tree, 3), 3);
+    assert_eq!(BinarySearchTree::rank(&tree, 6), 6)
+  }
+
+  #[test]
+  fn to_filled_missing_item() {
+    let tree = setup::binary_tree_filled();
+    assert_eq!(BinarySearchTree::rank(&tree, -4), 0);
+    assert_eq!(BinarySearchTree::rank(&tree, 10), 7)
+  }
+
+  #[test]
+  fn to_filled_with_duplicates() {
+    let tree = BinarySearchTree::insert(&setup::binary_tree_filled(), 2);
+    assert_eq!(BinarySearchTree::rank(&tree, 2), 2);
+    assert_eq!(BinarySearchTree::rank(&tree, 3), 4)
+  }
+}
+
+#[cfg(test)]
+mod select {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::binary_tree_empty();
+    assert_eq!(BinarySearchTree::select(&tree, 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::binary_tree_filled();
+    for index in 0..7 {
+      assert_eq!(BinarySearchTree::select(&tree, index), Some(index))
+    }
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let tree = setup::binary_tree_filled();
+    assert_eq!(BinarySearchTree::select(&tree, 7), None);
+    assert_eq!(BinarySearchTree::select(&tree, -1), None)
+  }
+
+  #[test]
+  fn after_remove() {
+    let tree = BinarySearchTree::remove(&setup::binary_tree_filled(), 3);
+    assert_eq!(BinarySearchTree::len(&tree), 6);
+    assert_eq!(BinarySearchTree::select(&tree, 3), Some(4))
+  }
+}
+
+#[cfg(test)]
+mod median {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::binary_tree_empty();
+    assert_eq!(BinarySearchTree::median(&tree), None)
+  }
+
+  #[test]
+  fn to_filled_odd() {
+    let tree = setup::binary_tree_filled();
+    assert_eq!(BinarySearchTree::median(&tree), Some(3))
+  }
+
+  #[test]
+  fn to_filled_even() {
+    let tree = BinarySearchTree::insert(&setup::binary_tree_filled(), 7);
+    assert_eq!(BinarySearchTree::median(&tree), Some(3))
+  }
+}
+
+#[cfg(test)]
+mod percentile {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::binary_tree_empty();
+    assert_eq!(BinarySearchTree::percentile(&tree, 50.0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = (1..=100).fold(BinarySearchTree::new(), |tree, item| {
+      BinarySearchTree::insert(&tree, item)
+    });
+    assert_eq!(BinarySearchTree::percentile(&tree, 0.0), Some(1));
+    assert_eq!(BinarySearchTree::percentile(&tree, 50.0), Some(50));
+    assert_eq!(BinarySearchTree::percentile(&tree, 95.0), Some(95));
+    assert_eq!(BinarySearchTree::percentile(&tree, 99.5), Some(100));
+    assert_eq!(BinarySearchTree::percentile(&tree, 100.0), Some(100))
+  }
+
+  #[test]
+  fn to_filled_out_of_range() {
+    let tree = setup::binary_tree_filled();
+    assert_eq!(BinarySearchTree::percentile(&tree, -1.0), None);
+    assert_eq!(BinarySearchTree::percentile(&tree, 100.5), None)
+  }
+}
+
+#[cfg(test)]
+mod zipper_from_tree {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::binary_tree_empty();
+    let op = TreeZipper::from_tree(&tree);
+    assert_eq!(TreeZipper::focus(&op), None);
+    assert!(TreeZipper::is_root(&op));
+    assert_eq!(TreeZipper::to_tree(&op), tree)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::binary_tree_filled();
+    let op = TreeZipper::from_tree(&tree);
+    assert_eq!(TreeZipper::focus(&op), Some(3));
+    assert_eq!(TreeZipper::to_tree(&op), tree)
+  }
+
+  #[test]
+  fn to_filled_shares_untouched_subtrees() {
+    let tree = setup::binary_tree_filled();
+    let zipper = TreeZipper::from_tree(&tree);
+    let left = TreeZipper::left(&zipper).unwrap();
+    let op = TreeZipper::replace(&TreeZipper::left(&left).unwrap(), -1).unwrap();
+    let TreeNode::Node { left, right, .. } = &TreeZipper::to_tree(&op).root else {
+      panic!("empty tree")
+    };
+    let TreeNode::Node {
+      left: old_left,
+      right: old_right,
+      ..
+    } = &tree.root
+    else {
+      panic!("empty tree")
+    };
+    assert!(Rc::ptr_eq(right, old_right));
+    assert!(!Rc::ptr_eq(left, old_left))
+  }
+
+  #[test]
+  fn to_owned_values() {
+    let leaf = |value: &str| setup::node(String::from(value), TreeNode::Empty, TreeNode::Empty);
+    let tree = BinarySearchTree {
+      root: setup::node(String::from("b"), leaf("a"), leaf("c")),
+    };
+    let zipper = TreeZipper::seek(&TreeZipper::from_tree(&tree), String::from("d"));
+    let op = TreeZipper::insert(&zipper, String::from("d")).unwrap();
+    let expected = BinarySearchTree {
+      root: setup::node(
+        String::from("b"),
+        leaf("a"),
+        setup::node(String::from("c"), TreeNode::Empty, leaf("d")),
+      ),
+    };
+    assert_eq!(TreeZipper::to_tree(&op), expected)
+  }
+}
+
+#[cfg(test)]
+mod zipper_moves {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let zipper = TreeZipper::from_tree(&setup::binary_tree_empty());
+    assert_eq!(TreeZipper::left(&zipper), None);
+    assert_eq!(TreeZipper::right(&zipper), None);
+    assert_eq!(TreeZipper::up(&zipper), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::binary_tree_filled();
+    let zipper = TreeZipper::from_tree(&tree);
+    let op = TreeZipper::right(&TreeZipper::left(&zipper).unwrap()).unwrap();
+    assert_eq!(TreeZipper::focus(&op), Some(2));
+    assert!(!TreeZipper::is_root(&op));
+    let op = TreeZipper::right(&op).unwrap();
+    assert_eq!(TreeZipper::focus(&op), None);
+    assert_eq!(TreeZipper::right(&op), None);
+    let op = TreeZipper::up(&TreeZipper::up(&op).unwrap()).unwrap();
+    assert_eq!(TreeZipper::focus(&op), Some(1));
+    assert_eq!(TreeZipper::top(&op), zipper);
+    assert_eq!(TreeZipper::to_tree(&op), tree)
+  }
+}
+
+#[cfg(test)]
+mod zipper_seek {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let zipper = TreeZipper::from_tree(&setup::binary_tree_empty());
+    let op = TreeZipper::seek(&zipper, 4);
+    assert_eq!(op, zipper)
+  }
+
+  #[test]
+  fn to_filled_with_found_item() {
+    let zipper = TreeZipper::from_tree(&setup::binary_tree_filled());
+    let op = TreeZipper::seek(&zipper, 4);
+    assert_eq!(TreeZipper::focus(&op), Some(4));
+    let op = TreeZipper::seek(&op, 2);
+    assert_eq!(TreeZipper::focus(&op), Some(2));
+    let parent = TreeZipper::up(&op).unwrap();
+    assert_eq!(TreeZipper::focus(&parent), Some(1))
+  }
+
+  #[test]
+  fn to_filled_without_found_item() {
+    let zipper = TreeZipper::from_tree(&setup::binary_tree_filled());
+    let op = TreeZipper::seek(&zipper, 10);
+    assert_eq!(TreeZipper::focus(&op), None);
+    let parent = TreeZipper::up(&op).unwrap();
+    assert_eq!(TreeZipper::focus(&parent), Some(6))
+  }
+}
+
+#[cfg(test)]
+mod zipper_replace {
+  use super::*;
+
+  #[test]
+  fn to_empty_focus() {
+    let zipper = TreeZipper::from_tree(&setup::binary_tree_empty());
+    assert_eq!(
+      TreeZipper::replace(&zipper, 1),
+      Err(ZipperError::EmptyFocus)
+    )
+  }
+
+  #[test]
+  fn to_filled_in_order() {
+    let tree = BinarySearchTree::insert(&setup::binary_tree_filled(), 10);
+    let zipper = TreeZipper::seek(&TreeZipper::from_tree(&tree), 6);
+    let op = TreeZipper::replace(&zipper, 8).unwrap();
+    let expected = BinarySearchTree::insert(&BinarySearchTree::remove(&tree, 6), 8);
+    assert_eq!(TreeZipper::focus(&op), Some(8));
+    assert_eq!(
+      BinarySearchTree::reduce(
+        &TreeZipper::to_tree(&op),
+        |v, acc: Vec<i32>| [acc, vec![*v]].concat(),
+        vec![]
+      ),
+      BinarySearchTree::reduce(
+        &expected,
+        |v, acc: Vec<i32>| [acc, vec![*v]].concat(),
+        vec![]
+      )
+    )
+  }
+
+  #[test]
+  fn to_filled_out_of_order() {
+    let zipper = TreeZipper::from_tree(&setup::binary_tree_filled());
+    let left = TreeZipper::left(&zipper).unwrap();
+    assert_eq!(TreeZipper::replace(&left, 4), Err(ZipperError::OutOfOrder));
+    assert_eq!(
+      TreeZipper::replace(&zipper, 0),
+      Err(ZipperError::OutOfOrder)
+    );
+    assert_eq!(
+      TreeZipper::replace(&zipper, 5),
+      Err(ZipperError::OutOfOrder)
+    )
+  }
+}
+
+#[cfg(test)]
+mod zipper_insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let zipper = TreeZipper::from_tree(&setup::binary_tree_empty());
+    let op = TreeZipper::insert(&zipper, 1).unwrap();
+    let expected = BinarySearchTree::insert(&setup::binary_tree_empty(), 1);
+    assert_eq!(TreeZipper::to_tree(&op), expected)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::binary_tree_filled();
+    let zipper = TreeZipper::seek(&TreeZipper::from_tree(&tree), 7);
+    let op = TreeZipper::insert(&zipper, 7).unwrap();
+    assert_eq!(TreeZipper::focus(&op), Some(7));
+    assert_eq!(TreeZipper::to_tree(&op), BinarySearchTree::insert(&tree, 7));
+    assert_eq!(TreeZipper::to_tree(&zipper), tree)
+  }
+
+  #[test]
+  fn to_filled_out_of_order() {
+    let zipper = TreeZipper::from_tree(&setup::binary_tree_filled());
+    let left = TreeZipper::left(&zipper).unwrap();
+    assert_eq!(TreeZipper::insert(&left, 4), Err(ZipperError::OutOfOrder))
+  }
+}
+
+#[cfg(test)]
+mod zipper_delete {
+  use super::*;
+
+  #[test]
+  fn to_empty_focus() {
+    let zipper = TreeZipper::from_tree(&setup::binary_tree_empty());
+    assert_eq!(TreeZipper::delete(&zipper), Err(ZipperError::EmptyFocus))
+  }
+
+  #[test]
+  fn to_filled_leaf() {
+    let tree = setup::binary_tree_filled();
+    let zipper = TreeZipper::seek(&TreeZipper::from_tree(&tree), 4);
+    let op = TreeZipper::delete(&zipper).unwrap();
+    assert_eq!(TreeZipper::focus(&op), None);
+    assert_eq!(TreeZipper::to_tree(&op), BinarySearchTree::remove(&tree, 4))
+  }
+
+  #[test]
+  fn to_filled_root() {
+    let tree = setup::binary_tree_filled();
+    let zipper = TreeZipper::from_tree(&tree);
+    let op = TreeZipper::delete(&zipper).unwrap();
+    let expected = setup::BinaryTreeT {
+      root: setup::node(
+        2,
+        setup::node(
+          1,
+          setup::node(0, TreeNode::Empty, TreeNode::Empty),
+          TreeNode::Empty,
+        ),
+        setup::node(
+          5,
+          setup::node(4, TreeNode::Empty, TreeNode::Empty),
+          setup::node(6, TreeNode::Empty, TreeNode::Empty),
+        ),
+      ),
+    };
+    assert_eq!(TreeZipper::focus(&op), Some(2));
+    assert_eq!(TreeZipper::to_tree(&op), expected)
+  }
+}
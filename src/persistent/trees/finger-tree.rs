@@ -0,0 +1,737 @@
+use std::{fmt, marker::PhantomData, rc::Rc};
+
+use super::Measure;
+
+// Every level of the spine holds elements one level deeper than the one
+// above it: plain values on top, then 2-3 nodes of values, then 2-3 nodes of
+// those, and so on. Rust has no polymorphic recursion, so all levels share
+// this type and the depth is only implied by the position in the spine.
+#[derive(Clone, PartialEq, Debug)]
+pub enum FingerNode<T, V> {
+  Leaf(T),
+  Node2(V, Rc<FingerNode<T, V>>, Rc<FingerNode<T, V>>),
+  Node3(
+    V,
+    Rc<FingerNode<T, V>>,
+    Rc<FingerNode<T, V>>,
+    Rc<FingerNode<T, V>>,
+  ),
+}
+
+type Elem<T, V> = Rc<FingerNode<T, V>>;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum FingerTreeNode<T, V> {
+  Empty,
+  Single(Elem<T, V>),
+  Deep {
+    measure: V,
+    prefix: Vec<Elem<T, V>>,
+    middle: Rc<FingerTreeNode<T, V>>,
+    suffix: Vec<Elem<T, V>>,
+  },
+}
+
+type Spine<T, V> = Rc<FingerTreeNode<T, V>>;
+
+// Hinze and Paterson's 2-3 finger tree. The ends are reached through the
+// digits in O(1), while the spine only changes every few operations, so push
+// and pop are amortized O(1); this version is strict, the bound holds as long
+// as old versions are not the ones being pushed to over and over.
+//
+// Invariants:
+//    - prefix and suffix hold between 1 and 4 elements
+//    - elements of the top level are leaves, the ones of the middle of a
+//      level are 2-3 nodes of elements of that level
+//    - measure is the combined measure of the tree, in order
+pub struct FingerTree<T, M>
+where
+  M: Measure<T>,
+{
+  root: Spine<T, M::Output>,
+  marker: PhantomData<M>,
+}
+
+impl<T, M> Clone for FingerTree<T, M>
+where
+  M: Measure<T>,
+{
+  fn clone(&self) -> Self {
+    Self {
+      root: self.root.clone(),
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<T, M> PartialEq for FingerTree<T, M>
+where
+  T: PartialEq,
+  M: Measure<T>,
+  M::Output: PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.root == other.root
+  }
+}
+
+impl<T, M> fmt::Debug for FingerTree<T, M>
+where
+  T: fmt::Debug,
+  M: Measure<T>,
+  M::Output: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("FingerTree")
+      .field("root", &self.root)
+      .finish()
+  }
+}
+
+mod private {
+  use super::*;
+
+  type DigitSplit<T, V> = (Vec<Elem<T, V>>, Elem<T, V>, Vec<Elem<T, V>>);
+  type TreeSplit<T, V> = (Spine<T, V>, Elem<T, V>, Spine<T, V>);
+  type View<T, V> = Option<(Elem<T, V>, Spine<T, V>)>;
+
+  pub fn node_measure<T, M>(node: &FingerNode<T, M::Output>) -> M::Output
+  where
+    M: Measure<T>,
+  {
+    match node {
+      FingerNode::Leaf(value) => M::measure(value),
+      FingerNode::Node2(measure, ..) => measure.clone(),
+      FingerNode::Node3(measure, ..) => measure.clone(),
+    }
+  }
+
+  pub fn digit_measure<T, M>(digit: &[Elem<T, M::Output>]) -> M::Output
+  where
+    M: Measure<T>,
+  {
+    digit.iter().fold(M::identity(), |acc, node| {
+      M::combine(&acc, &node_measure::<T, M>(node))
+    })
+  }
+
+  pub fn tree_measure<T, M>(tree: &FingerTreeNode<T, M::Output>) -> M::Output
+  where
+    M: Measure<T>,
+  {
+    match tree {
+      FingerTreeNode::Empty => M::identity(),
+      FingerTreeNode::Single(node) => node_measure::<T, M>(node),
+      FingerTreeNode::Deep { measure, .. } => measure.clone(),
+    }
+  }
+
+  pub fn leaf<T, V>(value: T) -> Elem<T, V> {
+    Rc::new(FingerNode::Leaf(value))
+  }
+
+  fn node2<T, M>(a: Elem<T, M::Output>, b: Elem<T, M::Output>) -> Elem<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    let measure = digit_measure::<T, M>(&[a.clone(), b.clone()]);
+    Rc::new(FingerNode::Node2(measure, a, b))
+  }
+
+  fn node3<T, M>(
+    a: Elem<T, M::Output>,
+    b: Elem<T, M::Output>,
+    c: Elem<T, M::Output>,
+  ) -> Elem<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    let measure = digit_measure::<T, M>(&[a.clone(), b.clone(), c.clone()]);
+    Rc::new(FingerNode::Node3(measure, a, b, c))
+  }
+
+  fn node_to_digit<T, V>(node: &Elem<T, V>) -> Vec<Elem<T, V>> {
+    match node.as_ref() {
+      FingerNode::Leaf(_) => vec![node.clone()],
+      FingerNode::Node2(_, a, b) => vec![a.clone(), b.clone()],
+      FingerNode::Node3(_, a, b, c) => vec![a.clone(), b.clone(), c.clone()],
+    }
+  }
+
+  pub fn deep<T, M>(
+    prefix: Vec<Elem<T, M::Output>>,
+    middle: Spine<T, M::Output>,
+    suffix: Vec<Elem<T, M::Output>>,
+  ) -> Spine<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    let measure = M::combine(
+      &M::combine(
+        &digit_measure::<T, M>(&prefix),
+        &tree_measure::<T, M>(&middle),
+      ),
+      &digit_measure::<T, M>(&suffix),
+    );
+    Rc::new(FingerTreeNode::Deep {
+      measure,
+      prefix,
+      middle,
+      suffix,
+    })
+  }
+
+  pub fn empty<T, V>() -> Spine<T, V> {
+    Rc::new(FingerTreeNode::Empty)
+  }
+
+  fn digit_to_tree<T, M>(digit: &[Elem<T, M::Output>]) -> Spine<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    digit.iter().fold(empty(), |acc, node| {
+      push_back_aux::<T, M>(&acc, node.clone())
+    })
+  }
+
+  pub fn push_front_aux<T, M>(
+    tree: &Spine<T, M::Output>,
+    node: Elem<T, M::Output>,
+  ) -> Spine<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    match tree.as_ref() {
+      FingerTreeNode::Empty => Rc::new(FingerTreeNode::Single(node)),
+      FingerTreeNode::Single(other) => deep::<T, M>(vec![node], empty(), vec![other.clone()]),
+      FingerTreeNode::Deep {
+        prefix,
+        middle,
+        suffix,
+        ..
+      } => {
+        if prefix.len() == 4 {
+          let carried = node3::<T, M>(prefix[1].clone(), prefix[2].clone(), prefix[3].clone());
+          deep::<T, M>(
+            vec![node, prefix[0].clone()],
+            push_front_aux::<T, M>(middle, carried),
+            suffix.clone(),
+          )
+        } else {
+          let mut new_prefix = vec![node];
+          new_prefix.extend_from_slice(prefix);
+          deep::<T, M>(new_prefix, middle.clone(), suffix.clone())
+        }
+      }
+    }
+  }
+
+  pub fn push_back_aux<T, M>(
+    tree: &Spine<T, M::Output>,
+    node: Elem<T, M::Output>,
+  ) -> Spine<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    match tree.as_ref() {
+      FingerTreeNode::Empty => Rc::new(FingerTreeNode::Single(node)),
+      FingerTreeNode::Single(other) => deep::<T, M>(vec![other.clone()], empty(), vec![node]),
+      FingerTreeNode::Deep {
+        prefix,
+        middle,
+        suffix,
+        ..
+      } => {
+        if suffix.len() == 4 {
+          let carried = node3::<T, M>(suffix[0].clone(), suffix[1].clone(), suffix[2].clone());
+          deep::<T, M>(
+            prefix.clone(),
+            push_back_aux::<T, M>(middle, carried),
+            vec![suffix[3].clone(), node],
+          )
+        } else {
+          let mut new_suffix = suffix.clone();
+          new_suffix.push(node);
+          deep::<T, M>(prefix.clone(), middle.clone(), new_suffix)
+        }
+      }
+    }
+  }
+
+  // A deep tree whose prefix may have run out, borrowing a node from the
+  // middle to refill it.
+  fn deep_front<T, M>(
+    prefix: Vec<Elem<T, M::Output>>,
+    middle: &Spine<T, M::Output>,
+    suffix: Vec<Elem<T, M::Output>>,
+  ) -> Spine<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    if !prefix.is_empty() {
+      return deep::<T, M>(prefix, middle.clone(), suffix);
+    }
+    match pop_front_aux::<T, M>(middle) {
+      None => digit_to_tree::<T, M>(&suffix),
+      Some((node, rest)) => deep::<T, M>(node_to_digit(&node), rest, suffix),
+    }
+  }
+
+  fn deep_back<T, M>(
+    prefix: Vec<Elem<T, M::Output>>,
+    middle: &Spine<T, M::Output>,
+    suffix: Vec<Elem<T, M::Output>>,
+  ) -> Spine<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    if !suffix.is_empty() {
+      return deep::<T, M>(prefix, middle.clone(), suffix);
+    }
+    match pop_back_aux::<T, M>(middle) {
+      None => digit_to_tree::<T, M>(&prefix),
+      Some((node, rest)) => deep::<T, M>(prefix, rest, node_to_digit(&node)),
+    }
+  }
+
+  pub fn pop_front_aux<T, M>(tree: &Spine<T, M::Output>) -> View<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    match tree.as_ref() {
+      FingerTreeNode::Empty => None,
+      FingerTreeNode::Single(node) => Some((node.clone(), empty())),
+      FingerTreeNode::Deep {
+        prefix,
+        middle,
+        suffix,
+        ..
+      } => Some((
+        prefix[0].clone(),
+        deep_front::<T, M>(prefix[1..].to_vec(), middle, suffix.clone()),
+      )),
+    }
+  }
+
+  pub fn pop_back_aux<T, M>(tree: &Spine<T, M::Output>) -> View<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    match tree.as_ref() {
+      FingerTreeNode::Empty => None,
+      FingerTreeNode::Single(node) => Some((node.clone(), empty())),
+      FingerTreeNode::Deep {
+        prefix,
+        middle,
+        suffix,
+        ..
+      } => Some((
+        suffix[suffix.len() - 1].clone(),
+        deep_back::<T, M>(prefix.clone(), middle, suffix[..suffix.len() - 1].to_vec()),
+      )),
+    }
+  }
+
+  // Groups between 2 and 12 elements into 2-3 nodes, keeping their order.
+  fn nodes<T, M>(elems: &[Elem<T, M::Output>]) -> Vec<Elem<T, M::Output>>
+  where
+    M: Measure<T>,
+  {
+    match elems.len() {
+      2 => vec![node2::<T, M>(elems[0].clone(), elems[1].clone())],
+      3 => vec![node3::<T, M>(
+        elems[0].clone(),
+        elems[1].clone(),
+        elems[2].clone(),
+      )],
+      4 => vec![
+        node2::<T, M>(elems[0].clone(), elems[1].clone()),
+        node2::<T, M>(elems[2].clone(), elems[3].clone()),
+      ],
+      _ => {
+        let mut acc = vec![node3::<T, M>(
+          elems[0].clone(),
+          elems[1].clone(),
+          elems[2].clone(),
+        )];
+        acc.extend(nodes::<T, M>(&elems[3..]));
+        acc
+      }
+    }
+  }
+
+  // Concatenates t1, the elements in between and t2, going down only as deep
+  // as the shallowest of both trees.
+  pub fn concat_aux<T, M>(
+    t1: &Spine<T, M::Output>,
+    between: Vec<Elem<T, M::Output>>,
+    t2: &Spine<T, M::Output>,
+  ) -> Spine<T, M::Output>
+  where
+    M: Measure<T>,
+  {
+    match (t1.as_ref(), t2.as_ref()) {
+      (FingerTreeNode::Empty, _) => between
+        .into_iter()
+        .rev()
+        .fold(t2.clone(), |acc, node| push_front_aux::<T, M>(&acc, node)),
+      (_, FingerTreeNode::Empty) => between
+        .into_iter()
+        .fold(t1.clone(), |acc, node| push_back_aux::<T, M>(&acc, node)),
+      (FingerTreeNode::Single(node), _) => {
+        let rest = concat_aux::<T, M>(&empty(), between, t2);
+        push_front_aux::<T, M>(&rest, node.clone())
+      }
+      (_, FingerTreeNode::Single(node)) => {
+        let rest = concat_aux::<T, M>(t1, between, &empty());
+        push_back_aux::<T, M>(&rest, node.clone())
+      }
+      (
+        FingerTreeNode::Deep {
+          prefix: prefix1,
+          middle: middle1,
+          suffix: suffix1,
+          ..
+        },
+        FingerTreeNode::Deep {
+          prefix: prefix2,
+          middle: middle2,
+          suffix: suffix2,
+          ..
+        },
+      ) => {
+        let mut elems = suffix1.clone();
+        elems.extend(between);
+        elems.extend_from_slice(prefix2);
+        deep::<T, M>(
+          prefix1.clone(),
+          concat_aux::<T, M>(middle1, nodes::<T, M>(&elems), middle2),
+          suffix2.clone(),
+        )
+      }
+    }
+  }
+
+  // Splits a digit around the first element that turns `f` true once its
+  // measure is added to `acc`; the last one when none does.
+  fn split_digit<T, M, F>(
+    f: &F,
+    acc: M::Output,
+    digit: &[Elem<T, M::Output>],
+  ) -> DigitSplit<T, M::Output>
+  where
+    M: Measure<T>,
+    F: Fn(&M::Output) -> bool,
+  {
+    let mut acc = acc;
+    for (index, node) in digit.iter().enumerate() {
+      acc = M::combine(&acc, &node_measure::<T, M>(node));
+      if f(&acc) || index == digit.len() - 1 {
+        return (
+          digit[..index].to_vec(),
+          node.clone(),
+          digit[index + 1..].to_vec(),
+        );
+      }
+    }
+    unreachable!("digits are never empty")
+  }
+
+  // Splits a non-empty tree around the element on which `f` first becomes
+  // true, `acc` being the measure of everything before the tree.
+  pub fn split_tree_aux<T, M, F>(
+    f: &F,
+    acc: M::Output,
+    tree: &Spine<T, M::Output>,
+  ) -> TreeSplit<T, M::Output>
+  where
+    M: Measure<T>,
+    F: Fn(&M::Output) -> bool,
+  {
+    match tree.as_ref() {
+      FingerTreeNode::Empty => unreachable!("split_tree_aux needs a non-empty tree"),
+      FingerTreeNode::Single(node) => (empty(), node.clone(), empty()),
+      FingerTreeNode::Deep {
+        prefix,
+        middle,
+        suffix,
+        ..
+      } => {
+        let with_prefix = M::combine(&acc, &digit_measure::<T, M>(prefix));
+        if f(&with_prefix) {
+          let (before, node, after) = split_digit::<T, M, F>(f, acc, prefix);
+          return (
+            digit_to_tree::<T, M>(&before),
+            node,
+            deep_front::<T, M>(after, middle, suffix.clone()),
+          );
+        }
+        let with_middle = M::combine(&with_prefix, &tree_measure::<T, M>(middle));
+        if f(&with_middle) {
+          let (middle_before, middle_node, middle_after) =
+            split_tree_aux::<T, M, F>(f, with_prefix.clone(), middle);
+          let acc = M::combine(&with_prefix, &tree_measure::<T, M>(&middle_before));
+          let (before, node, after) = split_digit::<T, M, F>(f, acc, &node_to_digit(&middle_node));
+          return (
+            deep_back::<T, M>(prefix.clone(), &middle_before, before),
+            node,
+            deep_front::<T, M>(after, &middle_after, suffix.clone()),
+          );
+        }
+        let (before, node, after) = split_digit::<T, M, F>(f, with_middle, suffix);
+        (
+          deep_back::<T, M>(prefix.clone(), middle, before),
+          node,
+          digit_to_tree::<T, M>(&after),
+        )
+      }
+    }
+  }
+
+  pub fn first_value<T, V>(node: &FingerNode<T, V>) -> &T {
+    match node {
+      FingerNode::Leaf(value) => value,
+      FingerNode::Node2(_, a, _) => first_value(a),
+      FingerNode::Node3(_, a, _, _) => first_value(a),
+    }
+  }
+
+  pub fn last_value<T, V>(node: &FingerNode<T, V>) -> &T {
+    match node {
+      FingerNode::Leaf(value) => value,
+      FingerNode::Node2(_, _, b) => last_value(b),
+      FingerNode::Node3(_, _, _, c) => last_value(c),
+    }
+  }
+
+  fn find_node_aux<'a, T, V, F>(node: &'a FingerNode<T, V>, f: &F) -> Option<&'a T>
+  where
+    F: Fn(&T) -> bool,
+  {
+    match node {
+      FingerNode::Leaf(value) => f(value).then_some(value),
+      FingerNode::Node2(_, a, b) => find_node_aux(a, f).or_else(|| find_node_aux(b, f)),
+      FingerNode::Node3(_, a, b, c) => find_node_aux(a, f)
+        .or_else(|| find_node_aux(b, f))
+        .or_else(|| find_node_aux(c, f)),
+    }
+  }
+
+  pub fn find_tree_aux<'a, T, V, F>(tree: &'a FingerTreeNode<T, V>, f: &F) -> Option<&'a T>
+  where
+    F: Fn(&T) -> bool,
+  {
+    match tree {
+      FingerTreeNode::Empty => None,
+      FingerTreeNode::Single(node) => find_node_aux(node, f),
+      FingerTreeNode::Deep {
+        prefix,
+        middle,
+        suffix,
+        ..
+      } => prefix
+        .iter()
+        .find_map(|node| find_node_aux(node, f))
+        .or_else(|| find_tree_aux(middle, f))
+        .or_else(|| suffix.iter().find_map(|node| find_node_aux(node, f))),
+    }
+  }
+
+  fn reduce_node_aux<T, V, U>(node: &FingerNode<T, V>, f: fn(&T, U) -> U, acc: U) -> U {
+    match node {
+      FingerNode::Leaf(value) => f(value, acc),
+      FingerNode::Node2(_, a, b) => reduce_node_aux(b, f, reduce_node_aux(a, f, acc)),
+      FingerNode::Node3(_, a, b, c) => {
+        reduce_node_aux(c, f, reduce_node_aux(b, f, reduce_node_aux(a, f, acc)))
+      }
+    }
+  }
+
+  pub fn reduce_tree_aux<T, V, U>(tree: &FingerTreeNode<T, V>, f: fn(&T, U) -> U, acc: U) -> U {
+    match tree {
+      FingerTreeNode::Empty => acc,
+      FingerTreeNode::Single(node) => reduce_node_aux(node, f, acc),
+      FingerTreeNode::Deep {
+        prefix,
+        middle,
+        suffix,
+        ..
+      } => {
+        let acc = prefix
+          .iter()
+          .fold(acc, |acc, node| reduce_node_aux(node, f, acc));
+        let acc = reduce_tree_aux(middle, f, acc);
+        suffix
+          .iter()
+          .fold(acc, |acc, node| reduce_node_aux(node, f, acc))
+      }
+    }
+  }
+}
+
+impl<T, M> From<Vec<T>> for FingerTree<T, M>
+where
+  T: PartialEq + Clone + Copy,
+  M: Measure<T>,
+{
+  fn from(value: Vec<T>) -> Self {
+    value
+      .into_iter()
+      .fold(Self::new(), |acc, item| Self::push_back(&acc, item))
+  }
+}
+
+impl<T, M> From<FingerTree<T, M>> for Vec<T>
+where
+  T: PartialEq + Clone + Copy,
+  M: Measure<T>,
+{
+  fn from(value: FingerTree<T, M>) -> Self {
+    FingerTree::reduce(
+      &value,
+      |item, mut acc: Vec<T>| {
+        acc.push(*item);
+        acc
+      },
+      vec![],
+    )
+  }
+}
+
+impl<T, M> Default for FingerTree<T, M>
+where
+  T: PartialEq + Clone + Copy,
+  M: Measure<T>,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, M> FingerTree<T, M>
+where
+  T: PartialEq + Clone + Copy,
+  M: Measure<T>,
+{
+  pub fn new() -> Self {
+    Self {
+      root: private::empty(),
+      marker: PhantomData,
+    }
+  }
+
+  fn tree(root: Spine<T, M::Output>) -> Self {
+    Self {
+      root,
+      marker: PhantomData,
+    }
+  }
+
+  pub fn is_empty(tree: &Self) -> bool {
+    matches!(tree.root.as_ref(), FingerTreeNode::Empty)
+  }
+
+  // O(n), with `Count` as the measure `measure` gives it in O(1).
+  pub fn len(tree: &Self) -> i32 {
+    Self::reduce(tree, |_, acc| acc + 1, 0)
+  }
+
+  // The combined measure of every value, in O(1).
+  pub fn measure(tree: &Self) -> M::Output {
+    private::tree_measure::<T, M>(&tree.root)
+  }
+
+  pub fn push_front(tree: &Self, item: T) -> Self {
+    Self::tree(private::push_front_aux::<T, M>(
+      &tree.root,
+      private::leaf(item),
+    ))
+  }
+
+  pub fn push_back(tree: &Self, item: T) -> Self {
+    Self::tree(private::push_back_aux::<T, M>(
+      &tree.root,
+      private::leaf(item),
+    ))
+  }
+
+  pub fn pop_front(tree: &Self) -> Option<(T, Self)> {
+    private::pop_front_aux::<T, M>(&tree.root)
+      .map(|(node, rest)| (*private::first_value(&node), Self::tree(rest)))
+  }
+
+  pub fn pop_back(tree: &Self) -> Option<(T, Self)> {
+    private::pop_back_aux::<T, M>(&tree.root)
+      .map(|(node, rest)| (*private::last_value(&node), Self::tree(rest)))
+  }
+
+  pub fn front(tree: &Self) -> Option<&T> {
+    match tree.root.as_ref() {
+      FingerTreeNode::Empty => None,
+      FingerTreeNode::Single(node) => Some(private::first_value(node)),
+      FingerTreeNode::Deep { prefix, .. } => Some(private::first_value(&prefix[0])),
+    }
+  }
+
+  pub fn back(tree: &Self) -> Option<&T> {
+    match tree.root.as_ref() {
+      FingerTreeNode::Empty => None,
+      FingerTreeNode::Single(node) => Some(private::last_value(node)),
+      FingerTreeNode::Deep { suffix, .. } => Some(private::last_value(&suffix[suffix.len() - 1])),
+    }
+  }
+
+  pub fn concat(t1: &Self, t2: &Self) -> Self {
+    Self::tree(private::concat_aux::<T, M>(&t1.root, vec![], &t2.root))
+  }
+
+  // Splits before the first value whose prefix measure, up to and including
+  // it, makes `f` true. `f` has to be monotone; when it never becomes true
+  // everything ends up in the first half.
+  pub fn split<F>(tree: &Self, f: F) -> (Self, Self)
+  where
+    F: Fn(&M::Output) -> bool,
+  {
+    if Self::is_empty(tree) || !f(&Self::measure(tree)) {
+      return (tree.clone(), Self::new());
+    }
+    let (before, node, after) = private::split_tree_aux::<T, M, F>(&f, M::identity(), &tree.root);
+    (
+      Self::tree(before),
+      Self::tree(private::push_front_aux::<T, M>(&after, node)),
+    )
+  }
+
+  // The first value whose prefix measure, up to and including it, makes `f`
+  // true, like `MeasuredTree::search_by`.
+  pub fn search_by<F>(tree: &Self, f: F) -> Option<T>
+  where
+    F: Fn(&M::Output) -> bool,
+  {
+    if Self::is_empty(tree) || !f(&Self::measure(tree)) {
+      return None;
+    }
+    let (_, node, _) = private::split_tree_aux::<T, M, F>(&f, M::identity(), &tree.root);
+    Some(*private::first_value(&node))
+  }
+
+  pub fn any(tree: &Self, f: fn(&T) -> bool) -> bool {
+    private::find_tree_aux(&tree.root, &f).is_some()
+  }
+
+  pub fn all(tree: &Self, f: fn(&T) -> bool) -> bool {
+    private::find_tree_aux(&tree.root, &|item: &T| !f(item)).is_none()
+  }
+
+  pub fn find(tree: &Self, f: fn(&T) -> bool) -> Option<&T> {
+    private::find_tree_aux(&tree.root, &f)
+  }
+
+  pub fn reduce<U>(tree: &Self, f: fn(&T, U) -> U, acc: U) -> U {
+    private::reduce_tree_aux(&tree.root, f, acc)
+  }
+}
+
+#[cfg(test)]
+#[path = "./finger-tree_test.rs"]
+mod test;
@@ -0,0 +1,522 @@
+use super::*;
+use crate::{Count, Max, Sum};
+
+mod setup {
+  use super::*;
+
+  pub type SequenceT = FingerTree<i32, Count>;
+
+  pub fn leaf(value: i32) -> Elem<i32, i32> {
+    private::leaf(value)
+  }
+
+  pub fn finger_tree_empty() -> SequenceT {
+    SequenceT {
+      root: Rc::new(FingerTreeNode::Empty),
+      marker: PhantomData,
+    }
+  }
+
+  pub fn finger_tree_filled() -> SequenceT {
+    SequenceT {
+      root: private::deep::<i32, Count>(
+        vec![leaf(0), leaf(1)],
+        private::empty(),
+        vec![leaf(2), leaf(3)],
+      ),
+      marker: PhantomData,
+    }
+  }
+
+  pub fn from_range<M: Measure<i32>>(start: i32, end: i32) -> FingerTree<i32, M> {
+    FingerTree::from((start..end).collect::<Vec<i32>>())
+  }
+
+  pub fn to_vec<M: Measure<i32>>(tree: &FingerTree<i32, M>) -> Vec<i32> {
+    Vec::from(tree.clone())
+  }
+
+  // Checks the shape of every level, returning how many values it holds.
+  fn check_node_aux(node: &FingerNode<i32, i32>, depth: usize) -> i32 {
+    match node {
+      FingerNode::Leaf(_) => {
+        assert_eq!(depth, 0);
+        1
+      }
+      FingerNode::Node2(measure, a, b) => {
+        assert!(depth > 0);
+        let count = check_node_aux(a, depth - 1) + check_node_aux(b, depth - 1);
+        assert_eq!(*measure, count);
+        count
+      }
+      FingerNode::Node3(measure, a, b, c) => {
+        assert!(depth > 0);
+        let count = check_node_aux(a, depth - 1)
+          + check_node_aux(b, depth - 1)
+          + check_node_aux(c, depth - 1);
+        assert_eq!(*measure, count);
+        count
+      }
+    }
+  }
+
+  fn check_tree_aux(tree: &FingerTreeNode<i32, i32>, depth: usize) -> i32 {
+    match tree {
+      FingerTreeNode::Empty => 0,
+      FingerTreeNode::Single(node) => check_node_aux(node, depth),
+      FingerTreeNode::Deep {
+        measure,
+        prefix,
+        middle,
+        suffix,
+      } => {
+        assert!((1..=4).contains(&prefix.len()));
+        assert!((1..=4).contains(&suffix.len()));
+        let count = prefix
+          .iter()
+          .chain(suffix)
+          .map(|node| check_node_aux(node, depth))
+          .sum::<i32>()
+          + check_tree_aux(middle, depth + 1);
+        assert_eq!(*measure, count);
+        count
+      }
+    }
+  }
+
+  pub fn check(tree: &SequenceT) {
+    check_tree_aux(&tree.root, 0);
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = FingerTree::<i32, Count>::new();
+    assert_eq!(op, setup::finger_tree_empty())
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(FingerTree::is_empty(&setup::finger_tree_empty()))
+  }
+
+  #[test]
+  fn to_filled() {
+    assert!(!FingerTree::is_empty(&setup::finger_tree_filled()))
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(FingerTree::len(&setup::finger_tree_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(FingerTree::len(&setup::finger_tree_filled()), 4)
+  }
+}
+
+#[cfg(test)]
+mod measure {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(FingerTree::measure(&setup::finger_tree_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(FingerTree::measure(&setup::finger_tree_filled()), 4);
+    assert_eq!(FingerTree::measure(&setup::from_range::<Sum>(0, 100)), 4950);
+    assert_eq!(
+      FingerTree::measure(&setup::from_range::<(Count, Max)>(0, 100)),
+      (100, Some(99))
+    )
+  }
+}
+
+#[cfg(test)]
+mod push_front {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::finger_tree_empty();
+    let op = FingerTree::push_front(&tree, 0);
+    let expected = FingerTree {
+      root: Rc::new(FingerTreeNode::Single(setup::leaf(0))),
+      marker: PhantomData,
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::finger_tree_filled();
+    let op = FingerTree::push_front(&tree, -1);
+    let expected = FingerTree {
+      root: private::deep::<i32, Count>(
+        vec![setup::leaf(-1), setup::leaf(0), setup::leaf(1)],
+        private::empty(),
+        vec![setup::leaf(2), setup::leaf(3)],
+      ),
+      marker: PhantomData,
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_many() {
+    let op = (0..1000).rev().fold(FingerTree::new(), |acc, item| {
+      FingerTree::push_front(&acc, item)
+    });
+    setup::check(&op);
+    assert_eq!(setup::to_vec(&op), (0..1000).collect::<Vec<i32>>())
+  }
+}
+
+#[cfg(test)]
+mod push_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::finger_tree_empty();
+    let op = FingerTree::push_back(&tree, 0);
+    let expected = FingerTree {
+      root: Rc::new(FingerTreeNode::Single(setup::leaf(0))),
+      marker: PhantomData,
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::finger_tree_filled();
+    let op = FingerTree::push_back(&tree, 4);
+    let expected = FingerTree {
+      root: private::deep::<i32, Count>(
+        vec![setup::leaf(0), setup::leaf(1)],
+        private::empty(),
+        vec![setup::leaf(2), setup::leaf(3), setup::leaf(4)],
+      ),
+      marker: PhantomData,
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_many() {
+    let op = setup::from_range::<Count>(0, 1000);
+    setup::check(&op);
+    assert_eq!(setup::to_vec(&op), (0..1000).collect::<Vec<i32>>())
+  }
+}
+
+#[cfg(test)]
+mod pop_front {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(FingerTree::pop_front(&setup::finger_tree_empty()), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let (item, op) = FingerTree::pop_front(&setup::finger_tree_filled()).unwrap();
+    assert_eq!(item, 0);
+    assert_eq!(setup::to_vec(&op), vec![1, 2, 3])
+  }
+
+  #[test]
+  fn to_filled_until_empty() {
+    let mut tree = setup::from_range::<Count>(0, 500);
+    for expected in 0..500 {
+      let (item, rest) = FingerTree::pop_front(&tree).unwrap();
+      assert_eq!(item, expected);
+      setup::check(&rest);
+      tree = rest;
+    }
+    assert_eq!(tree, setup::finger_tree_empty())
+  }
+}
+
+#[cfg(test)]
+mod pop_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(FingerTree::pop_back(&setup::finger_tree_empty()), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let (item, op) = FingerTree::pop_back(&setup::finger_tree_filled()).unwrap();
+    assert_eq!(item, 3);
+    assert_eq!(setup::to_vec(&op), vec![0, 1, 2])
+  }
+
+  #[test]
+  fn to_filled_from_both_ends() {
+    let mut tree = setup::from_range::<Count>(0, 500);
+    for expected in 0..250 {
+      let (first, rest) = FingerTree::pop_front(&tree).unwrap();
+      let (last, rest) = FingerTree::pop_back(&rest).unwrap();
+      assert_eq!((first, last), (expected, 499 - expected));
+      setup::check(&rest);
+      tree = rest;
+    }
+    assert!(FingerTree::is_empty(&tree))
+  }
+}
+
+#[cfg(test)]
+mod front {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(FingerTree::front(&setup::finger_tree_empty()), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(FingerTree::front(&setup::finger_tree_filled()), Some(&0));
+    assert_eq!(
+      FingerTree::front(&setup::from_range::<Count>(7, 300)),
+      Some(&7)
+    )
+  }
+}
+
+#[cfg(test)]
+mod back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(FingerTree::back(&setup::finger_tree_empty()), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(FingerTree::back(&setup::finger_tree_filled()), Some(&3));
+    assert_eq!(
+      FingerTree::back(&setup::from_range::<Count>(7, 300)),
+      Some(&299)
+    )
+  }
+}
+
+#[cfg(test)]
+mod concat {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::finger_tree_empty();
+    assert_eq!(FingerTree::concat(&tree, &tree), setup::finger_tree_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let t1 = setup::finger_tree_filled();
+    let t2 = setup::from_range::<Count>(4, 10);
+    let op = FingerTree::concat(&t1, &t2);
+    setup::check(&op);
+    assert_eq!(setup::to_vec(&op), (0..10).collect::<Vec<i32>>())
+  }
+
+  #[test]
+  fn to_every_pair_of_sizes() {
+    for n1 in 0..30 {
+      for n2 in 0..30 {
+        let t1 = setup::from_range::<Count>(0, n1);
+        let t2 = setup::from_range::<Count>(n1, n1 + n2);
+        let op = FingerTree::concat(&t1, &t2);
+        setup::check(&op);
+        assert_eq!(setup::to_vec(&op), (0..n1 + n2).collect::<Vec<i32>>())
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod split {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::finger_tree_empty();
+    let (op1, op2) = FingerTree::split(&tree, |count| *count > 0);
+    assert!(FingerTree::is_empty(&op1));
+    assert!(FingerTree::is_empty(&op2))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::finger_tree_filled();
+    let (op1, op2) = FingerTree::split(&tree, |count| *count > 1);
+    assert_eq!(setup::to_vec(&op1), vec![0]);
+    assert_eq!(setup::to_vec(&op2), vec![1, 2, 3])
+  }
+
+  #[test]
+  fn to_filled_never_true() {
+    let tree = setup::finger_tree_filled();
+    let (op1, op2) = FingerTree::split(&tree, |count| *count > 10);
+    assert_eq!(op1, tree);
+    assert!(FingerTree::is_empty(&op2))
+  }
+
+  #[test]
+  fn to_filled_at_every_index() {
+    let tree = setup::from_range::<Count>(0, 200);
+    for index in 0..=200 {
+      let (op1, op2) = FingerTree::split(&tree, |count| *count > index);
+      setup::check(&op1);
+      setup::check(&op2);
+      assert_eq!(setup::to_vec(&op1), (0..index).collect::<Vec<i32>>());
+      assert_eq!(setup::to_vec(&op2), (index..200).collect::<Vec<i32>>())
+    }
+  }
+
+  #[test]
+  fn to_ordered_sequence() {
+    // Keeping the values sorted with the greatest value as the measure: a
+    // new value goes right before the first one that is not smaller.
+    let insert = |tree: &FingerTree<i32, Max>, item: i32| {
+      let (lower, upper) = FingerTree::split(tree, |max| *max >= Some(item));
+      FingerTree::concat(&FingerTree::push_back(&lower, item), &upper)
+    };
+    let op = [5, 1, 9, 3, 7, 1, 8]
+      .into_iter()
+      .fold(FingerTree::new(), |acc, item| insert(&acc, item));
+    assert_eq!(setup::to_vec(&op), vec![1, 1, 3, 5, 7, 8, 9])
+  }
+}
+
+#[cfg(test)]
+mod search_by {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::finger_tree_empty();
+    assert_eq!(FingerTree::search_by(&tree, |count| *count > 0), None)
+  }
+
+  #[test]
+  fn to_indexed_sequence() {
+    let tree = setup::from_range::<Count>(100, 200);
+    assert_eq!(FingerTree::search_by(&tree, |count| *count > 42), Some(142));
+    assert_eq!(FingerTree::search_by(&tree, |count| *count > 100), None)
+  }
+
+  #[test]
+  fn to_priority_queue() {
+    let tree: FingerTree<i32, Max> = FingerTree::from(vec![4, 8, 1, 9, 2, 9, 3]);
+    let max = FingerTree::measure(&tree);
+    assert_eq!(max, Some(9));
+    assert_eq!(FingerTree::search_by(&tree, |m| *m == max), Some(9));
+    let (before, rest) = FingerTree::split(&tree, |m| *m == max);
+    let (_, after) = FingerTree::pop_front(&rest).unwrap();
+    let op = FingerTree::concat(&before, &after);
+    assert_eq!(setup::to_vec(&op), vec![4, 8, 1, 2, 9, 3]);
+    assert_eq!(FingerTree::measure(&op), Some(9))
+  }
+}
+
+#[cfg(test)]
+mod any {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(!FingerTree::any(&setup::finger_tree_empty(), |item| *item == 3))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::from_range::<Count>(0, 100);
+    assert!(FingerTree::any(&tree, |item| *item == 57));
+    assert!(!FingerTree::any(&tree, |item| *item > 99))
+  }
+}
+
+#[cfg(test)]
+mod all {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(FingerTree::all(&setup::finger_tree_empty(), |item| *item > 0))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::from_range::<Count>(0, 100);
+    assert!(FingerTree::all(&tree, |item| *item < 100));
+    assert!(!FingerTree::all(&tree, |item| *item < 99))
+  }
+}
+
+#[cfg(test)]
+mod find {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(
+      FingerTree::find(&setup::finger_tree_empty(), |_| true),
+      None
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::from_range::<Count>(0, 100);
+    assert_eq!(FingerTree::find(&tree, |item| item % 37 == 36), Some(&36))
+  }
+}
+
+#[cfg(test)]
+mod reduce {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::finger_tree_empty();
+    assert_eq!(FingerTree::reduce(&tree, |item, acc| item + acc, 0), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::finger_tree_filled();
+    assert_eq!(
+      FingerTree::reduce(
+        &tree,
+        |item, acc: Vec<i32>| [acc, vec![*item]].concat(),
+        vec![]
+      ),
+      vec![0, 1, 2, 3]
+    )
+  }
+}
@@ -0,0 +1,441 @@
+use std::rc::Rc;
+
+use crate::{IndexError, SplitMix64};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ImplicitTreapNode<T> {
+  Empty,
+  Node {
+    value: T,
+    priority: u64,
+    size: usize,
+    left: Rc<ImplicitTreapNode<T>>,
+    right: Rc<ImplicitTreapNode<T>>,
+  },
+}
+
+// A treap keyed by position instead of by value: the key of a node is the
+// size of everything to its left, so nothing is ever compared and the tree
+// behaves as a sequence.
+//
+// Invariants:
+//    - size is 1 + the size of both children
+//    - the node priority is always >= the priority of both children
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImplicitTreap<T> {
+  root: Rc<ImplicitTreapNode<T>>,
+  rng: SplitMix64,
+}
+
+mod private {
+  use super::*;
+
+  pub fn size<T>(node: &ImplicitTreapNode<T>) -> usize {
+    match node {
+      ImplicitTreapNode::Empty => 0,
+      ImplicitTreapNode::Node { size, .. } => *size,
+    }
+  }
+
+  pub fn node<T>(
+    value: T,
+    priority: u64,
+    left: Rc<ImplicitTreapNode<T>>,
+    right: Rc<ImplicitTreapNode<T>>,
+  ) -> Rc<ImplicitTreapNode<T>> {
+    Rc::new(ImplicitTreapNode::Node {
+      value,
+      priority,
+      size: size(&left) + size(&right) + 1,
+      left,
+      right,
+    })
+  }
+
+  pub fn leaf<T>(value: T, priority: u64) -> Rc<ImplicitTreapNode<T>> {
+    node(
+      value,
+      priority,
+      Rc::new(ImplicitTreapNode::Empty),
+      Rc::new(ImplicitTreapNode::Empty),
+    )
+  }
+
+  pub fn split_node_aux<T>(
+    node: &Rc<ImplicitTreapNode<T>>,
+    index: usize,
+  ) -> (Rc<ImplicitTreapNode<T>>, Rc<ImplicitTreapNode<T>>)
+  where
+    T: Clone,
+  {
+    match node.as_ref() {
+      ImplicitTreapNode::Empty => (node.clone(), node.clone()),
+      ImplicitTreapNode::Node {
+        value,
+        priority,
+        left,
+        right,
+        ..
+      } => {
+        let left_size = size(left);
+        if index <= left_size {
+          let (lower, upper) = split_node_aux(left, index);
+          (
+            lower,
+            self::node(value.clone(), *priority, upper, right.clone()),
+          )
+        } else {
+          let (lower, upper) = split_node_aux(right, index - left_size - 1);
+          (
+            self::node(value.clone(), *priority, left.clone(), lower),
+            upper,
+          )
+        }
+      }
+    }
+  }
+
+  pub fn merge_node_aux<T>(
+    n1: &Rc<ImplicitTreapNode<T>>,
+    n2: &Rc<ImplicitTreapNode<T>>,
+  ) -> Rc<ImplicitTreapNode<T>>
+  where
+    T: Clone,
+  {
+    match (n1.as_ref(), n2.as_ref()) {
+      (ImplicitTreapNode::Empty, _) => n2.clone(),
+      (_, ImplicitTreapNode::Empty) => n1.clone(),
+      (
+        ImplicitTreapNode::Node {
+          value: v1,
+          priority: p1,
+          left: l1,
+          right: r1,
+          ..
+        },
+        ImplicitTreapNode::Node {
+          value: v2,
+          priority: p2,
+          left: l2,
+          right: r2,
+          ..
+        },
+      ) => {
+        if p1 >= p2 {
+          node(v1.clone(), *p1, l1.clone(), merge_node_aux(r1, n2))
+        } else {
+          node(v2.clone(), *p2, merge_node_aux(n1, l2), r2.clone())
+        }
+      }
+    }
+  }
+
+  pub fn get_node_aux<T>(node: &ImplicitTreapNode<T>, index: usize) -> Option<&T> {
+    match node {
+      ImplicitTreapNode::Empty => None,
+      ImplicitTreapNode::Node {
+        value, left, right, ..
+      } => {
+        let left_size = size(left);
+        if index < left_size {
+          get_node_aux(left, index)
+        } else if index == left_size {
+          Some(value)
+        } else {
+          get_node_aux(right, index - left_size - 1)
+        }
+      }
+    }
+  }
+
+  pub fn set_node_aux<T>(
+    node: &Rc<ImplicitTreapNode<T>>,
+    index: usize,
+    item: T,
+  ) -> Rc<ImplicitTreapNode<T>>
+  where
+    T: Clone,
+  {
+    match node.as_ref() {
+      ImplicitTreapNode::Empty => node.clone(),
+      ImplicitTreapNode::Node {
+        value,
+        priority,
+        left,
+        right,
+        ..
+      } => {
+        let left_size = size(left);
+        if index < left_size {
+          self::node(
+            value.clone(),
+            *priority,
+            set_node_aux(left, index, item),
+            right.clone(),
+          )
+        } else if index == left_size {
+          self::node(item, *priority, left.clone(), right.clone())
+        } else {
+          self::node(
+            value.clone(),
+            *priority,
+            left.clone(),
+            set_node_aux(right, index - left_size - 1, item),
+          )
+        }
+      }
+    }
+  }
+
+  pub fn height_node_aux<T>(node: &ImplicitTreapNode<T>) -> i32 {
+    match node {
+      ImplicitTreapNode::Empty => 0,
+      ImplicitTreapNode::Node { left, right, .. } => {
+        std::cmp::max(height_node_aux(left), height_node_aux(right)) + 1
+      }
+    }
+  }
+
+  pub fn any_node_aux<T>(node: &ImplicitTreapNode<T>, f: fn(&T) -> bool) -> bool {
+    match node {
+      ImplicitTreapNode::Empty => false,
+      ImplicitTreapNode::Node {
+        value, left, right, ..
+      } => any_node_aux(left, f) || f(value) || any_node_aux(right, f),
+    }
+  }
+
+  pub fn all_node_aux<T>(node: &ImplicitTreapNode<T>, f: fn(&T) -> bool) -> bool {
+    match node {
+      ImplicitTreapNode::Empty => true,
+      ImplicitTreapNode::Node {
+        value, left, right, ..
+      } => all_node_aux(left, f) && f(value) && all_node_aux(right, f),
+    }
+  }
+
+  pub fn find_node_aux<T>(node: &ImplicitTreapNode<T>, f: fn(&T) -> bool) -> Option<&T> {
+    match node {
+      ImplicitTreapNode::Empty => None,
+      ImplicitTreapNode::Node {
+        value, left, right, ..
+      } => match find_node_aux(left, f) {
+        Some(value) => Some(value),
+        None if f(value) => Some(value),
+        None => find_node_aux(right, f),
+      },
+    }
+  }
+
+  pub fn map_node_aux<T, U>(
+    node: &ImplicitTreapNode<T>,
+    f: fn(&T) -> U,
+  ) -> Rc<ImplicitTreapNode<U>> {
+    match node {
+      ImplicitTreapNode::Empty => Rc::new(ImplicitTreapNode::Empty),
+      ImplicitTreapNode::Node {
+        value,
+        priority,
+        left,
+        right,
+        ..
+      } => self::node(
+        f(value),
+        *priority,
+        map_node_aux(left, f),
+        map_node_aux(right, f),
+      ),
+    }
+  }
+
+  pub fn reduce_node_aux<T, U>(node: &ImplicitTreapNode<T>, f: fn(&T, U) -> U, acc: U) -> U {
+    match node {
+      ImplicitTreapNode::Empty => acc,
+      ImplicitTreapNode::Node {
+        value, left, right, ..
+      } => reduce_node_aux(right, f, f(value, reduce_node_aux(left, f, acc))),
+    }
+  }
+}
+
+impl<T> From<Vec<T>> for ImplicitTreap<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  fn from(value: Vec<T>) -> Self {
+    value
+      .into_iter()
+      .fold(Self::new(), |tree, item| Self::push_back(&tree, item))
+  }
+}
+
+impl<T> From<ImplicitTreap<T>> for Vec<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  fn from(value: ImplicitTreap<T>) -> Self {
+    ImplicitTreap::reduce(
+      &value,
+      |item, mut acc: Vec<T>| {
+        acc.push(*item);
+        acc
+      },
+      vec![],
+    )
+  }
+}
+
+impl<T> Default for ImplicitTreap<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> ImplicitTreap<T>
+where
+  T: PartialEq + Clone + Copy,
+{
+  pub fn new() -> Self {
+    Self {
+      root: Rc::new(ImplicitTreapNode::Empty),
+      rng: SplitMix64::default(),
+    }
+  }
+
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      root: Rc::new(ImplicitTreapNode::Empty),
+      rng: SplitMix64::new(seed),
+    }
+  }
+
+  pub fn is_empty(tree: &Self) -> bool {
+    matches!(tree.root.as_ref(), ImplicitTreapNode::Empty)
+  }
+
+  pub fn len(tree: &Self) -> i32 {
+    private::size(&tree.root) as i32
+  }
+
+  pub fn height(tree: &Self) -> i32 {
+    private::height_node_aux(&tree.root)
+  }
+
+  pub fn get(tree: &Self, index: usize) -> Option<&T> {
+    private::get_node_aux(&tree.root, index)
+  }
+
+  pub fn set(tree: &Self, index: usize, item: T) -> Result<Self, IndexError> {
+    if index >= private::size(&tree.root) {
+      return Err(IndexError::OutOfBounds);
+    }
+    Ok(Self {
+      root: private::set_node_aux(&tree.root, index, item),
+      rng: tree.rng,
+    })
+  }
+
+  pub fn insert_at(tree: &Self, index: usize, item: T) -> Result<Self, IndexError> {
+    if index > private::size(&tree.root) {
+      return Err(IndexError::OutOfBounds);
+    }
+    let mut rng = tree.rng;
+    let (lower, upper) = private::split_node_aux(&tree.root, index);
+    let middle = private::leaf(item, rng.next_u64());
+    Ok(Self {
+      root: private::merge_node_aux(&private::merge_node_aux(&lower, &middle), &upper),
+      rng,
+    })
+  }
+
+  pub fn remove_at(tree: &Self, index: usize) -> Result<(T, Self), IndexError> {
+    if index >= private::size(&tree.root) {
+      return Err(IndexError::OutOfBounds);
+    }
+    let (lower, rest) = private::split_node_aux(&tree.root, index);
+    let (middle, upper) = private::split_node_aux(&rest, 1);
+    match middle.as_ref() {
+      ImplicitTreapNode::Empty => Err(IndexError::OutOfBounds),
+      ImplicitTreapNode::Node { value, .. } => Ok((
+        *value,
+        Self {
+          root: private::merge_node_aux(&lower, &upper),
+          rng: tree.rng,
+        },
+      )),
+    }
+  }
+
+  pub fn push_front(tree: &Self, item: T) -> Self {
+    let mut rng = tree.rng;
+    Self {
+      root: private::merge_node_aux(&private::leaf(item, rng.next_u64()), &tree.root),
+      rng,
+    }
+  }
+
+  pub fn push_back(tree: &Self, item: T) -> Self {
+    let mut rng = tree.rng;
+    Self {
+      root: private::merge_node_aux(&tree.root, &private::leaf(item, rng.next_u64())),
+      rng,
+    }
+  }
+
+  // Splits into the first `index` items and the rest; an index past the end
+  // leaves the second half empty. The second half is reseeded so that the
+  // two halves don't draw the same priorities from then on.
+  pub fn split_at(tree: &Self, index: usize) -> (Self, Self) {
+    let (lower, upper) = private::split_node_aux(&tree.root, index);
+    let mut rng = tree.rng;
+    (
+      Self {
+        root: lower,
+        rng: tree.rng,
+      },
+      Self {
+        root: upper,
+        rng: SplitMix64::new(rng.next_u64()),
+      },
+    )
+  }
+
+  // Keeps drawing from `t1`'s generator: the priorities already in `t2` stay
+  // where they are, so only one stream has to carry on.
+  pub fn concat(t1: &Self, t2: &Self) -> Self {
+    Self {
+      root: private::merge_node_aux(&t1.root, &t2.root),
+      rng: t1.rng,
+    }
+  }
+
+  pub fn any(tree: &Self, f: fn(&T) -> bool) -> bool {
+    private::any_node_aux(&tree.root, f)
+  }
+
+  pub fn all(tree: &Self, f: fn(&T) -> bool) -> bool {
+    private::all_node_aux(&tree.root, f)
+  }
+
+  pub fn find(tree: &Self, f: fn(&T) -> bool) -> Option<&T> {
+    private::find_node_aux(&tree.root, f)
+  }
+
+  pub fn map<U>(tree: &Self, f: fn(&T) -> U) -> ImplicitTreap<U> {
+    ImplicitTreap::<U> {
+      root: private::map_node_aux(&tree.root, f),
+      rng: tree.rng,
+    }
+  }
+
+  pub fn reduce<U>(tree: &Self, f: fn(&T, U) -> U, acc: U) -> U {
+    private::reduce_node_aux(&tree.root, f, acc)
+  }
+}
+
+#[cfg(test)]
+#[path = "./implicit-treap_test.rs"]
+mod test;
@@ -0,0 +1,479 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type ImplicitTreapT = ImplicitTreap<i32>;
+
+  pub fn node<T>(
+    value: T,
+    priority: u64,
+    left: ImplicitTreapNode<T>,
+    right: ImplicitTreapNode<T>,
+  ) -> ImplicitTreapNode<T> {
+    ImplicitTreapNode::Node {
+      value,
+      priority,
+      size: private::size(&left) + private::size(&right) + 1,
+      left: Rc::new(left),
+      right: Rc::new(right),
+    }
+  }
+
+  pub fn leaf<T>(value: T, priority: u64) -> ImplicitTreapNode<T> {
+    node(
+      value,
+      priority,
+      ImplicitTreapNode::Empty,
+      ImplicitTreapNode::Empty,
+    )
+  }
+
+  pub fn implicit_treap_empty() -> ImplicitTreapT {
+    ImplicitTreapT {
+      root: Rc::new(ImplicitTreapNode::Empty),
+      rng: SplitMix64::default(),
+    }
+  }
+
+  pub fn implicit_treap_filled() -> ImplicitTreapT {
+    /*
+           40 (70)
+         /        \
+      20 (50)    60 (60)
+       / \        / \
+     10   30    50   70
+    (10) (20)  (30) (40)
+    */
+    ImplicitTreapT {
+      root: Rc::new(node(
+        40,
+        70,
+        node(20, 50, leaf(10, 10), leaf(30, 20)),
+        node(60, 60, leaf(50, 30), leaf(70, 40)),
+      )),
+      rng: SplitMix64::default(),
+    }
+  }
+
+  fn check_node<T>(node: &ImplicitTreapNode<T>, max_priority: u64) {
+    if let ImplicitTreapNode::Node {
+      priority,
+      size,
+      left,
+      right,
+      ..
+    } = node
+    {
+      assert!(*priority <= max_priority, "heap order broken");
+      assert_eq!(*size, private::size(left) + private::size(right) + 1);
+      check_node(left, *priority);
+      check_node(right, *priority);
+    }
+  }
+
+  pub fn check_invariants<T>(tree: &ImplicitTreap<T>) {
+    check_node(&tree.root, u64::MAX)
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = ImplicitTreap::<i32>::new();
+    let expected = setup::implicit_treap_empty();
+    assert_eq!(op, expected)
+  }
+}
+
+#[cfg(test)]
+mod from {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = ImplicitTreap::<i32>::from(vec![]);
+    assert_eq!(op, setup::implicit_treap_empty())
+  }
+
+  #[test]
+  fn round_trip() {
+    let op = ImplicitTreap::from(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+    setup::check_invariants(&op);
+    assert_eq!(Vec::from(op), vec![3, 1, 4, 1, 5, 9, 2, 6])
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert!(ImplicitTreap::is_empty(&tree))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    assert!(!ImplicitTreap::is_empty(&tree))
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert_eq!(ImplicitTreap::len(&tree), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    assert_eq!(ImplicitTreap::len(&tree), 7)
+  }
+}
+
+#[cfg(test)]
+mod height {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert_eq!(ImplicitTreap::height(&tree), 0)
+  }
+
+  #[test]
+  fn to_long_sequence() {
+    let tree = (0..1024).fold(ImplicitTreap::with_seed(5), |tree, item| {
+      ImplicitTreap::push_back(&tree, item)
+    });
+    setup::check_invariants(&tree);
+    assert!(ImplicitTreap::height(&tree) < 40)
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert_eq!(ImplicitTreap::get(&tree, 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    assert_eq!(ImplicitTreap::get(&tree, 0), Some(&10));
+    assert_eq!(ImplicitTreap::get(&tree, 3), Some(&40));
+    assert_eq!(ImplicitTreap::get(&tree, 6), Some(&70))
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let tree = setup::implicit_treap_filled();
+    assert_eq!(ImplicitTreap::get(&tree, 7), None)
+  }
+}
+
+#[cfg(test)]
+mod set {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert_eq!(
+      ImplicitTreap::set(&tree, 0, 1),
+      Err(IndexError::OutOfBounds)
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    let op = ImplicitTreap::set(&tree, 4, 55).unwrap();
+    assert_eq!(Vec::from(op), vec![10, 20, 30, 40, 55, 60, 70]);
+    assert_eq!(Vec::from(tree), vec![10, 20, 30, 40, 50, 60, 70])
+  }
+}
+
+#[cfg(test)]
+mod insert_at {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    let op = ImplicitTreap::insert_at(&tree, 0, 1).unwrap();
+    assert_eq!(Vec::from(op), vec![1])
+  }
+
+  #[test]
+  fn to_filled_at_beginning() {
+    let tree = setup::implicit_treap_filled();
+    let op = ImplicitTreap::insert_at(&tree, 0, 0).unwrap();
+    setup::check_invariants(&op);
+    assert_eq!(Vec::from(op), vec![0, 10, 20, 30, 40, 50, 60, 70])
+  }
+
+  #[test]
+  fn to_filled_in_the_middle() {
+    let tree = setup::implicit_treap_filled();
+    let op = ImplicitTreap::insert_at(&tree, 3, 35).unwrap();
+    setup::check_invariants(&op);
+    assert_eq!(Vec::from(op), vec![10, 20, 30, 35, 40, 50, 60, 70])
+  }
+
+  #[test]
+  fn to_filled_at_end() {
+    let tree = setup::implicit_treap_filled();
+    let op = ImplicitTreap::insert_at(&tree, 7, 80).unwrap();
+    assert_eq!(Vec::from(op), vec![10, 20, 30, 40, 50, 60, 70, 80])
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let tree = setup::implicit_treap_filled();
+    let op = ImplicitTreap::insert_at(&tree, 8, 80);
+    assert_eq!(op, Err(IndexError::OutOfBounds))
+  }
+}
+
+#[cfg(test)]
+mod remove_at {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert_eq!(
+      ImplicitTreap::remove_at(&tree, 0),
+      Err(IndexError::OutOfBounds)
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    let (value, op) = ImplicitTreap::remove_at(&tree, 3).unwrap();
+    setup::check_invariants(&op);
+    assert_eq!(value, 40);
+    assert_eq!(Vec::from(op), vec![10, 20, 30, 50, 60, 70])
+  }
+
+  #[test]
+  fn to_filled_last() {
+    let tree = setup::implicit_treap_filled();
+    let (value, op) = ImplicitTreap::remove_at(&tree, 6).unwrap();
+    assert_eq!(value, 70);
+    assert_eq!(Vec::from(op), vec![10, 20, 30, 40, 50, 60])
+  }
+}
+
+#[cfg(test)]
+mod push_front {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = ImplicitTreap::with_seed(1);
+    let op = ImplicitTreap::push_front(&tree, 1);
+    let mut rng = SplitMix64::new(1);
+    let expected = ImplicitTreap {
+      root: Rc::new(setup::leaf(1, rng.next_u64())),
+      rng,
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    let op = ImplicitTreap::push_front(&tree, 0);
+    assert_eq!(Vec::from(op), vec![0, 10, 20, 30, 40, 50, 60, 70])
+  }
+}
+
+#[cfg(test)]
+mod push_back {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    let op = ImplicitTreap::push_back(&tree, 1);
+    assert_eq!(Vec::from(op), vec![1])
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    let op = ImplicitTreap::push_back(&tree, 80);
+    assert_eq!(Vec::from(op), vec![10, 20, 30, 40, 50, 60, 70, 80])
+  }
+}
+
+#[cfg(test)]
+mod split_at {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    let (t1, t2) = ImplicitTreap::split_at(&tree, 0);
+    assert!(ImplicitTreap::is_empty(&t1));
+    assert!(ImplicitTreap::is_empty(&t2))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    let (t1, t2) = ImplicitTreap::split_at(&tree, 2);
+    setup::check_invariants(&t1);
+    setup::check_invariants(&t2);
+    assert_eq!(Vec::from(t1), vec![10, 20]);
+    assert_eq!(Vec::from(t2), vec![30, 40, 50, 60, 70])
+  }
+
+  #[test]
+  fn to_filled_past_the_end() {
+    let tree = setup::implicit_treap_filled();
+    let (t1, t2) = ImplicitTreap::split_at(&tree, 10);
+    assert_eq!(t1, tree);
+    assert!(ImplicitTreap::is_empty(&t2))
+  }
+
+  #[test]
+  fn to_filled_halves_draw_apart() {
+    let tree = setup::implicit_treap_filled();
+    let (mut t1, mut t2) = ImplicitTreap::split_at(&tree, 3);
+    assert_ne!(t1.rng.next_u64(), t2.rng.next_u64())
+  }
+}
+
+#[cfg(test)]
+mod concat {
+  use super::*;
+
+  #[test]
+  fn to_both_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert_eq!(ImplicitTreap::concat(&tree, &tree), tree)
+  }
+
+  #[test]
+  fn to_split_halves() {
+    let tree = setup::implicit_treap_filled();
+    let (t1, t2) = ImplicitTreap::split_at(&tree, 5);
+    assert_eq!(ImplicitTreap::concat(&t1, &t2), tree)
+  }
+
+  #[test]
+  fn to_filled_on_both() {
+    let t1 = ImplicitTreap::from(vec![1, 2, 3]);
+    let t2 = ImplicitTreap::from(vec![4, 5]);
+    let op = ImplicitTreap::concat(&t2, &t1);
+    setup::check_invariants(&op);
+    assert_eq!(Vec::from(op), vec![4, 5, 1, 2, 3])
+  }
+}
+
+#[cfg(test)]
+mod any {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert!(!ImplicitTreap::any(&tree, |_| true))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    assert!(ImplicitTreap::any(&tree, |item| item == &70));
+    assert!(!ImplicitTreap::any(&tree, |item| item > &70))
+  }
+}
+
+#[cfg(test)]
+mod all {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert!(ImplicitTreap::all(&tree, |_| false))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    assert!(ImplicitTreap::all(&tree, |item| item % 10 == 0));
+    assert!(!ImplicitTreap::all(&tree, |item| item < &70))
+  }
+}
+
+#[cfg(test)]
+mod find {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert_eq!(ImplicitTreap::find(&tree, |_| true), None)
+  }
+
+  #[test]
+  fn to_filled_should_match_in_order() {
+    let tree = setup::implicit_treap_filled();
+    assert_eq!(ImplicitTreap::find(&tree, |item| item > &25), Some(&30))
+  }
+}
+
+#[cfg(test)]
+mod map {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert_eq!(ImplicitTreap::map(&tree, |item| item + 1), tree)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::implicit_treap_filled();
+    let op = ImplicitTreap::map(&tree, |item| item / 10);
+    assert_eq!(Vec::from(op), vec![1, 2, 3, 4, 5, 6, 7])
+  }
+}
+
+#[cfg(test)]
+mod reduce {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::implicit_treap_empty();
+    assert_eq!(ImplicitTreap::reduce(&tree, |item, acc| item + acc, 0), 0)
+  }
+
+  #[test]
+  fn to_filled_in_order() {
+    let tree = setup::implicit_treap_filled();
+    let op = ImplicitTreap::reduce(&tree, |item, acc| acc * 10 + item / 10, 0);
+    assert_eq!(op, 1234567)
+  }
+}
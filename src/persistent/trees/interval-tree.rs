@@ -0,0 +1,238 @@
+use super::{Measure, MeasuredTree, Min};
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub enum IntervalKind {
+  // [start, end]
+  Closed,
+  // [start, end)
+  HalfOpen,
+}
+
+// Ordered by start, then end, then kind.
+//
+// Invariants:
+//    - start <= end, a half-open interval with start == end being empty
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct Interval<T> {
+  start: T,
+  end: T,
+  kind: IntervalKind,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum IntervalError {
+  StartAfterEnd,
+}
+
+mod private {
+  use super::*;
+
+  pub fn interval<T>(start: T, end: T, kind: IntervalKind) -> Result<Interval<T>, IntervalError>
+  where
+    T: PartialOrd,
+  {
+    if start > end {
+      return Err(IntervalError::StartAfterEnd);
+    }
+    Ok(Interval { start, end, kind })
+  }
+}
+
+impl<T> Interval<T>
+where
+  T: PartialOrd,
+{
+  pub fn closed(start: T, end: T) -> Result<Self, IntervalError> {
+    private::interval(start, end, IntervalKind::Closed)
+  }
+
+  pub fn half_open(start: T, end: T) -> Result<Self, IntervalError> {
+    private::interval(start, end, IntervalKind::HalfOpen)
+  }
+
+  pub fn start(&self) -> &T {
+    &self.start
+  }
+
+  pub fn end(&self) -> &T {
+    &self.end
+  }
+
+  pub fn kind(&self) -> IntervalKind {
+    self.kind
+  }
+
+  pub fn contains(&self, point: &T) -> bool {
+    match self.kind {
+      IntervalKind::Closed => &self.start <= point && point <= &self.end,
+      IntervalKind::HalfOpen => &self.start <= point && point < &self.end,
+    }
+  }
+
+  pub fn overlaps(&self, other: &Self) -> bool {
+    let starts_before_other_ends = match other.kind {
+      IntervalKind::Closed => self.start <= other.end,
+      IntervalKind::HalfOpen => self.start < other.end,
+    };
+    let other_starts_before_end = match self.kind {
+      IntervalKind::Closed => other.start <= self.end,
+      IntervalKind::HalfOpen => other.start < self.end,
+    };
+    starts_before_other_ends && other_starts_before_end
+  }
+}
+
+// Measures a subtree by the interval spanning all of its intervals: the
+// smallest start and the greatest end, closed when any interval ending there
+// is. It's the max end of a classic interval tree, plus the start bound the
+// value order gives for free.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Span;
+
+impl<T> Measure<Interval<T>> for Span
+where
+  T: PartialOrd + Copy,
+{
+  type Output = Option<Interval<T>>;
+
+  fn identity() -> Option<Interval<T>> {
+    None
+  }
+
+  fn measure(item: &Interval<T>) -> Option<Interval<T>> {
+    Some(*item)
+  }
+
+  fn combine(m1: &Option<Interval<T>>, m2: &Option<Interval<T>>) -> Option<Interval<T>> {
+    match (m1, m2) {
+      (Some(i1), Some(i2)) => {
+        let start = <Min as Measure<T>>::combine(&Some(i1.start), &Some(i2.start));
+        let last = if i2.end > i1.end || (i2.end == i1.end && i2.kind == IntervalKind::Closed) {
+          i2
+        } else {
+          i1
+        };
+        Some(Interval {
+          start: start.unwrap(),
+          end: last.end,
+          kind: last.kind,
+        })
+      }
+      (Some(_), None) => *m1,
+      (None, _) => *m2,
+    }
+  }
+}
+
+// Intervals kept in a `MeasuredTree` measured by `Span`, so every subtree
+// knows how far its intervals reach and the overlap queries skip the ones
+// that can't match.
+#[derive(Clone, PartialEq, Debug)]
+pub struct IntervalTree<T>
+where
+  T: PartialOrd + Copy,
+{
+  tree: MeasuredTree<Interval<T>, Span>,
+}
+
+impl<T> Default for IntervalTree<T>
+where
+  T: PartialEq + PartialOrd + Clone + Copy,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> IntervalTree<T>
+where
+  T: PartialEq + PartialOrd + Clone + Copy,
+{
+  pub fn new() -> Self {
+    Self {
+      tree: MeasuredTree::new(),
+    }
+  }
+
+  pub fn is_empty(tree: &Self) -> bool {
+    MeasuredTree::is_empty(&tree.tree)
+  }
+
+  pub fn insert(tree: &Self, item: Interval<T>) -> Self {
+    Self {
+      tree: MeasuredTree::insert(&tree.tree, item),
+    }
+  }
+
+  pub fn remove(tree: &Self, item: Interval<T>) -> Self {
+    Self {
+      tree: MeasuredTree::remove(&tree.tree, item),
+    }
+  }
+
+  pub fn search(tree: &Self, item: Interval<T>) -> Option<Interval<T>> {
+    MeasuredTree::search(&tree.tree, item)
+  }
+
+  pub fn len(tree: &Self) -> i32 {
+    MeasuredTree::len(&tree.tree)
+  }
+
+  pub fn height(tree: &Self) -> i32 {
+    MeasuredTree::height(&tree.tree)
+  }
+
+  pub fn max_end(tree: &Self) -> Option<T> {
+    MeasuredTree::measure(&tree.tree).map(|span| span.end)
+  }
+
+  // All intervals containing `point`, ordered by (start, end).
+  pub fn overlapping_point(tree: &Self, point: T) -> Vec<Interval<T>> {
+    Self::overlapping(
+      tree,
+      Interval {
+        start: point,
+        end: point,
+        kind: IntervalKind::Closed,
+      },
+    )
+  }
+
+  // All intervals overlapping `query`, ordered by (start, end). Only visits
+  // subtrees whose span overlaps `query`.
+  pub fn overlapping(tree: &Self, query: Interval<T>) -> Vec<Interval<T>> {
+    MeasuredTree::filter_by(&tree.tree, |span| {
+      span.is_some_and(|span| span.overlaps(&query))
+    })
+    .copied()
+    .collect()
+  }
+
+  pub fn any_overlap(tree: &Self, query: Interval<T>) -> bool {
+    MeasuredTree::filter_by(&tree.tree, |span| {
+      span.is_some_and(|span| span.overlaps(&query))
+    })
+    .next()
+    .is_some()
+  }
+
+  pub fn any(tree: &Self, f: fn(&Interval<T>) -> bool) -> bool {
+    MeasuredTree::any(&tree.tree, f)
+  }
+
+  pub fn all(tree: &Self, f: fn(&Interval<T>) -> bool) -> bool {
+    MeasuredTree::all(&tree.tree, f)
+  }
+
+  pub fn find(tree: &Self, f: fn(&Interval<T>) -> bool) -> Option<&Interval<T>> {
+    MeasuredTree::find(&tree.tree, f)
+  }
+
+  pub fn reduce<U>(tree: &Self, f: fn(&Interval<T>, U) -> U, acc: U) -> U {
+    MeasuredTree::reduce(&tree.tree, f, acc)
+  }
+}
+
+#[cfg(test)]
+#[path = "./interval-tree_test.rs"]
+mod test;
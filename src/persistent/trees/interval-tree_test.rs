@@ -0,0 +1,549 @@
+use super::*;
+use crate::{Avl, TreeNode};
+use std::rc::Rc;
+
+mod setup {
+  use super::*;
+
+  pub type IntervalTreeT = IntervalTree<i32>;
+
+  pub type IntervalNodeT = TreeNode<Interval<i32>, Option<Interval<i32>>>;
+
+  pub fn node(
+    value: Interval<i32>,
+    left: Rc<IntervalNodeT>,
+    right: Rc<IntervalNodeT>,
+  ) -> Rc<IntervalNodeT> {
+    MeasuredTree::<Interval<i32>, Span>::node(value, left, right)
+  }
+
+  pub fn leaf(value: Interval<i32>) -> Rc<IntervalNodeT> {
+    node(value, empty(), empty())
+  }
+
+  pub fn empty() -> Rc<IntervalNodeT> {
+    Rc::new(TreeNode::Empty)
+  }
+
+  pub fn tree_of(root: Rc<IntervalNodeT>) -> IntervalTreeT {
+    IntervalTreeT {
+      tree: MeasuredTree::from_root(root),
+    }
+  }
+
+  pub fn interval_tree_empty() -> IntervalTreeT {
+    tree_of(empty())
+  }
+
+  pub fn interval_tree_filled() -> IntervalTreeT {
+    /*
+                [5, 8]
+              /        \
+         [2, 4)        [9, 12]
+         /    \              \
+     [1, 3]  [3, 10)       [15, 20]
+    */
+    tree_of(node(
+      Interval::closed(5, 8).unwrap(),
+      node(
+        Interval::half_open(2, 4).unwrap(),
+        leaf(Interval::closed(1, 3).unwrap()),
+        leaf(Interval::half_open(3, 10).unwrap()),
+      ),
+      node(
+        Interval::closed(9, 12).unwrap(),
+        empty(),
+        leaf(Interval::closed(15, 20).unwrap()),
+      ),
+    ))
+  }
+
+  pub fn to_vec(tree: &IntervalTreeT) -> Vec<Interval<i32>> {
+    IntervalTree::reduce(
+      tree,
+      |item, mut acc: Vec<Interval<i32>>| {
+        acc.push(*item);
+        acc
+      },
+      vec![],
+    )
+  }
+
+  // Per node measures and balance are checked by the `MeasuredTree` tests,
+  // this checks what the interval tree adds on top.
+  pub fn check_invariants(tree: &IntervalTreeT) {
+    let items = to_vec(tree);
+    assert!(items
+      .windows(2)
+      .all(|pair| (pair[0].start, pair[0].end) <= (pair[1].start, pair[1].end)));
+    let span = MeasuredTree::measure(&tree.tree);
+    assert_eq!(
+      span.map(|span| span.start),
+      items.first().map(|item| *item.start())
+    );
+    assert_eq!(
+      span.map(|span| span.end),
+      items.iter().map(|item| *item.end()).max()
+    );
+    let bound = 1.45 * ((items.len() + 2) as f64).log2();
+    assert!(IntervalTree::height(tree) as f64 <= bound, "unbalanced")
+  }
+}
+
+#[cfg(test)]
+mod interval {
+  use super::*;
+
+  #[test]
+  fn start_after_end() {
+    assert_eq!(Interval::closed(3, 1), Err(IntervalError::StartAfterEnd));
+    assert_eq!(Interval::half_open(3, 1), Err(IntervalError::StartAfterEnd))
+  }
+
+  #[test]
+  fn start_at_end() {
+    let point = Interval::closed(2, 2).unwrap();
+    let empty = Interval::half_open(2, 2).unwrap();
+    assert_eq!(
+      (point.start(), point.end(), point.kind()),
+      (&2, &2, IntervalKind::Closed)
+    );
+    assert!(point.contains(&2));
+    assert!(!empty.contains(&2));
+    assert!(!empty.overlaps(&point))
+  }
+
+  #[test]
+  fn contains_closed() {
+    let interval = Interval::closed(1, 3).unwrap();
+    assert!(interval.contains(&1));
+    assert!(interval.contains(&3));
+    assert!(!interval.contains(&4))
+  }
+
+  #[test]
+  fn contains_half_open() {
+    let interval = Interval::half_open(1, 3).unwrap();
+    assert!(interval.contains(&1));
+    assert!(!interval.contains(&3))
+  }
+
+  #[test]
+  fn overlaps_touching() {
+    assert!(Interval::closed(1, 3)
+      .unwrap()
+      .overlaps(&Interval::closed(3, 5).unwrap()));
+    assert!(!Interval::half_open(1, 3)
+      .unwrap()
+      .overlaps(&Interval::closed(3, 5).unwrap()));
+    assert!(!Interval::closed(3, 5)
+      .unwrap()
+      .overlaps(&Interval::half_open(1, 3).unwrap()))
+  }
+
+  #[test]
+  fn overlaps_disjoint() {
+    assert!(!Interval::closed(1, 2)
+      .unwrap()
+      .overlaps(&Interval::closed(3, 5).unwrap()))
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = IntervalTree::<i32>::new();
+    assert_eq!(op, setup::interval_tree_empty())
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(IntervalTree::is_empty(&setup::interval_tree_empty()))
+  }
+
+  #[test]
+  fn to_filled() {
+    assert!(!IntervalTree::is_empty(&setup::interval_tree_filled()))
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::interval_tree_empty();
+    let op = IntervalTree::insert(&tree, Interval::closed(1, 2).unwrap());
+    let expected = setup::tree_of(setup::leaf(Interval::closed(1, 2).unwrap()));
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::interval_tree_filled();
+    let op = IntervalTree::insert(&tree, Interval::closed(16, 30).unwrap());
+    setup::check_invariants(&op);
+    assert_eq!(IntervalTree::len(&op), 7);
+    assert_eq!(IntervalTree::max_end(&op), Some(30))
+  }
+
+  #[test]
+  fn keeps_previous_version() {
+    let tree = setup::interval_tree_filled();
+    let _ = IntervalTree::insert(&tree, Interval::closed(16, 30).unwrap());
+    assert_eq!(tree, setup::interval_tree_filled())
+  }
+
+  #[test]
+  fn sorted_input_stays_balanced() {
+    let tree = (0..1000).fold(IntervalTree::new(), |tree, start| {
+      IntervalTree::insert(&tree, Interval::half_open(start, start + 5).unwrap())
+    });
+    setup::check_invariants(&tree);
+    assert!(IntervalTree::height(&tree) <= 15)
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::interval_tree_empty();
+    let op = IntervalTree::remove(&tree, Interval::closed(1, 3).unwrap());
+    assert_eq!(op, tree)
+  }
+
+  #[test]
+  fn to_filled_leaf() {
+    let tree = setup::interval_tree_filled();
+    let op = IntervalTree::remove(&tree, Interval::closed(15, 20).unwrap());
+    setup::check_invariants(&op);
+    assert_eq!(IntervalTree::max_end(&op), Some(12));
+    assert_eq!(IntervalTree::len(&op), 5)
+  }
+
+  #[test]
+  fn to_filled_root() {
+    let tree = setup::interval_tree_filled();
+    let op = IntervalTree::remove(&tree, Interval::closed(5, 8).unwrap());
+    setup::check_invariants(&op);
+    assert_eq!(
+      IntervalTree::search(&op, Interval::closed(5, 8).unwrap()),
+      None
+    );
+    assert_eq!(IntervalTree::len(&op), 5)
+  }
+
+  #[test]
+  fn to_same_bounds_with_other_kinds() {
+    let tree = (0..20).fold(IntervalTree::new(), |tree, item| {
+      let interval = match item % 2 {
+        0 => Interval::closed(1, 5).unwrap(),
+        _ => Interval::half_open(1, 5).unwrap(),
+      };
+      IntervalTree::insert(&tree, interval)
+    });
+    let op = (0..10).fold(tree, |tree, _| {
+      IntervalTree::remove(&tree, Interval::half_open(1, 5).unwrap())
+    });
+    setup::check_invariants(&op);
+    assert_eq!(IntervalTree::len(&op), 10);
+    assert!(IntervalTree::all(&op, |item| item.kind() == IntervalKind::Closed))
+  }
+
+  #[test]
+  fn to_filled_kind_must_match() {
+    let tree = setup::interval_tree_filled();
+    let op = IntervalTree::remove(&tree, Interval::half_open(5, 8).unwrap());
+    assert_eq!(op, tree)
+  }
+}
+
+#[cfg(test)]
+mod search {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::interval_tree_empty();
+    assert_eq!(
+      IntervalTree::search(&tree, Interval::closed(1, 3).unwrap()),
+      None
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::interval_tree_filled();
+    assert_eq!(
+      IntervalTree::search(&tree, Interval::half_open(3, 10).unwrap()),
+      Some(Interval::half_open(3, 10).unwrap())
+    );
+    assert_eq!(
+      IntervalTree::search(&tree, Interval::closed(3, 10).unwrap()),
+      None
+    )
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(IntervalTree::len(&setup::interval_tree_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(IntervalTree::len(&setup::interval_tree_filled()), 6)
+  }
+}
+
+#[cfg(test)]
+mod height {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(IntervalTree::height(&setup::interval_tree_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(IntervalTree::height(&setup::interval_tree_filled()), 3)
+  }
+}
+
+#[cfg(test)]
+mod max_end {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(IntervalTree::max_end(&setup::interval_tree_empty()), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(
+      IntervalTree::max_end(&setup::interval_tree_filled()),
+      Some(20)
+    )
+  }
+}
+
+#[cfg(test)]
+mod overlapping_point {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::interval_tree_empty();
+    assert_eq!(IntervalTree::overlapping_point(&tree, 3), vec![])
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::interval_tree_filled();
+    let op = IntervalTree::overlapping_point(&tree, 3);
+    let expected = vec![
+      Interval::closed(1, 3).unwrap(),
+      Interval::half_open(2, 4).unwrap(),
+      Interval::half_open(3, 10).unwrap(),
+    ];
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_half_open_end() {
+    let tree = setup::interval_tree_filled();
+    let op = IntervalTree::overlapping_point(&tree, 10);
+    assert_eq!(op, vec![Interval::closed(9, 12).unwrap()])
+  }
+
+  #[test]
+  fn to_shared_end_of_both_kinds() {
+    let tree = [
+      Interval::closed(2, 5).unwrap(),
+      Interval::half_open(1, 5).unwrap(),
+      Interval::half_open(0, 5).unwrap(),
+    ]
+    .into_iter()
+    .fold(IntervalTree::new(), |tree, item| {
+      IntervalTree::insert(&tree, item)
+    });
+    let op = IntervalTree::overlapping_point(&tree, 5);
+    assert_eq!(op, vec![Interval::closed(2, 5).unwrap()])
+  }
+
+  #[test]
+  fn to_filled_without_match() {
+    let tree = setup::interval_tree_filled();
+    assert_eq!(IntervalTree::overlapping_point(&tree, 13), vec![])
+  }
+}
+
+#[cfg(test)]
+mod overlapping {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::interval_tree_empty();
+    assert_eq!(
+      IntervalTree::overlapping(&tree, Interval::half_open(0, 100).unwrap()),
+      vec![]
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::interval_tree_filled();
+    let op = IntervalTree::overlapping(&tree, Interval::half_open(8, 15).unwrap());
+    let expected = vec![
+      Interval::half_open(3, 10).unwrap(),
+      Interval::closed(5, 8).unwrap(),
+      Interval::closed(9, 12).unwrap(),
+    ];
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_matches_brute_force() {
+    let tree = (0..60).fold(IntervalTree::new(), |tree, item| {
+      IntervalTree::insert(
+        &tree,
+        Interval::half_open((item * 37) % 50, (item * 37) % 50 + item % 7).unwrap(),
+      )
+    });
+    for start in 0..60 {
+      let query = Interval::half_open(start, start + 3).unwrap();
+      let expected: Vec<_> = setup::to_vec(&tree)
+        .into_iter()
+        .filter(|interval| interval.overlaps(&query))
+        .collect();
+      assert_eq!(IntervalTree::overlapping(&tree, query), expected)
+    }
+  }
+}
+
+#[cfg(test)]
+mod any_overlap {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::interval_tree_empty();
+    assert!(!IntervalTree::any_overlap(
+      &tree,
+      Interval::closed(0, 100).unwrap()
+    ))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::interval_tree_filled();
+    assert!(IntervalTree::any_overlap(
+      &tree,
+      Interval::closed(12, 14).unwrap()
+    ));
+    assert!(!IntervalTree::any_overlap(
+      &tree,
+      Interval::half_open(13, 15).unwrap()
+    ));
+    assert!(!IntervalTree::any_overlap(
+      &tree,
+      Interval::closed(21, 30).unwrap()
+    ))
+  }
+}
+
+#[cfg(test)]
+mod any {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(!IntervalTree::any(&setup::interval_tree_empty(), |_| true))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::interval_tree_filled();
+    assert!(IntervalTree::any(&tree, |item| item.kind() == IntervalKind::HalfOpen));
+    assert!(!IntervalTree::any(&tree, |item| *item.start() > 15))
+  }
+}
+
+#[cfg(test)]
+mod all {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(IntervalTree::all(&setup::interval_tree_empty(), |_| false))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::interval_tree_filled();
+    assert!(IntervalTree::all(&tree, |item| item.start() < item.end()));
+    assert!(!IntervalTree::all(&tree, |item| item.kind() == IntervalKind::Closed))
+  }
+}
+
+#[cfg(test)]
+mod find {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(
+      IntervalTree::find(&setup::interval_tree_empty(), |_| true),
+      None
+    )
+  }
+
+  #[test]
+  fn to_filled_should_match_in_order() {
+    let tree = setup::interval_tree_filled();
+    assert_eq!(
+      IntervalTree::find(&tree, |item| item.end() - item.start() > 2),
+      Some(&Interval::half_open(3, 10).unwrap())
+    )
+  }
+}
+
+#[cfg(test)]
+mod reduce {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::interval_tree_empty();
+    assert_eq!(
+      IntervalTree::reduce(&tree, |item, acc| acc + item.end(), 0),
+      0
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::interval_tree_filled();
+    let op = IntervalTree::reduce(&tree, |item, acc| acc + item.end() - item.start(), 0);
+    assert_eq!(op, 22)
+  }
+}
@@ -0,0 +1,130 @@
+use std::ops::Add;
+
+// A monoid over the measures of single values: `combine` has to be
+// associative and `identity` neutral on both sides, so the measure of a
+// subtree does not depend on its shape, only on the order of its values.
+pub trait Measure<T> {
+  type Output: Clone;
+
+  fn identity() -> Self::Output;
+  fn measure(item: &T) -> Self::Output;
+  fn combine(m1: &Self::Output, m2: &Self::Output) -> Self::Output;
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Count;
+
+impl<T> Measure<T> for Count {
+  type Output = i32;
+
+  fn identity() -> i32 {
+    0
+  }
+
+  fn measure(_: &T) -> i32 {
+    1
+  }
+
+  fn combine(m1: &i32, m2: &i32) -> i32 {
+    m1 + m2
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Sum;
+
+impl<T> Measure<T> for Sum
+where
+  T: Add<Output = T> + Default + Copy,
+{
+  type Output = T;
+
+  fn identity() -> T {
+    T::default()
+  }
+
+  fn measure(item: &T) -> T {
+    *item
+  }
+
+  fn combine(m1: &T, m2: &T) -> T {
+    *m1 + *m2
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Min;
+
+impl<T> Measure<T> for Min
+where
+  T: PartialOrd + Copy,
+{
+  type Output = Option<T>;
+
+  fn identity() -> Option<T> {
+    None
+  }
+
+  fn measure(item: &T) -> Option<T> {
+    Some(*item)
+  }
+
+  fn combine(m1: &Option<T>, m2: &Option<T>) -> Option<T> {
+    match (m1, m2) {
+      (Some(v1), Some(v2)) if v2 < v1 => Some(*v2),
+      (Some(_), _) => *m1,
+      (None, _) => *m2,
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Max;
+
+impl<T> Measure<T> for Max
+where
+  T: PartialOrd + Copy,
+{
+  type Output = Option<T>;
+
+  fn identity() -> Option<T> {
+    None
+  }
+
+  fn measure(item: &T) -> Option<T> {
+    Some(*item)
+  }
+
+  fn combine(m1: &Option<T>, m2: &Option<T>) -> Option<T> {
+    match (m1, m2) {
+      (Some(v1), Some(v2)) if v2 > v1 => Some(*v2),
+      (Some(_), _) => *m1,
+      (None, _) => *m2,
+    }
+  }
+}
+
+// Two measures kept side by side, e.g. `(Count, Sum)` for averages.
+impl<T, M1, M2> Measure<T> for (M1, M2)
+where
+  M1: Measure<T>,
+  M2: Measure<T>,
+{
+  type Output = (M1::Output, M2::Output);
+
+  fn identity() -> Self::Output {
+    (M1::identity(), M2::identity())
+  }
+
+  fn measure(item: &T) -> Self::Output {
+    (M1::measure(item), M2::measure(item))
+  }
+
+  fn combine(m1: &Self::Output, m2: &Self::Output) -> Self::Output {
+    (M1::combine(&m1.0, &m2.0), M2::combine(&m1.1, &m2.1))
+  }
+}
+
+#[cfg(test)]
+#[path = "./measure_test.rs"]
+mod test;
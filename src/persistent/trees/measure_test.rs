@@ -0,0 +1,86 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub fn fold<M: Measure<i32>>(items: &[i32]) -> M::Output {
+    items.iter().fold(M::identity(), |acc, item| {
+      M::combine(&acc, &M::measure(item))
+    })
+  }
+}
+
+#[cfg(test)]
+mod count {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::fold::<Count>(&[]), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(setup::fold::<Count>(&[4, 2, 9]), 3)
+  }
+}
+
+#[cfg(test)]
+mod sum {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::fold::<Sum>(&[]), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(setup::fold::<Sum>(&[4, 2, 9]), 15)
+  }
+}
+
+#[cfg(test)]
+mod min {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::fold::<Min>(&[]), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(setup::fold::<Min>(&[4, 2, 9]), Some(2))
+  }
+}
+
+#[cfg(test)]
+mod max {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::fold::<Max>(&[]), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(setup::fold::<Max>(&[4, 2, 9]), Some(9))
+  }
+}
+
+#[cfg(test)]
+mod pair {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(setup::fold::<(Count, Sum)>(&[]), (0, 0))
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(setup::fold::<(Count, Max)>(&[4, 2, 9]), (3, Some(9)))
+  }
+}
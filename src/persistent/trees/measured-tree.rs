@@ -0,0 +1,533 @@
+use std::{
+  fmt,
+  marker::PhantomData,
+  ops::{Bound, RangeBounds},
+  rc::Rc,
+};
+
+use super::{Avl, AvlParts, Measure, TreeNode};
+
+// Balanced on the same `TreeNode` layout as `BinarySearchTree`, with the
+// measure of each subtree cached in its root.
+//
+// Invariants:
+//    - values are in order: left_node values <= node value <= right_node values
+//    - measure is the combined measure of the subtree, in order
+//    - the heights of both children differ by at most one
+pub struct MeasuredTree<T, M>
+where
+  M: Measure<T>,
+{
+  root: Rc<TreeNode<T, M::Output>>,
+  marker: PhantomData<M>,
+}
+
+impl<T, M> Clone for MeasuredTree<T, M>
+where
+  M: Measure<T>,
+{
+  fn clone(&self) -> Self {
+    Self {
+      root: self.root.clone(),
+      marker: PhantomData,
+    }
+  }
+}
+
+impl<T, M> PartialEq for MeasuredTree<T, M>
+where
+  T: PartialEq,
+  M: Measure<T>,
+  M::Output: PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.root == other.root
+  }
+}
+
+impl<T, M> fmt::Debug for MeasuredTree<T, M>
+where
+  T: fmt::Debug,
+  M: Measure<T>,
+  M::Output: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("MeasuredTree")
+      .field("root", &self.root)
+      .finish()
+  }
+}
+
+// In-order values whose own measure passes `keep`, skipping whole the
+// subtrees whose cached measure doesn't.
+pub struct FilterBy<'a, T, M, F>
+where
+  M: Measure<T>,
+{
+  stack: Vec<&'a TreeNode<T, M::Output>>,
+  keep: F,
+}
+
+impl<'a, T, M, F> FilterBy<'a, T, M, F>
+where
+  M: Measure<T>,
+  F: Fn(&M::Output) -> bool,
+{
+  fn push_left(&mut self, mut node: &'a TreeNode<T, M::Output>) {
+    while let TreeNode::Node { measure, left, .. } = node {
+      if !(self.keep)(measure) {
+        return;
+      }
+      self.stack.push(node);
+      node = left;
+    }
+  }
+}
+
+impl<'a, T, M, F> Iterator for FilterBy<'a, T, M, F>
+where
+  M: Measure<T>,
+  F: Fn(&M::Output) -> bool,
+{
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while let Some(node) = self.stack.pop() {
+      if let TreeNode::Node { value, right, .. } = node {
+        self.push_left(right);
+        if (self.keep)(&M::measure(value)) {
+          return Some(value);
+        }
+      }
+    }
+    None
+  }
+}
+
+mod private {
+  use super::*;
+
+  pub fn measure<T, M>(node: &TreeNode<T, M::Output>) -> M::Output
+  where
+    M: Measure<T>,
+  {
+    match node {
+      TreeNode::Empty => M::identity(),
+      TreeNode::Node { measure, .. } => measure.clone(),
+    }
+  }
+
+  pub fn insert_node_aux<T, M>(
+    node: &Rc<TreeNode<T, M::Output>>,
+    item: T,
+  ) -> Rc<TreeNode<T, M::Output>>
+  where
+    T: Clone + PartialOrd,
+    M: Measure<T>,
+  {
+    match node.as_ref() {
+      TreeNode::Empty => MeasuredTree::<T, M>::node(item, node.clone(), node.clone()),
+      TreeNode::Node {
+        value, left, right, ..
+      } => {
+        if &item > value {
+          MeasuredTree::<T, M>::balance(
+            value.clone(),
+            left.clone(),
+            insert_node_aux::<T, M>(right, item),
+          )
+        } else {
+          MeasuredTree::<T, M>::balance(
+            value.clone(),
+            insert_node_aux::<T, M>(left, item),
+            right.clone(),
+          )
+        }
+      }
+    }
+  }
+
+  fn remove_min_node_aux<T, M>(node: &Rc<TreeNode<T, M::Output>>) -> (T, Rc<TreeNode<T, M::Output>>)
+  where
+    T: Clone,
+    M: Measure<T>,
+  {
+    match node.as_ref() {
+      TreeNode::Empty => unreachable!("remove_min_node_aux is only called on filled nodes"),
+      TreeNode::Node {
+        value, left, right, ..
+      } => match left.as_ref() {
+        TreeNode::Empty => (value.clone(), right.clone()),
+        TreeNode::Node { .. } => {
+          let (min, left) = remove_min_node_aux::<T, M>(left);
+          (
+            min,
+            MeasuredTree::<T, M>::balance(value.clone(), left, right.clone()),
+          )
+        }
+      },
+    }
+  }
+
+  // Hands back the very same `Rc` when `item` is missing, so removing an
+  // absent value shares the whole tree instead of copying a path.
+  pub fn remove_node_aux<T, M>(
+    node: &Rc<TreeNode<T, M::Output>>,
+    item: &T,
+  ) -> Rc<TreeNode<T, M::Output>>
+  where
+    T: Clone + PartialOrd,
+    M: Measure<T>,
+  {
+    match node.as_ref() {
+      TreeNode::Empty => node.clone(),
+      TreeNode::Node {
+        value, left, right, ..
+      } => {
+        if item == value {
+          match (left.as_ref(), right.as_ref()) {
+            (_, TreeNode::Empty) => left.clone(),
+            (TreeNode::Empty, _) => right.clone(),
+            _ => {
+              let (successor, right) = remove_min_node_aux::<T, M>(right);
+              MeasuredTree::<T, M>::balance(successor, left.clone(), right)
+            }
+          }
+        } else if item > value {
+          let new_right = remove_node_aux::<T, M>(right, item);
+          if Rc::ptr_eq(&new_right, right) {
+            node.clone()
+          } else {
+            MeasuredTree::<T, M>::balance(value.clone(), left.clone(), new_right)
+          }
+        } else {
+          let new_left = remove_node_aux::<T, M>(left, item);
+          if Rc::ptr_eq(&new_left, left) {
+            node.clone()
+          } else {
+            MeasuredTree::<T, M>::balance(value.clone(), new_left, right.clone())
+          }
+        }
+      }
+    }
+  }
+
+  pub fn search_node_aux<'a, T, V>(node: &'a TreeNode<T, V>, item: &T) -> Option<&'a T>
+  where
+    T: PartialOrd,
+  {
+    match node {
+      TreeNode::Empty => None,
+      TreeNode::Node {
+        value, left, right, ..
+      } => {
+        if value == item {
+          Some(value)
+        } else if item > value {
+          search_node_aux(right, item)
+        } else {
+          search_node_aux(left, item)
+        }
+      }
+    }
+  }
+
+  fn is_below<T>(value: &T, bound: Bound<&T>) -> bool
+  where
+    T: PartialOrd,
+  {
+    match bound {
+      Bound::Included(low) => value < low,
+      Bound::Excluded(low) => value <= low,
+      Bound::Unbounded => false,
+    }
+  }
+
+  fn is_above<T>(value: &T, bound: Bound<&T>) -> bool
+  where
+    T: PartialOrd,
+  {
+    match bound {
+      Bound::Included(high) => value > high,
+      Bound::Excluded(high) => value >= high,
+      Bound::Unbounded => false,
+    }
+  }
+
+  // Once a subtree lies entirely inside one of the bounds that bound is
+  // dropped, so below the split point each step either skips a whole side or
+  // takes its cached measure: O(log n) nodes are visited overall.
+  pub fn measure_range_node_aux<T, M>(
+    node: &TreeNode<T, M::Output>,
+    low: Bound<&T>,
+    high: Bound<&T>,
+  ) -> M::Output
+  where
+    T: PartialOrd,
+    M: Measure<T>,
+  {
+    match node {
+      TreeNode::Empty => M::identity(),
+      TreeNode::Node {
+        value,
+        measure,
+        left,
+        right,
+        ..
+      } => {
+        if matches!((low, high), (Bound::Unbounded, Bound::Unbounded)) {
+          measure.clone()
+        } else if is_below(value, low) {
+          measure_range_node_aux::<T, M>(right, low, high)
+        } else if is_above(value, high) {
+          measure_range_node_aux::<T, M>(left, low, high)
+        } else {
+          M::combine(
+            &M::combine(
+              &measure_range_node_aux::<T, M>(left, low, Bound::Unbounded),
+              &M::measure(value),
+            ),
+            &measure_range_node_aux::<T, M>(right, Bound::Unbounded, high),
+          )
+        }
+      }
+    }
+  }
+
+  pub fn search_by_node_aux<'a, T, M, F>(
+    node: &'a TreeNode<T, M::Output>,
+    f: &F,
+    acc: M::Output,
+  ) -> Option<&'a T>
+  where
+    M: Measure<T>,
+    F: Fn(&M::Output) -> bool,
+  {
+    match node {
+      TreeNode::Empty => None,
+      TreeNode::Node {
+        value, left, right, ..
+      } => {
+        let with_left = M::combine(&acc, &measure::<T, M>(left));
+        if f(&with_left) {
+          return search_by_node_aux::<T, M, F>(left, f, acc);
+        }
+        let with_value = M::combine(&with_left, &M::measure(value));
+        if f(&with_value) {
+          Some(value)
+        } else {
+          search_by_node_aux::<T, M, F>(right, f, with_value)
+        }
+      }
+    }
+  }
+
+  pub fn len_node_aux<T, V>(node: &TreeNode<T, V>, acc: i32) -> i32 {
+    match node {
+      TreeNode::Empty => acc,
+      TreeNode::Node { left, right, .. } => len_node_aux(right, len_node_aux(left, acc + 1)),
+    }
+  }
+
+  pub fn any_node_aux<T, V>(node: &TreeNode<T, V>, f: fn(&T) -> bool) -> bool {
+    match node {
+      TreeNode::Empty => false,
+      TreeNode::Node {
+        value, left, right, ..
+      } => any_node_aux(left, f) || f(value) || any_node_aux(right, f),
+    }
+  }
+
+  pub fn all_node_aux<T, V>(node: &TreeNode<T, V>, f: fn(&T) -> bool) -> bool {
+    match node {
+      TreeNode::Empty => true,
+      TreeNode::Node {
+        value, left, right, ..
+      } => all_node_aux(left, f) && f(value) && all_node_aux(right, f),
+    }
+  }
+
+  pub fn find_node_aux<T, V>(node: &TreeNode<T, V>, f: fn(&T) -> bool) -> Option<&T> {
+    match node {
+      TreeNode::Empty => None,
+      TreeNode::Node {
+        value, left, right, ..
+      } => match find_node_aux(left, f) {
+        Some(value) => Some(value),
+        None if f(value) => Some(value),
+        None => find_node_aux(right, f),
+      },
+    }
+  }
+
+  pub fn reduce_node_aux<T, V, U>(node: &TreeNode<T, V>, f: fn(&T, U) -> U, acc: U) -> U {
+    match node {
+      TreeNode::Empty => acc,
+      TreeNode::Node {
+        value, left, right, ..
+      } => reduce_node_aux(right, f, f(value, reduce_node_aux(left, f, acc))),
+    }
+  }
+}
+
+impl<T, M> Avl for MeasuredTree<T, M>
+where
+  T: Clone,
+  M: Measure<T>,
+{
+  type Node = TreeNode<T, M::Output>;
+  type Value = T;
+
+  fn height(node: &Self::Node) -> i32 {
+    match node {
+      TreeNode::Empty => 0,
+      TreeNode::Node { height, .. } => *height,
+    }
+  }
+
+  fn parts(node: &Self::Node) -> Option<AvlParts<'_, T, Self::Node>> {
+    match node {
+      TreeNode::Empty => None,
+      TreeNode::Node {
+        value, left, right, ..
+      } => Some((value, left, right)),
+    }
+  }
+
+  fn node(value: T, left: Rc<Self::Node>, right: Rc<Self::Node>) -> Rc<Self::Node> {
+    let measure = M::combine(
+      &M::combine(&private::measure::<T, M>(&left), &M::measure(&value)),
+      &private::measure::<T, M>(&right),
+    );
+    Rc::new(TreeNode::Node {
+      value,
+      measure,
+      height: std::cmp::max(<Self as Avl>::height(&left), <Self as Avl>::height(&right)) + 1,
+      left,
+      right,
+    })
+  }
+}
+
+impl<T, M> Default for MeasuredTree<T, M>
+where
+  T: PartialEq + PartialOrd + Clone + Copy,
+  M: Measure<T>,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T, M> MeasuredTree<T, M>
+where
+  T: PartialEq + PartialOrd + Clone + Copy,
+  M: Measure<T>,
+{
+  pub fn new() -> Self {
+    Self {
+      root: Rc::new(TreeNode::Empty),
+      marker: PhantomData,
+    }
+  }
+
+  // Views a tree built elsewhere on the same nodes, like the size annotated
+  // `BinarySearchTree`, so it can use the measured queries. Those stay
+  // correct on an unbalanced tree, only slower.
+  pub(crate) fn from_root(root: Rc<TreeNode<T, M::Output>>) -> Self {
+    Self {
+      root,
+      marker: PhantomData,
+    }
+  }
+
+  pub fn is_empty(tree: &Self) -> bool {
+    matches!(tree.root.as_ref(), TreeNode::Empty)
+  }
+
+  pub fn insert(tree: &Self, item: T) -> Self {
+    Self {
+      root: private::insert_node_aux::<T, M>(&tree.root, item),
+      marker: PhantomData,
+    }
+  }
+
+  pub fn remove(tree: &Self, item: T) -> Self {
+    Self {
+      root: private::remove_node_aux::<T, M>(&tree.root, &item),
+      marker: PhantomData,
+    }
+  }
+
+  pub fn search(tree: &Self, item: T) -> Option<T> {
+    private::search_node_aux(&tree.root, &item).copied()
+  }
+
+  pub fn len(tree: &Self) -> i32 {
+    private::len_node_aux(&tree.root, 0)
+  }
+
+  pub fn height(tree: &Self) -> i32 {
+    <Self as Avl>::height(&tree.root)
+  }
+
+  // Measure of the whole tree, read from the root in O(1).
+  pub fn measure(tree: &Self) -> M::Output {
+    private::measure::<T, M>(&tree.root)
+  }
+
+  // Combined measure of the values inside `range`, in O(log n).
+  pub fn measure_range<R>(tree: &Self, range: R) -> M::Output
+  where
+    R: RangeBounds<T>,
+  {
+    private::measure_range_node_aux::<T, M>(&tree.root, range.start_bound(), range.end_bound())
+  }
+
+  // First value, in order, for which `f` holds on the measure of everything
+  // up to and including it. `f` has to be monotone: once true for a prefix,
+  // true for every longer one. With `Count` this is select, with `Sum` a
+  // weighted select, and so on.
+  pub fn search_by<F>(tree: &Self, f: F) -> Option<T>
+  where
+    F: Fn(&M::Output) -> bool,
+  {
+    private::search_by_node_aux::<T, M, F>(&tree.root, &f, M::identity()).copied()
+  }
+
+  // Lazily yields, in order, the values whose measure passes `keep`. Whole
+  // subtrees are skipped on their cached measure, so `keep` has to hold on a
+  // combined measure whenever it holds on one of its parts, like "max end is
+  // past x" does.
+  pub fn filter_by<F>(tree: &Self, keep: F) -> FilterBy<'_, T, M, F>
+  where
+    F: Fn(&M::Output) -> bool,
+  {
+    let mut iter = FilterBy {
+      stack: vec![],
+      keep,
+    };
+    iter.push_left(&tree.root);
+    iter
+  }
+
+  pub fn any(tree: &Self, f: fn(&T) -> bool) -> bool {
+    private::any_node_aux(&tree.root, f)
+  }
+
+  pub fn all(tree: &Self, f: fn(&T) -> bool) -> bool {
+    private::all_node_aux(&tree.root, f)
+  }
+
+  pub fn find(tree: &Self, f: fn(&T) -> bool) -> Option<&T> {
+    private::find_node_aux(&tree.root, f)
+  }
+
+  pub fn reduce<U>(tree: &Self, f: fn(&T, U) -> U, acc: U) -> U {
+    private::reduce_node_aux(&tree.root, f, acc)
+  }
+}
+
+#[cfg(test)]
+#[path = "./measured-tree_test.rs"]
+mod test;
@@ -0,0 +1,454 @@
+use super::*;
+use crate::{Count, Max, Sum};
+
+mod setup {
+  use super::*;
+
+  pub type SumTreeT = MeasuredTree<i32, Sum>;
+  pub type CountTreeT = MeasuredTree<i32, Count>;
+
+  // Measure over (start, end) pairs keeping the greatest end, the
+  // augmentation behind interval trees.
+  pub struct MaxEnd;
+
+  impl Measure<(i32, i32)> for MaxEnd {
+    type Output = Option<i32>;
+
+    fn identity() -> Option<i32> {
+      None
+    }
+
+    fn measure(item: &(i32, i32)) -> Option<i32> {
+      Some(item.1)
+    }
+
+    fn combine(m1: &Option<i32>, m2: &Option<i32>) -> Option<i32> {
+      std::cmp::max(*m1, *m2)
+    }
+  }
+
+  pub fn sum_tree_empty() -> SumTreeT {
+    SumTreeT {
+      root: Rc::new(TreeNode::Empty),
+      marker: PhantomData,
+    }
+  }
+
+  pub fn sum_tree_filled() -> SumTreeT {
+    /*
+           3
+         /   \
+        1     5
+       / \   / \
+      0   2 4   6
+    */
+    let leaf = |value| MeasuredTree::<i32, Sum>::node(value, empty(), empty());
+    SumTreeT {
+      root: MeasuredTree::<i32, Sum>::node(
+        3,
+        MeasuredTree::<i32, Sum>::node(1, leaf(0), leaf(2)),
+        MeasuredTree::<i32, Sum>::node(5, leaf(4), leaf(6)),
+      ),
+      marker: PhantomData,
+    }
+  }
+
+  pub fn empty() -> Rc<TreeNode<i32, i32>> {
+    Rc::new(TreeNode::Empty)
+  }
+
+  pub fn to_vec<M: Measure<i32>>(tree: &MeasuredTree<i32, M>) -> Vec<i32> {
+    MeasuredTree::reduce(
+      tree,
+      |item, mut acc: Vec<i32>| {
+        acc.push(*item);
+        acc
+      },
+      vec![],
+    )
+  }
+
+  fn check_node(node: &TreeNode<i32, i32>) -> (i32, i32) {
+    match node {
+      TreeNode::Empty => (0, 0),
+      TreeNode::Node {
+        value,
+        measure,
+        height,
+        left,
+        right,
+      } => {
+        let (left_height, left_sum) = check_node(left);
+        let (right_height, right_sum) = check_node(right);
+        assert!((left_height - right_height).abs() <= 1, "unbalanced");
+        assert_eq!(*height, std::cmp::max(left_height, right_height) + 1);
+        assert_eq!(*measure, left_sum + value + right_sum);
+        (*height, *measure)
+      }
+    }
+  }
+
+  pub fn check_invariants(tree: &SumTreeT) {
+    check_node(&tree.root);
+    assert!(to_vec(tree).windows(2).all(|pair| pair[0] <= pair[1]));
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = MeasuredTree::<i32, Sum>::new();
+    assert_eq!(op, setup::sum_tree_empty())
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(MeasuredTree::is_empty(&setup::sum_tree_empty()))
+  }
+
+  #[test]
+  fn to_filled() {
+    assert!(!MeasuredTree::is_empty(&setup::sum_tree_filled()))
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::sum_tree_empty();
+    let op = MeasuredTree::insert(&tree, 4);
+    let expected = MeasuredTree::<i32, Sum> {
+      root: MeasuredTree::<i32, Sum>::node(4, setup::empty(), setup::empty()),
+      marker: PhantomData,
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::sum_tree_filled();
+    let op = MeasuredTree::insert(&tree, 7);
+    setup::check_invariants(&op);
+    assert_eq!(MeasuredTree::measure(&op), 28)
+  }
+
+  #[test]
+  fn shares_untouched_subtrees() {
+    let tree = setup::sum_tree_filled();
+    let op = MeasuredTree::insert(&tree, 7);
+    match (tree.root.as_ref(), op.root.as_ref()) {
+      (TreeNode::Node { left: l1, .. }, TreeNode::Node { left: l2, .. }) => {
+        assert!(Rc::ptr_eq(l1, l2))
+      }
+      _ => panic!("both trees should be filled"),
+    }
+  }
+
+  #[test]
+  fn sorted_input_stays_balanced() {
+    let tree = (0..1000).fold(MeasuredTree::new(), |tree, item| {
+      MeasuredTree::insert(&tree, item)
+    });
+    setup::check_invariants(&tree);
+    assert!(MeasuredTree::height(&tree) <= 15);
+    assert_eq!(MeasuredTree::measure(&tree), 499500)
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::sum_tree_empty();
+    assert_eq!(MeasuredTree::remove(&tree, 1), tree)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::sum_tree_filled();
+    let op = MeasuredTree::remove(&tree, 3);
+    setup::check_invariants(&op);
+    assert_eq!(setup::to_vec(&op), vec![0, 1, 2, 4, 5, 6]);
+    assert_eq!(MeasuredTree::measure(&op), 18)
+  }
+
+  #[test]
+  fn to_filled_missing_item_shares_root() {
+    let tree = setup::sum_tree_filled();
+    let op = MeasuredTree::remove(&tree, 9);
+    assert!(Rc::ptr_eq(&tree.root, &op.root))
+  }
+
+  #[test]
+  fn to_filled_until_empty() {
+    let tree = (0..100).fold(MeasuredTree::new(), |tree, item| {
+      MeasuredTree::insert(&tree, item % 10)
+    });
+    let op = (0..100).fold(tree, |tree, item| {
+      let op = MeasuredTree::remove(&tree, item % 10);
+      setup::check_invariants(&op);
+      op
+    });
+    assert!(MeasuredTree::is_empty(&op))
+  }
+}
+
+#[cfg(test)]
+mod search {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(MeasuredTree::search(&setup::sum_tree_empty(), 1), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::sum_tree_filled();
+    assert_eq!(MeasuredTree::search(&tree, 6), Some(6));
+    assert_eq!(MeasuredTree::search(&tree, 7), None)
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(MeasuredTree::len(&setup::sum_tree_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(MeasuredTree::len(&setup::sum_tree_filled()), 7)
+  }
+}
+
+#[cfg(test)]
+mod measure {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(MeasuredTree::measure(&setup::sum_tree_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(MeasuredTree::measure(&setup::sum_tree_filled()), 21)
+  }
+
+  #[test]
+  fn to_filled_with_pair() {
+    let tree = (1..=4).fold(MeasuredTree::<i32, (Count, Max)>::new(), |tree, item| {
+      MeasuredTree::insert(&tree, item)
+    });
+    assert_eq!(MeasuredTree::measure(&tree), (4, Some(4)))
+  }
+}
+
+#[cfg(test)]
+mod measure_range {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(
+      MeasuredTree::measure_range(&setup::sum_tree_empty(), 0..10),
+      0
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::sum_tree_filled();
+    assert_eq!(MeasuredTree::measure_range(&tree, 2..5), 9);
+    assert_eq!(MeasuredTree::measure_range(&tree, 2..=5), 14);
+    assert_eq!(MeasuredTree::measure_range(&tree, ..3), 3);
+    assert_eq!(MeasuredTree::measure_range(&tree, 4..), 15);
+    assert_eq!(MeasuredTree::measure_range(&tree, ..), 21)
+  }
+
+  #[test]
+  fn to_filled_empty_range() {
+    let tree = setup::sum_tree_filled();
+    assert_eq!(MeasuredTree::measure_range(&tree, 3..3), 0);
+    assert_eq!(MeasuredTree::measure_range(&tree, 10..20), 0)
+  }
+
+  #[test]
+  fn matches_brute_force() {
+    let items: Vec<i32> = (0..200).map(|item| (item * 73) % 101).collect();
+    let tree = items
+      .iter()
+      .fold(MeasuredTree::<i32, Sum>::new(), |tree, item| {
+        MeasuredTree::insert(&tree, *item)
+      });
+    for low in (0..110).step_by(7) {
+      for high in (low..110).step_by(5) {
+        let expected: i32 = items
+          .iter()
+          .filter(|item| (low..high).contains(*item))
+          .sum();
+        assert_eq!(MeasuredTree::measure_range(&tree, low..high), expected)
+      }
+    }
+  }
+
+  #[test]
+  fn interval_maxima() {
+    let tree = [(1, 3), (2, 9), (4, 5), (6, 7), (8, 12)].into_iter().fold(
+      MeasuredTree::<(i32, i32), setup::MaxEnd>::new(),
+      |tree, item| MeasuredTree::insert(&tree, item),
+    );
+    assert_eq!(MeasuredTree::measure(&tree), Some(12));
+    assert_eq!(MeasuredTree::measure_range(&tree, (3, 0)..(8, 0)), Some(7));
+    assert_eq!(MeasuredTree::measure_range(&tree, ..(4, 0)), Some(9))
+  }
+}
+
+#[cfg(test)]
+mod search_by {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = MeasuredTree::<i32, Count>::new();
+    assert_eq!(MeasuredTree::search_by(&tree, |count| *count > 0), None)
+  }
+
+  #[test]
+  fn select_with_count() {
+    let tree = [50, 10, 40, 20, 30]
+      .into_iter()
+      .fold(setup::CountTreeT::new(), |tree, item| {
+        MeasuredTree::insert(&tree, item)
+      });
+    for (index, expected) in [10, 20, 30, 40, 50].into_iter().enumerate() {
+      let op = MeasuredTree::search_by(&tree, |count| *count > index as i32);
+      assert_eq!(op, Some(expected))
+    }
+    assert_eq!(MeasuredTree::search_by(&tree, |count| *count > 5), None)
+  }
+
+  #[test]
+  fn weighted_select_with_sum() {
+    let tree = setup::sum_tree_filled();
+    assert_eq!(MeasuredTree::search_by(&tree, |sum| *sum >= 10), Some(4));
+    assert_eq!(MeasuredTree::search_by(&tree, |sum| *sum > 21), None)
+  }
+}
+
+#[cfg(test)]
+mod filter_by {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = MeasuredTree::<i32, Max>::new();
+    let mut op = MeasuredTree::filter_by(&tree, |_| true);
+    assert_eq!(op.next(), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = (0..100).fold(MeasuredTree::<i32, Max>::new(), |tree, item| {
+      MeasuredTree::insert(&tree, (item * 37) % 100)
+    });
+    let op = MeasuredTree::filter_by(&tree, |max| max.is_some_and(|max| max >= 95));
+    assert_eq!(op.copied().collect::<Vec<_>>(), vec![95, 96, 97, 98, 99])
+  }
+
+  #[test]
+  fn to_filled_lazily() {
+    let tree = setup::sum_tree_filled();
+    let mut op = MeasuredTree::filter_by(&tree, |sum| *sum > 3);
+    assert_eq!(op.next(), Some(&4));
+    assert_eq!(op.next(), Some(&5))
+  }
+}
+
+#[cfg(test)]
+mod any {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(!MeasuredTree::any(&setup::sum_tree_empty(), |_| true))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::sum_tree_filled();
+    assert!(MeasuredTree::any(&tree, |item| item == &6));
+    assert!(!MeasuredTree::any(&tree, |item| item > &6))
+  }
+}
+
+#[cfg(test)]
+mod all {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(MeasuredTree::all(&setup::sum_tree_empty(), |_| false))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::sum_tree_filled();
+    assert!(MeasuredTree::all(&tree, |item| item >= &0));
+    assert!(!MeasuredTree::all(&tree, |item| item < &6))
+  }
+}
+
+#[cfg(test)]
+mod find {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(MeasuredTree::find(&setup::sum_tree_empty(), |_| true), None)
+  }
+
+  #[test]
+  fn to_filled_should_match_in_order() {
+    let tree = setup::sum_tree_filled();
+    assert_eq!(
+      MeasuredTree::find(&tree, |item| item % 2 == 0 && item > &1),
+      Some(&2)
+    )
+  }
+}
+
+#[cfg(test)]
+mod reduce {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::sum_tree_empty();
+    assert_eq!(MeasuredTree::reduce(&tree, |item, acc| item + acc, 0), 0)
+  }
+
+  #[test]
+  fn to_filled_in_order() {
+    let tree = setup::sum_tree_filled();
+    let op = MeasuredTree::reduce(&tree, |item, acc| acc * 10 + item, 0);
+    assert_eq!(op, 123456)
+  }
+}
@@ -1,3 +1,39 @@
+pub mod avl;
+pub use avl::*;
+
 #[path = "./binary-search-tree.rs"]
 pub mod binary_search_tree;
 pub use binary_search_tree::*;
+
+pub mod treap;
+pub use treap::*;
+
+#[path = "./implicit-treap.rs"]
+pub mod implicit_treap;
+pub use implicit_treap::*;
+
+#[path = "./interval-tree.rs"]
+pub mod interval_tree;
+pub use interval_tree::*;
+
+pub mod measure;
+pub use measure::*;
+
+#[path = "./measured-tree.rs"]
+pub mod measured_tree;
+pub use measured_tree::*;
+
+#[path = "./radix-trie.rs"]
+pub mod radix_trie;
+pub use radix_trie::*;
+
+#[path = "./finger-tree.rs"]
+pub mod finger_tree;
+pub use finger_tree::*;
+
+pub mod rope;
+pub use rope::*;
+
+#[path = "./union-find.rs"]
+pub mod union_find;
+pub use union_find::*;
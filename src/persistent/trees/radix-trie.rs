@@ -0,0 +1,362 @@
+use std::rc::Rc;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RadixNode<V> {
+  prefix: Vec<u8>,
+  value: Option<V>,
+  children: Vec<Rc<RadixNode<V>>>,
+}
+
+// Invariants:
+//    - the root prefix is always empty
+//    - every other prefix is non-empty
+//    - children are sorted by the first byte of their prefix, no two of them
+//      share it
+//    - a node other than the root without a value has at least two children
+//    - len is the number of values stored
+#[derive(Clone, PartialEq, Debug)]
+pub struct RadixTrie<V> {
+  root: Rc<RadixNode<V>>,
+  len: i32,
+}
+
+pub struct PrefixIter<'a, V> {
+  stack: Vec<(&'a RadixNode<V>, Vec<u8>)>,
+}
+
+impl<'a, V> Iterator for PrefixIter<'a, V> {
+  type Item = (Vec<u8>, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while let Some((node, key)) = self.stack.pop() {
+      for child in node.children.iter().rev() {
+        let mut child_key = key.clone();
+        child_key.extend_from_slice(&child.prefix);
+        self.stack.push((child, child_key));
+      }
+      if let Some(value) = &node.value {
+        return Some((key, value));
+      }
+    }
+    None
+  }
+}
+
+mod private {
+  use super::*;
+
+  pub fn common_prefix_len(s1: &[u8], s2: &[u8]) -> usize {
+    s1.iter().zip(s2).take_while(|(b1, b2)| b1 == b2).count()
+  }
+
+  pub fn leaf<V>(prefix: &[u8], value: V) -> Rc<RadixNode<V>> {
+    Rc::new(RadixNode {
+      prefix: prefix.to_vec(),
+      value: Some(value),
+      children: vec![],
+    })
+  }
+
+  pub fn child_index<V>(node: &RadixNode<V>, byte: u8) -> Result<usize, usize> {
+    node
+      .children
+      .binary_search_by(|child| child.prefix[0].cmp(&byte))
+  }
+
+  fn with_child<V>(node: &RadixNode<V>, index: usize, child: Rc<RadixNode<V>>) -> RadixNode<V>
+  where
+    V: Clone,
+  {
+    let mut children = node.children.clone();
+    children[index] = child;
+    RadixNode {
+      prefix: node.prefix.clone(),
+      value: node.value.clone(),
+      children,
+    }
+  }
+
+  // Returns the new node and whether a value was added (rather than
+  // replaced).
+  pub fn insert_node_aux<V>(node: &RadixNode<V>, key: &[u8], item: V) -> (Rc<RadixNode<V>>, bool)
+  where
+    V: Clone,
+  {
+    if key.is_empty() {
+      return (
+        Rc::new(RadixNode {
+          prefix: node.prefix.clone(),
+          value: Some(item),
+          children: node.children.clone(),
+        }),
+        node.value.is_none(),
+      );
+    }
+    match child_index(node, key[0]) {
+      Err(index) => {
+        let mut children = node.children.clone();
+        children.insert(index, leaf(key, item));
+        (
+          Rc::new(RadixNode {
+            prefix: node.prefix.clone(),
+            value: node.value.clone(),
+            children,
+          }),
+          true,
+        )
+      }
+      Ok(index) => {
+        let child = &node.children[index];
+        let common = common_prefix_len(&child.prefix, key);
+        if common == child.prefix.len() {
+          let (new_child, added) = insert_node_aux(child, &key[common..], item);
+          (Rc::new(with_child(node, index, new_child)), added)
+        } else {
+          let moved_child = Rc::new(RadixNode {
+            prefix: child.prefix[common..].to_vec(),
+            value: child.value.clone(),
+            children: child.children.clone(),
+          });
+          let split = if common == key.len() {
+            RadixNode {
+              prefix: key.to_vec(),
+              value: Some(item),
+              children: vec![moved_child],
+            }
+          } else {
+            let new_leaf = leaf(&key[common..], item);
+            let children = if new_leaf.prefix[0] < moved_child.prefix[0] {
+              vec![new_leaf, moved_child]
+            } else {
+              vec![moved_child, new_leaf]
+            };
+            RadixNode {
+              prefix: key[..common].to_vec(),
+              value: None,
+              children,
+            }
+          };
+          (Rc::new(with_child(node, index, Rc::new(split))), true)
+        }
+      }
+    }
+  }
+
+  // Restores the invariants of a non-root node after one of its descendants
+  // changed: drops it when it became useless and merges it with its only
+  // child when it no longer branches.
+  fn compress<V>(node: RadixNode<V>) -> Option<Rc<RadixNode<V>>>
+  where
+    V: Clone,
+  {
+    match (&node.value, node.children.len()) {
+      (None, 0) => None,
+      (None, 1) => {
+        let child = &node.children[0];
+        let mut prefix = node.prefix.clone();
+        prefix.extend_from_slice(&child.prefix);
+        Some(Rc::new(RadixNode {
+          prefix,
+          value: child.value.clone(),
+          children: child.children.clone(),
+        }))
+      }
+      _ => Some(Rc::new(node)),
+    }
+  }
+
+  // `None` when `key` is missing, otherwise the node without it.
+  pub fn remove_node_aux<V>(node: &RadixNode<V>, key: &[u8]) -> Option<RadixNode<V>>
+  where
+    V: Clone,
+  {
+    if key.is_empty() {
+      return node.value.as_ref().map(|_| RadixNode {
+        prefix: node.prefix.clone(),
+        value: None,
+        children: node.children.clone(),
+      });
+    }
+    let index = child_index(node, key[0]).ok()?;
+    let child = &node.children[index];
+    if !key.starts_with(&child.prefix) {
+      return None;
+    }
+    let new_child = remove_node_aux(child, &key[child.prefix.len()..])?;
+    let mut children = node.children.clone();
+    match compress(new_child) {
+      Some(new_child) => children[index] = new_child,
+      None => {
+        children.remove(index);
+      }
+    }
+    Some(RadixNode {
+      prefix: node.prefix.clone(),
+      value: node.value.clone(),
+      children,
+    })
+  }
+
+  pub fn get_node_aux<'a, V>(node: &'a RadixNode<V>, key: &[u8]) -> Option<&'a V> {
+    if key.is_empty() {
+      return node.value.as_ref();
+    }
+    let child = &node.children[child_index(node, key[0]).ok()?];
+    if key.starts_with(&child.prefix) {
+      get_node_aux(child, &key[child.prefix.len()..])
+    } else {
+      None
+    }
+  }
+
+  pub fn longest_prefix_node_aux<'a, V>(
+    node: &'a RadixNode<V>,
+    key: &[u8],
+    depth: usize,
+    acc: Option<(usize, &'a V)>,
+  ) -> Option<(usize, &'a V)> {
+    let acc = match &node.value {
+      Some(value) => Some((depth, value)),
+      None => acc,
+    };
+    if depth == key.len() {
+      return acc;
+    }
+    match child_index(node, key[depth]) {
+      Ok(index) => {
+        let child = &node.children[index];
+        if key[depth..].starts_with(&child.prefix) {
+          longest_prefix_node_aux(child, key, depth + child.prefix.len(), acc)
+        } else {
+          acc
+        }
+      }
+      Err(_) => acc,
+    }
+  }
+
+  // The node holding every key that starts with `prefix`, with the full key
+  // leading to it.
+  pub fn prefix_node_aux<'a, V>(
+    node: &'a RadixNode<V>,
+    prefix: &[u8],
+    acc: Vec<u8>,
+  ) -> Option<(&'a RadixNode<V>, Vec<u8>)> {
+    if prefix.is_empty() {
+      return Some((node, acc));
+    }
+    let child = &node.children[child_index(node, prefix[0]).ok()?];
+    let common = common_prefix_len(&child.prefix, prefix);
+    let mut acc = acc;
+    acc.extend_from_slice(&child.prefix);
+    if common == prefix.len() {
+      Some((child, acc))
+    } else if common == child.prefix.len() {
+      prefix_node_aux(child, &prefix[common..], acc)
+    } else {
+      None
+    }
+  }
+}
+
+impl<V> Default for RadixTrie<V>
+where
+  V: Clone,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<V> RadixTrie<V>
+where
+  V: Clone,
+{
+  pub fn new() -> Self {
+    Self {
+      root: Rc::new(RadixNode {
+        prefix: vec![],
+        value: None,
+        children: vec![],
+      }),
+      len: 0,
+    }
+  }
+
+  pub fn is_empty(trie: &Self) -> bool {
+    trie.len == 0
+  }
+
+  pub fn len(trie: &Self) -> i32 {
+    trie.len
+  }
+
+  // Replaces the value when `key` is already there.
+  pub fn insert<K>(trie: &Self, key: K, item: V) -> Self
+  where
+    K: AsRef<[u8]>,
+  {
+    let (root, added) = private::insert_node_aux(&trie.root, key.as_ref(), item);
+    Self {
+      root,
+      len: if added { trie.len + 1 } else { trie.len },
+    }
+  }
+
+  pub fn remove<K>(trie: &Self, key: K) -> Self
+  where
+    K: AsRef<[u8]>,
+  {
+    match private::remove_node_aux(&trie.root, key.as_ref()) {
+      Some(root) => Self {
+        root: Rc::new(root),
+        len: trie.len - 1,
+      },
+      None => trie.clone(),
+    }
+  }
+
+  pub fn get<K>(trie: &Self, key: K) -> Option<&V>
+  where
+    K: AsRef<[u8]>,
+  {
+    private::get_node_aux(&trie.root, key.as_ref())
+  }
+
+  pub fn contains_key<K>(trie: &Self, key: K) -> bool
+  where
+    K: AsRef<[u8]>,
+  {
+    Self::get(trie, key).is_some()
+  }
+
+  // The longest stored key that is a prefix of `key`, with its value.
+  pub fn longest_prefix<K>(trie: &Self, key: K) -> Option<(Vec<u8>, &V)>
+  where
+    K: AsRef<[u8]>,
+  {
+    let key = key.as_ref();
+    private::longest_prefix_node_aux(&trie.root, key, 0, None)
+      .map(|(len, value)| (key[..len].to_vec(), value))
+  }
+
+  // Every key starting with `prefix`, in lexicographic order.
+  pub fn prefix<K>(trie: &Self, prefix: K) -> PrefixIter<'_, V>
+  where
+    K: AsRef<[u8]>,
+  {
+    PrefixIter {
+      stack: private::prefix_node_aux(&trie.root, prefix.as_ref(), vec![])
+        .into_iter()
+        .collect(),
+    }
+  }
+
+  pub fn iter(trie: &Self) -> PrefixIter<'_, V> {
+    Self::prefix(trie, [])
+  }
+}
+
+#[cfg(test)]
+#[path = "./radix-trie_test.rs"]
+mod test;
@@ -0,0 +1,449 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type RadixTrieT = RadixTrie<i32>;
+
+  pub fn node(prefix: &str, value: Option<i32>, children: Vec<RadixNode<i32>>) -> RadixNode<i32> {
+    RadixNode {
+      prefix: prefix.as_bytes().to_vec(),
+      value,
+      children: children.into_iter().map(Rc::new).collect(),
+    }
+  }
+
+  pub fn radix_trie_empty() -> RadixTrieT {
+    RadixTrieT {
+      root: Rc::new(node("", None, vec![])),
+      len: 0,
+    }
+  }
+
+  pub fn radix_trie_filled() -> RadixTrieT {
+    /*
+      ""
+      ├── "r"
+      │   ├── "oman" (1)
+      │   │   └── "us" (2)
+      │   └── "ub" (3)
+      │       ├── "ens" (4)
+      │       └── "icon" (5)
+      └── "test" (6)
+    */
+    RadixTrieT {
+      root: Rc::new(node(
+        "",
+        None,
+        vec![
+          node(
+            "r",
+            None,
+            vec![
+              node("oman", Some(1), vec![node("us", Some(2), vec![])]),
+              node(
+                "ub",
+                Some(3),
+                vec![node("ens", Some(4), vec![]), node("icon", Some(5), vec![])],
+              ),
+            ],
+          ),
+          node("test", Some(6), vec![]),
+        ],
+      )),
+      len: 6,
+    }
+  }
+
+  pub fn keys(iter: PrefixIter<'_, i32>) -> Vec<(String, i32)> {
+    iter
+      .map(|(key, value)| (String::from_utf8(key).unwrap(), *value))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = RadixTrie::<i32>::new();
+    assert_eq!(op, setup::radix_trie_empty())
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(RadixTrie::is_empty(&setup::radix_trie_empty()))
+  }
+
+  #[test]
+  fn to_filled() {
+    assert!(!RadixTrie::is_empty(&setup::radix_trie_filled()))
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(RadixTrie::len(&setup::radix_trie_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(RadixTrie::len(&setup::radix_trie_filled()), 6)
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let trie = setup::radix_trie_empty();
+    let op = RadixTrie::insert(&trie, "test", 6);
+    let expected = RadixTrie {
+      root: Rc::new(setup::node(
+        "",
+        None,
+        vec![setup::node("test", Some(6), vec![])],
+      )),
+      len: 1,
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_empty_building_filled() {
+    let op = [
+      ("roman", 1),
+      ("romanus", 2),
+      ("rub", 3),
+      ("rubens", 4),
+      ("rubicon", 5),
+      ("test", 6),
+    ]
+    .into_iter()
+    .fold(RadixTrie::new(), |trie, (key, value)| {
+      RadixTrie::insert(&trie, key, value)
+    });
+    assert_eq!(op, setup::radix_trie_filled())
+  }
+
+  #[test]
+  fn to_filled_splitting_an_edge() {
+    let trie = setup::radix_trie_filled();
+    let op = RadixTrie::insert(&trie, "tea", 7);
+    assert_eq!(RadixTrie::len(&op), 7);
+    assert_eq!(RadixTrie::get(&op, "tea"), Some(&7));
+    assert_eq!(RadixTrie::get(&op, "test"), Some(&6));
+    assert_eq!(RadixTrie::get(&op, "te"), None)
+  }
+
+  #[test]
+  fn to_filled_inside_an_edge() {
+    let trie = setup::radix_trie_filled();
+    let op = RadixTrie::insert(&trie, "rom", 8);
+    assert_eq!(RadixTrie::len(&op), 7);
+    assert_eq!(RadixTrie::get(&op, "rom"), Some(&8));
+    assert_eq!(RadixTrie::get(&op, "roman"), Some(&1))
+  }
+
+  #[test]
+  fn to_filled_replacing() {
+    let trie = setup::radix_trie_filled();
+    let op = RadixTrie::insert(&trie, "rub", 30);
+    assert_eq!(RadixTrie::len(&op), 6);
+    assert_eq!(RadixTrie::get(&op, "rub"), Some(&30));
+    assert_eq!(RadixTrie::get(&trie, "rub"), Some(&3))
+  }
+
+  #[test]
+  fn to_filled_empty_key() {
+    let trie = setup::radix_trie_filled();
+    let op = RadixTrie::insert(&trie, "", 0);
+    assert_eq!(RadixTrie::len(&op), 7);
+    assert_eq!(RadixTrie::get(&op, ""), Some(&0))
+  }
+
+  #[test]
+  fn to_filled_byte_keys() {
+    let trie = RadixTrie::insert(&RadixTrie::new(), [0xff, 0x00], 1);
+    let op = RadixTrie::insert(&trie, vec![0xff, 0x01], 2);
+    assert_eq!(RadixTrie::get(&op, [0xff, 0x00]), Some(&1));
+    assert_eq!(RadixTrie::get(&op, [0xff, 0x01]), Some(&2))
+  }
+
+  #[test]
+  fn shares_untouched_branches() {
+    let trie = setup::radix_trie_filled();
+    let op = RadixTrie::insert(&trie, "rubber", 7);
+    assert!(Rc::ptr_eq(&trie.root.children[1], &op.root.children[1]));
+    assert!(Rc::ptr_eq(
+      &trie.root.children[0].children[0],
+      &op.root.children[0].children[0]
+    ))
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let trie = setup::radix_trie_empty();
+    assert_eq!(RadixTrie::remove(&trie, "test"), trie)
+  }
+
+  #[test]
+  fn to_filled_leaf() {
+    let trie = setup::radix_trie_filled();
+    let op = RadixTrie::remove(&trie, "test");
+    let expected = RadixTrie {
+      root: Rc::new(setup::node(
+        "",
+        None,
+        vec![setup::node(
+          "r",
+          None,
+          vec![
+            setup::node("oman", Some(1), vec![setup::node("us", Some(2), vec![])]),
+            setup::node(
+              "ub",
+              Some(3),
+              vec![
+                setup::node("ens", Some(4), vec![]),
+                setup::node("icon", Some(5), vec![]),
+              ],
+            ),
+          ],
+        )],
+      )),
+      len: 5,
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_merging_edges() {
+    let trie = setup::radix_trie_filled();
+    let op = RadixTrie::remove(&trie, "roman");
+    assert_eq!(
+      op.root.children[0].children[0].as_ref(),
+      &setup::node("omanus", Some(2), vec![])
+    );
+    assert_eq!(RadixTrie::len(&op), 5)
+  }
+
+  #[test]
+  fn to_filled_down_to_single_branch() {
+    let trie = setup::radix_trie_filled();
+    let op = ["roman", "romanus"]
+      .into_iter()
+      .fold(trie, |trie, key| RadixTrie::remove(&trie, key));
+    assert_eq!(
+      op.root.children[0].as_ref(),
+      &setup::node(
+        "rub",
+        Some(3),
+        vec![
+          setup::node("ens", Some(4), vec![]),
+          setup::node("icon", Some(5), vec![]),
+        ],
+      )
+    )
+  }
+
+  #[test]
+  fn to_filled_missing_keys() {
+    let trie = setup::radix_trie_filled();
+    assert_eq!(RadixTrie::remove(&trie, "r"), trie);
+    assert_eq!(RadixTrie::remove(&trie, "roma"), trie);
+    assert_eq!(RadixTrie::remove(&trie, "rubicons"), trie)
+  }
+
+  #[test]
+  fn to_filled_until_empty() {
+    let keys = ["roman", "romanus", "rub", "rubens", "rubicon", "test"];
+    let op = keys
+      .into_iter()
+      .fold(setup::radix_trie_filled(), |trie, key| {
+        RadixTrie::remove(&trie, key)
+      });
+    assert_eq!(op, setup::radix_trie_empty())
+  }
+}
+
+#[cfg(test)]
+mod get {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(RadixTrie::get(&setup::radix_trie_empty(), "test"), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let trie = setup::radix_trie_filled();
+    assert_eq!(RadixTrie::get(&trie, "romanus"), Some(&2));
+    assert_eq!(RadixTrie::get(&trie, "rub"), Some(&3));
+    assert_eq!(RadixTrie::get(&trie, "rubicon"), Some(&5))
+  }
+
+  #[test]
+  fn to_filled_missing_keys() {
+    let trie = setup::radix_trie_filled();
+    assert_eq!(RadixTrie::get(&trie, "r"), None);
+    assert_eq!(RadixTrie::get(&trie, "ru"), None);
+    assert_eq!(RadixTrie::get(&trie, "rubicons"), None);
+    assert_eq!(RadixTrie::get(&trie, ""), None)
+  }
+}
+
+#[cfg(test)]
+mod contains_key {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let trie = setup::radix_trie_filled();
+    assert!(RadixTrie::contains_key(&trie, "test"));
+    assert!(!RadixTrie::contains_key(&trie, "tes"))
+  }
+}
+
+#[cfg(test)]
+mod longest_prefix {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(
+      RadixTrie::longest_prefix(&setup::radix_trie_empty(), "test"),
+      None
+    )
+  }
+
+  #[test]
+  fn to_filled() {
+    let trie = setup::radix_trie_filled();
+    assert_eq!(
+      RadixTrie::longest_prefix(&trie, "rubicons"),
+      Some((b"rubicon".to_vec(), &5))
+    );
+    assert_eq!(
+      RadixTrie::longest_prefix(&trie, "rubric"),
+      Some((b"rub".to_vec(), &3))
+    );
+    assert_eq!(
+      RadixTrie::longest_prefix(&trie, "romanes"),
+      Some((b"roman".to_vec(), &1))
+    )
+  }
+
+  #[test]
+  fn to_filled_without_match() {
+    let trie = setup::radix_trie_filled();
+    assert_eq!(RadixTrie::longest_prefix(&trie, "roma"), None);
+    assert_eq!(RadixTrie::longest_prefix(&trie, "tes"), None)
+  }
+
+  #[test]
+  fn to_url_routes() {
+    let trie = [("/", 0), ("/api", 1), ("/api/users", 2)]
+      .into_iter()
+      .fold(RadixTrie::new(), |trie, (key, value)| {
+        RadixTrie::insert(&trie, key, value)
+      });
+    assert_eq!(
+      RadixTrie::longest_prefix(&trie, "/api/users/42"),
+      Some((b"/api/users".to_vec(), &2))
+    );
+    assert_eq!(
+      RadixTrie::longest_prefix(&trie, "/apix"),
+      Some((b"/api".to_vec(), &1))
+    );
+    assert_eq!(
+      RadixTrie::longest_prefix(&trie, "/static"),
+      Some((b"/".to_vec(), &0))
+    )
+  }
+}
+
+#[cfg(test)]
+mod prefix {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let trie = setup::radix_trie_empty();
+    assert_eq!(setup::keys(RadixTrie::prefix(&trie, "r")), vec![])
+  }
+
+  #[test]
+  fn to_filled() {
+    let trie = setup::radix_trie_filled();
+    let expected = vec![
+      ("rub".to_string(), 3),
+      ("rubens".to_string(), 4),
+      ("rubicon".to_string(), 5),
+    ];
+    assert_eq!(setup::keys(RadixTrie::prefix(&trie, "rub")), expected)
+  }
+
+  #[test]
+  fn to_filled_ending_inside_an_edge() {
+    let trie = setup::radix_trie_filled();
+    let expected = vec![("roman".to_string(), 1), ("romanus".to_string(), 2)];
+    assert_eq!(setup::keys(RadixTrie::prefix(&trie, "rom")), expected)
+  }
+
+  #[test]
+  fn to_filled_without_match() {
+    let trie = setup::radix_trie_filled();
+    assert_eq!(setup::keys(RadixTrie::prefix(&trie, "rx")), vec![]);
+    assert_eq!(setup::keys(RadixTrie::prefix(&trie, "tests")), vec![])
+  }
+}
+
+#[cfg(test)]
+mod iter {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(
+      setup::keys(RadixTrie::iter(&setup::radix_trie_empty())),
+      vec![]
+    )
+  }
+
+  #[test]
+  fn to_filled_in_lexicographic_order() {
+    let keys = ["b", "a", "abc", "ab", "ba", "", "abd", "aa"];
+    let trie = keys
+      .into_iter()
+      .enumerate()
+      .fold(RadixTrie::new(), |trie, (value, key)| {
+        RadixTrie::insert(&trie, key, value as i32)
+      });
+    let mut expected: Vec<&str> = keys.to_vec();
+    expected.sort();
+    let op: Vec<String> = setup::keys(RadixTrie::iter(&trie))
+      .into_iter()
+      .map(|(key, _)| key)
+      .collect();
+    assert_eq!(op, expected)
+  }
+}
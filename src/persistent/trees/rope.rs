@@ -0,0 +1,412 @@
+use std::{
+  fmt,
+  ops::{Bound, RangeBounds},
+  rc::Rc,
+};
+
+use super::{Avl, AvlParts};
+use crate::IndexError;
+
+// Longest chunk, in bytes, built from an inserted text; joining two leaves
+// only merges them while they fit in it.
+const MAX_CHUNK: usize = 256;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct TextInfo {
+  pub bytes: usize,
+  pub chars: usize,
+  pub newlines: usize,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum RopeNode {
+  Leaf {
+    text: String,
+    info: TextInfo,
+  },
+  Branch {
+    info: TextInfo,
+    height: i32,
+    left: Rc<RopeNode>,
+    right: Rc<RopeNode>,
+  },
+}
+
+// Positions are in chars, except for the byte and line conversions.
+//
+// Invariants:
+//    - only the root of an empty rope is an empty leaf
+//    - info is the sum of the info of both children
+//    - the heights of both children differ by at most one
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rope {
+  root: Rc<RopeNode>,
+}
+
+pub struct Chunks<'a> {
+  stack: Vec<&'a RopeNode>,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+  type Item = &'a str;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while let Some(node) = self.stack.pop() {
+      match node {
+        RopeNode::Leaf { text, .. } => {
+          if !text.is_empty() {
+            return Some(text);
+          }
+        }
+        RopeNode::Branch { left, right, .. } => {
+          self.stack.push(right);
+          self.stack.push(left);
+        }
+      }
+    }
+    None
+  }
+}
+
+mod private {
+  use super::*;
+
+  pub fn info(node: &RopeNode) -> TextInfo {
+    match node {
+      RopeNode::Leaf { info, .. } => *info,
+      RopeNode::Branch { info, .. } => *info,
+    }
+  }
+
+  pub fn height(node: &RopeNode) -> i32 {
+    match node {
+      RopeNode::Leaf { .. } => 0,
+      RopeNode::Branch { height, .. } => *height,
+    }
+  }
+
+  pub fn leaf(text: &str) -> Rc<RopeNode> {
+    Rc::new(RopeNode::Leaf {
+      text: text.to_string(),
+      info: TextInfo {
+        bytes: text.len(),
+        chars: text.chars().count(),
+        newlines: text.bytes().filter(|byte| *byte == b'\n').count(),
+      },
+    })
+  }
+
+  pub fn branch(left: Rc<RopeNode>, right: Rc<RopeNode>) -> Rc<RopeNode> {
+    let (i1, i2) = (info(&left), info(&right));
+    Rc::new(RopeNode::Branch {
+      info: TextInfo {
+        bytes: i1.bytes + i2.bytes,
+        chars: i1.chars + i2.chars,
+        newlines: i1.newlines + i2.newlines,
+      },
+      height: std::cmp::max(height(&left), height(&right)) + 1,
+      left,
+      right,
+    })
+  }
+
+  // Concatenates two ropes going down the spine of the taller one only.
+  pub fn join(left: &Rc<RopeNode>, right: &Rc<RopeNode>) -> Rc<RopeNode> {
+    if info(left).bytes == 0 {
+      return right.clone();
+    }
+    if info(right).bytes == 0 {
+      return left.clone();
+    }
+    match (left.as_ref(), right.as_ref()) {
+      (RopeNode::Leaf { text: t1, .. }, RopeNode::Leaf { text: t2, .. })
+        if t1.len() + t2.len() <= MAX_CHUNK =>
+      {
+        leaf(&[t1.as_str(), t2.as_str()].concat())
+      }
+      _ if height(left) > height(right) + 1 => match left.as_ref() {
+        RopeNode::Branch {
+          left: ll,
+          right: lr,
+          ..
+        } => Rope::balance((), ll.clone(), join(lr, right)),
+        RopeNode::Leaf { .. } => branch(left.clone(), right.clone()),
+      },
+      _ if height(right) > height(left) + 1 => match right.as_ref() {
+        RopeNode::Branch {
+          left: rl,
+          right: rr,
+          ..
+        } => Rope::balance((), join(left, rl), rr.clone()),
+        RopeNode::Leaf { .. } => branch(left.clone(), right.clone()),
+      },
+      _ => branch(left.clone(), right.clone()),
+    }
+  }
+
+  // Builds a balanced tree out of `text`, cut in chunks on char boundaries.
+  pub fn from_str_aux(text: &str) -> Rc<RopeNode> {
+    if text.len() <= MAX_CHUNK {
+      return leaf(text);
+    }
+    let mut middle = text.len() / 2;
+    while !text.is_char_boundary(middle) {
+      middle += 1;
+    }
+    let (left, right) = text.split_at(middle);
+    branch(from_str_aux(left), from_str_aux(right))
+  }
+
+  fn byte_of_char(text: &str, index: usize) -> usize {
+    text
+      .char_indices()
+      .nth(index)
+      .map_or(text.len(), |(byte, _)| byte)
+  }
+
+  // Splits into the first `index` chars and the rest.
+  pub fn split_node_aux(node: &Rc<RopeNode>, index: usize) -> (Rc<RopeNode>, Rc<RopeNode>) {
+    match node.as_ref() {
+      RopeNode::Leaf { text, info } => {
+        if index == 0 {
+          (leaf(""), node.clone())
+        } else if index >= info.chars {
+          (node.clone(), leaf(""))
+        } else {
+          let (lower, upper) = text.split_at(byte_of_char(text, index));
+          (leaf(lower), leaf(upper))
+        }
+      }
+      RopeNode::Branch { left, right, .. } => {
+        let left_chars = info(left).chars;
+        if index <= left_chars {
+          let (lower, upper) = split_node_aux(left, index);
+          (lower, join(&upper, right))
+        } else {
+          let (lower, upper) = split_node_aux(right, index - left_chars);
+          (join(left, &lower), upper)
+        }
+      }
+    }
+  }
+
+  pub fn char_at_node_aux(node: &RopeNode, index: usize) -> Option<char> {
+    match node {
+      RopeNode::Leaf { text, .. } => text.chars().nth(index),
+      RopeNode::Branch { left, right, .. } => {
+        let left_chars = info(left).chars;
+        if index < left_chars {
+          char_at_node_aux(left, index)
+        } else {
+          char_at_node_aux(right, index - left_chars)
+        }
+      }
+    }
+  }
+
+  // Byte offset right after the `newline`-th newline, counting from 1.
+  pub fn line_to_byte_node_aux(node: &RopeNode, newline: usize) -> usize {
+    match node {
+      RopeNode::Leaf { text, .. } => text
+        .bytes()
+        .enumerate()
+        .filter(|(_, byte)| *byte == b'\n')
+        .nth(newline - 1)
+        .map_or(text.len(), |(index, _)| index + 1),
+      RopeNode::Branch { left, right, .. } => {
+        let left_info = info(left);
+        if newline <= left_info.newlines {
+          line_to_byte_node_aux(left, newline)
+        } else {
+          left_info.bytes + line_to_byte_node_aux(right, newline - left_info.newlines)
+        }
+      }
+    }
+  }
+
+  // How many newlines come before `byte`.
+  pub fn byte_to_line_node_aux(node: &RopeNode, byte: usize) -> usize {
+    match node {
+      RopeNode::Leaf { text, .. } => text.as_bytes()[..byte]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count(),
+      RopeNode::Branch { left, right, .. } => {
+        let left_info = info(left);
+        if byte <= left_info.bytes {
+          byte_to_line_node_aux(left, byte)
+        } else {
+          left_info.newlines + byte_to_line_node_aux(right, byte - left_info.bytes)
+        }
+      }
+    }
+  }
+
+  pub fn height_node_aux(node: &RopeNode) -> i32 {
+    height(node)
+  }
+}
+
+// Branches carry no value of their own, so rotating one only regroups the
+// leaves under it.
+impl Avl for Rope {
+  type Node = RopeNode;
+  type Value = ();
+
+  fn height(node: &RopeNode) -> i32 {
+    private::height(node)
+  }
+
+  fn parts(node: &RopeNode) -> Option<AvlParts<'_, (), RopeNode>> {
+    match node {
+      RopeNode::Leaf { .. } => None,
+      RopeNode::Branch { left, right, .. } => Some((&(), left, right)),
+    }
+  }
+
+  fn node(_: (), left: Rc<RopeNode>, right: Rc<RopeNode>) -> Rc<RopeNode> {
+    private::branch(left, right)
+  }
+}
+
+impl From<&str> for Rope {
+  fn from(value: &str) -> Self {
+    Self {
+      root: private::from_str_aux(value),
+    }
+  }
+}
+
+impl fmt::Display for Rope {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    Rope::chunks(self).try_for_each(|chunk| f.write_str(chunk))
+  }
+}
+
+impl Default for Rope {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Rope {
+  pub fn new() -> Self {
+    Self {
+      root: private::leaf(""),
+    }
+  }
+
+  fn chars_range<R>(rope: &Self, range: R) -> Result<(usize, usize), IndexError>
+  where
+    R: RangeBounds<usize>,
+  {
+    let len = Self::len_chars(rope);
+    let start = match range.start_bound() {
+      Bound::Included(start) => *start,
+      Bound::Excluded(start) => start + 1,
+      Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+      Bound::Included(end) => end + 1,
+      Bound::Excluded(end) => *end,
+      Bound::Unbounded => len,
+    };
+    if start > end || end > len {
+      return Err(IndexError::OutOfBounds);
+    }
+    Ok((start, end))
+  }
+
+  pub fn is_empty(rope: &Self) -> bool {
+    private::info(&rope.root).bytes == 0
+  }
+
+  pub fn len_bytes(rope: &Self) -> usize {
+    private::info(&rope.root).bytes
+  }
+
+  pub fn len_chars(rope: &Self) -> usize {
+    private::info(&rope.root).chars
+  }
+
+  // A rope without newlines still has one line.
+  pub fn len_lines(rope: &Self) -> usize {
+    private::info(&rope.root).newlines + 1
+  }
+
+  pub fn height(rope: &Self) -> i32 {
+    private::height_node_aux(&rope.root)
+  }
+
+  pub fn insert(rope: &Self, index: usize, text: &str) -> Result<Self, IndexError> {
+    if index > Self::len_chars(rope) {
+      return Err(IndexError::OutOfBounds);
+    }
+    let (lower, upper) = private::split_node_aux(&rope.root, index);
+    let middle = private::from_str_aux(text);
+    Ok(Self {
+      root: private::join(&private::join(&lower, &middle), &upper),
+    })
+  }
+
+  pub fn remove<R>(rope: &Self, range: R) -> Result<Self, IndexError>
+  where
+    R: RangeBounds<usize>,
+  {
+    let (start, end) = Self::chars_range(rope, range)?;
+    let (lower, rest) = private::split_node_aux(&rope.root, start);
+    let (_, upper) = private::split_node_aux(&rest, end - start);
+    Ok(Self {
+      root: private::join(&lower, &upper),
+    })
+  }
+
+  pub fn slice<R>(rope: &Self, range: R) -> Result<Self, IndexError>
+  where
+    R: RangeBounds<usize>,
+  {
+    let (start, end) = Self::chars_range(rope, range)?;
+    let (_, rest) = private::split_node_aux(&rope.root, start);
+    let (middle, _) = private::split_node_aux(&rest, end - start);
+    Ok(Self { root: middle })
+  }
+
+  pub fn concat(r1: &Self, r2: &Self) -> Self {
+    Self {
+      root: private::join(&r1.root, &r2.root),
+    }
+  }
+
+  pub fn char_at(rope: &Self, index: usize) -> Option<char> {
+    private::char_at_node_aux(&rope.root, index)
+  }
+
+  // Byte offset where `line` starts, lines counting from 0.
+  pub fn line_to_byte(rope: &Self, line: usize) -> Option<usize> {
+    if line == 0 {
+      return Some(0);
+    }
+    if line >= Self::len_lines(rope) {
+      return None;
+    }
+    Some(private::line_to_byte_node_aux(&rope.root, line))
+  }
+
+  // The line holding `byte`; the end of the rope belongs to the last line.
+  pub fn byte_to_line(rope: &Self, byte: usize) -> Option<usize> {
+    if byte > Self::len_bytes(rope) {
+      return None;
+    }
+    Some(private::byte_to_line_node_aux(&rope.root, byte))
+  }
+
+  pub fn chunks(rope: &Self) -> Chunks<'_> {
+    Chunks {
+      stack: vec![&rope.root],
+    }
+  }
+}
+
+#[cfg(test)]
+#[path = "./rope_test.rs"]
+mod test;
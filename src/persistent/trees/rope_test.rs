@@ -0,0 +1,375 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub fn rope_empty() -> Rope {
+    Rope {
+      root: private::leaf(""),
+    }
+  }
+
+  pub fn rope_filled() -> Rope {
+    Rope {
+      root: private::join(&private::leaf("héllo\n"), &private::leaf("wörld\nagain")),
+    }
+  }
+
+  // Numbered lines, long enough to span many chunks.
+  pub fn long_text(lines: usize) -> String {
+    (0..lines).map(|line| format!("line {line}\n")).collect()
+  }
+
+  fn check_node_aux(node: &RopeNode) -> (i32, TextInfo) {
+    match node {
+      RopeNode::Leaf { text, info } => {
+        assert!(!text.is_empty());
+        assert_eq!(*info, private::info(&private::leaf(text)));
+        (0, *info)
+      }
+      RopeNode::Branch {
+        info,
+        height,
+        left,
+        right,
+      } => {
+        let (h1, i1) = check_node_aux(left);
+        let (h2, i2) = check_node_aux(right);
+        assert!((h1 - h2).abs() <= 1);
+        assert_eq!(*height, std::cmp::max(h1, h2) + 1);
+        assert_eq!(info.bytes, i1.bytes + i2.bytes);
+        assert_eq!(info.chars, i1.chars + i2.chars);
+        assert_eq!(info.newlines, i1.newlines + i2.newlines);
+        (*height, *info)
+      }
+    }
+  }
+
+  pub fn check(rope: &Rope) {
+    if !Rope::is_empty(rope) {
+      check_node_aux(&rope.root);
+    }
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    assert_eq!(Rope::new(), setup::rope_empty())
+  }
+}
+
+#[cfg(test)]
+mod from {
+  use super::*;
+
+  #[test]
+  fn to_short_text() {
+    let op = Rope::from("héllo\nwörld\nagain");
+    assert_eq!(op.root, private::leaf("héllo\nwörld\nagain"))
+  }
+
+  #[test]
+  fn to_long_text() {
+    let text = setup::long_text(1000);
+    let op = Rope::from(text.as_str());
+    setup::check(&op);
+    assert!(Rope::chunks(&op).all(|chunk| chunk.len() <= 256));
+    assert_eq!(op.to_string(), text)
+  }
+
+  #[test]
+  fn to_multibyte_text() {
+    let text = "ção🦀".repeat(200);
+    let op = Rope::from(text.as_str());
+    setup::check(&op);
+    assert_eq!(op.to_string(), text)
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert!(Rope::is_empty(&setup::rope_empty()))
+  }
+
+  #[test]
+  fn to_filled() {
+    assert!(!Rope::is_empty(&setup::rope_filled()))
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let rope = setup::rope_empty();
+    assert_eq!(Rope::len_bytes(&rope), 0);
+    assert_eq!(Rope::len_chars(&rope), 0);
+    assert_eq!(Rope::len_lines(&rope), 1)
+  }
+
+  #[test]
+  fn to_filled() {
+    let rope = setup::rope_filled();
+    assert_eq!(Rope::len_bytes(&rope), 19);
+    assert_eq!(Rope::len_chars(&rope), 17);
+    assert_eq!(Rope::len_lines(&rope), 3)
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let rope = setup::rope_empty();
+    let op = Rope::insert(&rope, 0, "hello").unwrap();
+    assert_eq!(op.root, private::leaf("hello"))
+  }
+
+  #[test]
+  fn to_filled() {
+    let rope = setup::rope_filled();
+    let op = Rope::insert(&rope, 2, "ey").unwrap();
+    assert_eq!(op.to_string(), "héeyllo\nwörld\nagain");
+    assert_eq!(rope.to_string(), "héllo\nwörld\nagain")
+  }
+
+  #[test]
+  fn to_filled_at_the_ends() {
+    let rope = setup::rope_filled();
+    let op = Rope::insert(&rope, 0, ">").unwrap();
+    let op = Rope::insert(&op, 18, "<").unwrap();
+    assert_eq!(op.to_string(), ">héllo\nwörld\nagain<")
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let rope = setup::rope_filled();
+    assert_eq!(Rope::insert(&rope, 18, "!"), Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled_typing() {
+    let text = setup::long_text(300);
+    let op = text
+      .chars()
+      .enumerate()
+      .fold(Rope::new(), |acc, (index, c)| {
+        Rope::insert(&acc, index, &c.to_string()).unwrap()
+      });
+    setup::check(&op);
+    assert_eq!(op.to_string(), text);
+    assert!(Rope::height(&op) <= 10)
+  }
+
+  #[test]
+  fn shares_untouched_chunks() {
+    let text = setup::long_text(1000);
+    let rope = Rope::from(text.as_str());
+    let op = Rope::insert(&rope, 5, "inserted").unwrap();
+    let old: Vec<*const u8> = Rope::chunks(&rope).map(|chunk| chunk.as_ptr()).collect();
+    let new: Vec<*const u8> = Rope::chunks(&op).map(|chunk| chunk.as_ptr()).collect();
+    assert_eq!(old[1..], new[new.len() - old.len() + 1..])
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let rope = setup::rope_empty();
+    assert_eq!(Rope::remove(&rope, ..), Ok(setup::rope_empty()));
+    assert_eq!(Rope::remove(&rope, 0..1), Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    let rope = setup::rope_filled();
+    let op = Rope::remove(&rope, 3..9).unwrap();
+    assert_eq!(op.to_string(), "hélld\nagain")
+  }
+
+  #[test]
+  fn to_filled_everything() {
+    let rope = setup::rope_filled();
+    let op = Rope::remove(&rope, 0..17).unwrap();
+    assert!(Rope::is_empty(&op))
+  }
+
+  #[test]
+  fn to_filled_out_of_bounds() {
+    let rope = setup::rope_filled();
+    assert_eq!(Rope::remove(&rope, 10..18), Err(IndexError::OutOfBounds));
+    assert_eq!(
+      Rope::remove(&rope, (Bound::Included(5), Bound::Excluded(4))),
+      Err(IndexError::OutOfBounds)
+    )
+  }
+
+  #[test]
+  fn to_filled_long() {
+    let text = setup::long_text(1000);
+    let rope = Rope::from(text.as_str());
+    let op = Rope::remove(&rope, 100..5000).unwrap();
+    setup::check(&op);
+    assert_eq!(op.to_string(), [&text[..100], &text[5000..]].concat())
+  }
+}
+
+#[cfg(test)]
+mod slice {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let rope = setup::rope_empty();
+    assert_eq!(Rope::slice(&rope, ..), Ok(setup::rope_empty()))
+  }
+
+  #[test]
+  fn to_filled() {
+    let rope = setup::rope_filled();
+    assert_eq!(Rope::slice(&rope, 1..=8).unwrap().to_string(), "éllo\nwör");
+    assert_eq!(Rope::slice(&rope, 12..).unwrap().to_string(), "again")
+  }
+
+  #[test]
+  fn to_filled_long() {
+    let text = setup::long_text(1000);
+    let rope = Rope::from(text.as_str());
+    let op = Rope::slice(&rope, 3000..7000).unwrap();
+    setup::check(&op);
+    assert_eq!(op.to_string(), &text[3000..7000])
+  }
+}
+
+#[cfg(test)]
+mod concat {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let rope = setup::rope_empty();
+    assert_eq!(Rope::concat(&rope, &rope), setup::rope_empty())
+  }
+
+  #[test]
+  fn to_filled() {
+    let r1 = Rope::from(setup::long_text(1000).as_str());
+    let r2 = setup::rope_filled();
+    let op = Rope::concat(&r1, &r2);
+    setup::check(&op);
+    assert_eq!(
+      op.to_string(),
+      setup::long_text(1000) + "héllo\nwörld\nagain"
+    )
+  }
+}
+
+#[cfg(test)]
+mod char_at {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(Rope::char_at(&setup::rope_empty(), 0), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let rope = setup::rope_filled();
+    assert_eq!(Rope::char_at(&rope, 1), Some('é'));
+    assert_eq!(Rope::char_at(&rope, 7), Some('ö'));
+    assert_eq!(Rope::char_at(&rope, 16), Some('n'));
+    assert_eq!(Rope::char_at(&rope, 17), None)
+  }
+}
+
+#[cfg(test)]
+mod line_to_byte {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let rope = setup::rope_empty();
+    assert_eq!(Rope::line_to_byte(&rope, 0), Some(0));
+    assert_eq!(Rope::line_to_byte(&rope, 1), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let rope = setup::rope_filled();
+    assert_eq!(Rope::line_to_byte(&rope, 1), Some(7));
+    assert_eq!(Rope::line_to_byte(&rope, 2), Some(14));
+    assert_eq!(Rope::line_to_byte(&rope, 3), None)
+  }
+
+  #[test]
+  fn to_filled_long() {
+    let text = setup::long_text(1000);
+    let rope = Rope::from(text.as_str());
+    let expected = text.find("line 777\n");
+    assert_eq!(Rope::line_to_byte(&rope, 777), expected);
+    assert_eq!(Rope::line_to_byte(&rope, 1000), Some(text.len()))
+  }
+}
+
+#[cfg(test)]
+mod byte_to_line {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let rope = setup::rope_empty();
+    assert_eq!(Rope::byte_to_line(&rope, 0), Some(0));
+    assert_eq!(Rope::byte_to_line(&rope, 1), None)
+  }
+
+  #[test]
+  fn to_filled() {
+    let rope = setup::rope_filled();
+    assert_eq!(Rope::byte_to_line(&rope, 6), Some(0));
+    assert_eq!(Rope::byte_to_line(&rope, 7), Some(1));
+    assert_eq!(Rope::byte_to_line(&rope, 19), Some(2));
+    assert_eq!(Rope::byte_to_line(&rope, 20), None)
+  }
+
+  #[test]
+  fn to_filled_long() {
+    let text = setup::long_text(1000);
+    let rope = Rope::from(text.as_str());
+    let byte = text.find("line 777\n").unwrap();
+    assert_eq!(Rope::byte_to_line(&rope, byte), Some(777));
+    assert_eq!(Rope::byte_to_line(&rope, byte - 1), Some(776))
+  }
+}
+
+#[cfg(test)]
+mod chunks {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(Rope::chunks(&setup::rope_empty()).count(), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let rope = setup::rope_filled();
+    let op: Vec<&str> = Rope::chunks(&rope).collect();
+    assert_eq!(op, vec!["héllo\nwörld\nagain"])
+  }
+}
@@ -0,0 +1,419 @@
+use std::rc::Rc;
+
+use crate::SplitMix64;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TreapNode<T> {
+  Empty,
+  Node {
+    value: T,
+    priority: u64,
+    left: Rc<TreapNode<T>>,
+    right: Rc<TreapNode<T>>,
+  },
+}
+
+// Invariants:
+//    - the left_node value is always <= node value
+//    - the right_node value is always > node value
+//    - the node priority is always >= the priority of both children
+//
+// Priorities come from `rng`, which travels with every version of the tree,
+// so the same seed and the same sequence of operations always build the same
+// shape.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Treap<T> {
+  root: Rc<TreapNode<T>>,
+  rng: SplitMix64,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum MergeError {
+  OverlappingRanges,
+}
+
+mod private {
+  use super::*;
+
+  pub fn node<T>(
+    value: T,
+    priority: u64,
+    left: Rc<TreapNode<T>>,
+    right: Rc<TreapNode<T>>,
+  ) -> Rc<TreapNode<T>> {
+    Rc::new(TreapNode::Node {
+      value,
+      priority,
+      left,
+      right,
+    })
+  }
+
+  pub fn split_node_aux<T>(
+    node: &Rc<TreapNode<T>>,
+    item: &T,
+    inclusive: bool,
+  ) -> (Rc<TreapNode<T>>, Rc<TreapNode<T>>)
+  where
+    T: Clone + PartialOrd,
+  {
+    match node.as_ref() {
+      TreapNode::Empty => (node.clone(), node.clone()),
+      TreapNode::Node {
+        value,
+        priority,
+        left,
+        right,
+      } => {
+        let goes_left = if inclusive {
+          value <= item
+        } else {
+          value < item
+        };
+        if goes_left {
+          let (lower, upper) = split_node_aux(right, item, inclusive);
+          (
+            self::node(value.clone(), *priority, left.clone(), lower),
+            upper,
+          )
+        } else {
+          let (lower, upper) = split_node_aux(left, item, inclusive);
+          (
+            lower,
+            self::node(value.clone(), *priority, upper, right.clone()),
+          )
+        }
+      }
+    }
+  }
+
+  pub fn merge_node_aux<T>(n1: &Rc<TreapNode<T>>, n2: &Rc<TreapNode<T>>) -> Rc<TreapNode<T>>
+  where
+    T: Clone,
+  {
+    match (n1.as_ref(), n2.as_ref()) {
+      (TreapNode::Empty, _) => n2.clone(),
+      (_, TreapNode::Empty) => n1.clone(),
+      (
+        TreapNode::Node {
+          value: v1,
+          priority: p1,
+          left: l1,
+          right: r1,
+        },
+        TreapNode::Node {
+          value: v2,
+          priority: p2,
+          left: l2,
+          right: r2,
+        },
+      ) => {
+        if p1 >= p2 {
+          node(v1.clone(), *p1, l1.clone(), merge_node_aux(r1, n2))
+        } else {
+          node(v2.clone(), *p2, merge_node_aux(n1, l2), r2.clone())
+        }
+      }
+    }
+  }
+
+  pub fn insert_node_aux<T>(node: &Rc<TreapNode<T>>, item: T, new_priority: u64) -> Rc<TreapNode<T>>
+  where
+    T: Clone + PartialOrd,
+  {
+    match node.as_ref() {
+      TreapNode::Empty => self::node(item, new_priority, node.clone(), node.clone()),
+      TreapNode::Node {
+        value,
+        priority,
+        left,
+        right,
+      } => {
+        if new_priority > *priority {
+          let (lower, upper) = split_node_aux(node, &item, true);
+          self::node(item, new_priority, lower, upper)
+        } else if &item > value {
+          self::node(
+            value.clone(),
+            *priority,
+            left.clone(),
+            insert_node_aux(right, item, new_priority),
+          )
+        } else {
+          self::node(
+            value.clone(),
+            *priority,
+            insert_node_aux(left, item, new_priority),
+            right.clone(),
+          )
+        }
+      }
+    }
+  }
+
+  pub fn remove_node_aux<T>(node: &Rc<TreapNode<T>>, item: &T) -> Rc<TreapNode<T>>
+  where
+    T: Clone + PartialOrd,
+  {
+    match node.as_ref() {
+      TreapNode::Empty => node.clone(),
+      TreapNode::Node {
+        value,
+        priority,
+        left,
+        right,
+      } => {
+        if item == value {
+          merge_node_aux(left, right)
+        } else if item > value {
+          self::node(
+            value.clone(),
+            *priority,
+            left.clone(),
+            remove_node_aux(right, item),
+          )
+        } else {
+          self::node(
+            value.clone(),
+            *priority,
+            remove_node_aux(left, item),
+            right.clone(),
+          )
+        }
+      }
+    }
+  }
+
+  pub fn search_node_aux<T>(node: &TreapNode<T>, item: T) -> Option<T>
+  where
+    T: PartialOrd,
+  {
+    match node {
+      TreapNode::Empty => None,
+      TreapNode::Node {
+        value, left, right, ..
+      } => {
+        if value == &item {
+          Some(item)
+        } else if &item > value {
+          search_node_aux(right, item)
+        } else {
+          search_node_aux(left, item)
+        }
+      }
+    }
+  }
+
+  pub fn min_node_aux<T>(node: &TreapNode<T>) -> Option<&T> {
+    match node {
+      TreapNode::Empty => None,
+      TreapNode::Node { value, left, .. } => match left.as_ref() {
+        TreapNode::Empty => Some(value),
+        TreapNode::Node { .. } => min_node_aux(left),
+      },
+    }
+  }
+
+  pub fn max_node_aux<T>(node: &TreapNode<T>) -> Option<&T> {
+    match node {
+      TreapNode::Empty => None,
+      TreapNode::Node { value, right, .. } => match right.as_ref() {
+        TreapNode::Empty => Some(value),
+        TreapNode::Node { .. } => max_node_aux(right),
+      },
+    }
+  }
+
+  pub fn len_node_aux<T>(node: &TreapNode<T>, acc: i32) -> i32 {
+    match node {
+      TreapNode::Empty => acc,
+      TreapNode::Node { left, right, .. } => len_node_aux(right, len_node_aux(left, acc + 1)),
+    }
+  }
+
+  pub fn height_node_aux<T>(node: &TreapNode<T>) -> i32 {
+    match node {
+      TreapNode::Empty => 0,
+      TreapNode::Node { left, right, .. } => {
+        std::cmp::max(height_node_aux(left), height_node_aux(right)) + 1
+      }
+    }
+  }
+
+  pub fn any_node_aux<T>(node: &TreapNode<T>, f: fn(&T) -> bool) -> bool {
+    match node {
+      TreapNode::Empty => false,
+      TreapNode::Node {
+        value, left, right, ..
+      } => f(value) || any_node_aux(left, f) || any_node_aux(right, f),
+    }
+  }
+
+  pub fn all_node_aux<T>(node: &TreapNode<T>, f: fn(&T) -> bool) -> bool {
+    match node {
+      TreapNode::Empty => true,
+      TreapNode::Node {
+        value, left, right, ..
+      } => f(value) && all_node_aux(left, f) && all_node_aux(right, f),
+    }
+  }
+
+  pub fn find_node_aux<T>(node: &TreapNode<T>, f: fn(&T) -> bool) -> Option<&T> {
+    match node {
+      TreapNode::Empty => None,
+      TreapNode::Node {
+        value, left, right, ..
+      } => match find_node_aux(left, f) {
+        Some(value) => Some(value),
+        None if f(value) => Some(value),
+        None => find_node_aux(right, f),
+      },
+    }
+  }
+
+  pub fn map_node_aux<T, U>(node: &TreapNode<T>, f: fn(&T) -> U) -> Rc<TreapNode<U>> {
+    match node {
+      TreapNode::Empty => Rc::new(TreapNode::Empty),
+      TreapNode::Node {
+        value,
+        priority,
+        left,
+        right,
+      } => self::node(
+        f(value),
+        *priority,
+        map_node_aux(left, f),
+        map_node_aux(right, f),
+      ),
+    }
+  }
+
+  pub fn reduce_node_aux<T, U>(node: &TreapNode<T>, f: fn(&T, U) -> U, acc: U) -> U {
+    match node {
+      TreapNode::Empty => acc,
+      TreapNode::Node {
+        value, left, right, ..
+      } => reduce_node_aux(right, f, f(value, reduce_node_aux(left, f, acc))),
+    }
+  }
+}
+
+impl<T> Default for Treap<T>
+where
+  T: PartialEq + PartialOrd + Clone + Copy,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> Treap<T>
+where
+  T: PartialEq + PartialOrd + Clone + Copy,
+{
+  pub fn new() -> Self {
+    Self {
+      root: Rc::new(TreapNode::Empty),
+      rng: SplitMix64::default(),
+    }
+  }
+
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      root: Rc::new(TreapNode::Empty),
+      rng: SplitMix64::new(seed),
+    }
+  }
+
+  pub fn is_empty(tree: &Self) -> bool {
+    matches!(tree.root.as_ref(), TreapNode::Empty)
+  }
+
+  pub fn insert(tree: &Self, item: T) -> Self {
+    let mut rng = tree.rng;
+    let priority = rng.next_u64();
+    Self {
+      root: private::insert_node_aux(&tree.root, item, priority),
+      rng,
+    }
+  }
+
+  pub fn remove(tree: &Self, item: T) -> Self {
+    Self {
+      root: private::remove_node_aux(&tree.root, &item),
+      rng: tree.rng,
+    }
+  }
+
+  // Splits into (values < item, values >= item). Both halves keep the random
+  // state of `tree`.
+  pub fn split(tree: &Self, item: T) -> (Self, Self) {
+    let (lower, upper) = private::split_node_aux(&tree.root, &item, false);
+    (
+      Self {
+        root: lower,
+        rng: tree.rng,
+      },
+      Self {
+        root: upper,
+        rng: tree.rng,
+      },
+    )
+  }
+
+  // Every value of `t1` must be < every value of `t2`, which is exactly what
+  // `split` hands back. The result keeps the random state of `t1`.
+  pub fn merge(t1: &Self, t2: &Self) -> Result<Self, MergeError> {
+    if let (Some(max), Some(min)) = (
+      private::max_node_aux(&t1.root),
+      private::min_node_aux(&t2.root),
+    ) {
+      if max >= min {
+        return Err(MergeError::OverlappingRanges);
+      }
+    }
+    Ok(Self {
+      root: private::merge_node_aux(&t1.root, &t2.root),
+      rng: t1.rng,
+    })
+  }
+
+  pub fn search(tree: &Self, item: T) -> Option<T> {
+    private::search_node_aux(&tree.root, item)
+  }
+
+  pub fn len(tree: &Self) -> i32 {
+    private::len_node_aux(&tree.root, 0)
+  }
+
+  pub fn height(tree: &Self) -> i32 {
+    private::height_node_aux(&tree.root)
+  }
+
+  pub fn any(tree: &Self, f: fn(&T) -> bool) -> bool {
+    private::any_node_aux(&tree.root, f)
+  }
+
+  pub fn all(tree: &Self, f: fn(&T) -> bool) -> bool {
+    private::all_node_aux(&tree.root, f)
+  }
+
+  pub fn find(tree: &Self, f: fn(&T) -> bool) -> Option<&T> {
+    private::find_node_aux(&tree.root, f)
+  }
+
+  pub fn map<U>(tree: &Self, f: fn(&T) -> U) -> Treap<U> {
+    Treap::<U> {
+      root: private::map_node_aux(&tree.root, f),
+      rng: tree.rng,
+    }
+  }
+
+  pub fn reduce<U>(tree: &Self, f: fn(&T, U) -> U, acc: U) -> U {
+    private::reduce_node_aux(&tree.root, f, acc)
+  }
+}
+
+#[cfg(test)]
+#[path = "./treap_test.rs"]
+mod test;
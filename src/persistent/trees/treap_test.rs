@@ -0,0 +1,467 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub type TreapT = Treap<i32>;
+
+  pub fn node<T>(value: T, priority: u64, left: TreapNode<T>, right: TreapNode<T>) -> TreapNode<T> {
+    TreapNode::Node {
+      value,
+      priority,
+      left: Rc::new(left),
+      right: Rc::new(right),
+    }
+  }
+
+  pub fn leaf<T>(value: T, priority: u64) -> TreapNode<T> {
+    node(value, priority, TreapNode::Empty, TreapNode::Empty)
+  }
+
+  pub fn treap_empty() -> TreapT {
+    TreapT {
+      root: Rc::new(TreapNode::Empty),
+      rng: SplitMix64::default(),
+    }
+  }
+
+  pub fn treap_filled() -> TreapT {
+    /*
+           3 (70)
+         /       \
+      1 (50)    5 (60)
+       / \       / \
+     0    2     4   6
+    (10) (20) (30) (40)
+    */
+    TreapT {
+      root: Rc::new(node(
+        3,
+        70,
+        node(1, 50, leaf(0, 10), leaf(2, 20)),
+        node(5, 60, leaf(4, 30), leaf(6, 40)),
+      )),
+      rng: SplitMix64::default(),
+    }
+  }
+
+  pub fn to_vec(tree: &TreapT) -> Vec<i32> {
+    Treap::reduce(
+      tree,
+      |item, mut acc: Vec<i32>| {
+        acc.push(*item);
+        acc
+      },
+      vec![],
+    )
+  }
+
+  fn check_node(node: &TreapNode<i32>, low: Option<i32>, high: Option<i32>, max_priority: u64) {
+    if let TreapNode::Node {
+      value,
+      priority,
+      left,
+      right,
+    } = node
+    {
+      assert!(*priority <= max_priority, "heap order broken at {}", value);
+      assert!(low.is_none_or(|low| *value > low), "{} out of range", value);
+      assert!(
+        high.is_none_or(|high| *value <= high),
+        "{} out of range",
+        value
+      );
+      check_node(left, low, Some(*value), *priority);
+      check_node(right, Some(*value), high, *priority);
+    }
+  }
+
+  pub fn check_invariants(tree: &TreapT) {
+    check_node(&tree.root, None, None, u64::MAX)
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = Treap::<i32>::new();
+    let expected = setup::treap_empty();
+    assert_eq!(op, expected)
+  }
+}
+
+#[cfg(test)]
+mod with_seed {
+  use super::*;
+
+  #[test]
+  fn single_case() {
+    let op = Treap::<i32>::with_seed(99);
+    assert_eq!(op.rng, SplitMix64::new(99));
+    assert!(Treap::is_empty(&op))
+  }
+}
+
+#[cfg(test)]
+mod is_empty {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    assert!(Treap::is_empty(&tree))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::treap_filled();
+    assert!(!Treap::is_empty(&tree))
+  }
+}
+
+#[cfg(test)]
+mod insert {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = Treap::with_seed(7);
+    let op = Treap::insert(&tree, 0);
+    let mut rng = SplitMix64::new(7);
+    let expected = Treap {
+      root: Rc::new(setup::leaf(0, rng.next_u64())),
+      rng,
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::treap_filled();
+    let op = Treap::insert(&tree, 7);
+    setup::check_invariants(&op);
+    assert_eq!(setup::to_vec(&op), vec![0, 1, 2, 3, 4, 5, 6, 7])
+  }
+
+  #[test]
+  fn to_filled_with_duplicate() {
+    let tree = setup::treap_filled();
+    let op = Treap::insert(&tree, 4);
+    setup::check_invariants(&op);
+    assert_eq!(setup::to_vec(&op), vec![0, 1, 2, 3, 4, 4, 5, 6])
+  }
+
+  #[test]
+  fn keeps_previous_version() {
+    let tree = setup::treap_filled();
+    let _ = Treap::insert(&tree, 7);
+    assert_eq!(tree, setup::treap_filled())
+  }
+
+  #[test]
+  fn same_seed_same_shape() {
+    let items = [5, 3, 9, 1, 7, 2, 8];
+    let t1 = items.iter().fold(Treap::with_seed(3), |tree, item| {
+      Treap::insert(&tree, *item)
+    });
+    let t2 = items.iter().fold(Treap::with_seed(3), |tree, item| {
+      Treap::insert(&tree, *item)
+    });
+    assert_eq!(t1, t2)
+  }
+
+  #[test]
+  fn sorted_input_stays_shallow() {
+    let tree = (0..1024).fold(Treap::with_seed(11), |tree, item| {
+      Treap::insert(&tree, item)
+    });
+    setup::check_invariants(&tree);
+    assert_eq!(Treap::len(&tree), 1024);
+    assert!(Treap::height(&tree) < 40)
+  }
+}
+
+#[cfg(test)]
+mod remove {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    let op = Treap::remove(&tree, 0);
+    assert_eq!(op, tree)
+  }
+
+  #[test]
+  fn to_filled_no_children() {
+    let tree = setup::treap_filled();
+    let op = Treap::remove(&tree, 6);
+    let expected = Treap {
+      root: Rc::new(setup::node(
+        3,
+        70,
+        setup::node(1, 50, setup::leaf(0, 10), setup::leaf(2, 20)),
+        setup::node(5, 60, setup::leaf(4, 30), TreapNode::Empty),
+      )),
+      rng: SplitMix64::default(),
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_root() {
+    let tree = setup::treap_filled();
+    let op = Treap::remove(&tree, 3);
+    let expected = Treap {
+      root: Rc::new(setup::node(
+        5,
+        60,
+        setup::node(
+          1,
+          50,
+          setup::leaf(0, 10),
+          setup::node(4, 30, setup::leaf(2, 20), TreapNode::Empty),
+        ),
+        setup::leaf(6, 40),
+      )),
+      rng: SplitMix64::default(),
+    };
+    assert_eq!(op, expected)
+  }
+
+  #[test]
+  fn to_filled_not_found() {
+    let tree = setup::treap_filled();
+    let op = Treap::remove(&tree, 9);
+    assert_eq!(op, tree)
+  }
+}
+
+#[cfg(test)]
+mod split {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    let (t1, t2) = Treap::split(&tree, 3);
+    assert!(Treap::is_empty(&t1));
+    assert!(Treap::is_empty(&t2))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::treap_filled();
+    let (t1, t2) = Treap::split(&tree, 3);
+    setup::check_invariants(&t1);
+    setup::check_invariants(&t2);
+    assert_eq!(setup::to_vec(&t1), vec![0, 1, 2]);
+    assert_eq!(setup::to_vec(&t2), vec![3, 4, 5, 6])
+  }
+
+  #[test]
+  fn to_filled_below_minimum() {
+    let tree = setup::treap_filled();
+    let (t1, t2) = Treap::split(&tree, -1);
+    assert!(Treap::is_empty(&t1));
+    assert_eq!(t2, tree)
+  }
+}
+
+#[cfg(test)]
+mod merge {
+  use super::*;
+
+  #[test]
+  fn to_both_empty() {
+    let tree = setup::treap_empty();
+    let op = Treap::merge(&tree, &tree);
+    assert_eq!(op, Ok(tree))
+  }
+
+  #[test]
+  fn to_split_halves() {
+    let tree = setup::treap_filled();
+    let (t1, t2) = Treap::split(&tree, 4);
+    let op = Treap::merge(&t1, &t2);
+    assert_eq!(op, Ok(tree))
+  }
+
+  #[test]
+  fn to_overlapping() {
+    let tree = setup::treap_filled();
+    let (t1, t2) = Treap::split(&tree, 4);
+    let op = Treap::merge(&t2, &t1);
+    assert_eq!(op, Err(MergeError::OverlappingRanges))
+  }
+
+  #[test]
+  fn to_shared_boundary() {
+    let tree = setup::treap_filled();
+    let t1 = Treap::insert(&Treap::new(), 0);
+    let op = Treap::merge(&t1, &tree);
+    assert_eq!(op, Err(MergeError::OverlappingRanges))
+  }
+}
+
+#[cfg(test)]
+mod search {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    assert_eq!(Treap::search(&tree, 0), None)
+  }
+
+  #[test]
+  fn to_filled_with_item() {
+    let tree = setup::treap_filled();
+    assert_eq!(Treap::search(&tree, 0), Some(0));
+    assert_eq!(Treap::search(&tree, 6), Some(6))
+  }
+
+  #[test]
+  fn to_filled_without_item() {
+    let tree = setup::treap_filled();
+    assert_eq!(Treap::search(&tree, 7), None)
+  }
+}
+
+#[cfg(test)]
+mod len {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    assert_eq!(Treap::len(&tree), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::treap_filled();
+    assert_eq!(Treap::len(&tree), 7)
+  }
+}
+
+#[cfg(test)]
+mod height {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    assert_eq!(Treap::height(&tree), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::treap_filled();
+    assert_eq!(Treap::height(&tree), 3)
+  }
+}
+
+#[cfg(test)]
+mod any {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    assert!(!Treap::any(&tree, |_| true))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::treap_filled();
+    assert!(Treap::any(&tree, |item| item == &6));
+    assert!(!Treap::any(&tree, |item| item > &6))
+  }
+}
+
+#[cfg(test)]
+mod all {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    assert!(Treap::all(&tree, |_| false))
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::treap_filled();
+    assert!(Treap::all(&tree, |item| item >= &0));
+    assert!(!Treap::all(&tree, |item| item < &6))
+  }
+}
+
+#[cfg(test)]
+mod find {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    assert_eq!(Treap::find(&tree, |_| true), None)
+  }
+
+  #[test]
+  fn to_filled_should_match_in_order() {
+    let tree = setup::treap_filled();
+    assert_eq!(
+      Treap::find(&tree, |item| item % 2 == 0 && item > &1),
+      Some(&2)
+    )
+  }
+}
+
+#[cfg(test)]
+mod map {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    let op = Treap::map(&tree, |item| item + 5);
+    assert_eq!(op, tree)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::treap_filled();
+    let op = Treap::map(&tree, |item| item * 2);
+    let expected = Treap {
+      root: Rc::new(setup::node(
+        6,
+        70,
+        setup::node(2, 50, setup::leaf(0, 10), setup::leaf(4, 20)),
+        setup::node(10, 60, setup::leaf(8, 30), setup::leaf(12, 40)),
+      )),
+      rng: SplitMix64::default(),
+    };
+    assert_eq!(op, expected)
+  }
+}
+
+#[cfg(test)]
+mod reduce {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let tree = setup::treap_empty();
+    assert_eq!(Treap::reduce(&tree, |item, acc| item + acc, 0), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let tree = setup::treap_filled();
+    assert_eq!(Treap::reduce(&tree, |item, acc| item + acc, 0), 21)
+  }
+}
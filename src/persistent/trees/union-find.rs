@@ -0,0 +1,127 @@
+use std::cell::RefCell;
+
+use crate::{IndexError, PersistentVector};
+
+// Invariants:
+//    - parents[x] == x exactly when x is the root of its set
+//    - sizes[r] is the number of elements in the set rooted at r, entries of
+//      non-root elements are stale
+//    - union by size keeps every path to a root O(log n) long
+//    - compressing `parents` in place only swaps in an equivalent vector owned
+//      by this version, so no other version can observe it
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+  parents: RefCell<PersistentVector<usize>>,
+  sizes: PersistentVector<usize>,
+  components: i32,
+}
+
+mod private {
+  use super::*;
+
+  pub fn root_aux(parents: &PersistentVector<usize>, item: usize) -> usize {
+    let parent = *PersistentVector::get(parents, item).unwrap();
+    if parent == item {
+      item
+    } else {
+      root_aux(parents, parent)
+    }
+  }
+
+  // Points every element on the path from `item` straight at `root`.
+  pub fn compress_aux(
+    parents: &PersistentVector<usize>,
+    item: usize,
+    root: usize,
+  ) -> PersistentVector<usize> {
+    let parent = *PersistentVector::get(parents, item).unwrap();
+    if parent == root || parent == item {
+      return parents.clone();
+    }
+    let parents = PersistentVector::set(parents, item, root).unwrap();
+    compress_aux(&parents, parent, root)
+  }
+}
+
+impl Default for UnionFind {
+  fn default() -> Self {
+    Self::new(0)
+  }
+}
+
+impl UnionFind {
+  // `len` singleton sets, numbered from 0.
+  pub fn new(len: usize) -> Self {
+    Self {
+      parents: RefCell::new(PersistentVector::from((0..len).collect::<Vec<_>>())),
+      sizes: PersistentVector::from(vec![1; len]),
+      components: len as i32,
+    }
+  }
+
+  pub fn is_empty(uf: &Self) -> bool {
+    PersistentVector::is_empty(&uf.sizes)
+  }
+
+  pub fn len(uf: &Self) -> i32 {
+    PersistentVector::len(&uf.sizes)
+  }
+
+  // Number of disjoint sets.
+  pub fn components(uf: &Self) -> i32 {
+    uf.components
+  }
+
+  // Adds a new singleton set, returning it along with the new version.
+  pub fn make_set(uf: &Self) -> (Self, usize) {
+    let item = PersistentVector::len(&uf.sizes) as usize;
+    let uf = Self {
+      parents: RefCell::new(PersistentVector::push_back(&uf.parents.borrow(), item)),
+      sizes: PersistentVector::push_back(&uf.sizes, 1),
+      components: uf.components + 1,
+    };
+    (uf, item)
+  }
+
+  // Representative of the set holding `item`.
+  pub fn find(uf: &Self, item: usize) -> Result<usize, IndexError> {
+    if item >= PersistentVector::len(&uf.sizes) as usize {
+      return Err(IndexError::OutOfBounds);
+    }
+    let parents = uf.parents.borrow().clone();
+    let root = private::root_aux(&parents, item);
+    *uf.parents.borrow_mut() = private::compress_aux(&parents, item, root);
+    Ok(root)
+  }
+
+  pub fn same_set(uf: &Self, a: usize, b: usize) -> Result<bool, IndexError> {
+    Ok(Self::find(uf, a)? == Self::find(uf, b)?)
+  }
+
+  pub fn set_size(uf: &Self, item: usize) -> Result<i32, IndexError> {
+    let root = Self::find(uf, item)?;
+    Ok(*PersistentVector::get(&uf.sizes, root).unwrap() as i32)
+  }
+
+  // Merges the sets of `a` and `b`, hanging the smaller one below the larger.
+  pub fn union(uf: &Self, a: usize, b: usize) -> Result<Self, IndexError> {
+    let (ra, rb) = (Self::find(uf, a)?, Self::find(uf, b)?);
+    if ra == rb {
+      return Ok(uf.clone());
+    }
+    let (sa, sb) = (
+      *PersistentVector::get(&uf.sizes, ra).unwrap(),
+      *PersistentVector::get(&uf.sizes, rb).unwrap(),
+    );
+    let (child, root) = if sa < sb { (ra, rb) } else { (rb, ra) };
+    Ok(Self {
+      parents: RefCell::new(PersistentVector::set(&uf.parents.borrow(), child, root).unwrap()),
+      sizes: PersistentVector::set(&uf.sizes, root, sa + sb).unwrap(),
+      components: uf.components - 1,
+    })
+  }
+}
+
+#[cfg(test)]
+#[path = "./union-find_test.rs"]
+mod test;
@@ -0,0 +1,217 @@
+use super::*;
+
+mod setup {
+  use super::*;
+
+  pub fn union_find_empty() -> UnionFind {
+    UnionFind::new(0)
+  }
+
+  // {0, 1, 2}, {3, 4}, {5}
+  pub fn union_find_filled() -> UnionFind {
+    [(0, 1), (1, 2), (3, 4)]
+      .iter()
+      .fold(UnionFind::new(6), |acc, (a, b)| {
+        UnionFind::union(&acc, *a, *b).unwrap()
+      })
+  }
+
+  pub fn parents(uf: &UnionFind) -> Vec<usize> {
+    Vec::from(uf.parents.borrow().clone())
+  }
+}
+
+#[cfg(test)]
+mod new {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = setup::union_find_empty();
+    assert!(UnionFind::is_empty(&op));
+    assert_eq!(UnionFind::components(&op), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    let op = UnionFind::new(4);
+    assert_eq!(UnionFind::len(&op), 4);
+    assert_eq!(UnionFind::components(&op), 4);
+    assert!((0..4).all(|item| UnionFind::find(&op, item) == Ok(item)))
+  }
+}
+
+#[cfg(test)]
+mod make_set {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let uf = setup::union_find_empty();
+    let (op, item) = UnionFind::make_set(&uf);
+    assert_eq!(item, 0);
+    assert_eq!(UnionFind::len(&op), 1);
+    assert_eq!(UnionFind::components(&op), 1);
+    assert!(UnionFind::is_empty(&uf))
+  }
+
+  #[test]
+  fn to_filled() {
+    let uf = setup::union_find_filled();
+    let (op, item) = UnionFind::make_set(&uf);
+    assert_eq!(item, 6);
+    assert_eq!(UnionFind::set_size(&op, item), Ok(1));
+    assert_eq!(UnionFind::components(&op), 4);
+    assert_eq!(UnionFind::find(&uf, 6), Err(IndexError::OutOfBounds))
+  }
+}
+
+#[cfg(test)]
+mod find {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = UnionFind::find(&setup::union_find_empty(), 0);
+    assert_eq!(op, Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    let uf = setup::union_find_filled();
+    let root = UnionFind::find(&uf, 2).unwrap();
+    assert!((0..3).all(|item| UnionFind::find(&uf, item) == Ok(root)));
+    assert_ne!(UnionFind::find(&uf, 3), Ok(root));
+    assert_eq!(UnionFind::find(&uf, 5), Ok(5))
+  }
+
+  #[test]
+  fn compresses_paths() {
+    // Equal sizes hang the second root below the first, building a chain.
+    let uf = UnionFind::new(8);
+    let uf = UnionFind::union(&uf, 6, 7).unwrap();
+    let uf = UnionFind::union(&uf, 4, 5).unwrap();
+    let uf = UnionFind::union(&uf, 4, 6).unwrap();
+    let uf = UnionFind::union(&uf, 0, 1).unwrap();
+    let uf = UnionFind::union(&uf, 2, 3).unwrap();
+    let uf = UnionFind::union(&uf, 0, 2).unwrap();
+    let uf = UnionFind::union(&uf, 0, 4).unwrap();
+    assert_eq!(setup::parents(&uf), vec![0, 0, 0, 2, 0, 4, 4, 6]);
+    let other = uf.clone();
+    assert_eq!(UnionFind::find(&uf, 7), Ok(0));
+    assert_eq!(setup::parents(&uf), vec![0, 0, 0, 2, 0, 4, 0, 0]);
+    assert_eq!(setup::parents(&other), vec![0, 0, 0, 2, 0, 4, 4, 6])
+  }
+
+  #[test]
+  fn to_long_chain() {
+    let uf = (1..5000).fold(UnionFind::new(5000), |acc, item| {
+      UnionFind::union(&acc, 0, item).unwrap()
+    });
+    assert_eq!(UnionFind::components(&uf), 1);
+    assert!((0..5000).all(|item| UnionFind::find(&uf, item) == Ok(0)))
+  }
+}
+
+#[cfg(test)]
+mod union {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = UnionFind::union(&setup::union_find_empty(), 0, 1);
+    assert_eq!(op.map(|_| ()), Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    let uf = setup::union_find_filled();
+    let op = UnionFind::union(&uf, 4, 5).unwrap();
+    assert_eq!(UnionFind::components(&op), 2);
+    assert_eq!(UnionFind::set_size(&op, 3), Ok(3));
+    assert_eq!(UnionFind::same_set(&op, 3, 5), Ok(true))
+  }
+
+  #[test]
+  fn to_filled_within_set() {
+    let uf = setup::union_find_filled();
+    let op = UnionFind::union(&uf, 0, 2).unwrap();
+    assert_eq!(UnionFind::components(&op), 3);
+    assert_eq!(UnionFind::set_size(&op, 0), Ok(3))
+  }
+
+  #[test]
+  fn keeps_old_versions() {
+    let uf = setup::union_find_filled();
+    let op = UnionFind::union(&uf, 2, 3).unwrap();
+    let op = UnionFind::union(&op, 5, 0).unwrap();
+    assert_eq!(UnionFind::components(&op), 1);
+    assert_eq!(UnionFind::set_size(&op, 4), Ok(6));
+    assert_eq!(UnionFind::components(&uf), 3);
+    assert_eq!(UnionFind::same_set(&uf, 2, 3), Ok(false));
+    assert_eq!(UnionFind::same_set(&uf, 5, 0), Ok(false));
+    assert_eq!(UnionFind::set_size(&uf, 4), Ok(2))
+  }
+
+  #[test]
+  fn hangs_smaller_below_larger() {
+    let uf = setup::union_find_filled();
+    let op = UnionFind::union(&uf, 5, 3).unwrap();
+    assert_eq!(UnionFind::find(&op, 5), UnionFind::find(&uf, 3));
+    let op = UnionFind::union(&op, 4, 1).unwrap();
+    assert_eq!(UnionFind::find(&op, 1), UnionFind::find(&uf, 3))
+  }
+}
+
+#[cfg(test)]
+mod same_set {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = UnionFind::same_set(&setup::union_find_empty(), 0, 0);
+    assert_eq!(op, Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    let uf = setup::union_find_filled();
+    assert_eq!(UnionFind::same_set(&uf, 0, 2), Ok(true));
+    assert_eq!(UnionFind::same_set(&uf, 2, 4), Ok(false));
+    assert_eq!(UnionFind::same_set(&uf, 5, 6), Err(IndexError::OutOfBounds))
+  }
+}
+
+#[cfg(test)]
+mod set_size {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    let op = UnionFind::set_size(&setup::union_find_empty(), 0);
+    assert_eq!(op, Err(IndexError::OutOfBounds))
+  }
+
+  #[test]
+  fn to_filled() {
+    let uf = setup::union_find_filled();
+    assert_eq!(UnionFind::set_size(&uf, 1), Ok(3));
+    assert_eq!(UnionFind::set_size(&uf, 4), Ok(2));
+    assert_eq!(UnionFind::set_size(&uf, 5), Ok(1))
+  }
+}
+
+#[cfg(test)]
+mod components {
+  use super::*;
+
+  #[test]
+  fn to_empty() {
+    assert_eq!(UnionFind::components(&setup::union_find_empty()), 0)
+  }
+
+  #[test]
+  fn to_filled() {
+    assert_eq!(UnionFind::components(&setup::union_find_filled()), 3)
+  }
+}
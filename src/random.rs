@@ -0,0 +1,31 @@
+// SplitMix64 (Steele, Lea & Flood). Small, fast and fully determined by its
+// seed, which is all the randomized structures need; it is not meant for
+// anything security related.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SplitMix64 {
+  state: u64,
+}
+
+impl SplitMix64 {
+  pub const fn new(seed: u64) -> Self {
+    Self { state: seed }
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+}
+
+impl Default for SplitMix64 {
+  fn default() -> Self {
+    Self::new(0x5EED)
+  }
+}
+
+#[cfg(test)]
+#[path = "./random_test.rs"]
+mod test;
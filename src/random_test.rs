@@ -0,0 +1,38 @@
+use super::*;
+
+#[cfg(test)]
+mod next_u64 {
+  use super::*;
+
+  #[test]
+  fn reference_values() {
+    let mut rng = SplitMix64::new(1234567);
+    assert_eq!(rng.next_u64(), 6457827717110365317);
+    assert_eq!(rng.next_u64(), 3203168211198807973);
+  }
+
+  #[test]
+  fn same_seed_same_sequence() {
+    let mut r1 = SplitMix64::new(42);
+    let mut r2 = SplitMix64::new(42);
+    for _ in 0..16 {
+      assert_eq!(r1.next_u64(), r2.next_u64());
+    }
+  }
+
+  #[test]
+  fn different_seed_different_sequence() {
+    let mut r1 = SplitMix64::new(1);
+    let mut r2 = SplitMix64::new(2);
+    assert_ne!(r1.next_u64(), r2.next_u64());
+  }
+
+  #[test]
+  fn copies_are_independent() {
+    let mut r1 = SplitMix64::new(7);
+    let mut r2 = r1;
+    let first = r1.next_u64();
+    assert_eq!(r2.next_u64(), first);
+    assert_ne!(r1.next_u64(), first);
+  }
+}
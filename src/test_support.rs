@@ -0,0 +1,12 @@
+use crate::SplitMix64;
+
+// Drives the randomized model tests: calls `step` with each step's number
+// and a generator seeded the same way every run, fewer times under Miri.
+pub fn replay<F>(mut step: F)
+where
+  F: FnMut(usize, &mut SplitMix64),
+{
+  let mut rng = SplitMix64::default();
+  let steps = if cfg!(miri) { 200 } else { 5_000 };
+  (0..steps).for_each(|index| step(index, &mut rng))
+}
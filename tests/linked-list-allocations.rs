@@ -0,0 +1,220 @@
+// Runs on its own counting allocator, which replaces the global one for the
+// whole binary, so it lives outside the lib's unit tests.
+use lib::ephemeral::lists::{LinkedList, ListArena};
+
+mod setup {
+  use super::*;
+  use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+  };
+
+  // Counts the blocks each thread still holds, so leak checks aren't thrown
+  // off by tests running in parallel.
+  pub struct CountingAllocator;
+
+  thread_local! {
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    static TOTAL: Cell<isize> = const { Cell::new(0) };
+  }
+
+  unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+      let _ = LIVE.try_with(|live| live.set(live.get() + 1));
+      let _ = TOTAL.try_with(|total| total.set(total.get() + 1));
+      System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+      let _ = LIVE.try_with(|live| live.set(live.get() - 1));
+      System.dealloc(ptr, layout)
+    }
+  }
+
+  #[global_allocator]
+  static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+  // Number of blocks allocated and not yet freed by `f`.
+  pub fn leaked(f: impl FnOnce()) -> isize {
+    let before = LIVE.with(Cell::get);
+    f();
+    LIVE.with(Cell::get) - before
+  }
+
+  // Number of blocks allocated by `f`, freed or not.
+  pub fn allocated(f: impl FnOnce()) -> isize {
+    let before = TOTAL.with(Cell::get);
+    f();
+    TOTAL.with(Cell::get) - before
+  }
+
+  pub fn list_of<T>(nodes: &mut ListArena<T>, items: Vec<T>) -> LinkedList<T> {
+    let mut list = LinkedList::new();
+    items.into_iter().for_each(|item| {
+      list.insert_at_end(nodes, item);
+    });
+    list
+  }
+
+  pub fn items<T: Clone>(nodes: &ListArena<T>, list: &LinkedList<T>) -> Vec<T> {
+    list.iter(nodes).cloned().collect()
+  }
+}
+
+#[cfg(test)]
+mod drop {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let op = setup::leaked(|| {
+      let mut nodes = ListArena::new();
+      let list = setup::list_of(&mut nodes, vec![0, 1, 2, 3]);
+      let clone = list.clone_in(&mut nodes);
+      assert_eq!(nodes.len(), list.len() + clone.len());
+      drop(nodes)
+    });
+    assert_eq!(op, 0)
+  }
+
+  #[test]
+  fn to_owned_values() {
+    let op = setup::leaked(|| {
+      let mut nodes = ListArena::new();
+      let mut list = LinkedList::new();
+      (0..10).for_each(|item| {
+        list.insert_at_end(&mut nodes, format!("item-{item}"));
+      });
+      assert_eq!(
+        list.remove_at_beginning(&mut nodes),
+        Ok(String::from("item-0"))
+      );
+      assert_eq!(list.remove_at_end(&mut nodes), Ok(String::from("item-9")));
+      assert_eq!(
+        list.remove_item(&mut nodes, String::from("item-5")),
+        Ok(String::from("item-5"))
+      );
+      let mut other = list.clone_in(&mut nodes);
+      other.concat(&mut nodes, list);
+      let mut iter = other.drain(&mut nodes);
+      assert_eq!(iter.next(), Some(String::from("item-1")))
+    });
+    assert_eq!(op, 0)
+  }
+}
+
+#[cfg(test)]
+mod sort {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, vec![5, 3, 9, 1, 3, 0, 7, 2]);
+    let op = setup::allocated(|| list.sort(&mut nodes));
+    assert_eq!(op, 0);
+    assert_eq!(setup::items(&nodes, &list), vec![0, 1, 2, 3, 3, 5, 7, 9])
+  }
+}
+
+#[cfg(test)]
+mod dedup {
+  use super::*;
+
+  #[test]
+  fn dedup_by_key() {
+    let op = setup::leaked(|| {
+      let mut nodes = ListArena::new();
+      let mut list = setup::list_of(&mut nodes, vec![10, 11, 20, 35, 31, 12]);
+      list.dedup_by_key(&mut nodes, |item| item / 10);
+      assert_eq!(setup::items(&nodes, &list), vec![10, 20, 35, 12])
+    });
+    assert_eq!(op, 0)
+  }
+}
+
+#[cfg(test)]
+mod extract_if {
+  use super::*;
+
+  #[test]
+  fn to_filled_partially_consumed() {
+    let op = setup::leaked(|| {
+      let mut nodes = ListArena::new();
+      let mut list = setup::list_of(&mut nodes, vec![0, 1, 2, 3, 4, 5]);
+      let mut iter = list.extract_if(&mut nodes, |item| {
+        *item += 10;
+        *item > 11
+      });
+      assert_eq!(iter.next(), Some(12));
+      assert_eq!(setup::items(&nodes, &list), vec![10, 11, 3, 4, 5])
+    });
+    assert_eq!(op, 0)
+  }
+}
+
+#[cfg(test)]
+mod iter_mut {
+  use super::*;
+
+  #[test]
+  fn to_filled() {
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, (0..1000).collect());
+    let op = setup::allocated(|| list.iter_mut(&mut nodes).for_each(|item| *item += 1));
+    assert_eq!(op, 0);
+    assert_eq!(setup::items(&nodes, &list), (1..1001).collect::<Vec<_>>())
+  }
+}
+
+// Moving nodes between lists of one arena only relinks them, so it neither
+// allocates nor copies however long the lists are.
+#[cfg(test)]
+mod split_after {
+  use super::*;
+
+  #[test]
+  fn to_long_list() {
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, (0..1000).collect());
+    let mut rest = LinkedList::new();
+    let op = setup::allocated(|| {
+      let mut cursor = list.cursor_front_mut(&mut nodes);
+      cursor.move_next();
+      rest = cursor.split_after()
+    });
+    assert_eq!(op, 0);
+    assert_eq!((list.len(), rest.len()), (2, 998));
+    assert_eq!(setup::items(&nodes, &rest), (2..1000).collect::<Vec<_>>())
+  }
+}
+
+#[cfg(test)]
+mod splice_after {
+  use super::*;
+
+  #[test]
+  fn to_long_list() {
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, vec![-1, 1000]);
+    let other = setup::list_of(&mut nodes, (0..1000).collect());
+    let op = setup::allocated(|| list.cursor_front_mut(&mut nodes).splice_after(other));
+    assert_eq!(op, 0);
+    assert_eq!(setup::items(&nodes, &list), (-1..1001).collect::<Vec<_>>())
+  }
+}
+
+#[cfg(test)]
+mod concat {
+  use super::*;
+
+  #[test]
+  fn to_long_list() {
+    let mut nodes = ListArena::new();
+    let mut list = setup::list_of(&mut nodes, (0..1000).collect());
+    let other = setup::list_of(&mut nodes, (1000..2000).collect());
+    let op = setup::allocated(|| list.concat(&mut nodes, other));
+    assert_eq!(op, 0);
+    assert_eq!(setup::items(&nodes, &list), (0..2000).collect::<Vec<_>>())
+  }
+}